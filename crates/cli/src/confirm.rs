@@ -0,0 +1,137 @@
+//! Interactive confirmation shown before trading with real money.
+
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+
+use anyhow::Result;
+use eutrader_core::MarketConfig;
+use rust_decimal::Decimal;
+
+/// What the user must type to start live trading.
+pub const CONFIRM_WORD: &str = "LIVE";
+
+/// One market's contribution to the worst-case exposure.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExposureLine {
+    pub name: String,
+    pub max_inventory: Decimal,
+    /// Current price, or 1 (the most a share can cost) when unknown
+    pub price: Decimal,
+    pub exposure: Decimal,
+}
+
+/// Worst-case exposure across all markets: `max_inventory * price` each.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExposureSummary {
+    pub lines: Vec<ExposureLine>,
+    pub total: Decimal,
+}
+
+impl ExposureSummary {
+    /// Price each market from `prices`, falling back to 1 for markets
+    /// without one so the total is never understated.
+    pub fn new(markets: &[MarketConfig], prices: &HashMap<String, Decimal>) -> Self {
+        let lines: Vec<ExposureLine> = markets
+            .iter()
+            .map(|m| {
+                let price = prices.get(&m.token_id).copied().unwrap_or(Decimal::ONE);
+                ExposureLine {
+                    name: m.name.clone(),
+                    max_inventory: m.max_inventory,
+                    price,
+                    exposure: m.max_inventory * price,
+                }
+            })
+            .collect();
+        let total = lines.iter().map(|l| l.exposure).sum();
+        Self { lines, total }
+    }
+
+    /// Render as a table for the prompt.
+    pub fn render(&self) -> String {
+        let mut out = format!(
+            "{:<40} {:>12} {:>8} {:>12}\n",
+            "Market", "Max inv", "Price", "Exposure ($)"
+        );
+        out.push_str(&"-".repeat(75));
+        out.push('\n');
+        for line in &self.lines {
+            out.push_str(&format!(
+                "{:<40} {:>12} {:>8} {:>12.2}\n",
+                line.name, line.max_inventory, line.price, line.exposure
+            ));
+        }
+        out.push_str(&format!(
+            "{:<62} {:>12.2}\n",
+            "Total potential exposure", self.total
+        ));
+        out
+    }
+}
+
+/// Show `summary` and ask for `CONFIRM_WORD`. Returns whether the user typed it.
+pub fn confirm_live(
+    summary: &ExposureSummary,
+    input: impl BufRead,
+    mut output: impl Write,
+) -> Result<bool> {
+    writeln!(output, "\n*** LIVE TRADING — real orders, real money ***\n")?;
+    write!(output, "{}", summary.render())?;
+    write!(output, "\nType {CONFIRM_WORD} to start trading: ")?;
+    output.flush()?;
+
+    let answer = input.lines().next().transpose()?.unwrap_or_default();
+    Ok(answer.trim() == CONFIRM_WORD)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MARKETS: &str = r#"
+mode = "live"
+
+[risk]
+max_position_per_market = 100.0
+max_total_exposure = 500.0
+max_unrealized_loss = 50.0
+quote_refresh_interval_ms = 1000
+
+[[markets]]
+name = "Rain"
+token_id = "a"
+spread_bps = 400
+size = 10.0
+max_inventory = 100.0
+skew_factor = 0.0
+
+[[markets]]
+name = "Snow"
+token_id = "b"
+spread_bps = 400
+size = 10.0
+max_inventory = 50.0
+skew_factor = 0.0
+"#;
+
+    #[test]
+    fn exposure_sums_max_inventory_at_price() {
+        let markets = eutrader_core::Config::from_toml_profile(MARKETS, None)
+            .unwrap()
+            .markets;
+        let prices = HashMap::from([("a".to_string(), Decimal::new(40, 2))]);
+
+        let summary = ExposureSummary::new(&markets, &prices);
+        assert_eq!(summary.lines[0].exposure, Decimal::from(40));
+        // No price for "b": assume the worst, $1 a share
+        assert_eq!(summary.lines[1].price, Decimal::ONE);
+        assert_eq!(summary.total, Decimal::from(90));
+        assert!(summary.render().contains("90.00"));
+
+        let mut shown = Vec::new();
+        assert!(confirm_live(&summary, "LIVE\n".as_bytes(), &mut shown).unwrap());
+        assert!(String::from_utf8(shown).unwrap().contains("Rain"));
+        assert!(!confirm_live(&summary, "yes\n".as_bytes(), Vec::new()).unwrap());
+        assert!(!confirm_live(&summary, "".as_bytes(), Vec::new()).unwrap());
+    }
+}
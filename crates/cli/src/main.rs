@@ -1,17 +1,45 @@
+mod confirm;
+mod positions;
 mod tui;
 
+use std::collections::HashMap;
+use std::future::Future;
+use std::io::IsTerminal;
 use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::{Context, Result};
-use clap::{Parser, Subcommand, ValueEnum};
-use tracing::info;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use futures::StreamExt;
+use tracing::{debug, info, warn};
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
 use tracing_subscriber::EnvFilter;
 
-use eutrader_core::dashboard::new_shared_dashboard;
-use eutrader_core::{Config, Mode};
-use eutrader_engine::{OrderManager, PaperExecutor};
-use eutrader_feed::{FeedManager, GammaClient};
-use eutrader_strategy::{Quoter, RiskManager};
+use eutrader_core::dashboard::{
+    new_shared_dashboard_with_history, SharedDashboard, DEFAULT_MAX_FILLS, DEFAULT_PNL_HISTORY_LEN,
+};
+use eutrader_core::state::StateStore;
+use eutrader_core::{
+    truncate, Config, ConfigSource, Credentials, ExecutionConfig, MetricsBackend, MidSource, Mode,
+};
+use eutrader_engine::simulate::{compare_replay, read_snapshots, simulate};
+use eutrader_engine::status::DEFAULT_HEALTH_MAX_AGE_MS;
+use eutrader_engine::{
+    DryRunExecutor, FairValueProvider, FileFairValues, FileTradeLogger, HttpFairValues, NoMetrics,
+    OrderManagerBuilder, PaperExecutor, PnlDistribution, PrometheusSink, RateLimitedExecutor,
+    SharedMetrics, SimulationParams, StatsdSink, StatusLine, StatusServer,
+};
+use eutrader_feed::{
+    BookClient, Coalesce, FeedManager, GammaClient, LastTradePrices, MarketLimits, TradesFeed,
+    VolatilityTracker,
+};
+
+/// How often `--fair-values` are re-read.
+const FAIR_VALUE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// Default log filter under `--status-line`: warnings plus the summary.
+const STATUS_LINE_FILTER: &str = "warn,status_line=info";
 
 /// eutrader — Polymarket market-making engine
 #[derive(Parser)]
@@ -19,24 +47,16 @@ use eutrader_strategy::{Quoter, RiskManager};
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Log output format.
+    #[arg(long, global = true, value_enum, default_value_t = LogFormat::Human)]
+    log_format: LogFormat,
 }
 
 #[derive(Subcommand)]
 enum Commands {
     /// Start the market-making engine with TUI dashboard.
-    Run {
-        /// Path to the TOML configuration file.
-        #[arg(short, long, default_value = "config.toml")]
-        config: PathBuf,
-
-        /// Override the execution mode from the config file.
-        #[arg(short, long)]
-        mode: Option<ModeArg>,
-
-        /// Disable TUI and use plain log output instead.
-        #[arg(long)]
-        no_tui: bool,
-    },
+    Run(RunArgs),
     /// Discover available Polymarket markets sorted by volume.
     Discover {
         /// Minimum 24h volume in USD to show.
@@ -47,6 +67,141 @@ enum Commands {
         #[arg(long, default_value = "20")]
         limit: usize,
     },
+    /// Stress-test the quoter offline against synthetic random-walk prices.
+    Simulate(SimulateArgs),
+    /// Replay recorded snapshots through two configs and compare the sessions.
+    Compare(CompareArgs),
+    /// Print inventory positions saved in a state file.
+    Positions {
+        /// Path to the saved state file.
+        #[arg(long, default_value = "state.json")]
+        state: PathBuf,
+    },
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// TOML configuration: a file path, `-` for stdin, or an `http(s)://` URL.
+    #[arg(short, long, default_value = "config.toml", value_parser = parse_config_source)]
+    config: ConfigSource,
+
+    /// Apply this `[profiles.<name>]` table from the config file.
+    #[arg(long)]
+    profile: Option<String>,
+
+    /// Override the execution mode from the config file.
+    #[arg(short, long)]
+    mode: Option<ModeArg>,
+
+    /// Disable TUI and use plain log output instead.
+    #[arg(long)]
+    no_tui: bool,
+
+    /// Number of PnL samples kept for the TUI history chart.
+    #[arg(long, default_value_t = DEFAULT_PNL_HISTORY_LEN)]
+    pnl_history: usize,
+
+    /// Number of recent fills kept for the TUI activity log.
+    #[arg(long, default_value_t = DEFAULT_MAX_FILLS)]
+    max_fills: usize,
+
+    /// Skip checking configured token IDs against the Gamma API.
+    #[arg(long)]
+    no_validate: bool,
+
+    /// In live mode, log intended orders instead of sending them.
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Serve `/health` and `/status` over HTTP on this port.
+    #[arg(long)]
+    status_port: Option<u16>,
+
+    /// Write a session report here at shutdown (CSV if `.csv`, JSON otherwise).
+    #[arg(long)]
+    report: Option<PathBuf>,
+
+    /// Save positions here at shutdown, for `eutrader positions`.
+    #[arg(long, default_value = "state.json")]
+    state: PathBuf,
+
+    /// Annualize the report's Sharpe ratio with this many periods per year
+    /// (default: one per `quote_refresh_interval_ms`).
+    #[arg(long)]
+    periods_per_year: Option<f64>,
+
+    /// Read live credentials from this file of `POLY_*=value` lines instead
+    /// of the environment.
+    #[arg(long)]
+    creds_file: Option<PathBuf>,
+
+    /// Shut down cleanly after running this long (e.g. `30m`, `2h`).
+    #[arg(long, value_parser = humantime::parse_duration)]
+    max_runtime: Option<Duration>,
+
+    /// Quote around fair values from this JSON file, or `http(s)://` URL
+    /// serving one (`{"<token_id>": 0.60}`), instead of the book midpoint.
+    /// They are re-read every second.
+    #[arg(long)]
+    fair_values: Option<String>,
+
+    /// Start live mode without the interactive confirmation (required when
+    /// stdin is not a terminal).
+    #[arg(long, short = 'y', alias = "yes")]
+    force: bool,
+
+    /// Instead of the TUI or per-cycle logs, print one compact summary line
+    /// (PnL, fills, inventory, feed health) this often (e.g. `10s`).
+    #[arg(long, value_parser = humantime::parse_duration)]
+    status_line: Option<Duration>,
+}
+
+#[derive(Args)]
+struct CompareArgs {
+    /// Config the session ran with: a file path, `-` for stdin, or an
+    /// `http(s)://` URL.
+    #[arg(long, value_parser = parse_config_source)]
+    baseline: ConfigSource,
+
+    /// Config to compare against it.
+    #[arg(long, value_parser = parse_config_source)]
+    candidate: ConfigSource,
+
+    /// Recorded snapshots, one JSON `MarketSnapshot` per line.
+    #[arg(long)]
+    snapshots: PathBuf,
+}
+
+#[derive(Args)]
+struct SimulateArgs {
+    /// TOML configuration (markets and risk limits): a file path, `-` for
+    /// stdin, or an `http(s)://` URL.
+    #[arg(short, long, default_value = "config.toml", value_parser = parse_config_source)]
+    config: ConfigSource,
+
+    /// Apply this `[profiles.<name>]` table from the config file.
+    #[arg(long)]
+    profile: Option<String>,
+
+    /// Seed of the first run; run `i` uses `seed + i`.
+    #[arg(long, default_value_t = 1)]
+    seed: u64,
+
+    /// Snapshots generated per market in each run.
+    #[arg(long, default_value_t = 1000)]
+    ticks: usize,
+
+    /// Number of independent runs.
+    #[arg(long, default_value_t = 100)]
+    runs: u64,
+
+    /// Standard deviation of the per-tick log-odds price move.
+    #[arg(long, default_value_t = 0.05)]
+    volatility: f64,
+
+    /// Mean per-tick log-odds price move.
+    #[arg(long, default_value_t = 0.0, allow_negative_numbers = true)]
+    drift: f64,
 }
 
 /// CLI-level mode argument, mapped to `eutrader_core::Mode`.
@@ -65,29 +220,76 @@ impl From<ModeArg> for Mode {
     }
 }
 
+/// Log line format for both stdout and the TUI log file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum LogFormat {
+    /// Human-readable text.
+    Human,
+    /// One JSON object per line, with event fields as top-level keys.
+    Json,
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Run {
-            config: path,
-            mode,
-            no_tui,
-        } => run(path, mode, no_tui).await,
+        Commands::Run(args) => run(args, cli.log_format).await,
         Commands::Discover { min_volume, limit } => {
-            init_tracing();
+            init_tracing(cli.log_format);
             discover(min_volume, limit).await
         }
+        Commands::Positions { state } => show_positions(&state),
+        Commands::Simulate(args) => {
+            // Per-tick engine logs would drown the summary
+            init_subscriber(
+                cli.log_format,
+                "warn",
+                BoxMakeWriter::new(std::io::stderr),
+                true,
+            );
+            simulate_runs(args).await
+        }
+        Commands::Compare(args) => {
+            init_subscriber(
+                cli.log_format,
+                "warn",
+                BoxMakeWriter::new(std::io::stderr),
+                true,
+            );
+            compare_configs(args).await
+        }
     }
 }
 
-fn init_tracing() {
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
-        )
-        .init();
+fn parse_config_source(arg: &str) -> Result<ConfigSource, std::convert::Infallible> {
+    Ok(ConfigSource::parse(arg))
+}
+
+fn init_tracing(format: LogFormat) {
+    init_subscriber(format, "info", BoxMakeWriter::new(std::io::stdout), true);
+}
+
+/// Install the global tracing subscriber in the requested format.
+///
+/// `default_filter` applies when `RUST_LOG` is unset.
+fn init_subscriber(format: LogFormat, default_filter: &str, writer: BoxMakeWriter, ansi: bool) {
+    let filter =
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(default_filter));
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(writer)
+        .with_ansi(ansi);
+
+    match format {
+        LogFormat::Human => builder.init(),
+        LogFormat::Json => builder
+            .json()
+            .flatten_event(true)
+            .with_current_span(true)
+            .with_span_list(false)
+            .init(),
+    }
 }
 
 async fn discover(min_volume: f64, limit: usize) -> Result<()> {
@@ -114,20 +316,13 @@ async fn discover(min_volume: f64, limit: usize) -> Result<()> {
         return Ok(());
     }
 
-    println!(
-        "\n{:<60} {:>12} {}",
-        "Market", "Volume ($)", "YES Token ID"
-    );
+    println!("\n{:<60} {:>12} YES Token ID", "Market", "Volume ($)");
     println!("{}", "-".repeat(120));
     for m in &markets {
         let token_id = m.yes_token_id().unwrap_or("N/A");
         println!(
             "{:<60} {:>12.0} {}",
-            if m.question.len() > 57 {
-                format!("{}...", &m.question[..57])
-            } else {
-                m.question.clone()
-            },
+            truncate(&m.question, 60),
             m.volume_num,
             token_id,
         );
@@ -140,24 +335,224 @@ async fn discover(min_volume: f64, limit: usize) -> Result<()> {
     Ok(())
 }
 
-async fn run(config_path: PathBuf, mode_override: Option<ModeArg>, no_tui: bool) -> Result<()> {
+/// Show the markets and worst-case exposure and require the user to type
+/// `LIVE`. Refuses outright when there is no terminal to ask on.
+async fn confirm_live_start(config: &Config) -> Result<()> {
+    if !std::io::stdin().is_terminal() {
+        anyhow::bail!(
+            "refusing to start live mode without a terminal to confirm on (pass --force)"
+        );
+    }
+
+    let book = BookClient::new();
+    let mut prices = HashMap::new();
+    for market in &config.markets {
+        if let Ok(mid) = book.get_midpoint(&market.token_id).await {
+            prices.insert(market.token_id.clone(), mid);
+        }
+    }
+    let summary = confirm::ExposureSummary::new(&config.markets, &prices);
+
+    let stdin = std::io::stdin();
+    if !confirm::confirm_live(&summary, stdin.lock(), std::io::stderr())? {
+        anyhow::bail!("live mode not confirmed");
+    }
+    Ok(())
+}
+
+/// Each market's venue order size limits, fetched once at startup. A market
+/// whose limits can't be fetched is quoted with its configured limits only.
+async fn fetch_venue_limits(config: &Config) -> HashMap<String, MarketLimits> {
+    let book = BookClient::new();
+    let mut limits = HashMap::new();
+    for market in &config.markets {
+        if limits.contains_key(&market.token_id) {
+            continue;
+        }
+        match book.get_market_limits(&market.token_id).await {
+            Ok(found) => {
+                limits.insert(market.token_id.clone(), found);
+            }
+            Err(e) => {
+                warn!(market = %market.name, error = %e, "could not fetch venue order limits")
+            }
+        }
+    }
+    limits
+}
+
+fn show_positions(path: &std::path::Path) -> Result<()> {
+    let positions = StateStore::new(path)
+        .load()
+        .with_context(|| format!("failed to read state from {}", path.display()))?;
+    if positions.is_empty() {
+        println!("no saved positions in {}", path.display());
+        return Ok(());
+    }
+    print!("{}", positions::format_positions(&positions));
+    println!(
+        "\n{} positions saved in {}\n",
+        positions.len(),
+        path.display()
+    );
+    Ok(())
+}
+
+async fn simulate_runs(args: SimulateArgs) -> Result<()> {
+    let mut config = Config::load_source(&args.config, args.profile.as_deref())
+        .await
+        .with_context(|| format!("failed to load config from {}", args.config))?;
+    config.mode = Mode::Paper;
+    if config.markets.is_empty() {
+        // Auto-discover config: simulate one market quoted with its defaults
+        if let Some(ref discover_config) = config.auto_discover {
+            config.markets =
+                vec![discover_config.market_config("simulated".into(), "sim-0".into())];
+        }
+    }
+
+    let params = SimulationParams {
+        ticks: args.ticks,
+        volatility: args.volatility,
+        drift: args.drift,
+    };
+    let mut results = Vec::new();
+    for i in 0..args.runs {
+        results.push(simulate(&config, &params, args.seed.wrapping_add(i)).await);
+    }
+
+    let Some(dist) = PnlDistribution::from_results(&results) else {
+        anyhow::bail!("--runs must be at least 1");
+    };
+    println!(
+        "\nSimulated {} runs x {} ticks over {} market(s), seeds {}..={}",
+        dist.runs,
+        params.ticks,
+        config.markets.len(),
+        args.seed,
+        args.seed.wrapping_add(args.runs - 1),
+    );
+    println!("{}", "-".repeat(48));
+    println!("{:<24} {:>12.4}", "Mean PnL ($)", dist.mean);
+    println!("{:<24} {:>12.4}", "Std dev ($)", dist.std_dev);
+    println!("{:<24} {:>12.4}", "Min ($)", dist.min);
+    println!("{:<24} {:>12.4}", "5th percentile ($)", dist.p5);
+    println!("{:<24} {:>12.4}", "Median ($)", dist.median);
+    println!("{:<24} {:>12.4}", "95th percentile ($)", dist.p95);
+    println!("{:<24} {:>12.4}", "Max ($)", dist.max);
+    println!(
+        "{:<24} {:>11.1}%",
+        "Profitable runs",
+        dist.profitable_fraction * 100.0
+    );
+    println!("{:<24} {:>12}", "Kill switch tripped", dist.killed_runs);
+    println!();
+
+    Ok(())
+}
+
+async fn compare_configs(args: CompareArgs) -> Result<()> {
+    let load = |source: ConfigSource| async move {
+        let mut config = Config::load_source(&source, None)
+            .await
+            .with_context(|| format!("failed to load config from {source}"))?;
+        config.mode = Mode::Paper;
+        anyhow::Ok(config)
+    };
+    let baseline = load(args.baseline).await?;
+    let candidate = load(args.candidate).await?;
+    let snapshots = read_snapshots(&args.snapshots)
+        .with_context(|| format!("failed to read snapshots from {}", args.snapshots.display()))?;
+    if snapshots.is_empty() {
+        anyhow::bail!("no snapshots in {}", args.snapshots.display());
+    }
+
+    let comparison = compare_replay(&baseline, &candidate, &snapshots).await;
+    println!(
+        "\nReplayed {} snapshots from {}\n",
+        snapshots.len(),
+        args.snapshots.display()
+    );
+    println!("{comparison}");
+    Ok(())
+}
+
+async fn run(args: RunArgs, log_format: LogFormat) -> Result<()> {
+    let RunArgs {
+        config: config_source,
+        profile,
+        mode: mode_override,
+        no_tui,
+        pnl_history,
+        max_fills,
+        no_validate,
+        dry_run,
+        status_port,
+        report,
+        state,
+        periods_per_year,
+        creds_file,
+        max_runtime,
+        fair_values,
+        force,
+        status_line,
+    } = args;
+    let no_tui = no_tui || status_line.is_some();
+    // In status-line mode only warnings and the summary itself are logged
+    let log_filter = if status_line.is_some() {
+        STATUS_LINE_FILTER
+    } else {
+        "info"
+    };
+
     // --- Load configuration ---
-    let mut config = Config::load(&config_path)
-        .with_context(|| format!("failed to load config from {}", config_path.display()))?;
+    let mut config = Config::load_source(&config_source, profile.as_deref())
+        .await
+        .with_context(|| format!("failed to load config from {config_source}"))?;
+    // Only a file can be re-read on SIGHUP
+    let config_path = match &config_source {
+        ConfigSource::File(path) => Some(path.clone()),
+        ConfigSource::Stdin | ConfigSource::Url(_) => None,
+    };
+    if periods_per_year.is_some_and(|p| p.is_nan() || p <= 0.0) {
+        anyhow::bail!("--periods-per-year must be positive");
+    }
+    let fair_values = match fair_values {
+        Some(source) => Some(load_fair_values(&source).await?),
+        None => None,
+    };
 
     if let Some(m) = mode_override {
         config.mode = m.into();
     }
+    // Fail fast on missing credentials before touching the network, dry run
+    // included, so a rehearsal catches what the real session would. The dry
+    // run signs nothing, so they are only checked here.
+    if config.mode == Mode::Live {
+        Credentials::load(creds_file.as_deref()).context("failed to load live credentials")?;
+    }
 
-    // Auto-discover markets if configured and no manual markets specified
-    if config.markets.is_empty() {
+    // Validate manual markets, or auto-discover if no manual markets specified
+    if !config.markets.is_empty() && !no_validate {
+        eprintln!("Validating configured token IDs...");
+        let token_ids: Vec<String> = config.markets.iter().map(|m| m.token_id.clone()).collect();
+        GammaClient::new()
+            .validate_tokens(&token_ids)
+            .await
+            .context("token validation failed (use --no-validate to skip)")?;
+    } else if config.markets.is_empty() {
         if let Some(ref discover_config) = config.auto_discover {
             // Need tracing for discovery phase
             if no_tui {
-                init_tracing();
+                init_subscriber(
+                    log_format,
+                    log_filter,
+                    BoxMakeWriter::new(std::io::stdout),
+                    true,
+                );
             }
             eprintln!("Auto-discovering markets...");
-            let gamma = GammaClient::new();
+            let gamma = GammaClient::new().with_max_pages(discover_config.max_pages);
             let discovered = gamma
                 .discover_markets(discover_config)
                 .await
@@ -171,93 +566,291 @@ async fn run(config_path: PathBuf, mode_override: Option<ModeArg>, no_tui: bool)
 
     let mode = config.mode;
     let token_ids: Vec<String> = config.markets.iter().map(|m| m.token_id.clone()).collect();
-    let mode_str = format!("{:?}", mode);
+    let mode_str = if mode == Mode::Live && dry_run {
+        "Live (dry-run)".to_string()
+    } else {
+        format!("{:?}", mode)
+    };
+
+    if mode == Mode::Live && !dry_run && !force {
+        confirm_live_start(&config).await?;
+    }
+    // After the confirmation, so the prompt and `--force` are exercised
+    // ahead of the live executor landing
+    if mode == Mode::Live && !dry_run {
+        anyhow::bail!("live mode is not yet implemented (use --dry-run to log intended orders)");
+    }
 
     if no_tui {
         // Plain log mode (original behavior)
         if !tracing::dispatcher::has_been_set() {
-            init_tracing();
+            init_subscriber(
+                log_format,
+                log_filter,
+                BoxMakeWriter::new(std::io::stdout),
+                true,
+            );
         }
 
         info!("========================================");
         info!("  eutrader — Polymarket Market Maker");
         info!("========================================");
-        info!("mode: {:?} | markets: {}", mode, config.markets.len());
+        info!("mode: {} | markets: {}", mode_str, config.markets.len());
         for market in &config.markets {
             info!(
                 "  [{}] spread={}bps size={} max_inv={}",
                 market.name, market.spread_bps, market.size, market.max_inventory
             );
         }
+    } else {
+        // TUI dashboard mode
+        // Set tracing to write to a file instead of stdout (TUI owns stdout)
+        let log_file =
+            std::fs::File::create("eutrader.log").context("failed to create log file")?;
+        init_subscriber(log_format, "debug", BoxMakeWriter::new(log_file), false);
+    }
 
-        match mode {
-            Mode::Paper => {
-                let executor = PaperExecutor::new();
-                let dashboard = new_shared_dashboard(&mode_str);
-                let mut manager = OrderManager::new(executor, Quoter::new(), RiskManager::new(), config)
-                    .with_dashboard(dashboard);
-
-                let snapshots = FeedManager::new(token_ids)
-                    .stream()
-                    .await
-                    .context("failed to start feed")?;
+    let dashboard = new_shared_dashboard_with_history(&mode_str, pnl_history, max_fills);
+    if let Ok(mut state) = dashboard.write() {
+        state.dry_run = mode == Mode::Live && dry_run;
+    }
 
-                manager.run_paper(snapshots).await;
-            }
-            Mode::Live => {
-                anyhow::bail!("live mode is not yet implemented");
+    let mut prometheus = None;
+    let metrics: SharedMetrics = match &config.metrics {
+        MetricsBackend::None => Arc::new(NoMetrics),
+        MetricsBackend::Prometheus => {
+            if status_port.is_none() {
+                warn!("metrics backend is prometheus but no --status-port is set — nothing will serve /metrics");
             }
+            let sink = Arc::new(PrometheusSink::new());
+            prometheus = Some(sink.clone());
+            sink
         }
-    } else {
-        // TUI dashboard mode
-        // Set tracing to write to a file instead of stdout (TUI owns stdout)
-        let log_file = std::fs::File::create("eutrader.log")
-            .context("failed to create log file")?;
-        tracing_subscriber::fmt()
-            .with_env_filter(
-                EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("debug")),
-            )
-            .with_writer(log_file)
-            .with_ansi(false)
-            .init();
-
-        match mode {
-            Mode::Paper => {
-                let executor = PaperExecutor::new();
-                let dashboard = new_shared_dashboard(&mode_str);
-                let dash_clone = dashboard.clone();
-                let mut manager =
-                    OrderManager::new(executor, Quoter::new(), RiskManager::new(), config)
-                        .with_dashboard(dashboard);
-
-                let snapshots = FeedManager::new(token_ids)
-                    .stream()
-                    .await
-                    .context("failed to start feed")?;
-
-                // Shutdown signal: engine tells TUI to quit
-                let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
-
-                // Spawn engine in background task
-                let engine_handle = tokio::spawn(async move {
-                    manager.run_paper(snapshots).await;
-                    let _ = shutdown_tx.send(true);
-                });
-
-                // Run TUI on the main thread (must own terminal)
-                tui::run_dashboard(dash_clone, shutdown_rx)
-                    .await
-                    .context("TUI error")?;
-
-                // If TUI exited (user pressed 'q'), abort the engine
-                engine_handle.abort();
+        MetricsBackend::Statsd { host, port } => Arc::new(
+            StatsdSink::connect(host, *port)
+                .with_context(|| format!("failed to set up statsd metrics to {host}:{port}"))?,
+        ),
+    };
+
+    if let Some(port) = status_port {
+        let listener = tokio::net::TcpListener::bind(("0.0.0.0", port))
+            .await
+            .with_context(|| format!("failed to bind status server on port {port}"))?;
+        let max_age_ms = config
+            .risk
+            .max_snapshot_age_ms
+            .unwrap_or(DEFAULT_HEALTH_MAX_AGE_MS);
+        let mut server = StatusServer::new(dashboard.clone(), max_age_ms);
+        if let Some(sink) = prometheus {
+            server = server.with_metrics(sink);
+        }
+        tokio::spawn(server.serve(listener));
+    }
+
+    if let Some(interval) = status_line {
+        let max_age_ms = config
+            .risk
+            .max_snapshot_age_ms
+            .unwrap_or(DEFAULT_HEALTH_MAX_AGE_MS);
+        tokio::spawn(StatusLine::new(dashboard.clone(), interval, max_age_ms).run());
+    }
+
+    let mut feed = FeedManager::from_config(token_ids, &config);
+    // Markets quoting off the last trade get it pushed over the trades socket
+    // instead of polling the REST endpoint each tick
+    let last_trade_tokens: Vec<String> = config
+        .markets
+        .iter()
+        .filter(|m| m.mid_source == MidSource::LastTrade)
+        .map(|m| m.token_id.clone())
+        .collect();
+    if !last_trade_tokens.is_empty() {
+        let prices = LastTradePrices::new()
+            .with_max_age(Duration::from_millis(config.last_trade_max_age_ms));
+        let mut trades = TradesFeed::new(last_trade_tokens)
+            .with_last_trades(prices.clone())
+            .run();
+        tokio::spawn(async move {
+            // Realized volatility of these markets, measured trade to trade
+            let mut volatility = VolatilityTracker::default();
+            while let Some(trade) = trades.next().await {
+                let sigma = volatility.update_trade(&trade);
+                debug!(token = %trade.token_id, price = %trade.price, %sigma, "trade");
             }
-            Mode::Live => {
-                anyhow::bail!("live mode is not yet implemented");
+        });
+        feed = feed.with_last_trades(prices);
+    }
+    let venue_limits = fetch_venue_limits(&config).await;
+    let skipped = feed.skipped();
+    let unparseable = feed.unparseable_levels();
+    let snapshots = feed.stream().await.context("failed to start feed")?;
+    // If quoting falls behind the feed, act on the newest book rather than a backlog
+    let snapshots = Coalesce::new(snapshots);
+
+    // Quitting the TUI asks the engine to stop so it can cancel and flatten
+    let (stop_tx, stop_rx) = tokio::sync::watch::channel(false);
+    let execution = config.execution.clone();
+    let seed_positions = config.seed_positions.clone();
+    let mut builder = OrderManagerBuilder::new()
+        .config(config)
+        .dashboard(dashboard.clone())
+        .metrics(metrics)
+        .venue_limits(venue_limits)
+        .stop_signal(stop_rx);
+    if let Some(path) = config_path {
+        builder = builder.config_path(path);
+    }
+    if let Some(name) = profile {
+        builder = builder.config_profile(name);
+    }
+    if let Some(path) = report {
+        builder = builder.report_path(path);
+    }
+    builder = builder.state_path(state);
+    if let Some(periods) = periods_per_year {
+        builder = builder.periods_per_year(periods);
+    }
+    if let Some(limit) = max_runtime {
+        builder = builder.max_runtime(limit);
+    }
+    if let Some(provider) = fair_values {
+        builder = builder.fair_values(provider);
+    }
+    let engine: Pin<Box<dyn Future<Output = ()> + Send>> = match mode {
+        Mode::Paper => {
+            let executor = PaperExecutor::new()
+                .with_fill_model(execution.fill_model)
+                .with_seed_positions(&seed_positions)
+                .with_max_snapshot_age_ms(execution.max_fill_snapshot_age_ms)
+                .with_order_ttl_ms(execution.order_ttl_ms)
+                .with_trade_logger(Arc::new(FileTradeLogger::from_config(
+                    execution.trade_log_path.as_deref(),
+                    execution.trade_log_format,
+                )));
+            let mut manager = builder.build(executor)?;
+            Box::pin(async move { manager.run_paper(snapshots).await })
+        }
+        Mode::Live => {
+            let executor = live_executor(&execution);
+            if executor.is_limited() {
+                info!(
+                    per_sec = ?execution.rate_limit_per_sec,
+                    burst = ?execution.rate_limit_burst,
+                    "throttling order operations"
+                );
             }
+            let mut manager = builder.build(executor)?;
+            Box::pin(async move { manager.run(snapshots).await })
         }
+    };
+
+    if no_tui {
+        engine.await;
+    } else {
+        run_with_tui(engine, dashboard.clone(), stop_tx).await?;
     }
 
+    if skipped.get() > 0 {
+        eprintln!(
+            "feed dropped {} snapshots while the engine lagged behind",
+            skipped.get()
+        );
+    }
+    if unparseable.get() > 0 {
+        eprintln!(
+            "feed dropped {} book levels with unparseable prices",
+            unparseable.get()
+        );
+    }
+    if dashboard.read().is_ok_and(|s| s.killed) {
+        eprintln!("kill switch tripped: unrealized loss exceeded risk.max_unrealized_loss, quoting was halted");
+    }
     eprintln!("eutrader shut down cleanly");
     Ok(())
 }
+
+/// Load fair values from a file or an `http(s)://` URL and keep re-reading
+/// them every `FAIR_VALUE_POLL_INTERVAL`.
+async fn load_fair_values(source: &str) -> Result<Box<dyn FairValueProvider>> {
+    let context = || format!("failed to load fair values from {source}");
+    if source.starts_with("http://") || source.starts_with("https://") {
+        let provider = HttpFairValues::load(source).await.with_context(context)?;
+        provider.spawn_polling(FAIR_VALUE_POLL_INTERVAL);
+        Ok(Box::new(provider))
+    } else {
+        let provider = FileFairValues::load(source).with_context(context)?;
+        provider.spawn_polling(FAIR_VALUE_POLL_INTERVAL);
+        Ok(Box::new(provider))
+    }
+}
+
+/// The executor live mode sends orders through, throttled by the
+/// `rate_limit_*` settings when they are set.
+fn live_executor(execution: &ExecutionConfig) -> RateLimitedExecutor<DryRunExecutor> {
+    RateLimitedExecutor::from_config(DryRunExecutor::new(), execution)
+}
+
+/// Run the engine in a background task while the TUI owns the terminal.
+///
+/// Returns once the engine finishes. If the user quits the TUI first, the
+/// engine is sent `stop` and awaited, so it still shuts down gracefully.
+async fn run_with_tui<F>(
+    engine: F,
+    dashboard: SharedDashboard,
+    stop: tokio::sync::watch::Sender<bool>,
+) -> Result<()>
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    // Shutdown signal: engine tells TUI to quit
+    let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+
+    // Spawn engine in background task
+    let engine_handle = tokio::spawn(async move {
+        engine.await;
+        let _ = shutdown_tx.send(true);
+    });
+
+    // Run TUI on the main thread (must own terminal)
+    tui::run_dashboard(dashboard, shutdown_rx)
+        .await
+        .context("TUI error")?;
+
+    // If TUI exited (user pressed 'q'), let the engine wind down
+    let _ = stop.send(true);
+    engine_handle.await.context("engine task failed")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eutrader_core::Side;
+    use eutrader_engine::Executor;
+    use rust_decimal::Decimal;
+
+    #[tokio::test]
+    async fn live_executor_follows_rate_limit_config() {
+        let unlimited = live_executor(&ExecutionConfig::default());
+        assert!(!unlimited.is_limited());
+
+        let execution = ExecutionConfig {
+            rate_limit_per_sec: Some(1),
+            rate_limit_wait: false,
+            ..Default::default()
+        };
+        let limited = live_executor(&execution);
+        assert!(limited.is_limited());
+        let price = Decimal::new(50, 2);
+        let size = Decimal::from(10);
+        limited
+            .place_order("tok1", Side::Buy, price, size)
+            .await
+            .unwrap();
+        let err = limited
+            .place_order("tok1", Side::Buy, price, size)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("rate limited"));
+    }
+}
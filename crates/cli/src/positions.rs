@@ -0,0 +1,81 @@
+//! `eutrader positions`: print saved inventory without starting the engine.
+
+use eutrader_core::state::SavedPosition;
+use eutrader_core::truncate;
+
+/// Render saved positions as a table in the style of `discover`.
+pub fn format_positions(positions: &[SavedPosition]) -> String {
+    let mut out = format!(
+        "\n{:<20} {:>12} {:>10} {:>14} {:>8}\n",
+        "Market", "Net", "Avg entry", "Realized PnL", "Fills"
+    );
+    out.push_str(&"-".repeat(68));
+    out.push('\n');
+    for p in positions {
+        out.push_str(&format!(
+            "{:<20} {:>12} {:>10} {:>14} {:>8}\n",
+            truncate(&p.key().to_string(), 20),
+            p.net_position,
+            p.avg_entry,
+            eutrader_core::round_money(p.realized_pnl),
+            p.fill_count,
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eutrader_core::state::StateStore;
+    use rust_decimal::Decimal;
+
+    #[test]
+    fn formats_rows_from_a_saved_state_file() {
+        let path = std::env::temp_dir().join(format!("eutrader_state_{}.json", std::process::id()));
+        let store = StateStore::new(&path);
+        assert!(store.load().unwrap().is_empty());
+
+        store
+            .save(&[
+                SavedPosition {
+                    token_id: "tok_b".into(),
+                    strategy_id: String::new(),
+                    net_position: Decimal::new(-25, 0),
+                    avg_entry: Decimal::new(61, 2),
+                    realized_pnl: Decimal::new(-123456, 6),
+                    fees_paid: Decimal::ZERO,
+                    fill_count: 3,
+                },
+                SavedPosition {
+                    token_id: "tok_a".into(),
+                    strategy_id: String::new(),
+                    net_position: Decimal::new(10, 0),
+                    avg_entry: Decimal::new(45, 2),
+                    realized_pnl: Decimal::new(2, 0),
+                    fees_paid: Decimal::ZERO,
+                    fill_count: 7,
+                },
+            ])
+            .unwrap();
+
+        let table = format_positions(&store.load().unwrap());
+        let rows: Vec<&str> = table.lines().skip(3).collect();
+        assert_eq!(rows.len(), 2);
+        // Long keys are cut on character boundaries
+        let long = SavedPosition {
+            token_id: "é".repeat(30),
+            strategy_id: "wide".into(),
+            ..store.load().unwrap()[0].clone()
+        };
+        assert!(format_positions(&[long]).contains(&format!("{}...", "é".repeat(17))));
+        assert!(rows[0].starts_with("tok_a"));
+        assert!(rows[0].contains("0.45") && rows[0].contains("2.0000") && rows[0].ends_with('7'));
+        assert!(rows[1].contains("-25") && rows[1].contains("-0.1235"));
+
+        // An empty file is treated like a missing one
+        std::fs::write(&path, "").unwrap();
+        assert!(store.load().unwrap().is_empty());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
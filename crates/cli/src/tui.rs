@@ -1,21 +1,24 @@
 use std::io;
 use std::time::Duration;
 
-use chrono::Utc;
 use crossterm::event::{self, Event, KeyCode, KeyEventKind};
 use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
 use crossterm::ExecutableCommand;
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table};
+use ratatui::symbols;
+use ratatui::widgets::{
+    Axis, Block, Borders, Cell, Chart, Dataset, GraphType, Paragraph, Row, Table, Wrap,
+};
+use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
 
-use eutrader_core::dashboard::SharedDashboard;
-use eutrader_core::Side;
+use eutrader_core::dashboard::{DashboardState, SharedDashboard};
+use eutrader_core::{format_price, truncate, Side, DEFAULT_TICK_SIZE};
 
 /// Run the TUI dashboard until 'q' is pressed or the token signals shutdown.
 pub async fn run_dashboard(
     dashboard: SharedDashboard,
-    mut shutdown: tokio::sync::watch::Receiver<bool>,
+    shutdown: tokio::sync::watch::Receiver<bool>,
 ) -> io::Result<()> {
     // Setup terminal
     terminal::enable_raw_mode()?;
@@ -48,6 +51,35 @@ pub async fn run_dashboard(
     Ok(())
 }
 
+/// Shortest terminal the full layout fits in: every fixed-height panel plus
+/// the markets table's minimum.
+const FULL_MIN_HEIGHT: u16 = 36;
+
+/// Smallest terminal the compact view (header, markets, totals) fits in.
+const COMPACT_MIN_WIDTH: u16 = 60;
+const COMPACT_MIN_HEIGHT: u16 = 11;
+
+/// Which layout a terminal of a given size gets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum View {
+    /// Markets, fills, PnL chart and totals
+    Full,
+    /// Markets and totals only
+    Compact,
+    /// A "terminal too small" message
+    TooSmall,
+}
+
+fn view_for(area: Rect) -> View {
+    if area.width < COMPACT_MIN_WIDTH || area.height < COMPACT_MIN_HEIGHT {
+        View::TooSmall
+    } else if area.height < FULL_MIN_HEIGHT {
+        View::Compact
+    } else {
+        View::Full
+    }
+}
+
 fn draw(frame: &mut Frame, dashboard: &SharedDashboard) {
     let state = match dashboard.read() {
         Ok(s) => s.clone(),
@@ -55,44 +87,105 @@ fn draw(frame: &mut Frame, dashboard: &SharedDashboard) {
     };
 
     let area = frame.area();
+    match view_for(area) {
+        View::Full => {
+            // Layout: header, markets table, fills log, PnL chart, footer
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Length(3),  // Header
+                    Constraint::Min(8),     // Markets table
+                    Constraint::Length(12), // Recent fills
+                    Constraint::Length(10), // PnL history
+                    Constraint::Length(3),  // Footer / totals
+                ])
+                .split(area);
+            draw_header(frame, &state, chunks[0]);
+            draw_markets(frame, &state, chunks[1]);
+            draw_fills(frame, &state, chunks[2]);
+            draw_pnl_chart(frame, &state, chunks[3]);
+            draw_footer(frame, &state, chunks[4]);
+        }
+        View::Compact => {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Length(3), // Header
+                    Constraint::Min(5),    // Markets table
+                    Constraint::Length(3), // Footer / totals
+                ])
+                .split(area);
+            draw_header(frame, &state, chunks[0]);
+            draw_markets(frame, &state, chunks[1]);
+            draw_footer(frame, &state, chunks[2]);
+        }
+        View::TooSmall => {
+            let message = Paragraph::new(format!(
+                "Terminal too small ({}x{}), need {}x{} — press 'q' to quit",
+                area.width, area.height, COMPACT_MIN_WIDTH, COMPACT_MIN_HEIGHT
+            ))
+            .style(Style::default().fg(Color::Yellow))
+            .wrap(Wrap { trim: true });
+            frame.render_widget(message, area);
+        }
+    }
+}
 
-    // Layout: header, markets table, fills log, footer
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3),  // Header
-            Constraint::Min(8),    // Markets table
-            Constraint::Length(12), // Recent fills
-            Constraint::Length(3),  // Footer / totals
-        ])
-        .split(area);
-
-    // --- Header ---
-    let uptime = Utc::now() - state.uptime_start;
+fn draw_header(frame: &mut Frame, state: &DashboardState, area: Rect) {
+    let uptime = state.uptime();
     let uptime_str = format!(
         "{}h {}m {}s",
         uptime.num_hours(),
         uptime.num_minutes() % 60,
         uptime.num_seconds() % 60
     );
+    let dry_run_marker = if state.dry_run {
+        "  |  DRY RUN — * orders not sent"
+    } else {
+        ""
+    };
+    let kill_marker = if state.killed {
+        "  |  KILL SWITCH TRIPPED — quoting halted"
+    } else {
+        ""
+    };
+    let header_color = if state.killed {
+        Color::Red
+    } else {
+        Color::Cyan
+    };
     let header = Paragraph::new(format!(
-        " EUTRADER  |  Mode: {}  |  Markets: {}  |  Uptime: {}",
+        " EUTRADER  |  Mode: {}  |  Markets: {}  |  Uptime: {}{}{}",
         state.mode,
         state.markets.len(),
         uptime_str,
+        dry_run_marker,
+        kill_marker,
     ))
-    .style(Style::default().fg(Color::Cyan).bold())
+    .style(Style::default().fg(header_color).bold())
     .block(Block::default().borders(Borders::BOTTOM));
-    frame.render_widget(header, chunks[0]);
+    frame.render_widget(header, area);
+}
 
-    // --- Markets Table ---
+fn draw_markets(frame: &mut Frame, state: &DashboardState, area: Rect) {
     let header_cells = [
-        "Market", "Mid", "Bid", "Ask", "Spread", "Inventory", "Real PnL", "Unrl PnL", "Fills",
+        "Market",
+        "Mid",
+        "Bid",
+        "Ask",
+        "Spread",
+        "Inventory",
+        "Real PnL",
+        "Unrl PnL",
+        "Fills",
+        "Fill% B/A",
+        "Status",
     ]
     .into_iter()
     .map(|h| Cell::from(h).style(Style::default().fg(Color::Yellow).bold()));
     let header_row = Row::new(header_cells).height(1);
 
+    let order_marker = if state.dry_run { "*" } else { "" };
     let mut rows: Vec<Row> = state
         .markets
         .values()
@@ -110,17 +203,39 @@ fn draw(frame: &mut Frame, dashboard: &SharedDashboard) {
                 Color::Magenta
             };
 
+            // Midpoints sit between ticks, so show one more place than prices
+            let mid_tick = m.tick_size / Decimal::TEN;
             Row::new(vec![
                 Cell::from(truncate(&m.name, 30)),
-                Cell::from(format!("{:.4}", m.midpoint)),
-                Cell::from(format!("{:.2}", m.our_bid)).style(Style::default().fg(Color::Green)),
-                Cell::from(format!("{:.2}", m.our_ask)).style(Style::default().fg(Color::Red)),
-                Cell::from(format!("{:.2}", m.spread)),
+                Cell::from(format_price(m.midpoint, mid_tick)),
+                Cell::from(format!(
+                    "{}{}",
+                    format_price(m.our_bid, m.tick_size),
+                    order_marker
+                ))
+                .style(Style::default().fg(Color::Green)),
+                Cell::from(format!(
+                    "{}{}",
+                    format_price(m.our_ask, m.tick_size),
+                    order_marker
+                ))
+                .style(Style::default().fg(Color::Red)),
+                Cell::from(format_price(m.spread, m.tick_size)),
                 Cell::from(format!("{:.1}", m.inventory)).style(Style::default().fg(inv_color)),
-                Cell::from(format!("${:.2}", m.realized_pnl))
-                    .style(Style::default().fg(pnl_color)),
+                Cell::from(format!("${:.2}", m.realized_pnl)).style(Style::default().fg(pnl_color)),
                 Cell::from(format!("${:.2}", m.unrealized_pnl)),
                 Cell::from(format!("{}", m.fill_count)),
+                Cell::from(format!(
+                    "{}/{}",
+                    format_fill_prob(m.bid_fill_prob),
+                    format_fill_prob(m.ask_fill_prob)
+                )),
+                match &m.quote_status {
+                    Some(reason) => {
+                        Cell::from(reason.clone()).style(Style::default().fg(Color::Yellow))
+                    }
+                    None => Cell::from("quoting").style(Style::default().fg(Color::DarkGray)),
+                },
             ])
         })
         .collect();
@@ -142,6 +257,8 @@ fn draw(frame: &mut Frame, dashboard: &SharedDashboard) {
         Constraint::Length(10),
         Constraint::Length(10),
         Constraint::Length(6),
+        Constraint::Length(9),
+        Constraint::Min(12),
     ];
 
     let table = Table::new(rows, widths)
@@ -153,30 +270,38 @@ fn draw(frame: &mut Frame, dashboard: &SharedDashboard) {
                 .border_style(Style::default().fg(Color::DarkGray)),
         )
         .row_highlight_style(Style::default().bg(Color::DarkGray));
-    frame.render_widget(table, chunks[1]);
+    frame.render_widget(table, area);
+}
 
-    // --- Recent Fills ---
+fn draw_fills(frame: &mut Frame, state: &DashboardState, area: Rect) {
     let fill_header = Row::new(
         ["Time", "Market", "Side", "Price", "Size", "PnL After"]
             .into_iter()
             .map(|h| Cell::from(h).style(Style::default().fg(Color::Yellow).bold())),
     );
 
+    // Newest first, as many as fit inside the borders below the header
+    let visible_fills = area.height.saturating_sub(3) as usize;
     let fill_rows: Vec<Row> = state
         .recent_fills
         .iter()
         .rev()
-        .take(9)
+        .take(visible_fills)
         .map(|f| {
             let side_color = match f.side {
                 Side::Buy => Color::Green,
                 Side::Sell => Color::Red,
             };
+            let tick = state
+                .markets
+                .get(&f.token_id)
+                .map(|m| m.tick_size)
+                .unwrap_or(DEFAULT_TICK_SIZE);
             Row::new(vec![
                 Cell::from(f.timestamp.format("%H:%M:%S").to_string()),
                 Cell::from(truncate(&f.market_name, 25)),
                 Cell::from(format!("{}", f.side)).style(Style::default().fg(side_color)),
-                Cell::from(format!("{:.4}", f.price)),
+                Cell::from(format_price(f.price, tick)),
                 Cell::from(format!("{:.1}", f.size)),
                 Cell::from(format!("${:.2}", f.pnl_after)),
             ])
@@ -200,9 +325,10 @@ fn draw(frame: &mut Frame, dashboard: &SharedDashboard) {
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::DarkGray)),
         );
-    frame.render_widget(fills_table, chunks[2]);
+    frame.render_widget(fills_table, area);
+}
 
-    // --- Footer ---
+fn draw_footer(frame: &mut Frame, state: &DashboardState, area: Rect) {
     let total_pnl = state.total_realized_pnl;
     let pnl_color = if total_pnl >= Decimal::ZERO {
         Color::Green
@@ -210,19 +336,124 @@ fn draw(frame: &mut Frame, dashboard: &SharedDashboard) {
         Color::Red
     };
 
+    let cycle = state.cycle_latency;
     let footer = Paragraph::new(format!(
-        " Total PnL: ${:.4}  |  Total Fills: {}  |  Press 'q' to quit",
-        total_pnl, state.total_fills,
+        " Realized PnL: ${} (spread ${} / inventory ${})  |  Unrealized PnL: ${}  |  Total Fills: {}  |  Cycle p50/p95/p99: {:.1}/{:.1}/{:.1}ms  |  Order p95: {:.1}ms  |  Press 'q' to quit",
+        state.format_pnl(total_pnl),
+        state.format_pnl(state.total_spread_pnl),
+        state.format_pnl(state.total_inventory_pnl),
+        state.format_pnl(state.total_unrealized_pnl),
+        state.total_fills,
+        cycle.p50_ms,
+        cycle.p95_ms,
+        cycle.p99_ms,
+        state.order_latency.p95_ms,
     ))
     .style(Style::default().fg(pnl_color).bold())
     .block(Block::default().borders(Borders::TOP));
-    frame.render_widget(footer, chunks[3]);
+    frame.render_widget(footer, area);
 }
 
-fn truncate(s: &str, max: usize) -> String {
-    if s.len() <= max {
-        s.to_string()
+/// Render total realized PnL over time as a line chart.
+fn draw_pnl_chart(frame: &mut Frame, state: &DashboardState, area: Rect) {
+    let block = Block::default()
+        .title(" PnL History ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray));
+
+    let Some(&(first_ts, _)) = state.pnl_history.front() else {
+        frame.render_widget(Paragraph::new(" waiting for data...").block(block), area);
+        return;
+    };
+
+    let points: Vec<(f64, f64)> = state
+        .pnl_history
+        .iter()
+        .map(|(ts, pnl)| {
+            let x = (*ts - first_ts).num_milliseconds() as f64 / 1000.0;
+            (x, pnl.to_f64().unwrap_or(0.0))
+        })
+        .collect();
+
+    let x_max = points.last().map(|(x, _)| *x).unwrap_or(0.0).max(1.0);
+    let (y_min, y_max) = points.iter().fold((0.0_f64, 0.0_f64), |(lo, hi), (_, y)| {
+        (lo.min(*y), hi.max(*y))
+    });
+    // Pad the y range so a flat line is still visible
+    let pad = ((y_max - y_min) * 0.1).max(0.01);
+    let (y_min, y_max) = (y_min - pad, y_max + pad);
+
+    let color = if state.total_realized_pnl >= Decimal::ZERO {
+        Color::Green
     } else {
-        format!("{}...", &s[..max - 3])
+        Color::Red
+    };
+
+    let dataset = Dataset::default()
+        .marker(symbols::Marker::Braille)
+        .graph_type(GraphType::Line)
+        .style(Style::default().fg(color))
+        .data(&points);
+
+    let chart = Chart::new(vec![dataset])
+        .block(block)
+        .x_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::DarkGray))
+                .bounds([0.0, x_max])
+                .labels(["0s".to_string(), format!("{x_max:.0}s")]),
+        )
+        .y_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::DarkGray))
+                .bounds([y_min, y_max])
+                .labels([format!("${y_min:.2}"), format!("${y_max:.2}")]),
+        );
+    frame.render_widget(chart, area);
+}
+
+/// A fill probability as a whole percentage, or `-` for a side not quoted.
+fn format_fill_prob(prob: Option<f64>) -> String {
+    prob.map_or_else(|| "-".to_string(), |p| format!("{:.0}", p * 100.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eutrader_core::dashboard::new_shared_dashboard;
+    use ratatui::backend::TestBackend;
+
+    #[test]
+    fn layout_shrinks_with_the_terminal() {
+        assert_eq!(view_for(Rect::new(0, 0, 200, 50)), View::Full);
+        assert_eq!(
+            view_for(Rect::new(0, 0, COMPACT_MIN_WIDTH, FULL_MIN_HEIGHT)),
+            View::Full
+        );
+        assert_eq!(
+            view_for(Rect::new(0, 0, 200, FULL_MIN_HEIGHT - 1)),
+            View::Compact
+        );
+        assert_eq!(
+            view_for(Rect::new(0, 0, COMPACT_MIN_WIDTH, COMPACT_MIN_HEIGHT)),
+            View::Compact
+        );
+        assert_eq!(
+            view_for(Rect::new(0, 0, 200, COMPACT_MIN_HEIGHT - 1)),
+            View::TooSmall
+        );
+        assert_eq!(
+            view_for(Rect::new(0, 0, COMPACT_MIN_WIDTH - 1, 50)),
+            View::TooSmall
+        );
+    }
+
+    #[test]
+    fn every_view_renders_without_panicking() {
+        let dashboard = new_shared_dashboard("paper");
+        for (width, height) in [(200, 50), (80, 20), (30, 6), (1, 1)] {
+            let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+            terminal.draw(|frame| draw(frame, &dashboard)).unwrap();
+        }
     }
 }
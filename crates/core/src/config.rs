@@ -1,8 +1,18 @@
+use chrono::{DateTime, NaiveTime, Utc};
 use rust_decimal::Decimal;
-use serde::Deserialize;
-use std::path::Path;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-#[derive(Debug, Clone, Deserialize)]
+use crate::{
+    round_money_dp, FillModel, FlattenAggression, MidSource, MidpointMode, QuoteAnchor,
+    RoundingMode, SizeMode, SkewCurve, TradeLogFormat, DEFAULT_TICK_SIZE, MIN_ORDER_VALUE,
+    MONEY_DECIMALS,
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Config {
     pub mode: Mode,
     pub risk: RiskConfig,
@@ -10,9 +20,222 @@ pub struct Config {
     pub auto_discover: Option<AutoDiscoverConfig>,
     #[serde(default)]
     pub markets: Vec<MarketConfig>,
+    /// Global fee in basis points charged on each fill's notional.
+    /// Individual markets may override this via `MarketConfig::fee_bps`.
+    #[serde(default)]
+    pub fee_bps: u32,
+    /// How snapshot midpoints are computed: "simple" or "micro_price"
+    #[serde(default)]
+    pub midpoint_mode: MidpointMode,
+    /// Book levels with less than this many shares are skipped as dust when
+    /// picking the best bid and ask (0 keeps every level)
+    #[serde(default)]
+    pub min_level_size: Decimal,
+    /// Fail startup if the feed produces no snapshot within this long
+    #[serde(default = "default_feed_startup_timeout_ms")]
+    pub feed_startup_timeout_ms: u64,
+    /// A streamed last trade older than this is not used as a `last_trade`
+    /// mid; the price is fetched over REST instead
+    #[serde(default = "default_last_trade_max_age_ms")]
+    pub last_trade_max_age_ms: u64,
+    #[serde(default)]
+    pub execution: ExecutionConfig,
+    /// Widen spreads on markets where our fills keep getting picked off
+    #[serde(default)]
+    pub adverse_selection: Option<AdverseSelectionConfig>,
+    /// Rounding of PnL in logs, the dashboard and session reports
+    #[serde(default)]
+    pub pnl_rounding: PnlRounding,
+    /// Log an "engine alive" summary every this many quote cycles; disabled if unset
+    #[serde(default)]
+    pub heartbeat_every_cycles: Option<u64>,
+    /// Where fill, PnL and open-order metrics are exported
+    #[serde(default)]
+    pub metrics: MetricsBackend,
+    /// Paper only: positions to start from instead of flat
+    #[serde(default)]
+    pub seed_positions: Vec<SeedPosition>,
+}
+
+/// A position held at startup, e.g. copied from a live account, so paper
+/// trading can begin already carrying inventory.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeedPosition {
+    pub token_id: String,
+    /// Strategy instance holding the position; empty for the default
+    #[serde(default)]
+    pub strategy_id: String,
+    pub net_position: Decimal,
+    pub avg_entry: Decimal,
+}
+
+impl SeedPosition {
+    /// The strategy instance this seed belongs to.
+    pub fn key(&self) -> crate::StrategyKey {
+        crate::StrategyKey::new(&self.token_id, &self.strategy_id)
+    }
+}
+
+/// Metrics export backend, chosen by `backend` in the `[metrics]` table.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(tag = "backend", rename_all = "snake_case")]
+pub enum MetricsBackend {
+    /// No metrics are exported
+    #[default]
+    None,
+    /// Served for scraping at `/metrics` on the status server
+    Prometheus,
+    /// Pushed over UDP to a StatsD or DogStatsD agent
+    Statsd { host: String, port: u16 },
+}
+
+/// Rounding applied to displayed and reported PnL. Positions always
+/// accumulate at full precision; only what is shown is rounded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PnlRounding {
+    #[serde(default = "default_money_decimals")]
+    pub decimals: u32,
+    #[serde(default)]
+    pub mode: RoundingMode,
+}
+
+impl Default for PnlRounding {
+    fn default() -> Self {
+        Self {
+            decimals: MONEY_DECIMALS,
+            mode: RoundingMode::default(),
+        }
+    }
+}
+
+impl PnlRounding {
+    /// Round `value` for display.
+    pub fn round(&self, value: Decimal) -> Decimal {
+        round_money_dp(value, self.decimals, self.mode)
+    }
 }
 
-#[derive(Debug, Clone, Deserialize)]
+fn default_money_decimals() -> u32 {
+    MONEY_DECIMALS
+}
+
+/// Settings for the order execution layer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionConfig {
+    /// Maximum live order operations (place/cancel) per second; unlimited if unset
+    #[serde(default)]
+    pub rate_limit_per_sec: Option<u32>,
+    /// Burst capacity of the rate limiter (defaults to `rate_limit_per_sec`)
+    #[serde(default)]
+    pub rate_limit_burst: Option<u32>,
+    /// Wait for capacity when rate limited instead of failing the operation
+    #[serde(default = "default_rate_limit_wait")]
+    pub rate_limit_wait: bool,
+    /// Fill model used by the paper executor
+    #[serde(default)]
+    pub fill_model: FillModel,
+    /// Paper executor ignores snapshots older than this for fill evaluation
+    #[serde(default)]
+    pub max_fill_snapshot_age_ms: Option<u64>,
+    /// Paper orders expire this long after being placed or amended; good
+    /// till cancelled if unset
+    #[serde(default)]
+    pub order_ttl_ms: Option<u64>,
+    /// File paper fills are appended to (defaults to `paper_trades.<format>`)
+    #[serde(default)]
+    pub trade_log_path: Option<PathBuf>,
+    /// Serialization of the paper trade log
+    #[serde(default)]
+    pub trade_log_format: TradeLogFormat,
+    /// Send an executor heartbeat this often in live mode; disabled if unset
+    #[serde(default)]
+    pub heartbeat_interval_ms: Option<u64>,
+    /// Compare positions against the venue this often in live mode; disabled if unset
+    #[serde(default)]
+    pub position_reconcile_interval_ms: Option<u64>,
+    /// Overwrite local positions with the venue's when they disagree
+    /// (otherwise drift is only logged)
+    #[serde(default)]
+    pub correct_position_drift: bool,
+}
+
+impl Default for ExecutionConfig {
+    fn default() -> Self {
+        Self {
+            rate_limit_per_sec: None,
+            rate_limit_burst: None,
+            rate_limit_wait: default_rate_limit_wait(),
+            fill_model: FillModel::default(),
+            max_fill_snapshot_age_ms: None,
+            order_ttl_ms: None,
+            trade_log_path: None,
+            trade_log_format: TradeLogFormat::default(),
+            heartbeat_interval_ms: None,
+            position_reconcile_interval_ms: None,
+            correct_position_drift: false,
+        }
+    }
+}
+
+fn default_feed_startup_timeout_ms() -> u64 {
+    30_000
+}
+
+fn default_last_trade_max_age_ms() -> u64 {
+    60_000
+}
+
+fn default_rate_limit_wait() -> bool {
+    true
+}
+
+/// Spread widening driven by the mid move that follows each fill.
+///
+/// Each fill is marked out against the midpoint `markout_ms` later. When the
+/// average markout over the last `lookback_fills` fills of a market is
+/// adverse, that market's `spread_bps` is multiplied by
+/// `1 + sensitivity * adverse_move / half_spread`, capped at `max_multiplier`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AdverseSelectionConfig {
+    /// Number of recent marked-out fills per market to average
+    #[serde(default = "default_lookback_fills")]
+    pub lookback_fills: usize,
+    /// How long after a fill to measure the mid move
+    #[serde(default = "default_markout_ms")]
+    pub markout_ms: u64,
+    /// Extra spread multiple per half-spread of average adverse move
+    #[serde(default = "default_adverse_sensitivity")]
+    pub sensitivity: Decimal,
+    /// Upper bound on the spread multiplier
+    #[serde(default = "default_max_multiplier")]
+    pub max_multiplier: Decimal,
+}
+
+impl Default for AdverseSelectionConfig {
+    fn default() -> Self {
+        Self {
+            lookback_fills: default_lookback_fills(),
+            markout_ms: default_markout_ms(),
+            sensitivity: default_adverse_sensitivity(),
+            max_multiplier: default_max_multiplier(),
+        }
+    }
+}
+
+fn default_lookback_fills() -> usize {
+    10
+}
+fn default_markout_ms() -> u64 {
+    5_000
+}
+fn default_adverse_sensitivity() -> Decimal {
+    Decimal::ONE
+}
+fn default_max_multiplier() -> Decimal {
+    Decimal::from(3)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AutoDiscoverConfig {
     /// Minimum 24h volume (USD) to consider a market
     #[serde(default = "default_min_volume")]
@@ -30,6 +253,24 @@ pub struct AutoDiscoverConfig {
     /// Default skew factor for auto-discovered markets
     #[serde(default = "default_skew_factor")]
     pub skew_factor: Decimal,
+    /// Default minimum round-trip edge for auto-discovered markets
+    #[serde(default)]
+    pub min_round_trip_edge: Decimal,
+    /// Default max market spread for auto-discovered markets
+    #[serde(default)]
+    pub max_market_spread_bps: Option<u32>,
+    /// Default one-sided inventory threshold for auto-discovered markets
+    #[serde(default)]
+    pub one_sided_threshold: Option<Decimal>,
+    /// Default minimum quoted edge over fees for auto-discovered markets
+    #[serde(default)]
+    pub min_edge_bps: u32,
+    /// Quote every outcome of multi-outcome markets, not just the first
+    #[serde(default)]
+    pub expand_outcomes: bool,
+    /// Gamma result pages scanned for candidates, 100 markets each
+    #[serde(default = "default_max_pages")]
+    pub max_pages: usize,
 }
 
 fn default_min_volume() -> f64 {
@@ -38,6 +279,9 @@ fn default_min_volume() -> f64 {
 fn default_max_markets() -> usize {
     5
 }
+fn default_max_pages() -> usize {
+    10
+}
 fn default_spread_bps() -> u32 {
     400
 }
@@ -45,25 +289,58 @@ fn default_skew_factor() -> Decimal {
     rust_decimal_macros::dec!(0.001)
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Mode {
     Paper,
     Live,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RiskConfig {
     pub max_position_per_market: Decimal,
     pub max_total_exposure: Decimal,
     pub max_unrealized_loss: Decimal,
+    /// How often each market is polled and requoted
     pub quote_refresh_interval_ms: u64,
+    /// Offset each market's polling cycle by a random delay of up to this
+    /// many milliseconds so reconciliations don't all fire in the same tick
+    #[serde(default)]
+    pub quote_jitter_ms: u64,
+    /// Pull quotes when a snapshot is older than this many milliseconds
+    #[serde(default)]
+    pub max_snapshot_age_ms: Option<u64>,
+    /// Send marketable orders to close every position on shutdown
+    #[serde(default)]
+    pub flatten_on_shutdown: bool,
+    /// Where flattening orders are priced: `passive`, `mid` or `cross`
+    #[serde(default)]
+    pub flatten_aggression: FlattenAggression,
+    /// Refuse to place orders once a market has this many resting
+    #[serde(default)]
+    pub max_open_orders_per_market: Option<usize>,
+    /// Snapshots per market to observe before placing its first quote
+    #[serde(default)]
+    pub warmup_ticks: u32,
+    /// Check Gamma this often for markets that closed or resolved; disabled if unset
+    #[serde(default)]
+    pub market_status_interval_ms: Option<u64>,
+    /// When max runtime is reached, or a market is stopped or removed on
+    /// reload, spend up to this many quote cycles (and at most as many
+    /// refresh intervals) reducing inventory with passive orders, then
+    /// flatten the rest
+    #[serde(default)]
+    pub unwind_cycles: Option<u32>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MarketConfig {
     pub name: String,
     pub token_id: String,
+    /// Names one of several independent strategies quoting the same token,
+    /// each with its own orders and inventory; empty for the default
+    #[serde(default)]
+    pub strategy_id: String,
     /// Spread in basis points (e.g. 300 = 3%)
     pub spread_bps: u32,
     /// Number of shares to quote per side
@@ -72,19 +349,375 @@ pub struct MarketConfig {
     pub max_inventory: Decimal,
     /// How aggressively to skew quotes based on inventory
     pub skew_factor: Decimal,
+    /// Per-market fee override in basis points (falls back to the global `fee_bps`)
+    #[serde(default)]
+    pub fee_bps: Option<u32>,
+    /// Minimum net-of-fee edge per share for a round-trip to count as profitable
+    #[serde(default)]
+    pub min_round_trip_edge: Decimal,
+    /// Pull quotes when the market's own spread exceeds this many bps
+    #[serde(default)]
+    pub max_market_spread_bps: Option<u32>,
+    /// Fraction of `max_inventory` beyond which only the reducing side is quoted
+    #[serde(default)]
+    pub one_sided_threshold: Option<Decimal>,
+    /// Minimum half-spread edge over fees, in bps of mid, required to quote
+    #[serde(default)]
+    pub min_edge_bps: u32,
+    /// Outcome this token represents (e.g. "Yes", or a candidate name). A
+    /// label for logs and the dashboard only: `token_id` picks what is traded
+    #[serde(default)]
+    pub outcome: Option<String>,
+    /// Minimum price increment; quotes are rounded and prices displayed on it
+    #[serde(default = "default_tick_size")]
+    pub tick_size: Decimal,
+    /// Net position the inventory skew steers toward instead of flat
+    #[serde(default)]
+    pub target_inventory: Decimal,
+    /// Where the snapshot midpoint comes from: "book", "midpoint" or "last_trade"
+    #[serde(default)]
+    pub mid_source: MidSource,
+    /// Price shift per unit of top-of-book imbalance; 0 disables the signal
+    #[serde(default)]
+    pub imbalance_factor: Decimal,
+    /// Stop quoting this market once its PnL falls this far below its session peak
+    #[serde(default)]
+    pub max_market_loss: Option<Decimal>,
+    /// Close the position with a marketable order when `max_market_loss` trips
+    #[serde(default)]
+    pub flatten_on_stop: bool,
+    /// Fraction of `max_inventory` beyond which the reducing side is posted reduce-only
+    #[serde(default)]
+    pub reduce_only_threshold: Option<Decimal>,
+    /// Shape of inventory skew against position; `linear` uses `skew_factor`
+    #[serde(default)]
+    pub skew_curve: SkewCurve,
+    /// Skew at full saturation under the `tanh` curve, in price units
+    #[serde(default = "default_max_skew")]
+    pub max_skew: Decimal,
+    /// How quickly the `tanh` curve saturates as utilization grows
+    #[serde(default = "default_skew_steepness")]
+    pub skew_steepness: Decimal,
+    /// Smallest size the quoter will post on a side, in shares. Sizes are also
+    /// raised to meet the venue's $1 minimum notional at the quoted price.
+    #[serde(default = "default_min_order_size")]
+    pub min_order_size: Decimal,
+    /// Largest size the quoter will post on a side, in shares
+    #[serde(default)]
+    pub max_order_size: Option<Decimal>,
+    /// Refresh a resting order once it is this old, even if it still matches the target
+    #[serde(default)]
+    pub max_order_age_ms: Option<u64>,
+    /// Leave resting orders alone until the target price moves this many ticks
+    #[serde(default)]
+    pub min_requote_move: Option<u32>,
+    /// Requote regardless of `min_requote_move` once the mid moves this far
+    #[serde(default)]
+    pub min_requote_bps: Option<u32>,
+    /// Whether `size` counts shares or USDC notional per side
+    #[serde(default)]
+    pub size_mode: SizeMode,
+    /// Never quote a spread narrower than this multiple of the market's own
+    /// spread (e.g. 0.5 = at least half as wide); disabled at 0
+    #[serde(default)]
+    pub min_spread_over_market: Decimal,
+    /// Only quote while the mid is within `[low, high]`, e.g. `[0.10, 0.90]`
+    /// to stay out of markets priced near resolution
+    #[serde(default)]
+    pub quote_price_band: Option<(Decimal, Decimal)>,
+    /// Center quotes on the mid, or on a blend of the mid and our last fill
+    #[serde(default)]
+    pub quote_anchor: QuoteAnchor,
+    /// Under `last_fill`, how long until a fill stops pulling the quote center
+    #[serde(default = "default_anchor_decay_ms")]
+    pub anchor_decay_ms: u64,
+    /// Randomize each order's size within this fraction of target
+    /// (0.1 = ±10%) so fills don't reveal a fixed size; disabled at 0
+    #[serde(default)]
+    pub size_jitter_pct: Decimal,
+    /// Ticks the inventory-reducing side is moved toward the market after
+    /// rounding; fractions round up to a whole tick. Disabled at 0
+    #[serde(default)]
+    pub reduce_side_aggression: Decimal,
+    /// UTC windows during which the market is quoted; always when empty
+    #[serde(default)]
+    pub schedule: Vec<QuoteWindow>,
+    /// Smallest order value in USDC; smaller sizes are raised to meet it, or
+    /// the side is pulled if that would breach `max_inventory`
+    #[serde(default = "default_min_notional")]
+    pub min_notional: Decimal,
+}
+
+/// A daily UTC time window, e.g. `{ start = "13:30", end = "20:00" }`.
+/// A window whose end is before its start runs past midnight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuoteWindow {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl QuoteWindow {
+    /// Whether `time` falls in `[start, end)`.
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+fn default_tick_size() -> Decimal {
+    DEFAULT_TICK_SIZE
+}
+
+fn default_max_skew() -> Decimal {
+    Decimal::new(5, 2)
+}
+
+fn default_skew_steepness() -> Decimal {
+    Decimal::TWO
+}
+
+fn default_min_order_size() -> Decimal {
+    Decimal::ONE
+}
+
+fn default_min_notional() -> Decimal {
+    MIN_ORDER_VALUE
+}
+
+fn default_anchor_decay_ms() -> u64 {
+    60_000
+}
+
+/// Every optional setting at its serde default; the required ones (name,
+/// token, spread, size, inventory and skew) are empty or zero.
+impl Default for MarketConfig {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            token_id: String::new(),
+            strategy_id: String::new(),
+            spread_bps: 0,
+            size: Decimal::ZERO,
+            max_inventory: Decimal::ZERO,
+            skew_factor: Decimal::ZERO,
+            fee_bps: None,
+            min_round_trip_edge: Decimal::ZERO,
+            max_market_spread_bps: None,
+            one_sided_threshold: None,
+            min_edge_bps: 0,
+            outcome: None,
+            tick_size: default_tick_size(),
+            target_inventory: Decimal::ZERO,
+            mid_source: MidSource::default(),
+            imbalance_factor: Decimal::ZERO,
+            max_market_loss: None,
+            flatten_on_stop: false,
+            reduce_only_threshold: None,
+            skew_curve: SkewCurve::default(),
+            max_skew: default_max_skew(),
+            skew_steepness: default_skew_steepness(),
+            min_order_size: default_min_order_size(),
+            max_order_size: None,
+            max_order_age_ms: None,
+            min_requote_move: None,
+            min_requote_bps: None,
+            size_mode: SizeMode::default(),
+            min_spread_over_market: Decimal::ZERO,
+            quote_price_band: None,
+            quote_anchor: QuoteAnchor::default(),
+            anchor_decay_ms: default_anchor_decay_ms(),
+            size_jitter_pct: Decimal::ZERO,
+            reduce_side_aggression: Decimal::ZERO,
+            schedule: Vec::new(),
+            min_notional: default_min_notional(),
+        }
+    }
+}
+
+impl AutoDiscoverConfig {
+    /// A market quoted with these defaults, without a fee override.
+    pub fn market_config(&self, name: String, token_id: String) -> MarketConfig {
+        MarketConfig {
+            name,
+            token_id,
+            spread_bps: self.spread_bps,
+            size: self.size,
+            max_inventory: self.max_inventory,
+            skew_factor: self.skew_factor,
+            min_round_trip_edge: self.min_round_trip_edge,
+            max_market_spread_bps: self.max_market_spread_bps,
+            one_sided_threshold: self.one_sided_threshold,
+            min_edge_bps: self.min_edge_bps,
+            ..Default::default()
+        }
+    }
+}
+
+impl MarketConfig {
+    /// Fee in basis points for this market, preferring the per-market override.
+    pub fn effective_fee_bps(&self, global_fee_bps: u32) -> u32 {
+        self.fee_bps.unwrap_or(global_fee_bps)
+    }
+
+    /// This market's strategy instance.
+    pub fn key(&self) -> crate::StrategyKey {
+        crate::StrategyKey::new(&self.token_id, &self.strategy_id)
+    }
+
+    /// Whether `schedule` allows quoting at `now`.
+    pub fn is_scheduled(&self, now: DateTime<Utc>) -> bool {
+        self.schedule.is_empty() || self.schedule.iter().any(|w| w.contains(now.time()))
+    }
+}
+
+/// Total time allowed to fetch a config from a URL.
+pub const URL_FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+/// Time allowed to connect when fetching a config from a URL.
+pub const URL_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Where a config is read from: `-` is stdin, an `http://` or `https://`
+/// address is fetched, anything else is a file path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigSource {
+    Stdin,
+    Url(String),
+    File(PathBuf),
+}
+
+impl ConfigSource {
+    pub fn parse(arg: &str) -> Self {
+        if arg == "-" {
+            Self::Stdin
+        } else if arg.starts_with("http://") || arg.starts_with("https://") {
+            Self::Url(arg.to_string())
+        } else {
+            Self::File(PathBuf::from(arg))
+        }
+    }
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Stdin => write!(f, "stdin"),
+            Self::Url(url) => write!(f, "{url}"),
+            Self::File(path) => write!(f, "{}", path.display()),
+        }
+    }
 }
 
 impl Config {
     pub fn load(path: &Path) -> crate::Result<Self> {
+        Self::load_profile(path, None)
+    }
+
+    /// Load the config at `path` with the named `[profiles.<name>]` table
+    /// merged over it. See `from_toml_profile`.
+    pub fn load_profile(path: &Path, profile: Option<&str>) -> crate::Result<Self> {
         let contents = std::fs::read_to_string(path)
             .map_err(|e| crate::Error::Config(format!("Failed to read {}: {e}", path.display())))?;
-        let config: Config = toml::from_str(&contents)
+        Self::from_toml_profile(&contents, profile)
+    }
+
+    /// Read the whole of `reader` as TOML, e.g. a config piped on stdin.
+    pub fn from_reader(mut reader: impl Read, profile: Option<&str>) -> crate::Result<Self> {
+        let mut contents = String::new();
+        reader
+            .read_to_string(&mut contents)
+            .map_err(|e| crate::Error::Config(format!("Failed to read config: {e}")))?;
+        Self::from_toml_profile(&contents, profile)
+    }
+
+    /// Fetch the config from `url` over HTTP, giving up after
+    /// `URL_FETCH_TIMEOUT`.
+    pub async fn from_url(url: &str, profile: Option<&str>) -> crate::Result<Self> {
+        Self::fetch(url, profile, URL_FETCH_TIMEOUT).await
+    }
+
+    async fn fetch(url: &str, profile: Option<&str>, timeout: Duration) -> crate::Result<Self> {
+        let fetch_err =
+            |e: reqwest::Error| crate::Error::Config(format!("Failed to fetch {url}: {e}"));
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .connect_timeout(timeout.min(URL_CONNECT_TIMEOUT))
+            .build()
+            .map_err(fetch_err)?;
+        let contents = client
+            .get(url)
+            .send()
+            .await
+            .and_then(|resp| resp.error_for_status())
+            .map_err(fetch_err)?
+            .text()
+            .await
+            .map_err(fetch_err)?;
+        Self::from_toml_profile(&contents, profile)
+    }
+
+    /// Load from any `ConfigSource`; every source is validated the same way.
+    pub async fn load_source(source: &ConfigSource, profile: Option<&str>) -> crate::Result<Self> {
+        match source {
+            ConfigSource::Stdin => Self::from_reader(std::io::stdin().lock(), profile),
+            ConfigSource::Url(url) => Self::from_url(url, profile).await,
+            ConfigSource::File(path) => Self::load_profile(path, profile),
+        }
+    }
+
+    /// Parse and validate a config, applying `profile` if one is named.
+    ///
+    /// A profile's tables are merged key by key over the base config, so it
+    /// only needs the settings it changes. Its optional `market` table is
+    /// applied to every `[[markets]]` entry, while a `markets` array replaces
+    /// the base list outright. Naming a profile the file does not define is
+    /// an error.
+    pub fn from_toml_profile(contents: &str, profile: Option<&str>) -> crate::Result<Self> {
+        let mut table: toml::Table = toml::from_str(contents)
             .map_err(|e| crate::Error::Config(format!("Failed to parse config: {e}")))?;
+        let mut profiles = match table.remove("profiles") {
+            Some(toml::Value::Table(profiles)) => profiles,
+            Some(_) => return Err(crate::Error::Config("`profiles` must be a table".into())),
+            None => toml::Table::new(),
+        };
+
+        if let Some(name) = profile {
+            let overrides = match profiles.remove(name) {
+                Some(toml::Value::Table(overrides)) => overrides,
+                Some(_) => {
+                    return Err(crate::Error::Config(format!(
+                        "profile `{name}` must be a table"
+                    )))
+                }
+                None => {
+                    let known: Vec<&str> = profiles.keys().map(String::as_str).collect();
+                    return Err(crate::Error::Config(format!(
+                        "unknown profile `{name}` (defined: {})",
+                        if known.is_empty() {
+                            "none".to_string()
+                        } else {
+                            known.join(", ")
+                        }
+                    )));
+                }
+            };
+            apply_profile(&mut table, overrides)?;
+        }
 
+        let config: Config = toml::Value::Table(table)
+            .try_into()
+            .map_err(|e| crate::Error::Config(format!("Failed to parse config: {e}")))?;
         config.validate()?;
         Ok(config)
     }
 
+    /// Render the config as TOML that `load` parses back to an equal `Config`.
+    pub fn to_toml(&self) -> crate::Result<String> {
+        toml::to_string_pretty(self)
+            .map_err(|e| crate::Error::Config(format!("Failed to serialize config: {e}")))
+    }
+
     fn validate(&self) -> crate::Result<()> {
         if self.markets.is_empty() && self.auto_discover.is_none() {
             return Err(crate::Error::Config(
@@ -93,7 +726,98 @@ impl Config {
                     .into(),
             ));
         }
+        if self.execution.rate_limit_per_sec == Some(0) {
+            return Err(crate::Error::Config(
+                "execution.rate_limit_per_sec must be positive (omit it to disable)".into(),
+            ));
+        }
+        if self.risk.quote_refresh_interval_ms == 0 {
+            return Err(crate::Error::Config(
+                "risk.quote_refresh_interval_ms must be positive".into(),
+            ));
+        }
+        if self.risk.max_open_orders_per_market == Some(0) {
+            return Err(crate::Error::Config(
+                "risk.max_open_orders_per_market must be positive (omit it to disable)".into(),
+            ));
+        }
+        if self.risk.market_status_interval_ms == Some(0) {
+            return Err(crate::Error::Config(
+                "risk.market_status_interval_ms must be positive (omit it to disable)".into(),
+            ));
+        }
+        if self.risk.unwind_cycles == Some(0) {
+            return Err(crate::Error::Config(
+                "risk.unwind_cycles must be positive (omit it to disable)".into(),
+            ));
+        }
+        if !self.seed_positions.is_empty() && self.mode == Mode::Live {
+            return Err(crate::Error::Config(
+                "seed_positions are for paper trading only — live positions come from the venue"
+                    .into(),
+            ));
+        }
+        let mut seeded = std::collections::HashSet::new();
+        for seed in &self.seed_positions {
+            if !seeded.insert(seed.key()) {
+                return Err(crate::Error::Config(format!(
+                    "seed_positions lists {} more than once",
+                    seed.key()
+                )));
+            }
+            if seed.avg_entry <= Decimal::ZERO || seed.avg_entry >= Decimal::ONE {
+                return Err(crate::Error::Config(format!(
+                    "seed position for {} needs 0 < avg_entry < 1, got {}",
+                    seed.key(),
+                    seed.avg_entry
+                )));
+            }
+            // Auto-discovered markets are only known once discovery runs
+            if !self.markets.is_empty() && !self.markets.iter().any(|m| m.key() == seed.key()) {
+                return Err(crate::Error::Config(format!(
+                    "seed position for {} does not match any configured market",
+                    seed.key()
+                )));
+            }
+        }
+        if let MetricsBackend::Statsd { host, port } = &self.metrics {
+            if host.is_empty() || *port == 0 {
+                return Err(crate::Error::Config(
+                    "metrics: statsd needs a host and a non-zero port".into(),
+                ));
+            }
+        }
+        if self.execution.heartbeat_interval_ms == Some(0) {
+            return Err(crate::Error::Config(
+                "execution.heartbeat_interval_ms must be positive (omit it to disable)".into(),
+            ));
+        }
+        if self.execution.position_reconcile_interval_ms == Some(0) {
+            return Err(crate::Error::Config(
+                "execution.position_reconcile_interval_ms must be positive (omit it to disable)"
+                    .into(),
+            ));
+        }
+        if let Some(adverse) = &self.adverse_selection {
+            if adverse.lookback_fills == 0 {
+                return Err(crate::Error::Config(
+                    "adverse_selection.lookback_fills must be positive".into(),
+                ));
+            }
+            if adverse.max_multiplier < Decimal::ONE {
+                return Err(crate::Error::Config(
+                    "adverse_selection.max_multiplier must be at least 1".into(),
+                ));
+            }
+        }
+        let mut keys = std::collections::HashSet::new();
         for m in &self.markets {
+            if !keys.insert(m.key()) {
+                return Err(crate::Error::Config(format!(
+                    "Market '{}' repeats token {} with strategy_id '{}' — give each strategy on a token its own strategy_id",
+                    m.name, m.token_id, m.strategy_id
+                )));
+            }
             if m.spread_bps == 0 {
                 return Err(crate::Error::Config(format!(
                     "Market '{}' has zero spread",
@@ -106,15 +830,228 @@ impl Config {
                     m.name
                 )));
             }
+            if let Some((low, high)) = m.quote_price_band.filter(|(low, high)| low >= high) {
+                return Err(crate::Error::Config(format!(
+                    "Market '{}' has an empty quote_price_band [{low}, {high}]",
+                    m.name
+                )));
+            }
+            // Share bounds can only be checked against a size given in shares
+            let share_size = m.size_mode == SizeMode::Shares;
+            if m.min_order_size <= Decimal::ZERO || (share_size && m.min_order_size > m.size) {
+                return Err(crate::Error::Config(format!(
+                    "Market '{}' needs 0 < min_order_size ({}) <= size ({})",
+                    m.name, m.min_order_size, m.size
+                )));
+            }
+            if let Some(max) = m.max_order_size.filter(|max| share_size && *max < m.size) {
+                return Err(crate::Error::Config(format!(
+                    "Market '{}' has max_order_size {} below size {}",
+                    m.name, max, m.size
+                )));
+            }
+            // Shares cost at most $1, so anything under min_notional shares
+            // (or dollars of notional) is below the minimum at every price
+            if m.min_notional < Decimal::ZERO {
+                return Err(crate::Error::Config(format!(
+                    "Market '{}' has negative min_notional {}",
+                    m.name, m.min_notional
+                )));
+            }
+            if m.size < m.min_notional {
+                return Err(crate::Error::Config(format!(
+                    "Market '{}' size {} is below the ${} minimum order value at any price",
+                    m.name, m.size, m.min_notional
+                )));
+            }
+            if m.tick_size <= Decimal::ZERO || m.tick_size >= Decimal::ONE {
+                return Err(crate::Error::Config(format!(
+                    "Market '{}' has tick_size {} outside (0, 1)",
+                    m.name, m.tick_size
+                )));
+            }
+            if let Some(t) = m.one_sided_threshold {
+                if t <= Decimal::ZERO {
+                    return Err(crate::Error::Config(format!(
+                        "Market '{}' has non-positive one_sided_threshold",
+                        m.name
+                    )));
+                }
+            }
+            if m.quote_anchor == QuoteAnchor::LastFill && m.anchor_decay_ms == 0 {
+                return Err(crate::Error::Config(format!(
+                    "Market '{}' needs a positive anchor_decay_ms for the last_fill quote anchor",
+                    m.name
+                )));
+            }
+            if m.schedule.iter().any(|w| w.start == w.end) {
+                return Err(crate::Error::Config(format!(
+                    "Market '{}' has an empty schedule window (start == end)",
+                    m.name
+                )));
+            }
+            if m.size_jitter_pct < Decimal::ZERO || m.size_jitter_pct >= Decimal::ONE {
+                return Err(crate::Error::Config(format!(
+                    "Market '{}' size_jitter_pct must be in [0, 1)",
+                    m.name
+                )));
+            }
+            if m.reduce_side_aggression < Decimal::ZERO {
+                return Err(crate::Error::Config(format!(
+                    "Market '{}' reduce_side_aggression must not be negative",
+                    m.name
+                )));
+            }
+            if m.skew_curve == SkewCurve::Tanh
+                && (m.max_skew < Decimal::ZERO || m.skew_steepness <= Decimal::ZERO)
+            {
+                return Err(crate::Error::Config(format!(
+                    "Market '{}' needs max_skew >= 0 and skew_steepness > 0 for the tanh skew curve",
+                    m.name
+                )));
+            }
+            if let Some(t) = m.reduce_only_threshold {
+                if t <= Decimal::ZERO {
+                    return Err(crate::Error::Config(format!(
+                        "Market '{}' has non-positive reduce_only_threshold",
+                        m.name
+                    )));
+                }
+            }
         }
         Ok(())
     }
 }
 
+/// Merge a profile's `overrides` into the base config `table`.
+fn apply_profile(table: &mut toml::Table, mut overrides: toml::Table) -> crate::Result<()> {
+    let market = overrides.remove("market");
+    merge_tables(table, overrides);
+
+    let Some(market) = market else { return Ok(()) };
+    let toml::Value::Table(market) = market else {
+        return Err(crate::Error::Config(
+            "profile `market` must be a table".into(),
+        ));
+    };
+    if let Some(toml::Value::Array(markets)) = table.get_mut("markets") {
+        for entry in markets.iter_mut() {
+            if let toml::Value::Table(entry) = entry {
+                merge_tables(entry, market.clone());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Recursively merge `overrides` into `base`; tables merge, anything else replaces.
+fn merge_tables(base: &mut toml::Table, overrides: toml::Table) {
+    for (key, value) in overrides {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(existing)), toml::Value::Table(inner)) => {
+                merge_tables(existing, inner)
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const MINIMAL: &str = r#"
+        mode = "paper"
+
+        [risk]
+        max_position_per_market = 100.0
+        max_total_exposure = 500.0
+        max_unrealized_loss = 50.0
+        quote_refresh_interval_ms = 1000
+
+        [[markets]]
+        name = "Piped"
+        token_id = "tok_piped"
+        spread_bps = 300
+        size = 10.0
+        max_inventory = 50.0
+        skew_factor = 0.001
+    "#;
+
+    #[test]
+    fn config_source_from_argument() {
+        assert_eq!(ConfigSource::parse("-"), ConfigSource::Stdin);
+        assert_eq!(
+            ConfigSource::parse("https://example.com/eutrader.toml"),
+            ConfigSource::Url("https://example.com/eutrader.toml".into())
+        );
+        assert_eq!(
+            ConfigSource::parse("config.toml"),
+            ConfigSource::File("config.toml".into())
+        );
+    }
+
+    #[test]
+    fn reads_config_from_a_reader() {
+        let config = Config::from_reader(MINIMAL.as_bytes(), None).unwrap();
+        assert_eq!(config.markets[0].token_id, "tok_piped");
+
+        // Validated like a file: no markets and no auto-discover is rejected
+        let without_markets = MINIMAL.split("[[markets]]").next().unwrap();
+        assert!(Config::from_reader(without_markets.as_bytes(), None).is_err());
+    }
+
+    #[tokio::test]
+    async fn fetches_config_over_http() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut buf = vec![0u8; 4096];
+                let n = stream.read(&mut buf).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&buf[..n]);
+                let response = if request.starts_with("GET /eutrader.toml ") {
+                    format!(
+                        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{MINIMAL}",
+                        MINIMAL.len()
+                    )
+                } else {
+                    "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                        .to_string()
+                };
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+
+        let source = ConfigSource::parse(&format!("http://{addr}/eutrader.toml"));
+        let config = Config::load_source(&source, None).await.unwrap();
+        assert_eq!(config.markets[0].name, "Piped");
+
+        let missing = Config::from_url(&format!("http://{addr}/missing.toml"), None).await;
+        assert!(matches!(missing, Err(crate::Error::Config(_))));
+    }
+
+    #[tokio::test]
+    async fn config_fetch_gives_up_on_a_silent_server() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        // Accepts the connection and never answers
+        tokio::spawn(async move {
+            let mut held = Vec::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                held.push(stream);
+            }
+        });
+
+        let url = format!("http://{addr}/eutrader.toml");
+        let result = Config::fetch(&url, None, Duration::from_millis(100)).await;
+        assert!(matches!(result, Err(crate::Error::Config(_))));
+    }
+
     #[test]
     fn parses_valid_config() {
         let toml = r#"
@@ -139,6 +1076,55 @@ mod tests {
         assert_eq!(config.mode, Mode::Paper);
         assert_eq!(config.markets.len(), 1);
         assert_eq!(config.markets[0].spread_bps, 300);
+        assert_eq!(config.fee_bps, 0);
+        assert_eq!(config.markets[0].fee_bps, None);
+    }
+
+    #[test]
+    fn example_config_round_trips() {
+        let config: Config = toml::from_str(include_str!("../../../config.toml")).unwrap();
+        let rendered = config.to_toml().unwrap();
+        let reparsed: Config = toml::from_str(&rendered).unwrap();
+        assert_eq!(reparsed, config);
+
+        let mut live = config.clone();
+        live.mode = Mode::Live;
+        assert!(live.to_toml().unwrap().contains("mode = \"live\""));
+    }
+
+    #[test]
+    fn market_fee_overrides_global() {
+        let toml = r#"
+            mode = "paper"
+            fee_bps = 10
+
+            [risk]
+            max_position_per_market = 100.0
+            max_total_exposure = 500.0
+            max_unrealized_loss = 50.0
+            quote_refresh_interval_ms = 1000
+
+            [[markets]]
+            name = "Default fee"
+            token_id = "abc123"
+            spread_bps = 300
+            size = 10.0
+            max_inventory = 50.0
+            skew_factor = 0.001
+
+            [[markets]]
+            name = "Override fee"
+            token_id = "def456"
+            spread_bps = 300
+            size = 10.0
+            max_inventory = 50.0
+            skew_factor = 0.001
+            fee_bps = 25
+        "#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        assert_eq!(config.markets[0].effective_fee_bps(config.fee_bps), 10);
+        assert_eq!(config.markets[1].effective_fee_bps(config.fee_bps), 25);
     }
 
     #[test]
@@ -156,4 +1142,187 @@ mod tests {
         let config: Config = toml::from_str(toml).unwrap();
         assert!(config.validate().is_err());
     }
+
+    #[test]
+    fn rejects_min_order_size_above_max() {
+        let toml = r#"
+            mode = "paper"
+
+            [risk]
+            max_position_per_market = 100.0
+            max_total_exposure = 500.0
+            max_unrealized_loss = 50.0
+            quote_refresh_interval_ms = 1000
+
+            [[markets]]
+            name = "Test"
+            token_id = "tok"
+            spread_bps = 400
+            size = 10.0
+            max_inventory = 50.0
+            skew_factor = 0.001
+            min_order_size = 12.0
+            max_order_size = 8.0
+        "#;
+
+        let mut config: Config = toml::from_str(toml).unwrap();
+        assert!(config.validate().is_err());
+
+        config.markets[0].min_order_size = Decimal::new(5, 0);
+        config.markets[0].max_order_size = Some(Decimal::new(20, 0));
+        assert!(config.validate().is_ok());
+
+        // The same token twice needs a strategy_id to tell the two apart
+        config.markets.push(config.markets[0].clone());
+        assert!(config.validate().is_err());
+        config.markets[1].strategy_id = "wide".into();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.markets[1].key().to_string(), "tok/wide");
+    }
+
+    #[test]
+    fn seed_positions_are_checked_against_markets() {
+        let toml = r#"
+            mode = "paper"
+
+            [risk]
+            max_position_per_market = 100.0
+            max_total_exposure = 500.0
+            max_unrealized_loss = 50.0
+            quote_refresh_interval_ms = 1000
+
+            [[markets]]
+            name = "Test"
+            token_id = "tok"
+            spread_bps = 400
+            size = 10.0
+            max_inventory = 50.0
+            skew_factor = 0.001
+
+            [[seed_positions]]
+            token_id = "tok"
+            net_position = 25.0
+            avg_entry = 0.42
+        "#;
+
+        let mut config: Config = toml::from_str(toml).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.seed_positions[0].net_position, Decimal::new(25, 0));
+
+        config.seed_positions[0].token_id = "other".into();
+        assert!(config.validate().is_err());
+        config.seed_positions[0].token_id = "tok".into();
+        config.seed_positions[0].avg_entry = Decimal::ONE;
+        assert!(config.validate().is_err());
+        config.seed_positions[0].avg_entry = Decimal::new(42, 2);
+        config.mode = Mode::Live;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn schedule_windows_parse_and_may_wrap_midnight() {
+        let toml = r#"
+            mode = "paper"
+
+            [risk]
+            max_position_per_market = 100.0
+            max_total_exposure = 500.0
+            max_unrealized_loss = 50.0
+            quote_refresh_interval_ms = 1000
+
+            [[markets]]
+            name = "Test"
+            token_id = "tok"
+            spread_bps = 400
+            size = 10.0
+            max_inventory = 50.0
+            skew_factor = 0.001
+            schedule = [{ start = "13:30", end = "20:00" }, { start = "23:00", end = "01:00" }]
+        "#;
+
+        let mut config: Config = toml::from_str(toml).unwrap();
+        assert!(config.validate().is_ok());
+        let market = &config.markets[0];
+        let at = |hm: &str| {
+            format!("2026-03-02T{hm}:00Z")
+                .parse::<DateTime<Utc>>()
+                .unwrap()
+        };
+        assert!(market.is_scheduled(at("13:30")));
+        assert!(!market.is_scheduled(at("20:00")));
+        assert!(!market.is_scheduled(at("09:00")));
+        assert!(market.is_scheduled(at("23:30")));
+        assert!(market.is_scheduled(at("00:30")));
+
+        config.markets[0].schedule[0].end = config.markets[0].schedule[0].start;
+        assert!(config.validate().is_err());
+    }
+
+    const PROFILED: &str = r#"
+        mode = "paper"
+
+        [risk]
+        max_position_per_market = 100.0
+        max_total_exposure = 500.0
+        max_unrealized_loss = 50.0
+        quote_refresh_interval_ms = 1000
+
+        [[markets]]
+        name = "A"
+        token_id = "tok_a"
+        spread_bps = 400
+        size = 10.0
+        max_inventory = 50.0
+        skew_factor = 0.001
+
+        [[markets]]
+        name = "B"
+        token_id = "tok_b"
+        spread_bps = 300
+        size = 10.0
+        max_inventory = 50.0
+        skew_factor = 0.001
+
+        [profiles.aggressive.risk]
+        max_position_per_market = 250.0
+
+        [profiles.aggressive.market]
+        spread_bps = 150
+
+        [profiles.conservative]
+        fee_bps = 5
+    "#;
+
+    #[test]
+    fn profile_overrides_merge_over_base() {
+        let base = Config::from_toml_profile(PROFILED, None).unwrap();
+        assert_eq!(base.risk.max_position_per_market, Decimal::new(100, 0));
+
+        let aggressive = Config::from_toml_profile(PROFILED, Some("aggressive")).unwrap();
+        assert_eq!(
+            aggressive.risk.max_position_per_market,
+            Decimal::new(250, 0)
+        );
+        // Untouched risk settings and the market list are inherited
+        assert_eq!(
+            aggressive.risk.max_total_exposure,
+            base.risk.max_total_exposure
+        );
+        assert_eq!(aggressive.markets.len(), 2);
+        assert_eq!(aggressive.markets[1].token_id, "tok_b");
+        assert!(aggressive.markets.iter().all(|m| m.spread_bps == 150));
+
+        let conservative = Config::from_toml_profile(PROFILED, Some("conservative")).unwrap();
+        assert_eq!(conservative.fee_bps, 5);
+        assert_eq!(conservative.markets, base.markets);
+    }
+
+    #[test]
+    fn rejects_unknown_profile() {
+        let err = Config::from_toml_profile(PROFILED, Some("yolo"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("unknown profile `yolo`"), "{err}");
+        assert!(err.contains("aggressive, conservative"), "{err}");
+    }
 }
@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+/// Environment variable holding the CLOB API key.
+pub const API_KEY_VAR: &str = "POLY_API_KEY";
+/// Environment variable holding the CLOB API secret.
+pub const SECRET_VAR: &str = "POLY_SECRET";
+/// Environment variable holding the CLOB API passphrase.
+pub const PASSPHRASE_VAR: &str = "POLY_PASSPHRASE";
+/// Environment variable holding the wallet private key used to sign orders.
+pub const PRIVATE_KEY_VAR: &str = "POLY_PRIVATE_KEY";
+
+const ALL_VARS: [&str; 4] = [API_KEY_VAR, SECRET_VAR, PASSPHRASE_VAR, PRIVATE_KEY_VAR];
+
+/// API credentials and signing key for live trading.
+///
+/// Never read from `config.toml`. `Debug` redacts every value so the struct
+/// is safe to log.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Credentials {
+    api_key: String,
+    secret: String,
+    passphrase: String,
+    private_key: String,
+}
+
+impl Credentials {
+    /// Read credentials from the `POLY_*` environment variables.
+    pub fn from_env() -> crate::Result<Self> {
+        Self::from_lookup(|name| std::env::var(name).ok(), "environment")
+    }
+
+    /// Read credentials from a file of `POLY_*=value` lines.
+    ///
+    /// Blank lines and `#` comments are ignored; values may be quoted.
+    pub fn from_file(path: &Path) -> crate::Result<Self> {
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            crate::Error::Config(format!(
+                "Failed to read credentials file {}: {e}",
+                path.display()
+            ))
+        })?;
+        let vars = parse_env_file(&contents);
+        Self::from_lookup(|name| vars.get(name).cloned(), &path.display().to_string())
+    }
+
+    /// Read credentials from `path` if given, otherwise from the environment.
+    pub fn load(path: Option<&Path>) -> crate::Result<Self> {
+        match path {
+            Some(path) => Self::from_file(path),
+            None => Self::from_env(),
+        }
+    }
+
+    /// Build credentials from `lookup`, failing with every missing variable
+    /// named. `source` describes where the values came from for the error.
+    pub fn from_lookup(
+        lookup: impl Fn(&str) -> Option<String>,
+        source: &str,
+    ) -> crate::Result<Self> {
+        let mut values = HashMap::new();
+        let mut missing = Vec::new();
+        for name in ALL_VARS {
+            match lookup(name)
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+            {
+                Some(value) => {
+                    values.insert(name, value);
+                }
+                None => missing.push(name),
+            }
+        }
+        if !missing.is_empty() {
+            return Err(crate::Error::Config(format!(
+                "Live mode requires credentials; missing {} in {source}",
+                missing.join(", ")
+            )));
+        }
+
+        let mut take = |name| values.remove(name).unwrap_or_default();
+        Ok(Self {
+            api_key: take(API_KEY_VAR),
+            secret: take(SECRET_VAR),
+            passphrase: take(PASSPHRASE_VAR),
+            private_key: take(PRIVATE_KEY_VAR),
+        })
+    }
+
+    pub fn api_key(&self) -> &str {
+        &self.api_key
+    }
+
+    pub fn secret(&self) -> &str {
+        &self.secret
+    }
+
+    pub fn passphrase(&self) -> &str {
+        &self.passphrase
+    }
+
+    pub fn private_key(&self) -> &str {
+        &self.private_key
+    }
+}
+
+impl fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Credentials")
+            .field("api_key", &"<redacted>")
+            .field("secret", &"<redacted>")
+            .field("passphrase", &"<redacted>")
+            .field("private_key", &"<redacted>")
+            .finish()
+    }
+}
+
+/// Parse `KEY=value` lines, skipping blanks and comments and unquoting values.
+fn parse_env_file(contents: &str) -> HashMap<String, String> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let (key, value) = line.split_once('=')?;
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+                .unwrap_or(value);
+            Some((key.trim().to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn full() -> HashMap<&'static str, String> {
+        ALL_VARS
+            .iter()
+            .map(|name| (*name, format!("{name}-value")))
+            .collect()
+    }
+
+    #[test]
+    fn missing_variables_are_named_in_config_error() {
+        let mut vars = full();
+        vars.remove(SECRET_VAR);
+        vars.insert(PRIVATE_KEY_VAR, "  ".into());
+
+        let err =
+            Credentials::from_lookup(|name| vars.get(name).cloned(), "environment").unwrap_err();
+        let crate::Error::Config(msg) = err else {
+            panic!("expected a config error, got {err:?}");
+        };
+        assert!(
+            msg.contains(SECRET_VAR) && msg.contains(PRIVATE_KEY_VAR),
+            "{msg}"
+        );
+        assert!(!msg.contains(API_KEY_VAR), "{msg}");
+    }
+
+    #[test]
+    fn debug_output_redacts_secrets() {
+        let vars = full();
+        let creds = Credentials::from_lookup(|name| vars.get(name).cloned(), "test").unwrap();
+        assert_eq!(creds.secret(), "POLY_SECRET-value");
+
+        let debug = format!("{creds:?}");
+        assert!(!debug.contains("-value"), "{debug}");
+        assert!(debug.contains("<redacted>"));
+    }
+
+    #[test]
+    fn parses_credentials_file_lines() {
+        let vars = parse_env_file(
+            "# live creds\n\
+             POLY_API_KEY=key\n\
+             export POLY_SECRET = \"s3cr=t\"\n\
+             \n\
+             POLY_PASSPHRASE='pass'\n",
+        );
+        assert_eq!(vars["POLY_API_KEY"], "key");
+        assert_eq!(vars["POLY_SECRET"], "s3cr=t");
+        assert_eq!(vars["POLY_PASSPHRASE"], "pass");
+        assert!(!vars.contains_key("POLY_PRIVATE_KEY"));
+    }
+}
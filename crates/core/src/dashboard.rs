@@ -1,14 +1,16 @@
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
-use std::collections::HashMap;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
 use std::sync::{Arc, RwLock};
 
-use crate::{Fill, Side};
+use crate::{system_clock, PnlRounding, SharedClock, Side, DEFAULT_TICK_SIZE};
 
 /// Per-market state displayed on the dashboard.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct MarketRow {
     pub name: String,
+    /// Strategy key of the market, as `StrategyKey` displays it
     pub token_id: String,
     pub midpoint: Decimal,
     pub our_bid: Decimal,
@@ -19,60 +21,224 @@ pub struct MarketRow {
     pub unrealized_pnl: Decimal,
     pub fill_count: u64,
     pub last_update: DateTime<Utc>,
+    /// Why the market is not being quoted, if it isn't
+    pub quote_status: Option<String>,
+    /// Orders resting for this market after the last reconcile
+    pub open_orders: usize,
+    /// Price increment of the market, for displaying prices
+    pub tick_size: Decimal,
+    /// Realized PnL from edge versus mid, net of fees
+    pub spread_pnl: Decimal,
+    /// Realized PnL from mid moves while holding inventory
+    pub inventory_pnl: Decimal,
+    /// Estimated chance the resting bid fills within the next minute
+    pub bid_fill_prob: Option<f64>,
+    /// Estimated chance the resting ask fills within the next minute
+    pub ask_fill_prob: Option<f64>,
 }
 
 /// A recent fill for the activity log.
 #[derive(Debug, Clone)]
 pub struct FillRow {
     pub timestamp: DateTime<Utc>,
+    /// Strategy key of the filled order, as `StrategyKey` displays it
+    pub token_id: String,
     pub market_name: String,
     pub side: Side,
     pub price: Decimal,
     pub size: Decimal,
+    /// Realized PnL of this fill's market after the fill.
     pub pnl_after: Decimal,
 }
 
+/// Rolling latency percentiles, in milliseconds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct LatencyStats {
+    /// Samples the percentiles were computed over
+    pub samples: usize,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+}
+
+/// Default number of PnL samples kept for the history chart.
+pub const DEFAULT_PNL_HISTORY_LEN: usize = 300;
+
+/// Default number of recent fills kept for the activity log.
+pub const DEFAULT_MAX_FILLS: usize = 50;
+
 /// Shared dashboard state, updated by the engine and read by the TUI.
 #[derive(Debug, Clone)]
 pub struct DashboardState {
     pub mode: String,
     pub uptime_start: DateTime<Utc>,
     pub markets: HashMap<String, MarketRow>,
-    pub recent_fills: Vec<FillRow>,
+    /// Most recent fills, oldest first.
+    pub recent_fills: VecDeque<FillRow>,
+    /// Maximum number of fills kept in `recent_fills`.
+    pub max_fills: usize,
     pub total_realized_pnl: Decimal,
+    pub total_unrealized_pnl: Decimal,
+    /// Spread and inventory components of `total_realized_pnl`
+    pub total_spread_pnl: Decimal,
+    pub total_inventory_pnl: Decimal,
     pub total_fills: u64,
+    /// Bounded history of total realized PnL samples, oldest first.
+    pub pnl_history: VecDeque<(DateTime<Utc>, Decimal)>,
+    /// Maximum number of samples kept in `pnl_history`.
+    pub pnl_history_len: usize,
+    /// Orders shown are dry-run intents that were never sent.
+    pub dry_run: bool,
+    /// The kill switch tripped and quoting is halted for the session.
+    pub killed: bool,
+    /// Time from snapshot received to orders reconciled.
+    pub cycle_latency: LatencyStats,
+    /// Round-trip time of `place_order` calls.
+    pub order_latency: LatencyStats,
+    /// How PnL totals are rounded for display.
+    pub pnl_rounding: PnlRounding,
+    /// Time source for uptime and PnL samples.
+    pub clock: SharedClock,
+    /// Timestamp of the latest snapshot the engine handled, quoted or not.
+    pub last_snapshot: Option<DateTime<Utc>>,
 }
 
 impl DashboardState {
     pub fn new(mode: &str) -> Self {
+        let clock = system_clock();
         Self {
             mode: mode.to_string(),
-            uptime_start: Utc::now(),
+            uptime_start: clock.now(),
             markets: HashMap::new(),
-            recent_fills: Vec::new(),
+            recent_fills: VecDeque::with_capacity(DEFAULT_MAX_FILLS),
+            max_fills: DEFAULT_MAX_FILLS,
             total_realized_pnl: Decimal::ZERO,
+            total_unrealized_pnl: Decimal::ZERO,
+            total_spread_pnl: Decimal::ZERO,
+            total_inventory_pnl: Decimal::ZERO,
             total_fills: 0,
+            pnl_history: VecDeque::with_capacity(DEFAULT_PNL_HISTORY_LEN),
+            pnl_history_len: DEFAULT_PNL_HISTORY_LEN,
+            dry_run: false,
+            killed: false,
+            cycle_latency: LatencyStats::default(),
+            order_latency: LatencyStats::default(),
+            pnl_rounding: PnlRounding::default(),
+            clock,
+            last_snapshot: None,
         }
     }
 
+    /// Read time from `clock`, restarting the uptime from its current time.
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.uptime_start = clock.now();
+        self.clock = clock;
+        self
+    }
+
+    /// Time since the session started.
+    pub fn uptime(&self) -> chrono::Duration {
+        self.clock.now() - self.uptime_start
+    }
+
+    /// Format a PnL amount with the configured rounding, e.g. `-0.1235`.
+    pub fn format_pnl(&self, value: Decimal) -> String {
+        self.pnl_rounding.round(value).to_string()
+    }
+
+    /// Set the number of PnL samples kept for the history chart.
+    pub fn with_pnl_history_len(mut self, len: usize) -> Self {
+        self.pnl_history_len = len;
+        self.pnl_history.truncate(len);
+        self
+    }
+
+    /// Set the number of recent fills kept for the activity log.
+    pub fn with_max_fills(mut self, max_fills: usize) -> Self {
+        self.max_fills = max_fills;
+        self.recent_fills.truncate(max_fills);
+        self
+    }
+
     pub fn update_market(&mut self, row: MarketRow) {
         self.markets.insert(row.token_id.clone(), row);
     }
 
+    /// Record a fill against its market row and refresh totals.
+    ///
+    /// Market rows are the single source of truth for totals; a fill for a
+    /// market without a row yet creates a placeholder row.
     pub fn add_fill(&mut self, fill: FillRow) {
-        self.total_fills += 1;
-        self.total_realized_pnl = fill.pnl_after;
-        self.recent_fills.push(fill);
-        // Keep only the last 50 fills
-        if self.recent_fills.len() > 50 {
-            self.recent_fills.remove(0);
+        let row = self
+            .markets
+            .entry(fill.token_id.clone())
+            .or_insert_with(|| MarketRow {
+                name: fill.market_name.clone(),
+                token_id: fill.token_id.clone(),
+                midpoint: Decimal::ZERO,
+                our_bid: Decimal::ZERO,
+                our_ask: Decimal::ZERO,
+                spread: Decimal::ZERO,
+                inventory: Decimal::ZERO,
+                realized_pnl: Decimal::ZERO,
+                unrealized_pnl: Decimal::ZERO,
+                fill_count: 0,
+                last_update: fill.timestamp,
+                quote_status: None,
+                open_orders: 0,
+                tick_size: DEFAULT_TICK_SIZE,
+                spread_pnl: Decimal::ZERO,
+                inventory_pnl: Decimal::ZERO,
+                bid_fill_prob: None,
+                ask_fill_prob: None,
+            });
+        row.realized_pnl = fill.pnl_after;
+        row.fill_count += 1;
+
+        if self.max_fills > 0 {
+            while self.recent_fills.len() >= self.max_fills {
+                self.recent_fills.pop_front();
+            }
+            self.recent_fills.push_back(fill);
         }
+        self.refresh_totals();
+    }
+
+    /// Note that the engine handled a snapshot taken at `at`, whether or not
+    /// it updated a market row.
+    pub fn record_snapshot(&mut self, at: DateTime<Utc>) {
+        self.last_snapshot = self.last_snapshot.max(Some(at));
+    }
+
+    /// Timestamp of the most recent snapshot seen for any market.
+    pub fn last_snapshot_at(&self) -> Option<DateTime<Utc>> {
+        self.markets
+            .values()
+            .map(|m| m.last_update)
+            .max()
+            .max(self.last_snapshot)
     }
 
-    /// Recalculate totals from market rows.
+    /// Recalculate totals from market rows and record a PnL history sample.
     pub fn refresh_totals(&mut self) {
         self.total_realized_pnl = self.markets.values().map(|m| m.realized_pnl).sum();
+        self.total_unrealized_pnl = self.markets.values().map(|m| m.unrealized_pnl).sum();
+        self.total_spread_pnl = self.markets.values().map(|m| m.spread_pnl).sum();
+        self.total_inventory_pnl = self.markets.values().map(|m| m.inventory_pnl).sum();
         self.total_fills = self.markets.values().map(|m| m.fill_count).sum();
+        self.record_pnl_sample(self.clock.now());
+    }
+
+    /// Append the current total realized PnL to the bounded history.
+    pub fn record_pnl_sample(&mut self, timestamp: DateTime<Utc>) {
+        if self.pnl_history_len == 0 {
+            return;
+        }
+        while self.pnl_history.len() >= self.pnl_history_len {
+            self.pnl_history.pop_front();
+        }
+        self.pnl_history
+            .push_back((timestamp, self.total_realized_pnl));
     }
 }
 
@@ -82,3 +248,134 @@ pub type SharedDashboard = Arc<RwLock<DashboardState>>;
 pub fn new_shared_dashboard(mode: &str) -> SharedDashboard {
     Arc::new(RwLock::new(DashboardState::new(mode)))
 }
+
+/// Create a shared dashboard that keeps `pnl_history_len` PnL samples and
+/// the last `max_fills` fills.
+pub fn new_shared_dashboard_with_history(
+    mode: &str,
+    pnl_history_len: usize,
+    max_fills: usize,
+) -> SharedDashboard {
+    Arc::new(RwLock::new(
+        DashboardState::new(mode)
+            .with_pnl_history_len(pnl_history_len)
+            .with_max_fills(max_fills),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn uptime_and_samples_follow_the_clock() {
+        let start = Utc::now();
+        let clock = crate::MockClock::new(start);
+        let mut state = DashboardState::new("Paper").with_clock(Arc::new(clock.clone()));
+
+        clock.advance(chrono::Duration::seconds(90));
+        assert_eq!(state.uptime().num_seconds(), 90);
+        state.refresh_totals();
+        assert_eq!(
+            state.pnl_history.back().unwrap().0,
+            start + chrono::Duration::seconds(90)
+        );
+    }
+
+    #[test]
+    fn pnl_history_is_bounded() {
+        let mut state = DashboardState::new("Paper").with_pnl_history_len(3);
+        for i in 0..5 {
+            state.total_realized_pnl = Decimal::from(i);
+            state.record_pnl_sample(Utc::now());
+        }
+
+        assert_eq!(state.pnl_history.len(), 3);
+        let values: Vec<Decimal> = state.pnl_history.iter().map(|(_, v)| *v).collect();
+        assert_eq!(values, vec![dec!(2), dec!(3), dec!(4)]);
+    }
+
+    fn row(token_id: &str, realized: Decimal, unrealized: Decimal, fills: u64) -> MarketRow {
+        MarketRow {
+            name: token_id.into(),
+            token_id: token_id.into(),
+            midpoint: dec!(0.50),
+            our_bid: dec!(0.48),
+            our_ask: dec!(0.52),
+            spread: dec!(0.04),
+            inventory: Decimal::ZERO,
+            realized_pnl: realized,
+            unrealized_pnl: unrealized,
+            fill_count: fills,
+            last_update: Utc::now(),
+            quote_status: None,
+            open_orders: 2,
+            tick_size: dec!(0.01),
+            spread_pnl: Decimal::ZERO,
+            inventory_pnl: Decimal::ZERO,
+            bid_fill_prob: None,
+            ask_fill_prob: None,
+        }
+    }
+
+    fn fill(token_id: &str, pnl_after: Decimal) -> FillRow {
+        FillRow {
+            timestamp: Utc::now(),
+            token_id: token_id.into(),
+            market_name: token_id.into(),
+            side: Side::Sell,
+            price: dec!(0.52),
+            size: dec!(10),
+            pnl_after,
+        }
+    }
+
+    #[test]
+    fn fills_across_markets_sum_into_totals() {
+        let mut state = DashboardState::new("Paper");
+        state.update_market(row("a", Decimal::ZERO, dec!(0.5), 0));
+        state.update_market(row("b", Decimal::ZERO, dec!(-0.2), 0));
+
+        state.add_fill(fill("a", dec!(1.25)));
+        state.add_fill(fill("b", dec!(0.75)));
+        assert_eq!(state.total_realized_pnl, dec!(2.00));
+        assert_eq!(state.total_fills, 2);
+        assert_eq!(state.total_unrealized_pnl, dec!(0.3));
+
+        // The engine's per-tick row update agrees with the fill path
+        state.update_market(row("a", dec!(1.25), dec!(0.5), 1));
+        state.refresh_totals();
+        assert_eq!(state.total_realized_pnl, dec!(2.00));
+        assert_eq!(state.total_fills, 2);
+    }
+
+    #[test]
+    fn recent_fills_keep_the_newest_up_to_cap() {
+        let mut state = DashboardState::new("Paper").with_max_fills(100);
+        for i in 0..200 {
+            state.add_fill(fill("a", Decimal::from(i)));
+        }
+
+        assert_eq!(state.recent_fills.len(), 100);
+        assert_eq!(state.recent_fills.front().unwrap().pnl_after, dec!(100));
+        assert_eq!(state.recent_fills.back().unwrap().pnl_after, dec!(199));
+        // Every fill still counts toward the market's totals
+        assert_eq!(state.total_fills, 200);
+    }
+
+    #[test]
+    fn fill_for_unknown_market_creates_row() {
+        let mut state = DashboardState::new("Paper");
+        state.add_fill(fill("a", dec!(1)));
+        assert_eq!(state.markets["a"].fill_count, 1);
+        assert_eq!(state.total_realized_pnl, dec!(1));
+    }
+
+    #[test]
+    fn zero_length_history_records_nothing() {
+        let mut state = DashboardState::new("Paper").with_pnl_history_len(0);
+        state.refresh_totals();
+        assert!(state.pnl_history.is_empty());
+    }
+}
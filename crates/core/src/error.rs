@@ -5,8 +5,21 @@ pub enum Error {
     #[error("Config error: {0}")]
     Config(String),
 
-    #[error("Feed error: {0}")]
-    Feed(String),
+    /// Network trouble or a server-side failure; likely to clear on retry
+    #[error("Feed unavailable: {0}")]
+    FeedTransient(String),
+
+    /// The venue is throttling requests (HTTP 429)
+    #[error("Feed rate limited: {0}")]
+    FeedRateLimited(String),
+
+    /// The requested market or resource does not exist (HTTP 404)
+    #[error("Feed resource not found: {0}")]
+    FeedNotFound(String),
+
+    /// A response arrived but could not be understood
+    #[error("Feed parse error: {0}")]
+    FeedParse(String),
 
     #[error("Execution error: {0}")]
     Execution(String),
@@ -26,3 +39,11 @@ pub enum Error {
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 }
+
+impl Error {
+    /// Whether repeating the same request could succeed: transient feed
+    /// failures and rate limiting are, everything else is not.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Error::FeedTransient(_) | Error::FeedRateLimited(_))
+    }
+}
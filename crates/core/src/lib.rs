@@ -1,10 +1,28 @@
+pub mod clock;
 pub mod config;
+pub mod credentials;
 pub mod dashboard;
 pub mod error;
+pub mod price;
+pub mod report;
+pub mod state;
+pub mod text;
 pub mod types;
 
-pub use config::{AutoDiscoverConfig, Config, MarketConfig, Mode, RiskConfig};
+pub use clock::{system_clock, Clock, MockClock, SharedClock, SystemClock};
+pub use config::{
+    AdverseSelectionConfig, AutoDiscoverConfig, Config, ConfigSource, ExecutionConfig,
+    MarketConfig, MetricsBackend, Mode, PnlRounding, QuoteWindow, RiskConfig, SeedPosition,
+};
+pub use credentials::Credentials;
 pub use error::Error;
+pub use price::{
+    floor_to_lot, format_price, min_order_size_at, min_size_for_notional, round_money,
+    round_money_dp, round_to_tick, shares_for_notional, DEFAULT_TICK_SIZE, MIN_ORDER_VALUE,
+    MONEY_DECIMALS, SIZE_LOT,
+};
+pub use report::{FillStats, MarketReport, PerformanceMetrics, PnlSample, SessionReport};
+pub use text::truncate;
 pub use types::*;
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,172 @@
+use rust_decimal::{Decimal, RoundingStrategy};
+use rust_decimal_macros::dec;
+
+use crate::RoundingMode;
+
+/// Minimum price increment of a standard Polymarket market.
+pub const DEFAULT_TICK_SIZE: Decimal = dec!(0.01);
+
+/// Smallest size increment, in shares, that Polymarket accepts.
+pub const SIZE_LOT: Decimal = dec!(0.01);
+
+/// Smallest notional, in USDC, that Polymarket accepts for an order.
+pub const MIN_ORDER_VALUE: Decimal = dec!(1);
+
+/// Fewest shares an order at `price` needs to meet `MIN_ORDER_VALUE`,
+/// rounded up to the venue's 0.01-share lot.
+pub fn min_order_size_at(price: Decimal) -> Decimal {
+    min_size_for_notional(MIN_ORDER_VALUE, price)
+}
+
+/// Fewest shares an order at `price` needs to be worth `notional`, rounded up
+/// to the venue's 0.01-share lot.
+pub fn min_size_for_notional(notional: Decimal, price: Decimal) -> Decimal {
+    if price <= Decimal::ZERO {
+        return Decimal::ZERO;
+    }
+    (notional / price).round_dp_with_strategy(2, RoundingStrategy::ToPositiveInfinity)
+}
+
+/// Shares that `notional` USDC buys at `price`, rounded down to the venue's
+/// 0.01-share lot so the order never exceeds the notional.
+pub fn shares_for_notional(notional: Decimal, price: Decimal) -> Decimal {
+    if price <= Decimal::ZERO {
+        return Decimal::ZERO;
+    }
+    (notional / price).round_dp_with_strategy(2, RoundingStrategy::ToZero)
+}
+
+/// Round `price` to the nearest multiple of `tick` (half away from zero).
+///
+/// A non-positive `tick` leaves the price unchanged.
+pub fn round_to_tick(price: Decimal, tick: Decimal) -> Decimal {
+    if tick <= Decimal::ZERO {
+        return price;
+    }
+    ((price / tick).round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero) * tick)
+        .normalize()
+}
+
+/// Round `price` down to a multiple of `tick` — the conservative side for a bid.
+pub fn floor_to_tick(price: Decimal, tick: Decimal) -> Decimal {
+    if tick <= Decimal::ZERO {
+        return price;
+    }
+    ((price / tick).floor() * tick).normalize()
+}
+
+/// Round `price` up to a multiple of `tick` — the conservative side for an ask.
+pub fn ceil_to_tick(price: Decimal, tick: Decimal) -> Decimal {
+    if tick <= Decimal::ZERO {
+        return price;
+    }
+    ((price / tick).ceil() * tick).normalize()
+}
+
+/// Decimal places money amounts are shown with unless configured otherwise.
+pub const MONEY_DECIMALS: u32 = 4;
+
+/// Round a PnL or fee amount for display: `MONEY_DECIMALS` places, halves
+/// away from zero. Accumulate at full precision and round only when showing.
+pub fn round_money(value: Decimal) -> Decimal {
+    round_money_dp(value, MONEY_DECIMALS, RoundingMode::default())
+}
+
+/// Round a money amount to `decimals` places with `mode`, always carrying
+/// exactly `decimals` places so columns line up. A loss too small to show
+/// rounds to plain zero rather than `-0.0000`.
+pub fn round_money_dp(value: Decimal, decimals: u32, mode: RoundingMode) -> Decimal {
+    let strategy = match mode {
+        RoundingMode::HalfUp => RoundingStrategy::MidpointAwayFromZero,
+        RoundingMode::HalfEven => RoundingStrategy::MidpointNearestEven,
+        RoundingMode::Down => RoundingStrategy::ToZero,
+    };
+    let mut rounded = value.round_dp_with_strategy(decimals, strategy);
+    if rounded.is_zero() {
+        rounded = Decimal::ZERO;
+    }
+    rounded.rescale(decimals);
+    rounded
+}
+
+/// Number of decimal places needed to show a price on `tick` (0.01 → 2, 0.001 → 3).
+pub fn tick_decimals(tick: Decimal) -> u32 {
+    tick.normalize().scale()
+}
+
+/// Format `price` rounded to `tick`, with exactly the tick's decimal places.
+/// Round `size` down to the venue's 0.01-share lot.
+pub fn floor_to_lot(size: Decimal) -> Decimal {
+    size.round_dp_with_strategy(2, RoundingStrategy::ToZero)
+}
+
+pub fn format_price(price: Decimal, tick: Decimal) -> String {
+    let dp = tick_decimals(tick) as usize;
+    format!("{:.dp$}", round_to_tick(price, tick))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rounds_to_cent_and_mill_ticks() {
+        assert_eq!(round_to_tick(dec!(0.4857), dec!(0.01)), dec!(0.49));
+        assert_eq!(round_to_tick(dec!(0.4857), dec!(0.001)), dec!(0.486));
+        assert_eq!(floor_to_tick(dec!(0.4857), dec!(0.01)), dec!(0.48));
+        assert_eq!(ceil_to_tick(dec!(0.4857), dec!(0.001)), dec!(0.486));
+        assert_eq!(floor_to_tick(dec!(0.4857), dec!(0.001)), dec!(0.485));
+        // Already on tick: unchanged
+        assert_eq!(ceil_to_tick(dec!(0.48), dec!(0.01)), dec!(0.48));
+    }
+
+    #[test]
+    fn sizes_round_down_to_the_lot() {
+        assert_eq!(floor_to_lot(dec!(7.8608)), dec!(7.86));
+        assert_eq!(floor_to_lot(dec!(7.86)), dec!(7.86));
+    }
+
+    #[test]
+    fn formats_with_tick_precision() {
+        assert_eq!(format_price(dec!(0.4857), dec!(0.01)), "0.49");
+        assert_eq!(format_price(dec!(0.4857), dec!(0.001)), "0.486");
+        // A 0.001-tick price is not truncated to two places
+        assert_eq!(format_price(dec!(0.485), dec!(0.001)), "0.485");
+        assert_eq!(format_price(dec!(0.5), dec!(0.01)), "0.50");
+        assert_eq!(tick_decimals(dec!(0.010)), 2);
+    }
+
+    #[test]
+    fn money_rounds_for_display_including_losses() {
+        assert_eq!(round_money(dec!(1.23456)).to_string(), "1.2346");
+        assert_eq!(round_money(dec!(-1.23455)).to_string(), "-1.2346");
+        assert_eq!(round_money(dec!(2)).to_string(), "2.0000");
+        // A loss too small to show is zero, not "-0.0000"
+        assert_eq!(round_money(dec!(-0.00004)).to_string(), "0.0000");
+
+        assert_eq!(
+            round_money_dp(dec!(-1.23455), 4, RoundingMode::HalfEven),
+            dec!(-1.2346)
+        );
+        assert_eq!(
+            round_money_dp(dec!(-1.23465), 4, RoundingMode::HalfEven),
+            dec!(-1.2346)
+        );
+        assert_eq!(
+            round_money_dp(dec!(-1.23469), 4, RoundingMode::Down),
+            dec!(-1.2346)
+        );
+        assert_eq!(
+            round_money_dp(dec!(0.125), 2, RoundingMode::HalfUp),
+            dec!(0.13)
+        );
+    }
+
+    #[test]
+    fn min_order_size_meets_min_notional() {
+        assert_eq!(min_order_size_at(dec!(0.50)), dec!(2));
+        assert_eq!(min_order_size_at(dec!(0.48)), dec!(2.09));
+        assert_eq!(min_order_size_at(dec!(0.99)), dec!(1.02));
+        assert_eq!(min_size_for_notional(dec!(5), dec!(0.03)), dec!(166.67));
+    }
+}
@@ -0,0 +1,540 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::Serialize;
+
+use crate::{Fill, InventoryPosition, PnlRounding, Result, Side, StrategyKey};
+
+/// End-of-session statistics for a single market.
+#[derive(Debug, Clone, Serialize)]
+pub struct MarketReport {
+    pub token_id: String,
+    /// Strategy instance the stats belong to; empty for the default
+    pub strategy_id: String,
+    pub fill_count: u64,
+    pub buy_volume: Decimal,
+    pub sell_volume: Decimal,
+    pub round_trips: u64,
+    pub profitable_round_trips: u64,
+    /// Fraction of round-trips closed at a profit, if any closed
+    pub win_rate: Option<Decimal>,
+    /// Realized PnL before fees
+    pub gross_pnl: Decimal,
+    /// Realized PnL after fees
+    pub net_pnl: Decimal,
+    pub fees_paid: Decimal,
+    /// Largest absolute net position held at any point
+    pub max_inventory: Decimal,
+    /// Mean time from opening a position to returning flat (or flipping)
+    pub avg_hold_secs: Option<f64>,
+    pub final_position: Decimal,
+    /// Size-weighted edge versus mid summed over all fills, in USDC
+    pub edge_captured: Decimal,
+    /// Mean per-share edge versus mid, if anything traded
+    pub avg_edge: Option<Decimal>,
+    /// Net PnL from edge captured at entry and exit, after fees
+    pub spread_pnl: Decimal,
+    /// Net PnL from the mid moving while inventory was held
+    pub inventory_pnl: Decimal,
+}
+
+/// Annualization used when the sampling rate is unknown: one sample per second.
+pub const DEFAULT_PERIODS_PER_YEAR: f64 = 365.0 * 24.0 * 60.0 * 60.0;
+
+/// Total session PnL (realized plus unrealized at mid) at one point in time.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct PnlSample {
+    pub timestamp: DateTime<Utc>,
+    pub pnl: Decimal,
+}
+
+/// Risk-adjusted performance derived from the PnL series.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PerformanceMetrics {
+    /// Annualized Sharpe ratio of the per-interval PnL, if it varied
+    pub sharpe: Option<f64>,
+    /// Largest fall in total PnL from a previous peak, in USDC
+    pub max_drawdown: Decimal,
+    /// Change in total PnL from the first sample to the last, in USDC
+    pub total_return: Decimal,
+}
+
+/// Summary of a trading session built from its fills and final positions.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionReport {
+    pub generated_at: DateTime<Utc>,
+    pub total_fills: u64,
+    pub total_round_trips: u64,
+    pub total_gross_pnl: Decimal,
+    pub total_net_pnl: Decimal,
+    pub total_fees: Decimal,
+    pub total_edge_captured: Decimal,
+    pub total_spread_pnl: Decimal,
+    pub total_inventory_pnl: Decimal,
+    pub markets: Vec<MarketReport>,
+    /// Number of sampling intervals per year, for annualizing the Sharpe ratio
+    pub periods_per_year: f64,
+    pub performance: PerformanceMetrics,
+    pub pnl_series: Vec<PnlSample>,
+}
+
+/// Running replay state used to derive path-dependent stats.
+#[derive(Debug, Clone, Default)]
+struct Replay {
+    fill_count: u64,
+    buy_volume: Decimal,
+    sell_volume: Decimal,
+    net: Decimal,
+    edge_captured: Decimal,
+    max_inventory: Decimal,
+    opened_at: Option<DateTime<Utc>>,
+    hold_secs_total: f64,
+    holds: u64,
+}
+
+/// The fill-derived half of a `SessionReport`, accumulated one fill at a time
+/// so a long session need not keep every fill around.
+#[derive(Debug, Clone, Default)]
+pub struct FillStats {
+    replays: BTreeMap<StrategyKey, Replay>,
+}
+
+impl FillStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fold in the session's next fill; fills must arrive in timestamp order.
+    pub fn record(&mut self, fill: &Fill) {
+        let r = self.replays.entry(fill.key()).or_default();
+        let signed = match fill.side {
+            Side::Buy => {
+                r.buy_volume += fill.size;
+                fill.size
+            }
+            Side::Sell => {
+                r.sell_volume += fill.size;
+                -fill.size
+            }
+        };
+        let old = r.net;
+        r.net += signed;
+        r.fill_count += 1;
+        r.edge_captured += fill.edge * fill.size;
+        r.max_inventory = r.max_inventory.max(r.net.abs());
+
+        let closed = old != Decimal::ZERO
+            && (r.net == Decimal::ZERO || (r.net > Decimal::ZERO) != (old > Decimal::ZERO));
+        if closed {
+            if let Some(opened) = r.opened_at.take() {
+                r.hold_secs_total += (fill.timestamp - opened).num_milliseconds() as f64 / 1000.0;
+                r.holds += 1;
+            }
+        }
+        if r.net != Decimal::ZERO && r.opened_at.is_none() {
+            r.opened_at = Some(fill.timestamp);
+        }
+    }
+}
+
+impl SessionReport {
+    /// Build a report from every fill of the session and the final positions.
+    ///
+    /// PnL, fees and round-trip counts come from the positions; volume, max
+    /// inventory and hold times are replayed from the fills in timestamp order.
+    pub fn new(fills: &[Fill], positions: &[InventoryPosition]) -> Self {
+        let mut ordered: Vec<&Fill> = fills.iter().collect();
+        ordered.sort_by_key(|f| f.timestamp);
+        let mut stats = FillStats::new();
+        for fill in ordered {
+            stats.record(fill);
+        }
+        Self::from_stats(&stats, positions)
+    }
+
+    /// Build a report from fill statistics gathered as the session ran and
+    /// the final positions.
+    pub fn from_stats(stats: &FillStats, positions: &[InventoryPosition]) -> Self {
+        let mut replays = stats.replays.clone();
+        let positions: BTreeMap<StrategyKey, &InventoryPosition> =
+            positions.iter().map(|p| (p.key(), p)).collect();
+        let mut keys: Vec<StrategyKey> = replays.keys().cloned().collect();
+        for key in positions.keys() {
+            if !replays.contains_key(key) {
+                keys.push(key.clone());
+            }
+        }
+        keys.sort();
+
+        let markets: Vec<MarketReport> = keys
+            .into_iter()
+            .map(|key| {
+                let replay = replays.remove(&key).unwrap_or_default();
+                let pos = positions.get(&key);
+                let net_pnl = pos.map(|p| p.realized_pnl).unwrap_or(Decimal::ZERO);
+                let fees_paid = pos.map(|p| p.fees_paid).unwrap_or(Decimal::ZERO);
+                let avg_hold_secs =
+                    (replay.holds > 0).then(|| replay.hold_secs_total / replay.holds as f64);
+                let volume = replay.buy_volume + replay.sell_volume;
+                let avg_edge = (volume > Decimal::ZERO).then(|| replay.edge_captured / volume);
+
+                MarketReport {
+                    fill_count: replay.fill_count,
+                    buy_volume: replay.buy_volume,
+                    sell_volume: replay.sell_volume,
+                    round_trips: pos.map(|p| p.round_trips).unwrap_or(0),
+                    profitable_round_trips: pos.map(|p| p.profitable_round_trips).unwrap_or(0),
+                    win_rate: pos.and_then(|p| p.profitable_round_trip_rate()),
+                    gross_pnl: net_pnl + fees_paid,
+                    net_pnl,
+                    fees_paid,
+                    max_inventory: replay.max_inventory,
+                    avg_hold_secs,
+                    final_position: pos.map(|p| p.net_position).unwrap_or(replay.net),
+                    edge_captured: replay.edge_captured,
+                    avg_edge,
+                    spread_pnl: pos.map(|p| p.spread_pnl).unwrap_or(Decimal::ZERO),
+                    inventory_pnl: pos.map(|p| p.inventory_pnl).unwrap_or(Decimal::ZERO),
+                    token_id: key.token_id,
+                    strategy_id: key.strategy_id,
+                }
+            })
+            .collect();
+
+        Self {
+            generated_at: Utc::now(),
+            total_fills: markets.iter().map(|m| m.fill_count).sum(),
+            total_round_trips: markets.iter().map(|m| m.round_trips).sum(),
+            total_gross_pnl: markets.iter().map(|m| m.gross_pnl).sum(),
+            total_net_pnl: markets.iter().map(|m| m.net_pnl).sum(),
+            total_fees: markets.iter().map(|m| m.fees_paid).sum(),
+            total_edge_captured: markets.iter().map(|m| m.edge_captured).sum(),
+            total_spread_pnl: markets.iter().map(|m| m.spread_pnl).sum(),
+            total_inventory_pnl: markets.iter().map(|m| m.inventory_pnl).sum(),
+            markets,
+            periods_per_year: DEFAULT_PERIODS_PER_YEAR,
+            performance: PerformanceMetrics::default(),
+            pnl_series: Vec::new(),
+        }
+    }
+
+    /// Attach the session's total-PnL series, sampled `periods_per_year`
+    /// times a year, and derive the performance metrics from it.
+    pub fn with_pnl_series(mut self, series: Vec<PnlSample>, periods_per_year: f64) -> Self {
+        self.pnl_series = series;
+        self.periods_per_year = periods_per_year;
+        self.performance = PerformanceMetrics {
+            sharpe: self.sharpe(),
+            max_drawdown: self.max_drawdown(),
+            total_return: self.total_return(),
+        };
+        self
+    }
+
+    /// Copy of the report with every PnL, fee and edge total rounded by
+    /// `rounding`, for writing out. Volumes, positions and the PnL series
+    /// are left at full precision.
+    pub fn rounded(mut self, rounding: PnlRounding) -> Self {
+        for m in &mut self.markets {
+            m.gross_pnl = rounding.round(m.gross_pnl);
+            m.net_pnl = rounding.round(m.net_pnl);
+            m.fees_paid = rounding.round(m.fees_paid);
+            m.edge_captured = rounding.round(m.edge_captured);
+            m.spread_pnl = rounding.round(m.spread_pnl);
+            m.inventory_pnl = rounding.round(m.inventory_pnl);
+        }
+        self.total_gross_pnl = rounding.round(self.total_gross_pnl);
+        self.total_net_pnl = rounding.round(self.total_net_pnl);
+        self.total_fees = rounding.round(self.total_fees);
+        self.total_edge_captured = rounding.round(self.total_edge_captured);
+        self.total_spread_pnl = rounding.round(self.total_spread_pnl);
+        self.total_inventory_pnl = rounding.round(self.total_inventory_pnl);
+        self.performance.max_drawdown = rounding.round(self.performance.max_drawdown);
+        self.performance.total_return = rounding.round(self.performance.total_return);
+        self
+    }
+
+    /// PnL earned over each interval between consecutive samples.
+    pub fn interval_pnl(&self) -> Vec<Decimal> {
+        self.pnl_series
+            .windows(2)
+            .map(|w| w[1].pnl - w[0].pnl)
+            .collect()
+    }
+
+    /// Annualized Sharpe ratio: mean over sample standard deviation of the
+    /// interval PnL, scaled by `sqrt(periods_per_year)`. `None` with fewer
+    /// than two intervals or when the PnL never varied.
+    pub fn sharpe(&self) -> Option<f64> {
+        let returns: Vec<f64> = self
+            .interval_pnl()
+            .iter()
+            .filter_map(|r| r.to_f64())
+            .collect();
+        if returns.len() < 2 {
+            return None;
+        }
+        let n = returns.len() as f64;
+        let mean = returns.iter().sum::<f64>() / n;
+        let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        let std_dev = variance.sqrt();
+        (std_dev > 0.0).then(|| mean / std_dev * self.periods_per_year.sqrt())
+    }
+
+    /// Largest peak-to-trough fall in total PnL across the series.
+    pub fn max_drawdown(&self) -> Decimal {
+        let mut peak: Option<Decimal> = None;
+        let mut worst = Decimal::ZERO;
+        for sample in &self.pnl_series {
+            let high = peak.map_or(sample.pnl, |p| p.max(sample.pnl));
+            peak = Some(high);
+            worst = worst.max(high - sample.pnl);
+        }
+        worst
+    }
+
+    /// Change in total PnL over the series.
+    pub fn total_return(&self) -> Decimal {
+        match (self.pnl_series.first(), self.pnl_series.last()) {
+            (Some(first), Some(last)) => last.pnl - first.pnl,
+            _ => Decimal::ZERO,
+        }
+    }
+
+    /// Render one CSV row per market, with a header row.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from(
+            "token_id,strategy_id,fill_count,buy_volume,sell_volume,round_trips,profitable_round_trips,\
+             win_rate,gross_pnl,net_pnl,fees_paid,max_inventory,avg_hold_secs,final_position,\
+             edge_captured,avg_edge,spread_pnl,inventory_pnl\n",
+        );
+        for m in &self.markets {
+            out.push_str(&format!(
+                "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}\n",
+                csv_field(&m.token_id),
+                csv_field(&m.strategy_id),
+                m.fill_count,
+                m.buy_volume,
+                m.sell_volume,
+                m.round_trips,
+                m.profitable_round_trips,
+                m.win_rate
+                    .map(|r| r.round_dp(4).to_string())
+                    .unwrap_or_default(),
+                m.gross_pnl,
+                m.net_pnl,
+                m.fees_paid,
+                m.max_inventory,
+                m.avg_hold_secs
+                    .map(|s| format!("{s:.3}"))
+                    .unwrap_or_default(),
+                m.final_position,
+                m.edge_captured,
+                m.avg_edge
+                    .map(|e| e.round_dp(6).to_string())
+                    .unwrap_or_default(),
+                m.spread_pnl,
+                m.inventory_pnl,
+            ));
+        }
+        out
+    }
+
+    /// Render the full report as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Write the report to `path`, as CSV if the extension is `.csv` and
+    /// JSON otherwise.
+    pub fn write_to(&self, path: &Path) -> Result<()> {
+        let is_csv = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"));
+        let body = if is_csv {
+            self.to_csv()
+        } else {
+            self.to_json()?
+        };
+        std::fs::write(path, body)?;
+        Ok(())
+    }
+}
+
+/// Quote a CSV field if it contains a delimiter, quote or newline.
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use rust_decimal_macros::dec;
+
+    fn fill(token: &str, side: Side, price: Decimal, size: Decimal, secs: i64) -> Fill {
+        // Pretend every fill traded against a 0.45 mid
+        let mid = dec!(0.45);
+        Fill {
+            token_id: token.into(),
+            strategy_id: String::new(),
+            side,
+            price,
+            size,
+            timestamp: DateTime::<Utc>::UNIX_EPOCH + Duration::seconds(secs),
+            is_simulated: true,
+            mid_at_fill: mid,
+            edge: Fill::edge_vs_mid(side, price, mid),
+        }
+    }
+
+    #[test]
+    fn builds_stats_from_fill_sequence() {
+        let fills = vec![
+            fill("a", Side::Buy, dec!(0.40), dec!(10), 0),
+            fill("a", Side::Buy, dec!(0.40), dec!(10), 5),
+            fill("a", Side::Sell, dec!(0.50), dec!(20), 10),
+            fill("a", Side::Sell, dec!(0.50), dec!(5), 20),
+            fill("a", Side::Buy, dec!(0.60), dec!(5), 50),
+            fill("b", Side::Buy, dec!(0.30), dec!(4), 1),
+        ];
+        let mut a = InventoryPosition::with_fee_bps("a".into(), 100);
+        let mut b = InventoryPosition::with_fee_bps("b".into(), 100);
+        for f in &fills {
+            match f.token_id.as_str() {
+                "a" => a.apply_fill(f),
+                _ => b.apply_fill(f),
+            }
+        }
+
+        let report = SessionReport::new(&fills, &[a.clone(), b]);
+        assert_eq!(report.markets.len(), 2);
+        assert_eq!(report.total_fills, 6);
+
+        let ma = &report.markets[0];
+        assert_eq!(ma.token_id, "a");
+        assert_eq!(ma.fill_count, 5);
+        assert_eq!(ma.buy_volume, dec!(25));
+        assert_eq!(ma.sell_volume, dec!(25));
+        assert_eq!(ma.max_inventory, dec!(20));
+        assert_eq!(ma.round_trips, 2);
+        assert_eq!(ma.profitable_round_trips, 1);
+        assert_eq!(ma.win_rate, Some(dec!(0.5)));
+        // Gross: +2.00 on the long, -0.50 on the short
+        assert_eq!(ma.gross_pnl, dec!(1.50));
+        assert_eq!(ma.net_pnl, a.realized_pnl);
+        assert_eq!(ma.gross_pnl - ma.fees_paid, ma.net_pnl);
+        // Long held 0s → 10s, short held 20s → 50s
+        assert_eq!(ma.avg_hold_secs, Some(20.0));
+        assert_eq!(ma.final_position, Decimal::ZERO);
+        // Buys at 0.40 (+0.05 x 20), sells at 0.50 (+0.05 x 25), buy at 0.60 (-0.15 x 5)
+        assert_eq!(ma.edge_captured, dec!(1.50));
+        assert_eq!(ma.avg_edge, Some(dec!(0.03)));
+
+        let mb = &report.markets[1];
+        assert_eq!(mb.round_trips, 0);
+        assert_eq!(mb.win_rate, None);
+        assert_eq!(mb.avg_hold_secs, None);
+        assert_eq!(mb.final_position, dec!(4));
+    }
+
+    #[test]
+    fn csv_has_header_and_row_per_market() {
+        let fills = vec![fill("a", Side::Buy, dec!(0.40), dec!(10), 0)];
+        let mut a = InventoryPosition::new("a".into());
+        a.apply_fill(&fills[0]);
+
+        let csv = SessionReport::new(&fills, &[a]).to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("token_id,strategy_id,fill_count"));
+        assert!(lines[1].starts_with("a,,1,10,0,0,0,,"));
+    }
+
+    #[test]
+    fn rounding_applies_to_reported_pnl_only() {
+        // Five small losing round-trips with fees leave long fractional tails
+        let mut fills = Vec::new();
+        for i in 0..5 {
+            fills.push(fill("a", Side::Buy, dec!(0.333), dec!(3), i * 10));
+            fills.push(fill("a", Side::Sell, dec!(0.3321), dec!(3), i * 10 + 5));
+        }
+        let mut position = InventoryPosition::with_fee_bps("a".into(), 7);
+        for f in &fills {
+            position.apply_fill(f);
+        }
+        // Per trip: -0.0027 gross, 0.00139671 fees
+        assert_eq!(position.realized_pnl, dec!(-0.02048355));
+
+        let report = SessionReport::new(&fills, &[position.clone()]);
+        assert_eq!(report.total_net_pnl, dec!(-0.02048355));
+        let rounded = report.clone().rounded(PnlRounding::default());
+        assert_eq!(rounded.total_net_pnl.to_string(), "-0.0205");
+        assert_eq!(rounded.markets[0].fees_paid.to_string(), "0.0070");
+        assert_eq!(rounded.markets[0].buy_volume, report.markets[0].buy_volume);
+
+        let truncated = report.rounded(PnlRounding {
+            decimals: 2,
+            mode: crate::RoundingMode::Down,
+        });
+        assert_eq!(truncated.total_net_pnl.to_string(), "-0.02");
+        // The position itself is untouched
+        assert_eq!(position.realized_pnl, dec!(-0.02048355));
+    }
+
+    #[test]
+    fn json_round_trips_totals() {
+        let report = SessionReport::new(&[], &[]);
+        let value: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+        assert_eq!(value["total_fills"], 0);
+        assert!(value["markets"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn sharpe_and_drawdown_from_known_pnl_path() {
+        // Total PnL 0 → 2 → 1 → 4 → 2 → 5
+        let series: Vec<PnlSample> = [0, 2, 1, 4, 2, 5]
+            .iter()
+            .enumerate()
+            .map(|(i, pnl)| PnlSample {
+                timestamp: DateTime::<Utc>::UNIX_EPOCH + Duration::seconds(i as i64),
+                pnl: Decimal::from(*pnl),
+            })
+            .collect();
+        let report = SessionReport::new(&[], &[]).with_pnl_series(series, 252.0);
+
+        assert_eq!(
+            report.interval_pnl(),
+            vec![dec!(2), dec!(-1), dec!(3), dec!(-2), dec!(3)]
+        );
+        // Worst fall is 4 → 2
+        assert_eq!(report.max_drawdown(), dec!(2));
+        assert_eq!(report.total_return(), dec!(5));
+
+        // Mean 1, sample variance (1 + 4 + 4 + 9 + 4) / 4 = 5.5
+        let expected = 1.0 / 5.5_f64.sqrt() * 252.0_f64.sqrt();
+        assert!((report.sharpe().unwrap() - expected).abs() < 1e-9);
+        assert_eq!(report.performance.sharpe, report.sharpe());
+        assert_eq!(report.performance.max_drawdown, dec!(2));
+
+        // Flat PnL has no meaningful Sharpe
+        let flat: Vec<PnlSample> = report
+            .pnl_series
+            .iter()
+            .map(|s| PnlSample {
+                pnl: Decimal::ONE,
+                ..*s
+            })
+            .collect();
+        let flat = SessionReport::new(&[], &[]).with_pnl_series(flat, 252.0);
+        assert_eq!(flat.sharpe(), None);
+        assert_eq!(flat.max_drawdown(), Decimal::ZERO);
+    }
+}
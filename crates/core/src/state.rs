@@ -0,0 +1,92 @@
+//! Positions saved at the end of a session, read back by `eutrader positions`.
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::{InventoryPosition, Result, StrategyKey};
+
+/// The part of an `InventoryPosition` worth keeping between sessions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedPosition {
+    pub token_id: String,
+    /// Strategy instance holding the position; empty for the default
+    #[serde(default)]
+    pub strategy_id: String,
+    pub net_position: Decimal,
+    pub avg_entry: Decimal,
+    pub realized_pnl: Decimal,
+    pub fees_paid: Decimal,
+    pub fill_count: u64,
+}
+
+impl From<&InventoryPosition> for SavedPosition {
+    fn from(p: &InventoryPosition) -> Self {
+        Self {
+            token_id: p.token_id.clone(),
+            strategy_id: p.strategy_id.clone(),
+            net_position: p.net_position,
+            avg_entry: p.avg_entry,
+            realized_pnl: p.realized_pnl,
+            fees_paid: p.fees_paid,
+            fill_count: p.fill_count,
+        }
+    }
+}
+
+impl SavedPosition {
+    /// The strategy instance this position belongs to.
+    pub fn key(&self) -> StrategyKey {
+        StrategyKey::new(&self.token_id, &self.strategy_id)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct StateFile {
+    saved_at: DateTime<Utc>,
+    positions: Vec<SavedPosition>,
+}
+
+/// Positions persisted as JSON at a fixed path.
+#[derive(Debug, Clone)]
+pub struct StateStore {
+    path: PathBuf,
+}
+
+impl StateStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Saved positions, sorted by strategy key. A missing or empty file has
+    /// none.
+    pub fn load(&self) -> Result<Vec<SavedPosition>> {
+        let text = match std::fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        if text.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut positions = serde_json::from_str::<StateFile>(&text)?.positions;
+        positions.sort_by_key(|p| p.key());
+        Ok(positions)
+    }
+
+    /// Replace the file's contents with `positions`.
+    pub fn save(&self, positions: &[SavedPosition]) -> Result<()> {
+        let file = StateFile {
+            saved_at: Utc::now(),
+            positions: positions.to_vec(),
+        };
+        std::fs::write(&self.path, serde_json::to_string_pretty(&file)?)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+/// `s` cut to at most `max` characters, ending in `...` when shortened and
+/// there is room for it. Cuts on character boundaries, as market names and
+/// questions often contain accents and emoji.
+pub fn truncate(s: &str, max: usize) -> String {
+    if s.chars().count() <= max {
+        s.to_string()
+    } else if max < 3 {
+        s.chars().take(max).collect()
+    } else {
+        format!("{}...", s.chars().take(max - 3).collect::<String>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncate_handles_tiny_limits() {
+        assert_eq!(truncate("market", 10), "market");
+        assert_eq!(truncate("market", 6), "market");
+        assert_eq!(truncate("market", 5), "ma...");
+        assert_eq!(truncate("market", 3), "...");
+        assert_eq!(truncate("market", 2), "ma");
+        assert_eq!(truncate("market", 0), "");
+    }
+
+    #[test]
+    fn truncate_cuts_on_char_boundaries() {
+        // "é" straddles byte 37, where a byte slice for max 40 would cut
+        let q = format!("{}é and more after the cut", "x".repeat(36));
+        assert!(!q.is_char_boundary(37));
+        assert_eq!(truncate(&q, 40), format!("{}é...", "x".repeat(36)));
+        assert_eq!(truncate("Will 🦀 win?", 7), "Will...");
+        assert_eq!(truncate("Will 🦀 win?", 8), "Will ...");
+        assert_eq!(truncate("Will 🦀 win?", 9), "Will 🦀...");
+        assert_eq!(truncate("Will 🦀 win?", 11), "Will 🦀 win?");
+        assert_eq!(truncate("€€€€€", 4), "€...");
+    }
+}
@@ -14,7 +14,7 @@ impl fmt::Display for OrderId {
 }
 
 /// Buy or Sell
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Side {
     Buy,
@@ -30,41 +30,151 @@ impl fmt::Display for Side {
     }
 }
 
+/// Identifies one strategy instance: the venue token it quotes and its
+/// strategy ID, empty for the default (unnamed) strategy.
+///
+/// Displays as the bare `token_id` for the default strategy and as
+/// `token_id/strategy_id` otherwise.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct StrategyKey {
+    pub token_id: String,
+    pub strategy_id: String,
+}
+
+impl StrategyKey {
+    pub fn new(token_id: impl Into<String>, strategy_id: impl Into<String>) -> Self {
+        Self {
+            token_id: token_id.into(),
+            strategy_id: strategy_id.into(),
+        }
+    }
+}
+
+/// The default strategy on `token_id`.
+impl From<&str> for StrategyKey {
+    fn from(token_id: &str) -> Self {
+        Self::new(token_id, "")
+    }
+}
+
+impl fmt::Display for StrategyKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.strategy_id.is_empty() {
+            write!(f, "{}", self.token_id)
+        } else {
+            write!(f, "{}/{}", self.token_id, self.strategy_id)
+        }
+    }
+}
+
 /// A two-sided quote to post on the book
 #[derive(Debug, Clone)]
 pub struct Quote {
     pub token_id: String,
+    /// Strategy instance the quote belongs to; empty for the default
+    pub strategy_id: String,
     pub bid_price: Decimal,
     pub ask_price: Decimal,
-    pub size: Decimal,
+    /// Shares to post on the bid
+    pub bid_size: Decimal,
+    /// Shares to post on the ask
+    pub ask_size: Decimal,
+    /// When set, only this side is posted and the other is suppressed
+    pub one_sided: Option<Side>,
+    /// Side to post reduce-only, so it can never grow the position
+    pub reduce_only: Option<Side>,
 }
 
 impl Quote {
+    /// The strategy instance this quote is for.
+    pub fn key(&self) -> StrategyKey {
+        StrategyKey::new(&self.token_id, &self.strategy_id)
+    }
+
     pub fn spread(&self) -> Decimal {
         self.ask_price - self.bid_price
     }
+
+    /// Whether the bid should be posted.
+    pub fn bid_active(&self) -> bool {
+        self.one_sided != Some(Side::Sell)
+    }
+
+    /// Whether the ask should be posted.
+    pub fn ask_active(&self) -> bool {
+        self.one_sided != Some(Side::Buy)
+    }
 }
 
 /// A simulated or real fill
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Fill {
     pub token_id: String,
+    /// Strategy instance whose order filled; empty for the default
+    #[serde(default)]
+    pub strategy_id: String,
     pub side: Side,
     pub price: Decimal,
     pub size: Decimal,
     pub timestamp: DateTime<Utc>,
     pub is_simulated: bool,
+    /// Snapshot midpoint when the fill happened
+    #[serde(default)]
+    pub mid_at_fill: Decimal,
+    /// Per-share edge captured versus `mid_at_fill` (positive = favourable)
+    #[serde(default)]
+    pub edge: Decimal,
+}
+
+impl Fill {
+    /// The strategy instance this fill belongs to.
+    pub fn key(&self) -> StrategyKey {
+        StrategyKey::new(&self.token_id, &self.strategy_id)
+    }
+
+    /// Edge of trading `side` at `price` against `mid`: `mid - price` for
+    /// buys, `price - mid` for sells.
+    pub fn edge_vs_mid(side: Side, price: Decimal, mid: Decimal) -> Decimal {
+        match side {
+            Side::Buy => mid - price,
+            Side::Sell => price - mid,
+        }
+    }
 }
 
 /// Current inventory for a single market
 #[derive(Debug, Clone, Default)]
 pub struct InventoryPosition {
     pub token_id: String,
+    /// Strategy instance holding the position; empty for the default
+    pub strategy_id: String,
     /// Positive = long, negative = short
     pub net_position: Decimal,
     pub avg_entry: Decimal,
+    /// Realized PnL net of fees
     pub realized_pnl: Decimal,
     pub fill_count: u64,
+    /// Fee in basis points charged on each fill's notional
+    pub fee_bps: u32,
+    /// Cumulative fees paid on all fills
+    pub fees_paid: Decimal,
+    /// Minimum net-of-fee edge per share for a round-trip to count as profitable
+    pub min_round_trip_edge: Decimal,
+    /// Completed round-trips (position returned to flat or flipped sides)
+    pub round_trips: u64,
+    /// Completed round-trips whose edge met `min_round_trip_edge`
+    pub profitable_round_trips: u64,
+    /// Net PnL accumulated by the round-trip currently in progress
+    pub round_trip_pnl: Decimal,
+    /// Shares closed by the round-trip currently in progress
+    pub round_trip_closed: Decimal,
+    /// Size-weighted mid at the fills that opened the current position
+    pub avg_entry_mid: Decimal,
+    /// Realized PnL from edge versus mid at entry and exit, net of fees
+    pub spread_pnl: Decimal,
+    /// Realized PnL from the mid moving while the position was held.
+    /// `spread_pnl + inventory_pnl == realized_pnl`.
+    pub inventory_pnl: Decimal,
 }
 
 impl InventoryPosition {
@@ -75,6 +185,31 @@ impl InventoryPosition {
         }
     }
 
+    /// Create a position that charges `fee_bps` on every fill.
+    pub fn with_fee_bps(token_id: String, fee_bps: u32) -> Self {
+        Self {
+            token_id,
+            fee_bps,
+            ..Default::default()
+        }
+    }
+
+    /// Create a position using the fee and round-trip settings of a market.
+    pub fn for_market(market: &crate::MarketConfig, global_fee_bps: u32) -> Self {
+        Self {
+            token_id: market.token_id.clone(),
+            strategy_id: market.strategy_id.clone(),
+            fee_bps: market.effective_fee_bps(global_fee_bps),
+            min_round_trip_edge: market.min_round_trip_edge,
+            ..Default::default()
+        }
+    }
+
+    /// The strategy instance holding this position.
+    pub fn key(&self) -> StrategyKey {
+        StrategyKey::new(&self.token_id, &self.strategy_id)
+    }
+
     /// Apply a fill to this position
     pub fn apply_fill(&mut self, fill: &Fill) {
         let signed_size = match fill.side {
@@ -84,6 +219,12 @@ impl InventoryPosition {
 
         let old_position = self.net_position;
         self.net_position += signed_size;
+        // Fills without a recorded mid are attributed entirely to inventory
+        let mid = if fill.mid_at_fill > Decimal::ZERO {
+            fill.mid_at_fill
+        } else {
+            fill.price
+        };
 
         // Update average entry for increasing positions
         if (old_position >= Decimal::ZERO && signed_size > Decimal::ZERO)
@@ -95,6 +236,9 @@ impl InventoryPosition {
             let total_size = old_position.abs() + signed_size.abs();
             if total_size > Decimal::ZERO {
                 self.avg_entry = (old_cost + new_cost) / total_size;
+                self.avg_entry_mid = (old_position.abs() * self.avg_entry_mid
+                    + signed_size.abs() * mid)
+                    / total_size;
             }
         } else {
             // Reducing or flipping — realize PnL on the closed portion
@@ -104,18 +248,89 @@ impl InventoryPosition {
                 Side::Buy => self.avg_entry - fill.price,
             };
             self.realized_pnl += closed_size * pnl_per_unit;
+            self.round_trip_pnl += closed_size * pnl_per_unit;
+            self.round_trip_closed += closed_size;
 
-            // If we flipped sides, reset avg entry to fill price
-            if (self.net_position > Decimal::ZERO && old_position < Decimal::ZERO)
-                || (self.net_position < Decimal::ZERO && old_position > Decimal::ZERO)
-            {
+            // The mid's move from entry to exit is inventory PnL; the rest
+            // is the edge captured at entry plus the edge at this exit
+            let inventory_per_unit = match fill.side {
+                Side::Sell => mid - self.avg_entry_mid,
+                Side::Buy => self.avg_entry_mid - mid,
+            };
+            self.inventory_pnl += closed_size * inventory_per_unit;
+            self.spread_pnl += closed_size * (pnl_per_unit - inventory_per_unit);
+
+            // Any overshoot past flat opens a new position on the other
+            // side, whose whole basis is this fill
+            let opened_size = signed_size.abs() - closed_size;
+            if opened_size > Decimal::ZERO {
                 self.avg_entry = fill.price;
+                self.avg_entry_mid = mid;
             }
         }
 
+        // Fees are charged on notional and always reduce realized PnL
+        let fee = fill.price * fill.size * Decimal::from(self.fee_bps) / Decimal::from(10_000);
+        self.fees_paid += fee;
+        self.realized_pnl -= fee;
+        self.spread_pnl -= fee;
+        self.round_trip_pnl -= fee;
+
+        // A round-trip completes when the position returns to flat or flips.
+        // The whole fee of a flipping fill is charged to the closing trip.
+        let closed_trip = old_position != Decimal::ZERO
+            && (self.net_position == Decimal::ZERO
+                || (self.net_position > Decimal::ZERO) != (old_position > Decimal::ZERO));
+        if closed_trip {
+            self.complete_round_trip();
+        }
+
         self.fill_count += 1;
     }
 
+    /// Close the whole position at a market's resolution `price` (0 or 1 for
+    /// a binary outcome). No fee is charged. Returns the PnL realized.
+    pub fn settle(&mut self, price: Decimal) -> Decimal {
+        if self.net_position == Decimal::ZERO {
+            return Decimal::ZERO;
+        }
+        let pnl = self.unrealized_pnl(price);
+        let inventory = self.net_position * (price - self.avg_entry_mid);
+        self.realized_pnl += pnl;
+        self.inventory_pnl += inventory;
+        self.spread_pnl += pnl - inventory;
+        self.round_trip_pnl += pnl;
+        self.round_trip_closed += self.net_position.abs();
+        self.net_position = Decimal::ZERO;
+        self.avg_entry = Decimal::ZERO;
+        self.avg_entry_mid = Decimal::ZERO;
+        self.complete_round_trip();
+        pnl
+    }
+
+    /// Classify and reset the round-trip in progress.
+    fn complete_round_trip(&mut self) {
+        if self.round_trip_closed > Decimal::ZERO {
+            let edge = self.round_trip_pnl / self.round_trip_closed;
+            self.round_trips += 1;
+            if edge >= self.min_round_trip_edge {
+                self.profitable_round_trips += 1;
+            }
+        }
+        self.round_trip_pnl = Decimal::ZERO;
+        self.round_trip_closed = Decimal::ZERO;
+    }
+
+    /// Fraction of completed round-trips that met the edge threshold.
+    ///
+    /// Returns `None` before the first round-trip completes.
+    pub fn profitable_round_trip_rate(&self) -> Option<Decimal> {
+        if self.round_trips == 0 {
+            return None;
+        }
+        Some(Decimal::from(self.profitable_round_trips) / Decimal::from(self.round_trips))
+    }
+
     pub fn unrealized_pnl(&self, mid_price: Decimal) -> Decimal {
         if self.net_position > Decimal::ZERO {
             self.net_position * (mid_price - self.avg_entry)
@@ -127,15 +342,222 @@ impl InventoryPosition {
     }
 }
 
+/// How the snapshot midpoint is derived from the top of book
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MidpointMode {
+    /// `(best_bid + best_ask) / 2`
+    #[default]
+    Simple,
+    /// Size-weighted micro-price: leans toward the side with less depth
+    MicroPrice,
+}
+
+/// Where a market's snapshot midpoint comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MidSource {
+    /// Top of the polled orderbook, per the feed's `MidpointMode`
+    #[default]
+    Book,
+    /// The CLOB `/midpoint` endpoint
+    Midpoint,
+    /// Price of the most recent trade
+    LastTrade,
+}
+
+/// What a market's quotes are centered on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuoteAnchor {
+    /// The snapshot midpoint, or an external fair value when one is set
+    #[default]
+    Mid,
+    /// The mid pulled toward our last fill price, fading as the fill ages
+    LastFill,
+}
+
+/// How inventory skew grows with position
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkewCurve {
+    /// `offset * skew_factor`
+    #[default]
+    Linear,
+    /// `skew_factor * offset * |offset| / max_inventory`: matches linear at
+    /// the limit but stays gentler while utilization is low
+    Quadratic,
+    /// `max_skew * tanh(offset / max_inventory * skew_steepness)`
+    Tanh,
+}
+
+/// Unit of a market's quote `size`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SizeMode {
+    /// `size` is a number of shares per side
+    #[default]
+    Shares,
+    /// `size` is USDC per side, converted to shares at each side's price
+    Notional,
+}
+
+/// How money amounts are rounded for display and reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundingMode {
+    /// Halves round away from zero
+    #[default]
+    HalfUp,
+    /// Halves round to the nearest even digit (banker's rounding)
+    HalfEven,
+    /// Truncate toward zero
+    Down,
+}
+
+/// How far into the book a flattening order reaches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FlattenAggression {
+    /// Join the best price on our own side and wait to be filled
+    Passive,
+    /// Rest at the midpoint, rounded to tick toward the opposite side
+    Mid,
+    /// Take the opposite top of book
+    #[default]
+    Cross,
+}
+
+impl FlattenAggression {
+    /// Limit price for a flattening order on `side` against `snapshot`.
+    pub fn price(self, side: Side, snapshot: &MarketSnapshot, tick: Decimal) -> Decimal {
+        match (self, side) {
+            (Self::Passive, Side::Buy) | (Self::Cross, Side::Sell) => snapshot.best_bid,
+            (Self::Passive, Side::Sell) | (Self::Cross, Side::Buy) => snapshot.best_ask,
+            (Self::Mid, Side::Buy) => crate::price::ceil_to_tick(snapshot.midpoint, tick),
+            (Self::Mid, Side::Sell) => crate::price::floor_to_tick(snapshot.midpoint, tick),
+        }
+    }
+}
+
+/// How the paper executor decides when a resting order is filled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FillModel {
+    /// Fill as soon as the opposite side reaches our price
+    #[default]
+    Touch,
+    /// Fill only once the volume queued ahead of us at our price has traded,
+    /// or the market trades through our price
+    QueuePosition,
+}
+
+/// Serialization of the paper trade log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TradeLogFormat {
+    /// One JSON object per line
+    #[default]
+    Jsonl,
+    /// Comma-separated values with a header row
+    Csv,
+}
+
+impl TradeLogFormat {
+    /// Log file used when no path is configured.
+    pub fn default_path(self) -> &'static str {
+        match self {
+            TradeLogFormat::Jsonl => "paper_trades.jsonl",
+            TradeLogFormat::Csv => "paper_trades.csv",
+        }
+    }
+}
+
 /// Snapshot of a market's orderbook state
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MarketSnapshot {
     pub token_id: String,
     pub best_bid: Decimal,
     pub best_ask: Decimal,
+    /// Midpoint the quoter centers on, computed per the feed's `MidpointMode`
     pub midpoint: Decimal,
+    /// Depth-weighted micro-price, always computed
+    pub micro_price: Decimal,
     pub spread: Decimal,
+    /// Size resting at the best bid
+    pub bid_size: Decimal,
+    /// Size resting at the best ask
+    pub ask_size: Decimal,
     pub timestamp: DateTime<Utc>,
+    /// Whether the book is sane enough to quote against
+    #[serde(default)]
+    pub book_status: BookStatus,
+}
+
+impl MarketSnapshot {
+    /// Whether the book has both sides and is neither crossed nor locked.
+    pub fn is_quotable(&self) -> bool {
+        self.book_status == BookStatus::Normal
+    }
+}
+
+/// Shape of the top of book a snapshot was taken from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BookStatus {
+    /// Best bid below best ask
+    #[default]
+    Normal,
+    /// Best bid above best ask
+    Crossed,
+    /// Best bid equal to best ask
+    Locked,
+    /// One or both sides have no levels; missing prices are zero
+    Empty,
+    /// The book's prices could not be parsed; all prices are zero
+    Invalid,
+}
+
+impl fmt::Display for BookStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BookStatus::Normal => write!(f, "normal"),
+            BookStatus::Crossed => write!(f, "crossed"),
+            BookStatus::Locked => write!(f, "locked"),
+            BookStatus::Empty => write!(f, "empty"),
+            BookStatus::Invalid => write!(f, "invalid"),
+        }
+    }
+}
+
+/// Venue-side state of an order as reported when it was placed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OrderStatus {
+    /// Resting on the book
+    Live,
+    /// Matched in full on arrival
+    Matched,
+    /// Accepted but held back by the venue before it rests or matches
+    Delayed,
+    /// Refused by the venue
+    Rejected,
+}
+
+/// Acknowledgement returned when an order is placed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrderAck {
+    pub id: OrderId,
+    pub status: OrderStatus,
+    /// When the venue accepted (or refused) the order
+    pub placed_at: DateTime<Utc>,
+}
+
+impl OrderAck {
+    /// Whether the venue took the order rather than rejecting it.
+    pub fn is_accepted(&self) -> bool {
+        self.status != OrderStatus::Rejected
+    }
 }
 
 /// An open order on the book
@@ -143,9 +565,24 @@ pub struct MarketSnapshot {
 pub struct OpenOrder {
     pub id: OrderId,
     pub token_id: String,
+    /// Strategy instance that placed the order; empty for the default
+    pub strategy_id: String,
     pub side: Side,
     pub price: Decimal,
     pub size: Decimal,
+    /// Only ever fills toward a flat position
+    pub reduce_only: bool,
+    /// When the order was placed or last amended
+    pub placed_at: DateTime<Utc>,
+    /// Good-till-date orders are cancelled by the venue at this time
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl OpenOrder {
+    /// The strategy instance that placed this order.
+    pub fn key(&self) -> StrategyKey {
+        StrategyKey::new(&self.token_id, &self.strategy_id)
+    }
 }
 
 #[cfg(test)]
@@ -160,11 +597,14 @@ mod tests {
         // Buy 10 at 0.50
         inv.apply_fill(&Fill {
             token_id: "test".into(),
+            strategy_id: String::new(),
             side: Side::Buy,
             price: dec!(0.50),
             size: dec!(10),
             timestamp: Utc::now(),
             is_simulated: true,
+            mid_at_fill: Decimal::ZERO,
+            edge: Decimal::ZERO,
         });
         assert_eq!(inv.net_position, dec!(10));
         assert_eq!(inv.avg_entry, dec!(0.50));
@@ -172,24 +612,238 @@ mod tests {
         // Sell 10 at 0.55 — realize profit
         inv.apply_fill(&Fill {
             token_id: "test".into(),
+            strategy_id: String::new(),
             side: Side::Sell,
             price: dec!(0.55),
             size: dec!(10),
             timestamp: Utc::now(),
             is_simulated: true,
+            mid_at_fill: Decimal::ZERO,
+            edge: Decimal::ZERO,
         });
         assert_eq!(inv.net_position, dec!(0));
         assert_eq!(inv.realized_pnl, dec!(0.50)); // 10 * 0.05
     }
 
+    #[test]
+    fn per_market_fee_override_used_in_apply_fill() {
+        let market = crate::MarketConfig {
+            name: "Test".into(),
+            token_id: "test".into(),
+            spread_bps: 300,
+            size: dec!(10),
+            max_inventory: dec!(50),
+            skew_factor: dec!(0.001),
+            fee_bps: Some(100),
+            ..Default::default()
+        };
+        let global_fee_bps = 10;
+        let mut inv = InventoryPosition::with_fee_bps(
+            "test".into(),
+            market.effective_fee_bps(global_fee_bps),
+        );
+
+        // Buy 10 at 0.50, sell 10 at 0.55 — gross 0.50, fees 1% of 5.0 + 5.5 notional
+        for (side, price) in [(Side::Buy, dec!(0.50)), (Side::Sell, dec!(0.55))] {
+            inv.apply_fill(&Fill {
+                token_id: "test".into(),
+                strategy_id: String::new(),
+                side,
+                price,
+                size: dec!(10),
+                timestamp: Utc::now(),
+                is_simulated: true,
+                mid_at_fill: Decimal::ZERO,
+                edge: Decimal::ZERO,
+            });
+        }
+
+        assert_eq!(inv.fees_paid, dec!(0.105));
+        assert_eq!(inv.realized_pnl, dec!(0.395));
+    }
+
+    #[test]
+    fn global_fee_used_without_override() {
+        let mut inv = InventoryPosition::with_fee_bps("test".into(), 10);
+        inv.apply_fill(&Fill {
+            token_id: "test".into(),
+            strategy_id: String::new(),
+            side: Side::Buy,
+            price: dec!(0.50),
+            size: dec!(10),
+            timestamp: Utc::now(),
+            is_simulated: true,
+            mid_at_fill: Decimal::ZERO,
+            edge: Decimal::ZERO,
+        });
+        // 0.1% of 5.0 notional
+        assert_eq!(inv.fees_paid, dec!(0.005));
+        assert_eq!(inv.realized_pnl, dec!(-0.005));
+    }
+
+    fn fill(side: Side, price: Decimal, size: Decimal) -> Fill {
+        Fill {
+            token_id: "test".into(),
+            strategy_id: String::new(),
+            side,
+            price,
+            size,
+            timestamp: Utc::now(),
+            is_simulated: true,
+            mid_at_fill: Decimal::ZERO,
+            edge: Decimal::ZERO,
+        }
+    }
+
+    #[test]
+    fn settle_closes_position_at_resolution_price() {
+        let mut inv = InventoryPosition::new("test".into());
+        inv.apply_fill(&Fill {
+            token_id: "test".into(),
+            strategy_id: String::new(),
+            side: Side::Sell,
+            price: dec!(0.30),
+            size: dec!(20),
+            timestamp: Utc::now(),
+            is_simulated: true,
+            mid_at_fill: Decimal::ZERO,
+            edge: Decimal::ZERO,
+        });
+
+        // Short 20 from 0.30 and the outcome resolves NO
+        assert_eq!(inv.settle(Decimal::ZERO), dec!(6));
+        assert_eq!(inv.net_position, Decimal::ZERO);
+        assert_eq!(inv.realized_pnl, dec!(6));
+        assert_eq!(inv.round_trips, 1);
+        assert_eq!(inv.settle(Decimal::ONE), Decimal::ZERO);
+    }
+
+    #[test]
+    fn classifies_good_and_bad_round_trips() {
+        let mut inv = InventoryPosition::new("test".into());
+        inv.min_round_trip_edge = dec!(0.02);
+
+        // Good: buy 0.48, sell 0.52 => edge 0.04
+        inv.apply_fill(&fill(Side::Buy, dec!(0.48), dec!(10)));
+        inv.apply_fill(&fill(Side::Sell, dec!(0.52), dec!(10)));
+        // Bad: churn at a loss, sell 0.50, buy back 0.51 => edge -0.01
+        inv.apply_fill(&fill(Side::Sell, dec!(0.50), dec!(10)));
+        inv.apply_fill(&fill(Side::Buy, dec!(0.51), dec!(10)));
+        // Marginal: positive but below threshold => edge 0.01
+        inv.apply_fill(&fill(Side::Buy, dec!(0.50), dec!(10)));
+        inv.apply_fill(&fill(Side::Sell, dec!(0.51), dec!(10)));
+
+        assert_eq!(inv.round_trips, 3);
+        assert_eq!(inv.profitable_round_trips, 1);
+        assert_eq!(inv.profitable_round_trip_rate(), Some(dec!(1) / dec!(3)));
+    }
+
+    #[test]
+    fn round_trip_spans_partial_fills_and_fees() {
+        let mut inv = InventoryPosition::with_fee_bps("test".into(), 100);
+        inv.min_round_trip_edge = dec!(0.02);
+
+        // Scale in and out in pieces: gross edge 0.03/share, fees ~0.01/share
+        inv.apply_fill(&fill(Side::Buy, dec!(0.50), dec!(5)));
+        inv.apply_fill(&fill(Side::Buy, dec!(0.50), dec!(5)));
+        assert_eq!(inv.round_trips, 0);
+        inv.apply_fill(&fill(Side::Sell, dec!(0.53), dec!(4)));
+        assert_eq!(inv.round_trips, 0);
+        inv.apply_fill(&fill(Side::Sell, dec!(0.53), dec!(6)));
+
+        // pnl = 0.30 - 0.103 fees = 0.197 over 10 shares => 0.0197 < 0.02
+        assert_eq!(inv.round_trips, 1);
+        assert_eq!(inv.profitable_round_trips, 0);
+    }
+
+    #[test]
+    fn flipping_position_closes_round_trip() {
+        let mut inv = InventoryPosition::new("test".into());
+
+        inv.apply_fill(&fill(Side::Buy, dec!(0.50), dec!(10)));
+        // Sell 15: closes long 10 at +0.05, opens short 5
+        inv.apply_fill(&fill(Side::Sell, dec!(0.55), dec!(15)));
+        assert_eq!(inv.round_trips, 1);
+        assert_eq!(inv.profitable_round_trips, 1);
+
+        // Cover the short at a loss
+        inv.apply_fill(&fill(Side::Buy, dec!(0.60), dec!(5)));
+        assert_eq!(inv.round_trips, 2);
+        assert_eq!(inv.profitable_round_trips, 1);
+        assert_eq!(inv.profitable_round_trip_rate(), Some(dec!(0.5)));
+    }
+
+    #[test]
+    fn realized_pnl_splits_into_spread_and_inventory() {
+        let at_mid = |side, price, size, mid| Fill {
+            mid_at_fill: mid,
+            ..fill(side, price, size)
+        };
+        let mut inv = InventoryPosition::with_fee_bps("test".into(), 10);
+
+        // Bought 2 under mid twice while the mid rose 0.50 -> 0.54
+        inv.apply_fill(&at_mid(Side::Buy, dec!(0.48), dec!(10), dec!(0.50)));
+        inv.apply_fill(&at_mid(Side::Buy, dec!(0.52), dec!(10), dec!(0.54)));
+        assert_eq!(inv.avg_entry_mid, dec!(0.52));
+        // Sold 15 two over a 0.56 mid, then 5 two under a 0.55 mid
+        inv.apply_fill(&at_mid(Side::Sell, dec!(0.58), dec!(15), dec!(0.56)));
+        inv.apply_fill(&at_mid(Side::Sell, dec!(0.53), dec!(5), dec!(0.55)));
+
+        // Edges 0.40 at entry + 0.20 at exit, less 0.02135 of fees
+        assert_eq!(inv.spread_pnl, dec!(0.57865));
+        // Mid moved +0.04 under 15 shares and +0.03 under 5
+        assert_eq!(inv.inventory_pnl, dec!(0.75));
+        assert_eq!(inv.spread_pnl + inv.inventory_pnl, inv.realized_pnl);
+
+        // Settlement marks the whole move from the entry mid as inventory
+        let mut held = InventoryPosition::new("test".into());
+        held.apply_fill(&at_mid(Side::Sell, dec!(0.62), dec!(10), dec!(0.60)));
+        held.settle(Decimal::ZERO);
+        assert_eq!(
+            (held.spread_pnl, held.inventory_pnl),
+            (dec!(0.20), dec!(6.00))
+        );
+        assert_eq!(held.realized_pnl, dec!(6.20));
+    }
+
+    #[test]
+    fn overshooting_fill_flips_with_new_basis() {
+        let mut inv = InventoryPosition::new("test".into());
+
+        // Long 10 @ 0.50, sell 30 @ 0.60: realize on 10, short 20 @ 0.60
+        inv.apply_fill(&fill(Side::Buy, dec!(0.50), dec!(10)));
+        inv.apply_fill(&fill(Side::Sell, dec!(0.60), dec!(30)));
+        assert_eq!(inv.net_position, dec!(-20));
+        assert_eq!(inv.avg_entry, dec!(0.60));
+        assert_eq!(inv.realized_pnl, dec!(1.00));
+
+        // The short's basis is independent of the old long: cover at 0.55
+        inv.apply_fill(&fill(Side::Buy, dec!(0.55), dec!(20)));
+        assert_eq!(inv.net_position, Decimal::ZERO);
+        assert_eq!(inv.realized_pnl, dec!(2.00));
+
+        // And the other way: short 10 @ 0.40, buy 25 @ 0.30
+        let mut inv = InventoryPosition::new("test".into());
+        inv.apply_fill(&fill(Side::Sell, dec!(0.40), dec!(10)));
+        inv.apply_fill(&fill(Side::Buy, dec!(0.30), dec!(25)));
+        assert_eq!(inv.net_position, dec!(15));
+        assert_eq!(inv.avg_entry, dec!(0.30));
+        assert_eq!(inv.realized_pnl, dec!(1.00));
+    }
+
     #[test]
     fn quote_spread_calculation() {
         let q = Quote {
             token_id: "test".into(),
+            strategy_id: String::new(),
             bid_price: dec!(0.48),
             ask_price: dec!(0.52),
-            size: dec!(10),
+            bid_size: dec!(10),
+            ask_size: dec!(10),
+            one_sided: None,
+            reduce_only: None,
         };
         assert_eq!(q.spread(), dec!(0.04));
+        assert!(q.bid_active() && q.ask_active());
     }
 }
@@ -0,0 +1,229 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use tokio::sync::Mutex;
+use tracing::info;
+
+use eutrader_core::{OpenOrder, OrderAck, OrderId, OrderStatus, Result, Side};
+
+use crate::executor::Executor;
+
+/// An order request that would have been sent to the venue.
+#[derive(Debug, Clone, Serialize)]
+pub struct DryRunOrder {
+    pub id: OrderId,
+    pub token_id: String,
+    pub side: Side,
+    pub price: Decimal,
+    pub size: Decimal,
+    pub reduce_only: bool,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Internal mutable state for the dry-run executor.
+struct DryRunState {
+    /// Orders we pretend are resting, so reconciliation behaves as in live mode.
+    orders: HashMap<OrderId, OpenOrder>,
+    /// Every order request that would have been sent.
+    requests: Vec<DryRunOrder>,
+    next_id: u64,
+}
+
+/// Live-mode executor that logs intended orders instead of sending them.
+///
+/// Order placement returns synthetic `dry-N` IDs and never touches the
+/// network. Unlike `PaperExecutor` it never simulates fills — it exists to
+/// exercise order construction and reconciliation against live data safely.
+pub struct DryRunExecutor {
+    state: Arc<Mutex<DryRunState>>,
+}
+
+impl DryRunExecutor {
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(DryRunState {
+                orders: HashMap::new(),
+                requests: Vec::new(),
+                next_id: 1,
+            })),
+        }
+    }
+
+    /// Return every order request recorded so far.
+    pub async fn intended_orders(&self) -> Vec<DryRunOrder> {
+        let state = self.state.lock().await;
+        state.requests.clone()
+    }
+
+    /// Log an order request and rest it in the pretend book.
+    async fn record_order(
+        &self,
+        strategy_id: &str,
+        token_id: &str,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+        reduce_only: bool,
+    ) -> Result<OrderAck> {
+        let mut state = self.state.lock().await;
+        let id = OrderId(format!("dry-{}", state.next_id));
+        state.next_id += 1;
+
+        let placed_at = Utc::now();
+        let request = DryRunOrder {
+            id: id.clone(),
+            token_id: token_id.to_string(),
+            side,
+            price,
+            size,
+            reduce_only,
+            timestamp: placed_at,
+        };
+        let payload = serde_json::to_string(&request)?;
+        info!(order_id = %id, payload = %payload, "DRY RUN — order not sent");
+
+        state.orders.insert(
+            id.clone(),
+            OpenOrder {
+                id: id.clone(),
+                token_id: token_id.to_string(),
+                strategy_id: strategy_id.to_string(),
+                side,
+                price,
+                size,
+                reduce_only,
+                placed_at,
+                expires_at: None,
+            },
+        );
+        state.requests.push(request);
+        Ok(OrderAck {
+            id,
+            status: OrderStatus::Live,
+            placed_at,
+        })
+    }
+}
+
+impl Default for DryRunExecutor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Executor for DryRunExecutor {
+    async fn place_order(
+        &self,
+        token_id: &str,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+    ) -> Result<OrderAck> {
+        self.record_order("", token_id, side, price, size, false)
+            .await
+    }
+
+    async fn place_reduce_only_order(
+        &self,
+        token_id: &str,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+    ) -> Result<OrderAck> {
+        self.record_order("", token_id, side, price, size, true)
+            .await
+    }
+
+    async fn place_strategy_order(
+        &self,
+        strategy_id: &str,
+        token_id: &str,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+        reduce_only: bool,
+    ) -> Result<OrderAck> {
+        self.record_order(strategy_id, token_id, side, price, size, reduce_only)
+            .await
+    }
+
+    async fn cancel_order(&self, id: &OrderId) -> Result<()> {
+        let mut state = self.state.lock().await;
+        if state.orders.remove(id).is_some() {
+            info!(order_id = %id, "DRY RUN — cancel not sent");
+        }
+        Ok(())
+    }
+
+    async fn cancel_all(&self) -> Result<()> {
+        let mut state = self.state.lock().await;
+        let count = state.orders.len();
+        state.orders.clear();
+        info!(count, "DRY RUN — cancel all not sent");
+        Ok(())
+    }
+
+    async fn open_orders(&self) -> Result<Vec<OpenOrder>> {
+        let state = self.state.lock().await;
+        Ok(state.orders.values().cloned().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[tokio::test]
+    async fn place_returns_synthetic_id_and_records_request() {
+        let exec = DryRunExecutor::new();
+        let ack = exec
+            .place_order("tok1", Side::Buy, dec!(0.48), dec!(10))
+            .await
+            .unwrap();
+
+        assert_eq!(ack.id, OrderId("dry-1".into()));
+        assert_eq!(ack.status, OrderStatus::Live);
+        let intended = exec.intended_orders().await;
+        assert_eq!(intended.len(), 1);
+        assert_eq!(intended[0].token_id, "tok1");
+        assert_eq!(intended[0].price, dec!(0.48));
+    }
+
+    #[tokio::test]
+    async fn cancels_keep_request_history() {
+        let exec = DryRunExecutor::new();
+        let ack = exec
+            .place_order("tok1", Side::Sell, dec!(0.52), dec!(10))
+            .await
+            .unwrap();
+        exec.cancel_order(&ack.id).await.unwrap();
+
+        assert!(exec.open_orders().await.unwrap().is_empty());
+        assert_eq!(exec.intended_orders().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn default_cancel_token_filters_open_orders() {
+        let exec = DryRunExecutor::new();
+        exec.place_order("tok1", Side::Buy, dec!(0.48), dec!(10))
+            .await
+            .unwrap();
+        exec.place_order("tok2", Side::Buy, dec!(0.30), dec!(10))
+            .await
+            .unwrap();
+        exec.place_order("tok2", Side::Sell, dec!(0.34), dec!(10))
+            .await
+            .unwrap();
+
+        exec.cancel_token("tok2").await.unwrap();
+        let open = exec.open_orders().await.unwrap();
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].token_id, "tok1");
+    }
+}
@@ -0,0 +1,97 @@
+use rust_decimal::Decimal;
+use tokio::sync::broadcast::{self, error::TryRecvError};
+use tracing::warn;
+
+use eutrader_core::dashboard::{FillRow, SharedDashboard};
+use eutrader_core::{Fill, Side, StrategyKey};
+
+/// Events buffered per subscriber before the slowest starts missing them.
+pub const EVENT_BUS_CAPACITY: usize = 1024;
+
+/// Something the engine did, published for tooling outside it (alerts,
+/// custom dashboards). Keys name the strategy instance, as `MarketConfig::key`.
+#[derive(Debug, Clone)]
+pub enum EngineEvent {
+    /// A fill was applied to a position.
+    Fill {
+        fill: Fill,
+        /// Market name, or the start of the token id when unconfigured
+        market: String,
+        /// The position's realized PnL after the fill
+        realized_pnl: Decimal,
+    },
+    /// An order went out, new or amended.
+    QuotePlaced {
+        key: StrategyKey,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+    },
+    /// A market's resting quotes were pulled; published once until it quotes
+    /// again, and not for a market that had nothing out.
+    QuotePulled { key: StrategyKey, reason: String },
+    /// A risk limit stopped a market from quoting.
+    RiskBreach { key: StrategyKey, reason: String },
+    /// The kill switch tripped and quoting halted for the session.
+    KillSwitch,
+    /// A market closed, or its resolution price became known.
+    MarketClosed {
+        key: StrategyKey,
+        resolution: Option<Decimal>,
+    },
+}
+
+/// Keeps a dashboard's fill log and kill-switch banner in step with the
+/// event bus.
+pub struct DashboardSubscriber {
+    events: broadcast::Receiver<EngineEvent>,
+    dashboard: SharedDashboard,
+}
+
+impl DashboardSubscriber {
+    pub fn new(events: broadcast::Receiver<EngineEvent>, dashboard: SharedDashboard) -> Self {
+        Self { events, dashboard }
+    }
+
+    /// Events published but not yet applied.
+    #[cfg(test)]
+    pub(crate) fn backlog(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Apply every event published since the last call.
+    pub fn drain(&mut self) {
+        loop {
+            match self.events.try_recv() {
+                Ok(event) => self.apply(&event),
+                Err(TryRecvError::Lagged(missed)) => {
+                    warn!(missed, "dashboard fell behind the event bus");
+                }
+                Err(TryRecvError::Empty | TryRecvError::Closed) => return,
+            }
+        }
+    }
+
+    fn apply(&self, event: &EngineEvent) {
+        let Ok(mut state) = self.dashboard.write() else {
+            return;
+        };
+        match event {
+            EngineEvent::Fill {
+                fill,
+                market,
+                realized_pnl,
+            } => state.add_fill(FillRow {
+                timestamp: fill.timestamp,
+                token_id: fill.key().to_string(),
+                market_name: market.clone(),
+                side: fill.side,
+                price: fill.price,
+                size: fill.size,
+                pnl_after: *realized_pnl,
+            }),
+            EngineEvent::KillSwitch => state.killed = true,
+            _ => {}
+        }
+    }
+}
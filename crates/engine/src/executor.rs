@@ -1,21 +1,89 @@
 use async_trait::async_trait;
-use eutrader_core::{OpenOrder, OrderId, Result, Side};
+use eutrader_core::{OpenOrder, OrderAck, OrderId, Result, Side};
 use rust_decimal::Decimal;
+use std::collections::HashMap;
 
 /// Trait for order execution backends.
 ///
-/// Implementations include `PaperExecutor` (simulated) and future
-/// live executors that hit the Polymarket CLOB API.
+/// Implementations include `PaperExecutor` (simulated), `DryRunExecutor`
+/// (logs intended live orders) and future live executors that hit the
+/// Polymarket CLOB API.
 #[async_trait]
 pub trait Executor: Send + Sync {
     /// Place a limit order on the given token/side.
+    ///
+    /// Transport failures are errors; an order the venue refuses comes back
+    /// as an ack with `OrderStatus::Rejected`.
     async fn place_order(
         &self,
         token_id: &str,
         side: Side,
         price: Decimal,
         size: Decimal,
-    ) -> Result<OrderId>;
+    ) -> Result<OrderAck>;
+
+    /// Place a limit order that may only shrink the position on `token_id`.
+    ///
+    /// The default has no venue-side guard and places a plain order, so the
+    /// resting order reports `reduce_only: false`.
+    async fn place_reduce_only_order(
+        &self,
+        token_id: &str,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+    ) -> Result<OrderAck> {
+        self.place_order(token_id, side, price, size).await
+    }
+
+    /// Place an order on behalf of strategy instance `strategy_id`, so its
+    /// orders and fills can be told apart from other strategies quoting the
+    /// same token.
+    ///
+    /// The default drops the tag and places a plain or reduce-only order;
+    /// backends that can label orders should override it.
+    async fn place_strategy_order(
+        &self,
+        strategy_id: &str,
+        token_id: &str,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+        reduce_only: bool,
+    ) -> Result<OrderAck> {
+        let _ = strategy_id;
+        if reduce_only {
+            self.place_reduce_only_order(token_id, side, price, size)
+                .await
+        } else {
+            self.place_order(token_id, side, price, size).await
+        }
+    }
+
+    /// Move a resting order to a new price and size in one operation.
+    ///
+    /// Venues that can amend keep the order resting throughout, avoiding the
+    /// gap with nothing on the book between a cancel and the next place. The
+    /// default falls back to `cancel_order` followed by a fresh placement with
+    /// the same `reduce_only` flag and strategy, so the returned ack may
+    /// carry a new ID.
+    async fn replace_order(
+        &self,
+        old: &OpenOrder,
+        price: Decimal,
+        size: Decimal,
+    ) -> Result<OrderAck> {
+        self.cancel_order(&old.id).await?;
+        self.place_strategy_order(
+            &old.strategy_id,
+            &old.token_id,
+            old.side,
+            price,
+            size,
+            old.reduce_only,
+        )
+        .await
+    }
 
     /// Cancel a single open order by its ID.
     async fn cancel_order(&self, id: &OrderId) -> Result<()>;
@@ -23,6 +91,48 @@ pub trait Executor: Send + Sync {
     /// Cancel every open order managed by this executor.
     async fn cancel_all(&self) -> Result<()>;
 
+    /// Cancel every open order on `token_id`, leaving other markets untouched.
+    ///
+    /// The default cancels the matching `open_orders` one at a time; venues
+    /// with a cancel-by-market endpoint should override it.
+    async fn cancel_token(&self, token_id: &str) -> Result<()> {
+        let open = self.open_orders().await?;
+        for order in open.iter().filter(|o| o.token_id == token_id) {
+            self.cancel_order(&order.id).await?;
+        }
+        Ok(())
+    }
+
+    /// Cancel the open orders strategy `strategy_id` has on `token_id`,
+    /// leaving other strategies on the same token untouched.
+    async fn cancel_strategy(&self, token_id: &str, strategy_id: &str) -> Result<()> {
+        let open = self.open_orders().await?;
+        for order in open
+            .iter()
+            .filter(|o| o.token_id == token_id && o.strategy_id == strategy_id)
+        {
+            self.cancel_order(&order.id).await?;
+        }
+        Ok(())
+    }
+
     /// Return all currently open orders.
     async fn open_orders(&self) -> Result<Vec<OpenOrder>>;
+
+    /// Keep the venue's dead man's switch alive, if it has one.
+    ///
+    /// Venues with cancel-on-disconnect pull every resting order once
+    /// heartbeats stop arriving. The default does nothing.
+    async fn heartbeat(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Net position per token as the venue reports it.
+    ///
+    /// Tokens missing from the map are flat. `None` means the backend cannot
+    /// report positions, which is the default, and position reconciliation
+    /// is skipped.
+    async fn fetch_positions(&self) -> Result<Option<HashMap<String, Decimal>>> {
+        Ok(None)
+    }
 }
@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use rust_decimal::Decimal;
+use tracing::{debug, warn};
+
+use eutrader_feed::http::{build_client, DEFAULT_CONNECT_TIMEOUT, DEFAULT_REQUEST_TIMEOUT};
+
+/// Source of externally computed fair values (e.g. a sports model).
+///
+/// When a token has a fair value, the quoter centers on it instead of the
+/// book midpoint.
+pub trait FairValueProvider: Send + Sync {
+    /// Current fair value for `token_id`, if the provider has one.
+    fn fair_value(&self, token_id: &str) -> Option<Decimal>;
+}
+
+impl<P: FairValueProvider + ?Sized> FairValueProvider for Box<P> {
+    fn fair_value(&self, token_id: &str) -> Option<Decimal> {
+        (**self).fair_value(token_id)
+    }
+}
+
+/// Provider with no opinion: every market quotes around its book.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoFairValue;
+
+impl FairValueProvider for NoFairValue {
+    fn fair_value(&self, _token_id: &str) -> Option<Decimal> {
+        None
+    }
+}
+
+/// Fair values read from a JSON file mapping token ID to price, e.g.
+/// `{"123...": 0.60}`.
+///
+/// `spawn_polling` re-reads the file on an interval so an external model can
+/// rewrite it while the engine runs. A read or parse failure keeps the last
+/// good values.
+#[derive(Debug, Clone)]
+pub struct FileFairValues {
+    path: PathBuf,
+    values: Arc<RwLock<HashMap<String, Decimal>>>,
+}
+
+impl FileFairValues {
+    /// Load fair values from `path`.
+    pub fn load(path: impl Into<PathBuf>) -> eutrader_core::Result<Self> {
+        let path = path.into();
+        let values = read_values(&path)?;
+        Ok(Self {
+            path,
+            values: Arc::new(RwLock::new(values)),
+        })
+    }
+
+    /// Re-read the file every `interval` for as long as the runtime lives.
+    pub fn spawn_polling(&self, interval: Duration) {
+        let this = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                this.refresh();
+            }
+        });
+    }
+
+    /// Re-read the file now, keeping the current values on error.
+    pub fn refresh(&self) {
+        match read_values(&self.path) {
+            Ok(values) => {
+                debug!(path = %self.path.display(), count = values.len(), "reloaded fair values");
+                if let Ok(mut current) = self.values.write() {
+                    *current = values;
+                }
+            }
+            Err(e) => {
+                warn!(path = %self.path.display(), error = %e, "failed to reload fair values — keeping previous");
+            }
+        }
+    }
+}
+
+impl FairValueProvider for FileFairValues {
+    fn fair_value(&self, token_id: &str) -> Option<Decimal> {
+        self.values.read().ok()?.get(token_id).copied()
+    }
+}
+
+/// Fair values served over HTTP as the same JSON object `FileFairValues`
+/// reads, e.g. by the model that computes them.
+///
+/// `spawn_polling` re-fetches on an interval. A failed or malformed fetch
+/// keeps the last good values.
+#[derive(Debug, Clone)]
+pub struct HttpFairValues {
+    url: String,
+    client: reqwest::Client,
+    values: Arc<RwLock<HashMap<String, Decimal>>>,
+}
+
+impl HttpFairValues {
+    /// Fetch fair values from `url`.
+    pub async fn load(url: impl Into<String>) -> eutrader_core::Result<Self> {
+        let url = url.into();
+        let client = build_client(DEFAULT_REQUEST_TIMEOUT, DEFAULT_CONNECT_TIMEOUT);
+        let values = fetch_values(&client, &url).await?;
+        Ok(Self {
+            url,
+            client,
+            values: Arc::new(RwLock::new(values)),
+        })
+    }
+
+    /// Re-fetch every `interval` for as long as the runtime lives.
+    pub fn spawn_polling(&self, interval: Duration) {
+        let this = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                this.refresh().await;
+            }
+        });
+    }
+
+    /// Re-fetch now, keeping the current values on error.
+    pub async fn refresh(&self) {
+        match fetch_values(&self.client, &self.url).await {
+            Ok(values) => {
+                debug!(url = %self.url, count = values.len(), "fetched fair values");
+                if let Ok(mut current) = self.values.write() {
+                    *current = values;
+                }
+            }
+            Err(e) => {
+                warn!(url = %self.url, error = %e, "failed to fetch fair values — keeping previous");
+            }
+        }
+    }
+}
+
+impl FairValueProvider for HttpFairValues {
+    fn fair_value(&self, token_id: &str) -> Option<Decimal> {
+        self.values.read().ok()?.get(token_id).copied()
+    }
+}
+
+fn read_values(path: &Path) -> eutrader_core::Result<HashMap<String, Decimal>> {
+    parse_values(&std::fs::read_to_string(path)?)
+}
+
+async fn fetch_values(
+    client: &reqwest::Client,
+    url: &str,
+) -> eutrader_core::Result<HashMap<String, Decimal>> {
+    let text = client
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+    parse_values(&text)
+}
+
+/// Parse a JSON object of token ID to fair value, each strictly inside (0, 1).
+fn parse_values(text: &str) -> eutrader_core::Result<HashMap<String, Decimal>> {
+    let values: HashMap<String, Decimal> = serde_json::from_str(text)?;
+    if let Some((token_id, value)) = values
+        .iter()
+        .find(|(_, v)| **v <= Decimal::ZERO || **v >= Decimal::ONE)
+    {
+        return Err(eutrader_core::Error::Config(format!(
+            "fair value {value} for {token_id} must be strictly between 0 and 1"
+        )));
+    }
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn file_values_reload_and_survive_bad_writes() {
+        let path = std::env::temp_dir().join(format!("eutrader_fair_{}.json", std::process::id()));
+        std::fs::write(&path, r#"{"tok1": 0.60, "tok2": "0.25"}"#).unwrap();
+
+        let provider = FileFairValues::load(&path).unwrap();
+        assert_eq!(provider.fair_value("tok1"), Some(dec!(0.60)));
+        assert_eq!(provider.fair_value("tok2"), Some(dec!(0.25)));
+        assert_eq!(provider.fair_value("tok3"), None);
+
+        std::fs::write(&path, r#"{"tok1": 0.65}"#).unwrap();
+        provider.refresh();
+        assert_eq!(provider.fair_value("tok1"), Some(dec!(0.65)));
+        assert_eq!(provider.fair_value("tok2"), None);
+
+        // A half-written or out-of-range file leaves the last values in place
+        std::fs::write(&path, r#"{"tok1": 1.5}"#).unwrap();
+        provider.refresh();
+        assert_eq!(provider.fair_value("tok1"), Some(dec!(0.65)));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn http_values_refresh_and_survive_bad_responses() {
+        use std::sync::Mutex;
+
+        let body = Arc::new(Mutex::new(r#"{"tok1": 0.60}"#.to_string()));
+        let served = body.clone();
+        let base =
+            crate::test_support::serve_json(move |_| Some(served.lock().unwrap().clone())).await;
+
+        let provider = HttpFairValues::load(format!("{base}/fair")).await.unwrap();
+        assert_eq!(provider.fair_value("tok1"), Some(dec!(0.60)));
+
+        *body.lock().unwrap() = r#"{"tok1": 0.65}"#.into();
+        provider.refresh().await;
+        assert_eq!(provider.fair_value("tok1"), Some(dec!(0.65)));
+
+        // A malformed response leaves the last values in place
+        *body.lock().unwrap() = "not json".into();
+        provider.refresh().await;
+        assert_eq!(provider.fair_value("tok1"), Some(dec!(0.65)));
+    }
+}
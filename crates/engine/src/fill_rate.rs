@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use eutrader_core::{Side, StrategyKey};
+
+/// Default half-life of a fill's weight in the rate estimate.
+pub const DEFAULT_FILL_RATE_HALF_LIFE: Duration = Duration::from_secs(300);
+
+/// Window the dashboard's fill probabilities are quoted over.
+pub const FILL_PROB_HORIZON: Duration = Duration::from_secs(60);
+
+/// Decay-weighted count of fills, as of `at`.
+#[derive(Debug, Clone, Copy)]
+struct Decayed {
+    weight: f64,
+    at: DateTime<Utc>,
+}
+
+/// Rolling per-market, per-side fill rate, with each fill's weight halving
+/// every `half_life`.
+#[derive(Debug, Clone)]
+pub struct FillRateTracker {
+    half_life_secs: f64,
+    sides: HashMap<(StrategyKey, Side), Decayed>,
+}
+
+impl Default for FillRateTracker {
+    fn default() -> Self {
+        Self::new(DEFAULT_FILL_RATE_HALF_LIFE)
+    }
+}
+
+impl FillRateTracker {
+    pub fn new(half_life: Duration) -> Self {
+        Self {
+            half_life_secs: half_life.as_secs_f64().max(f64::EPSILON),
+            sides: HashMap::new(),
+        }
+    }
+
+    pub fn record_fill(&mut self, key: &StrategyKey, side: Side, at: DateTime<Utc>) {
+        let half_life_secs = self.half_life_secs;
+        let entry = self
+            .sides
+            .entry((key.clone(), side))
+            .or_insert(Decayed { weight: 0.0, at });
+        entry.weight = entry.weight * decay(half_life_secs, entry.at, at) + 1.0;
+        entry.at = entry.at.max(at);
+    }
+
+    /// Fills per second on `side` of `key` as of `now`.
+    ///
+    /// Fills arriving steadily at rate `r` settle at a decayed weight of
+    /// `r * half_life / ln 2`, which this inverts.
+    pub fn rate(&self, key: &StrategyKey, side: Side, now: DateTime<Utc>) -> f64 {
+        let Some(d) = self.sides.get(&(key.clone(), side)) else {
+            return 0.0;
+        };
+        d.weight * decay(self.half_life_secs, d.at, now) * std::f64::consts::LN_2
+            / self.half_life_secs
+    }
+
+    /// Chance of at least one fill within `horizon` for a quote
+    /// `ticks_from_touch` behind the best price, treating fills as Poisson at
+    /// the side's rate, thinned by `1 / (1 + ticks_from_touch)`.
+    pub fn fill_probability(
+        &self,
+        key: &StrategyKey,
+        side: Side,
+        ticks_from_touch: f64,
+        horizon: Duration,
+        now: DateTime<Utc>,
+    ) -> f64 {
+        let rate = self.rate(key, side, now) / (1.0 + ticks_from_touch.max(0.0));
+        1.0 - (-rate * horizon.as_secs_f64()).exp()
+    }
+}
+
+/// Factor a weight recorded at `from` has decayed by at `to`.
+fn decay(half_life_secs: f64, from: DateTime<Utc>, to: DateTime<Utc>) -> f64 {
+    let elapsed = (to - from).num_milliseconds().max(0) as f64 / 1000.0;
+    0.5f64.powf(elapsed / half_life_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weight_halves_every_half_life() {
+        let start = Utc::now();
+        let mut tracker = FillRateTracker::new(Duration::from_secs(60));
+        let tok = StrategyKey::from("tok");
+        tracker.record_fill(&tok, Side::Buy, start);
+
+        let at = |secs| start + chrono::Duration::seconds(secs);
+        let fresh = tracker.rate(&tok, Side::Buy, start);
+        assert!((fresh - std::f64::consts::LN_2 / 60.0).abs() < 1e-12);
+        assert!((tracker.rate(&tok, Side::Buy, at(60)) / fresh - 0.5).abs() < 1e-9);
+        assert!((tracker.rate(&tok, Side::Buy, at(120)) / fresh - 0.25).abs() < 1e-9);
+        // Sides and markets are tracked separately
+        assert_eq!(tracker.rate(&tok, Side::Sell, start), 0.0);
+        assert_eq!(tracker.rate(&"other".into(), Side::Buy, start), 0.0);
+
+        // A second fill a half-life later stacks on the decayed weight
+        tracker.record_fill(&tok, Side::Buy, at(60));
+        assert!((tracker.rate(&tok, Side::Buy, at(60)) / fresh - 1.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn steady_fills_converge_to_their_rate() {
+        let start = Utc::now();
+        let mut tracker = FillRateTracker::new(Duration::from_secs(60));
+        let tok = StrategyKey::from("tok");
+        // One fill every 10s for 20 half-lives
+        for i in 0..120 {
+            tracker.record_fill(&tok, Side::Sell, start + chrono::Duration::seconds(i * 10));
+        }
+        let now = start + chrono::Duration::seconds(1195);
+        let rate = tracker.rate(&tok, Side::Sell, now);
+        assert!((rate - 0.1).abs() < 0.01, "rate {rate}");
+
+        let horizon = Duration::from_secs(10);
+        let at_touch = tracker.fill_probability(&tok, Side::Sell, 0.0, horizon, now);
+        assert!((at_touch - (1.0 - (-rate * 10.0).exp())).abs() < 1e-12);
+        assert!(tracker.fill_probability(&tok, Side::Sell, 3.0, horizon, now) < at_touch);
+        assert_eq!(
+            tracker.fill_probability(&tok, Side::Buy, 0.0, horizon, now),
+            0.0
+        );
+    }
+}
@@ -0,0 +1,80 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use eutrader_core::dashboard::LatencyStats;
+
+/// Default number of samples kept per latency window.
+pub const DEFAULT_LATENCY_WINDOW: usize = 500;
+
+/// Rolling window of the most recent latency samples.
+#[derive(Debug, Clone)]
+pub struct LatencyWindow {
+    samples: VecDeque<Duration>,
+    capacity: usize,
+}
+
+impl Default for LatencyWindow {
+    fn default() -> Self {
+        Self::new(DEFAULT_LATENCY_WINDOW)
+    }
+}
+
+impl LatencyWindow {
+    /// Keep the last `capacity` samples (at least one).
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn record(&mut self, elapsed: Duration) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(elapsed);
+    }
+
+    /// Nearest-rank percentiles of the samples in the window.
+    pub fn stats(&self) -> LatencyStats {
+        if self.samples.is_empty() {
+            return LatencyStats::default();
+        }
+        let mut sorted: Vec<Duration> = self.samples.iter().copied().collect();
+        sorted.sort();
+        let n = sorted.len();
+        let percentile = |q: f64| {
+            let rank = ((q * n as f64).ceil() as usize).clamp(1, n);
+            sorted[rank - 1].as_secs_f64() * 1000.0
+        };
+        LatencyStats {
+            samples: n,
+            p50_ms: percentile(0.50),
+            p95_ms: percentile(0.95),
+            p99_ms: percentile(0.99),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentiles_over_rolling_window() {
+        let mut window = LatencyWindow::new(100);
+        // An old outlier that scrolls out of the window
+        window.record(Duration::from_secs(10));
+        for ms in 1..=100 {
+            window.record(Duration::from_millis(ms));
+        }
+
+        let stats = window.stats();
+        assert_eq!(stats.samples, 100);
+        assert_eq!(stats.p50_ms, 50.0);
+        assert_eq!(stats.p95_ms, 95.0);
+        assert_eq!(stats.p99_ms, 99.0);
+        assert_eq!(LatencyWindow::default().stats(), LatencyStats::default());
+    }
+}
@@ -1,7 +1,36 @@
+pub mod dry_run;
+pub mod events;
 pub mod executor;
+pub mod fair_value;
+pub mod fill_rate;
+pub mod latency;
 pub mod manager;
+mod market_status;
+pub mod metrics;
 pub mod paper;
+pub mod rate_limit;
+pub mod shadow;
+pub mod simulate;
+pub mod status;
+pub mod status_line;
+#[cfg(test)]
+mod test_support;
+pub mod trade_log;
 
+pub use dry_run::DryRunExecutor;
+pub use events::{DashboardSubscriber, EngineEvent};
 pub use executor::Executor;
-pub use manager::OrderManager;
+pub use fair_value::{FairValueProvider, FileFairValues, HttpFairValues, NoFairValue};
+pub use fill_rate::FillRateTracker;
+pub use latency::LatencyWindow;
+pub use manager::{OrderManager, OrderManagerBuilder, PositionDrift};
+pub use metrics::{MetricsSink, NoMetrics, PrometheusSink, SharedMetrics, StatsdSink};
 pub use paper::PaperExecutor;
+pub use rate_limit::{RateLimitedExecutor, RateLimiter};
+pub use shadow::{Divergence, DivergenceKind, ShadowExecutor};
+pub use simulate::{
+    PnlDistribution, ReplayComparison, ReplayResult, SimulationParams, SimulationResult,
+};
+pub use status::StatusServer;
+pub use status_line::StatusLine;
+pub use trade_log::{FileTradeLogger, MemoryTradeLogger, SharedTradeLogger, TradeLogger};
@@ -1,18 +1,59 @@
-use std::collections::HashMap;
+use std::collections::hash_map::RandomState;
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasher, Hasher};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+use chrono::{DateTime, Utc};
 use futures::StreamExt;
+use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
-use tracing::{debug, error, info, warn};
+use tokio::sync::{broadcast, watch};
+use tracing::{debug, error, info, instrument, warn};
 
+use eutrader_core::dashboard::{LatencyStats, MarketRow, SharedDashboard};
+use eutrader_core::state::{SavedPosition, StateStore};
 use eutrader_core::{
-    Config, Fill, InventoryPosition, MarketConfig, MarketSnapshot, OpenOrder, Quote, Side,
+    system_clock, Config, Fill, FillStats, InventoryPosition, MarketConfig, MarketSnapshot, Mode,
+    OpenOrder, OrderAck, OrderId, PnlSample, Quote, SessionReport, SharedClock, Side, SizeMode,
+    StrategyKey, DEFAULT_TICK_SIZE, SIZE_LOT,
 };
-use eutrader_core::dashboard::{FillRow, MarketRow, SharedDashboard};
-use eutrader_strategy::{Quoter, RiskManager};
+use eutrader_feed::gamma::GammaMarket;
+use eutrader_feed::{GammaClient, MarketLimits};
+use eutrader_strategy::{AdverseSelectionTracker, Quoter, RiskManager};
 
+use crate::events::{DashboardSubscriber, EngineEvent, EVENT_BUS_CAPACITY};
 use crate::executor::Executor;
+use crate::fair_value::{FairValueProvider, NoFairValue};
+use crate::fill_rate::{FillRateTracker, FILL_PROB_HORIZON};
+use crate::latency::LatencyWindow;
+use crate::market_status::{next_statuses, StatusPoller};
+use crate::metrics::{NoMetrics, SharedMetrics};
 use crate::paper::PaperExecutor;
 
+/// Most PnL samples kept for the report; beyond this the series is thinned.
+const MAX_PNL_SAMPLES: usize = 100_000;
+
+/// A token whose local position disagrees with the venue's.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionDrift {
+    pub token_id: String,
+    pub local: Decimal,
+    pub venue: Decimal,
+}
+
+/// A market working its inventory off with passive quotes.
+#[derive(Debug, Clone, Copy)]
+struct Unwind {
+    /// Quote cycles allowed, fixed when the unwind starts
+    cycles: u32,
+    /// Quote cycles run so far
+    step: u32,
+    /// When whatever is left gets flattened, cycles used or not
+    deadline: DateTime<Utc>,
+}
+
 /// The main market-making loop. Receives market snapshots, computes target
 /// quotes via the `Quoter`, checks risk limits, and reconciles open orders
 /// through the `Executor`.
@@ -20,45 +61,448 @@ pub struct OrderManager<E: Executor> {
     executor: E,
     _quoter: Quoter,
     _risk_manager: RiskManager,
-    positions: HashMap<String, InventoryPosition>,
+    positions: HashMap<StrategyKey, InventoryPosition>,
     config: Config,
-    /// Lookup from token_id to its per-market config.
-    market_configs: HashMap<String, MarketConfig>,
+    /// Lookup from strategy instance to its per-market config. Positions and
+    /// the other per-market maps share the same keys.
+    market_configs: HashMap<StrategyKey, MarketConfig>,
     /// Optional shared dashboard state for TUI rendering.
     dashboard: Option<SharedDashboard>,
+    /// The dashboard's subscription to `events`, for its fill log and banner.
+    dashboard_events: Option<DashboardSubscriber>,
+    /// Publishes `EngineEvent`s to every subscriber.
+    events: broadcast::Sender<EngineEvent>,
+    /// Keys with quotes out since their last pull, so a pull is published
+    /// only when it cancels something.
+    quoting: HashSet<StrategyKey>,
+    /// Orders resting per key as of its last book fetch, kept current
+    /// through the placements and cancels since.
+    open_counts: HashMap<StrategyKey, usize>,
+    /// Config file to re-read on SIGHUP.
+    config_path: Option<PathBuf>,
+    /// Profile re-applied when the config file is re-read.
+    config_profile: Option<String>,
+    /// Most recent quotable snapshot per venue token, used to mark
+    /// positions and price flattening orders.
+    last_snapshots: HashMap<String, MarketSnapshot>,
+    /// Fills from closing out positions at shutdown.
+    flatten_fills: Vec<Fill>,
+    /// Statistics of every fill applied this session, for the report.
+    session_stats: FillStats,
+    /// Where to write the session report at shutdown.
+    report_path: Option<PathBuf>,
+    /// Where to save positions at shutdown, for `eutrader positions`.
+    state_store: Option<StateStore>,
+    /// Total PnL sampled at most once per `quote_refresh_interval_ms` times
+    /// `pnl_stride`.
+    pnl_series: Vec<PnlSample>,
+    /// Refresh intervals per PnL sample; doubles, halving the series,
+    /// whenever it reaches `MAX_PNL_SAMPLES`.
+    pnl_stride: u32,
+    /// Annualization for the report's Sharpe ratio; defaults to the sampling rate.
+    periods_per_year: Option<f64>,
+    /// Shut down once the run loop has been going this long.
+    max_runtime: Option<Duration>,
+    /// Ends the run loop gracefully once it reads `true`.
+    stop: Option<watch::Receiver<bool>>,
+    /// Latched once the kill switch trips; no further quotes are placed.
+    killed: bool,
+    /// Highest PnL seen per market, for the per-market trailing stop.
+    market_peaks: HashMap<StrategyKey, Decimal>,
+    /// Markets whose `max_market_loss` tripped; latched for the session.
+    stopped_markets: HashSet<StrategyKey>,
+    /// Markets that closed mid-session, with their resolution price once known.
+    closed_markets: HashMap<StrategyKey, Option<Decimal>>,
+    /// Midpoint each market was last requoted at, for `min_requote_bps`.
+    requote_mids: HashMap<StrategyKey, Decimal>,
+    /// Most recent fill per market, for `LastFill` quote anchoring.
+    last_fills: HashMap<StrategyKey, Fill>,
+    /// Quotable snapshots seen per market, counted until warmup completes.
+    warmup_seen: HashMap<StrategyKey, u32>,
+    /// External fair values that replace the book midpoint when present.
+    fair_values: Box<dyn FairValueProvider>,
+    /// Post-fill markouts per token, when `adverse_selection` is configured.
+    adverse: Option<AdverseSelectionTracker>,
+    /// Recent quote cycle durations.
+    cycle_latency: LatencyWindow,
+    /// Recent `place_order` round trips.
+    order_latency: LatencyWindow,
+    /// Decay-weighted fill rate per market and side, for the dashboard.
+    fill_rates: FillRateTracker,
+    /// Markets unwinding their inventory.
+    unwinds: HashMap<StrategyKey, Unwind>,
+    /// Whether the whole session is unwinding ahead of shutdown.
+    winding_down: bool,
+    /// Markets removed on reload, kept configured until their unwind ends.
+    retiring: HashSet<StrategyKey>,
+    /// Time source for snapshot freshness, order age and PnL sampling.
+    clock: SharedClock,
+    /// Where fill, PnL and open-order metrics go.
+    metrics: SharedMetrics,
+    /// Seed for `size_jitter_pct`; random per run unless fixed.
+    size_jitter_seed: u64,
+    /// Order size limits published by the venue, per token.
+    venue_limits: HashMap<String, MarketLimits>,
+    /// When the manager was built, by `clock`, for the heartbeat's uptime.
+    started: DateTime<Utc>,
+    /// Quote cycles run this session.
+    cycles: u64,
+    /// Heartbeats logged this session.
+    heartbeats: u64,
+    /// Where market statuses are polled from.
+    gamma: Arc<GammaClient>,
+}
+
+/// Step-by-step construction of an `OrderManager`.
+///
+/// The config is required and the executor is given last, to `build`, so one
+/// builder can wire up a manager whichever executor it ends up trading
+/// through. The quoter and risk manager default to new instances, and the
+/// optional components to what `new` uses.
+#[derive(Default)]
+pub struct OrderManagerBuilder {
+    quoter: Option<Quoter>,
+    risk_manager: Option<RiskManager>,
+    config: Option<Config>,
+    dashboard: Option<SharedDashboard>,
+    clock: Option<SharedClock>,
+    metrics: Option<SharedMetrics>,
+    size_jitter_seed: Option<u64>,
+    venue_limits: Option<HashMap<String, MarketLimits>>,
+    config_path: Option<PathBuf>,
+    config_profile: Option<String>,
+    report_path: Option<PathBuf>,
+    state_path: Option<PathBuf>,
+    periods_per_year: Option<f64>,
+    fair_values: Option<Box<dyn FairValueProvider>>,
+    gamma: Option<GammaClient>,
+    max_runtime: Option<Duration>,
+    stop: Option<watch::Receiver<bool>>,
+}
+
+impl OrderManagerBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn quoter(mut self, quoter: Quoter) -> Self {
+        self.quoter = Some(quoter);
+        self
+    }
+
+    pub fn risk_manager(mut self, risk_manager: RiskManager) -> Self {
+        self.risk_manager = Some(risk_manager);
+        self
+    }
+
+    pub fn config(mut self, config: Config) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    pub fn dashboard(mut self, dashboard: SharedDashboard) -> Self {
+        self.dashboard = Some(dashboard);
+        self
+    }
+
+    pub fn clock(mut self, clock: SharedClock) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    pub fn metrics(mut self, metrics: SharedMetrics) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    pub fn size_jitter_seed(mut self, seed: u64) -> Self {
+        self.size_jitter_seed = Some(seed);
+        self
+    }
+
+    pub fn venue_limits(mut self, limits: HashMap<String, MarketLimits>) -> Self {
+        self.venue_limits = Some(limits);
+        self
+    }
+
+    pub fn config_path(mut self, path: PathBuf) -> Self {
+        self.config_path = Some(path);
+        self
+    }
+
+    pub fn config_profile(mut self, profile: impl Into<String>) -> Self {
+        self.config_profile = Some(profile.into());
+        self
+    }
+
+    pub fn report_path(mut self, path: PathBuf) -> Self {
+        self.report_path = Some(path);
+        self
+    }
+
+    pub fn state_path(mut self, path: PathBuf) -> Self {
+        self.state_path = Some(path);
+        self
+    }
+
+    pub fn periods_per_year(mut self, periods: f64) -> Self {
+        self.periods_per_year = Some(periods);
+        self
+    }
+
+    pub fn fair_values(mut self, provider: impl FairValueProvider + 'static) -> Self {
+        self.fair_values = Some(Box::new(provider));
+        self
+    }
+
+    pub fn gamma(mut self, gamma: GammaClient) -> Self {
+        self.gamma = Some(gamma);
+        self
+    }
+
+    pub fn max_runtime(mut self, limit: Duration) -> Self {
+        self.max_runtime = Some(limit);
+        self
+    }
+
+    pub fn stop_signal(mut self, stop: watch::Receiver<bool>) -> Self {
+        self.stop = Some(stop);
+        self
+    }
+
+    /// Build a manager trading through `executor`, failing if no config was
+    /// given.
+    pub fn build<E: Executor>(self, executor: E) -> eutrader_core::Result<OrderManager<E>> {
+        let config = self
+            .config
+            .ok_or_else(|| eutrader_core::Error::Config("OrderManager needs a config".into()))?;
+        let mut manager = OrderManager::new(
+            executor,
+            self.quoter.unwrap_or_default(),
+            self.risk_manager.unwrap_or_default(),
+            config,
+        );
+        if let Some(dashboard) = self.dashboard {
+            manager = manager.with_dashboard(dashboard);
+        }
+        if let Some(clock) = self.clock {
+            manager = manager.with_clock(clock);
+        }
+        if let Some(metrics) = self.metrics {
+            manager = manager.with_metrics(metrics);
+        }
+        if let Some(seed) = self.size_jitter_seed {
+            manager = manager.with_size_jitter_seed(seed);
+        }
+        if let Some(limits) = self.venue_limits {
+            manager = manager.with_venue_limits(limits);
+        }
+        if let Some(path) = self.config_path {
+            manager = manager.with_config_path(path);
+        }
+        if let Some(profile) = self.config_profile {
+            manager = manager.with_config_profile(profile);
+        }
+        if let Some(path) = self.report_path {
+            manager = manager.with_report_path(path);
+        }
+        if let Some(path) = self.state_path {
+            manager = manager.with_state_path(path);
+        }
+        if let Some(periods) = self.periods_per_year {
+            manager = manager.with_periods_per_year(periods);
+        }
+        if let Some(provider) = self.fair_values {
+            manager.fair_values = provider;
+        }
+        if let Some(gamma) = self.gamma {
+            manager = manager.with_gamma(gamma);
+        }
+        if let Some(limit) = self.max_runtime {
+            manager = manager.with_max_runtime(limit);
+        }
+        if let Some(stop) = self.stop {
+            manager = manager.with_stop_signal(stop);
+        }
+        Ok(manager)
+    }
 }
 
 impl<E: Executor> OrderManager<E> {
     /// Build a new `OrderManager`.
-    pub fn new(
-        executor: E,
-        quoter: Quoter,
-        risk_manager: RiskManager,
-        config: Config,
-    ) -> Self {
-        let market_configs: HashMap<String, MarketConfig> = config
+    pub fn new(executor: E, quoter: Quoter, risk_manager: RiskManager, config: Config) -> Self {
+        let market_configs: HashMap<StrategyKey, MarketConfig> = config
             .markets
             .iter()
-            .map(|m| (m.token_id.clone(), m.clone()))
+            .map(|m| (m.key(), m.clone()))
             .collect();
+        let adverse = config
+            .adverse_selection
+            .clone()
+            .map(AdverseSelectionTracker::new);
+        let positions = seed_positions(&config, &market_configs);
 
         Self {
             executor,
             _quoter: quoter,
             _risk_manager: risk_manager,
-            positions: HashMap::new(),
+            positions,
             config,
             market_configs,
             dashboard: None,
+            dashboard_events: None,
+            events: broadcast::channel(EVENT_BUS_CAPACITY).0,
+            quoting: HashSet::new(),
+            open_counts: HashMap::new(),
+            config_path: None,
+            config_profile: None,
+            last_snapshots: HashMap::new(),
+            flatten_fills: Vec::new(),
+            session_stats: FillStats::new(),
+            report_path: None,
+            state_store: None,
+            pnl_series: Vec::new(),
+            pnl_stride: 1,
+            periods_per_year: None,
+            max_runtime: None,
+            stop: None,
+            killed: false,
+            market_peaks: HashMap::new(),
+            stopped_markets: HashSet::new(),
+            closed_markets: HashMap::new(),
+            requote_mids: HashMap::new(),
+            last_fills: HashMap::new(),
+            warmup_seen: HashMap::new(),
+            fair_values: Box::new(NoFairValue),
+            adverse,
+            cycle_latency: LatencyWindow::default(),
+            order_latency: LatencyWindow::default(),
+            fill_rates: FillRateTracker::default(),
+            unwinds: HashMap::new(),
+            winding_down: false,
+            retiring: HashSet::new(),
+            clock: system_clock(),
+            metrics: Arc::new(NoMetrics),
+            size_jitter_seed: RandomState::new().build_hasher().finish(),
+            venue_limits: HashMap::new(),
+            started: Utc::now(),
+            cycles: 0,
+            heartbeats: 0,
+            gamma: Arc::new(GammaClient::new()),
         }
     }
 
     /// Attach a shared dashboard for TUI rendering.
     pub fn with_dashboard(mut self, dashboard: SharedDashboard) -> Self {
+        self.dashboard_events = Some(DashboardSubscriber::new(
+            self.subscribe(),
+            dashboard.clone(),
+        ));
         self.dashboard = Some(dashboard);
         self
     }
 
+    /// Receive every `EngineEvent` published from now on. A subscriber more
+    /// than `EVENT_BUS_CAPACITY` events behind misses the oldest.
+    pub fn subscribe(&self) -> broadcast::Receiver<EngineEvent> {
+        self.events.subscribe()
+    }
+
+    /// Publish `event` to whoever is subscribed, if anyone.
+    fn emit(&self, event: EngineEvent) {
+        let _ = self.events.send(event);
+    }
+
+    /// Let the dashboard catch up with the events published so far.
+    fn sync_dashboard(&mut self) {
+        if let Some(ref mut subscriber) = self.dashboard_events {
+            subscriber.drain();
+        }
+    }
+
+    /// Read wall-clock time from `clock` instead of the system clock.
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.started = clock.now();
+        self.clock = clock;
+        self
+    }
+
+    /// Export metrics to `metrics` instead of discarding them.
+    pub fn with_metrics(mut self, metrics: SharedMetrics) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    /// Fix the seed behind `size_jitter_pct`, so jittered sizes are reproducible.
+    pub fn with_size_jitter_seed(mut self, seed: u64) -> Self {
+        self.size_jitter_seed = seed;
+        self
+    }
+
+    /// Clamp order sizes to the venue's published limits, per token, on top
+    /// of each market's configured limits. They survive config reloads.
+    pub fn with_venue_limits(mut self, limits: HashMap<String, MarketLimits>) -> Self {
+        self.venue_limits = limits;
+        apply_venue_limits(&mut self.market_configs, &self.venue_limits);
+        self
+    }
+
+    /// Enable config hot-reload from `path` when SIGHUP is received.
+    pub fn with_config_path(mut self, path: PathBuf) -> Self {
+        self.config_path = Some(path);
+        self
+    }
+
+    /// Annualize the report's Sharpe ratio with `periods` intervals per year
+    /// instead of the PnL sampling rate.
+    pub fn with_periods_per_year(mut self, periods: f64) -> Self {
+        self.periods_per_year = Some(periods);
+        self
+    }
+
+    /// Re-apply the named config profile whenever the config is reloaded.
+    pub fn with_config_profile(mut self, profile: impl Into<String>) -> Self {
+        self.config_profile = Some(profile.into());
+        self
+    }
+
+    /// Write a `SessionReport` to `path` at shutdown (CSV for `.csv`, JSON otherwise).
+    pub fn with_report_path(mut self, path: PathBuf) -> Self {
+        self.report_path = Some(path);
+        self
+    }
+
+    /// Save every position to `path` at shutdown, replacing what was there.
+    pub fn with_state_path(mut self, path: PathBuf) -> Self {
+        self.state_store = Some(StateStore::new(path));
+        self
+    }
+
+    /// Quote around fair values from `provider` instead of the book midpoint
+    /// for the tokens it covers.
+    pub fn with_fair_values(mut self, provider: impl FairValueProvider + 'static) -> Self {
+        self.fair_values = Box::new(provider);
+        self
+    }
+
+    /// Poll market statuses from `gamma` instead of the production Gamma API.
+    pub fn with_gamma(mut self, gamma: GammaClient) -> Self {
+        self.gamma = Arc::new(gamma);
+        self
+    }
+
+    /// Stop the run loop after `limit`, shutting down as on Ctrl+C.
+    pub fn with_max_runtime(mut self, limit: Duration) -> Self {
+        self.max_runtime = Some(limit);
+        self
+    }
+
+    /// Shut down gracefully, as on ctrl+c, once `stop` is set to `true`.
+    pub fn with_stop_signal(mut self, stop: watch::Receiver<bool>) -> Self {
+        self.stop = Some(stop);
+        self
+    }
+
     /// Run the main event loop, consuming a stream of `MarketSnapshot`s.
     ///
     /// For each snapshot the manager:
@@ -69,15 +513,47 @@ impl<E: Executor> OrderManager<E> {
     /// 5. Reconciles open orders (cancel stale, place new)
     /// 6. Logs current state
     ///
-    /// The loop runs until the stream ends or Ctrl+C is received.
+    /// The loop runs until the stream ends, Ctrl+C is received or the
+    /// `with_max_runtime` limit passes. SIGHUP reloads the config file set via
+    /// `with_config_path`.
+    ///
+    /// In live mode, orders left over from a previous session are cancelled
+    /// before quoting starts, `execution.heartbeat_interval_ms` drives
+    /// `Executor::heartbeat` and `execution.position_reconcile_interval_ms`
+    /// drives `reconcile_positions`. If the process dies without reaching `shutdown`
+    /// (SIGKILL, OOM, power loss), its orders keep resting on the venue until
+    /// either the venue's cancel-on-disconnect fires after missed heartbeats
+    /// or the next startup cancels them — that gap is the window of risk.
     pub async fn run(
         &mut self,
         mut snapshots: impl futures::Stream<Item = MarketSnapshot> + Unpin,
     ) {
         info!("order manager started — waiting for market data");
 
+        if self.config.mode == Mode::Live {
+            if let Err(e) = self.reconcile_on_startup().await {
+                error!(error = %e, "failed to clear stale orders on startup");
+            }
+        }
+
         let shutdown = tokio::signal::ctrl_c();
         tokio::pin!(shutdown);
+        let expiry = run_deadline(self.max_runtime);
+        tokio::pin!(expiry);
+        let mut unwind_expiry = Box::pin(run_deadline(None));
+        let mut hangup = HangupSignal::new();
+        let mut heartbeat = self
+            .config
+            .execution
+            .heartbeat_interval_ms
+            .map(|ms| tokio::time::interval(Duration::from_millis(ms)));
+        let mut position_check = self
+            .config
+            .execution
+            .position_reconcile_interval_ms
+            .map(|ms| tokio::time::interval(Duration::from_millis(ms)));
+        let mut status_poller = self.spawn_status_poller();
+        let mut stop = self.stop.take();
 
         loop {
             tokio::select! {
@@ -91,6 +567,10 @@ impl<E: Executor> OrderManager<E> {
                                     "error handling snapshot"
                                 );
                             }
+                            if self.unwind_finished() {
+                                info!("unwind finished — shutting down");
+                                break;
+                            }
                         }
                         None => {
                             info!("snapshot stream ended — shutting down");
@@ -98,288 +578,4009 @@ impl<E: Executor> OrderManager<E> {
                         }
                     }
                 }
+                _ = hangup.recv() => {
+                    self.reload_from_disk().await;
+                    self.refresh_status_tokens(&status_poller);
+                }
+                _ = next_tick(&mut heartbeat) => {
+                    if let Err(e) = self.executor.heartbeat().await {
+                        warn!(error = %e, "heartbeat failed");
+                    }
+                }
+                _ = next_tick(&mut position_check) => {
+                    if let Err(e) = self.reconcile_positions().await {
+                        warn!(error = %e, "position reconciliation failed");
+                    }
+                }
+                markets = next_statuses(&mut status_poller) => {
+                    self.apply_market_statuses(&markets).await;
+                    self.refresh_status_tokens(&status_poller);
+                }
                 _ = &mut shutdown => {
                     info!("ctrl+c received — shutting down gracefully");
                     break;
                 }
+                _ = stop_requested(&mut stop) => {
+                    info!("stop requested — shutting down gracefully");
+                    break;
+                }
+                _ = &mut expiry, if !self.winding_down => {
+                    if let Some(limit) = self.begin_unwind() {
+                        unwind_expiry = Box::pin(run_deadline(Some(limit)));
+                        continue;
+                    }
+                    info!("max runtime reached — shutting down gracefully");
+                    break;
+                }
+                _ = &mut unwind_expiry => {
+                    info!("unwind deadline reached — flattening what is left");
+                    break;
+                }
             }
         }
 
         // Graceful shutdown: cancel all outstanding orders
         self.shutdown().await;
+        self.print_pnl_summary();
+        self.write_report();
+        self.write_state();
     }
 
-    /// Process a single market snapshot.
-    async fn handle_snapshot(
-        &mut self,
-        snapshot: &MarketSnapshot,
-    ) -> eutrader_core::Result<()> {
-        let token_id = &snapshot.token_id;
+    /// Cancel any orders left resting from a previous session.
+    ///
+    /// The engine starts with no orders of its own, so anything the executor
+    /// reports as open is stale — typically from a process that was killed
+    /// before it could cancel. Every order is attempted even if some fail to
+    /// cancel; the failures are reported together afterwards. Returns the
+    /// number of orders cancelled.
+    pub async fn reconcile_on_startup(&self) -> eutrader_core::Result<usize> {
+        let stale = self.executor.open_orders().await?;
+        if stale.is_empty() {
+            return Ok(0);
+        }
 
-        let market_cfg = match self.market_configs.get(token_id) {
-            Some(cfg) => <MarketConfig as Clone>::clone(cfg),
-            None => {
-                debug!(token = %token_id, "ignoring snapshot for unconfigured token");
-                return Ok(());
+        warn!(
+            count = stale.len(),
+            "found orders from a previous session — cancelling"
+        );
+        let mut failures = Vec::new();
+        for order in &stale {
+            if let Err(e) = self.executor.cancel_order(&order.id).await {
+                warn!(order_id = %order.id, error = %e, "failed to cancel stale order");
+                failures.push(format!("{}: {e}", order.id));
             }
-        };
-
-        // Ensure we have a position tracker for this token
-        self.positions
-            .entry(token_id.clone())
-            .or_insert_with(|| InventoryPosition::new(token_id.clone()));
+        }
+        if !failures.is_empty() {
+            return Err(eutrader_core::Error::Execution(format!(
+                "{} of {} stale orders could not be cancelled ({})",
+                failures.len(),
+                stale.len(),
+                failures.join("; ")
+            )));
+        }
+        Ok(stale.len())
+    }
 
-        // --- Step 1: Compute target quote ---
-        // Borrow position temporarily for quote computation
-        let target_quote = {
-            let position = &self.positions[token_id];
-            Quoter::quote(snapshot, position, &market_cfg)
-        };
-        let target_quote = match target_quote {
-            Some(q) => q,
-            None => {
-                debug!(token = %token_id, "quoter returned None — spread too tight, pulling quotes");
-                self.executor.cancel_all().await?;
-                return Ok(());
-            }
+    /// Compare local positions against the venue's and report any drift.
+    ///
+    /// Only tokens we quote or hold are checked. Drift is always logged; local
+    /// positions are overwritten with the venue's only when
+    /// `execution.correct_position_drift` is set, so a transient bad answer
+    /// from the venue cannot clobber state by default. Returns nothing to
+    /// compare when the executor cannot report positions.
+    pub async fn reconcile_positions(&mut self) -> eutrader_core::Result<Vec<PositionDrift>> {
+        let Some(venue) = self.executor.fetch_positions().await? else {
+            return Ok(Vec::new());
         };
 
-        // --- Step 2: Risk checks ---
-        {
-            let position = &self.positions[token_id];
-            if let Err(e) = RiskManager::check_order(
-                position,
-                &target_quote,
-                &self.config.risk,
-            ) {
-                warn!(
-                    token = %token_id,
-                    reason = %e,
-                    "risk check failed — pulling quotes"
-                );
-                self.executor.cancel_all().await?;
-                return Ok(());
+        // The venue knows tokens, not strategies: compare each token's total
+        let mut local: HashMap<&str, Decimal> = HashMap::new();
+        for key in self.market_configs.keys() {
+            local.entry(&key.token_id).or_default();
+        }
+        for (key, position) in &self.positions {
+            *local.entry(&key.token_id).or_default() += position.net_position;
+        }
+        let mut drifts: Vec<PositionDrift> = local
+            .into_iter()
+            .filter_map(|(token_id, local)| {
+                let venue = venue.get(token_id).copied().unwrap_or(Decimal::ZERO);
+                (local != venue).then(|| PositionDrift {
+                    token_id: token_id.to_string(),
+                    local,
+                    venue,
+                })
+            })
+            .collect();
+        drifts.sort_by(|a, b| a.token_id.cmp(&b.token_id));
+
+        let correct = self.config.execution.correct_position_drift;
+        for drift in &drifts {
+            let keys = self.strategy_keys(&drift.token_id);
+            // A position split across strategies cannot be attributed to one
+            let correct = correct && keys.len() <= 1;
+            warn!(
+                token = %drift.token_id,
+                local = %drift.local,
+                venue = %drift.venue,
+                corrected = correct,
+                "position differs from venue"
+            );
+            if correct {
+                let key = keys
+                    .into_iter()
+                    .next()
+                    .unwrap_or_else(|| drift.token_id.as_str().into());
+                self.adopt_venue_position(&key, drift.venue);
             }
         }
+        Ok(drifts)
+    }
 
-        // --- Step 3: Reconcile orders ---
-        self.reconcile_orders(token_id, &target_quote).await?;
+    /// Overwrite the local net position for `key` with `venue`.
+    ///
+    /// A position opened from flat takes the latest mid as its entry price,
+    /// since the fills that built it were never seen.
+    fn adopt_venue_position(&mut self, key: &StrategyKey, venue: Decimal) {
+        let global_fee_bps = self.config.fee_bps;
+        let market_cfg = self.market_configs.get(key);
+        let mid = self.last_snapshots.get(&key.token_id).map(|s| s.midpoint);
+        let position = self
+            .positions
+            .entry(key.clone())
+            .or_insert_with(|| new_position(key, market_cfg, global_fee_bps));
+        if position.net_position.is_zero() {
+            position.avg_entry = mid.unwrap_or(position.avg_entry);
+        }
+        if venue.is_zero() {
+            position.avg_entry = Decimal::ZERO;
+        }
+        position.net_position = venue;
+    }
 
-        // --- Step 4: Update dashboard + log state ---
-        let position = &self.positions[token_id];
-        let unrealized = position.unrealized_pnl(snapshot.midpoint);
+    /// Re-read the config file and apply it, keeping the current config on error.
+    async fn reload_from_disk(&mut self) {
+        let Some(path) = self.config_path.clone() else {
+            warn!("SIGHUP received but no config path set — ignoring");
+            return;
+        };
 
-        if let Some(ref dash) = self.dashboard {
-            if let Ok(mut state) = dash.write() {
-                state.update_market(MarketRow {
-                    name: market_cfg.name.clone(),
-                    token_id: token_id.to_string(),
-                    midpoint: snapshot.midpoint,
-                    our_bid: target_quote.bid_price,
-                    our_ask: target_quote.ask_price,
-                    spread: target_quote.spread(),
-                    inventory: position.net_position,
-                    realized_pnl: position.realized_pnl,
-                    unrealized_pnl: unrealized,
-                    fill_count: position.fill_count,
-                    last_update: snapshot.timestamp,
-                });
-                state.refresh_totals();
+        info!(path = %path.display(), "SIGHUP received — reloading config");
+        match Config::load_profile(&path, self.config_profile.as_deref()) {
+            Ok(new_config) => {
+                if let Err(e) = self.reload_config(new_config).await {
+                    error!(error = %e, "failed to apply reloaded config");
+                }
             }
+            Err(e) => error!(error = %e, "config reload failed — keeping current config"),
         }
-
-        debug!(
-            token = %token_id,
-            mid = %snapshot.midpoint,
-            bid = %target_quote.bid_price,
-            ask = %target_quote.ask_price,
-            inv = %position.net_position,
-            pnl = %position.realized_pnl,
-            "tick"
-        );
-
-        Ok(())
     }
 
-    /// Cancel stale orders and place new ones to match the target quote.
-    async fn reconcile_orders(
-        &self,
-        token_id: &str,
-        target: &Quote,
-    ) -> eutrader_core::Result<()> {
-        let current_orders = self.executor.open_orders().await?;
+    /// Swap in a new config without losing session state.
+    ///
+    /// Spreads, sizes, skew, fees and risk limits take effect on the next
+    /// snapshot. Markets no longer configured have their quotes pulled, or
+    /// with `unwind_cycles` set, unwind their inventory before they go. The
+    /// execution mode cannot change at runtime, and newly added markets are
+    /// only quoted if the feed already polls them.
+    pub async fn reload_config(&mut self, mut new_config: Config) -> eutrader_core::Result<()> {
+        if new_config.mode != self.config.mode {
+            warn!(
+                current = ?self.config.mode,
+                requested = ?new_config.mode,
+                "mode cannot change on reload — restart required, keeping current mode"
+            );
+            new_config.mode = self.config.mode;
+        }
+
+        // Auto-discovered sessions keep their market set and pick up the new defaults
+        if new_config.markets.is_empty() {
+            new_config.markets = match new_config.auto_discover {
+                Some(ref defaults) => self
+                    .config
+                    .markets
+                    .iter()
+                    .map(|m| MarketConfig {
+                        spread_bps: defaults.spread_bps,
+                        size: defaults.size,
+                        max_inventory: defaults.max_inventory,
+                        skew_factor: defaults.skew_factor,
+                        min_round_trip_edge: defaults.min_round_trip_edge,
+                        max_market_spread_bps: defaults.max_market_spread_bps,
+                        one_sided_threshold: defaults.one_sided_threshold,
+                        min_edge_bps: defaults.min_edge_bps,
+                        ..m.clone()
+                    })
+                    .collect(),
+                None => self.config.markets.clone(),
+            };
+        }
 
-        // Filter to orders for this token
-        let my_orders: Vec<&OpenOrder> = current_orders
+        let mut new_market_configs: HashMap<StrategyKey, MarketConfig> = new_config
+            .markets
             .iter()
-            .filter(|o| o.token_id == token_id)
+            .map(|m| (m.key(), m.clone()))
             .collect();
+        apply_venue_limits(&mut new_market_configs, &self.venue_limits);
 
-        // Check if current orders already match target
-        let has_matching_bid = my_orders.iter().any(|o| {
-            o.side == Side::Buy
-                && o.price == target.bid_price
-                && o.size == target.size
-        });
-        let has_matching_ask = my_orders.iter().any(|o| {
-            o.side == Side::Sell
-                && o.price == target.ask_price
-                && o.size == target.size
-        });
-
-        if has_matching_bid && has_matching_ask && my_orders.len() == 2 {
-            debug!(token = %token_id, "orders already match target — no action");
-            return Ok(());
+        // A market readded on reload quotes normally again
+        for key in new_market_configs.keys() {
+            if self.retiring.remove(key) {
+                self.unwinds.remove(key);
+            }
         }
 
-        // Cancel all stale orders for this token
-        for order in &my_orders {
-            self.executor.cancel_order(&order.id).await?;
+        let removed: Vec<StrategyKey> = self
+            .market_configs
+            .keys()
+            .filter(|t| !new_market_configs.contains_key(*t))
+            .cloned()
+            .collect();
+        // A market's identity is its key: re-pointing one is a removal plus an addition
+        for key in &removed {
+            let name = &self.market_configs[key].name;
+            if let Some(renamed) = new_market_configs
+                .iter()
+                .find(|(k, m)| m.name == *name && !self.market_configs.contains_key(*k))
+                .map(|(k, _)| k)
+            {
+                warn!(
+                    market = %name,
+                    from = %key,
+                    to = %renamed,
+                    "token_id or strategy_id changed on reload — treated as a new market; \
+                     the old one's quotes are pulled and its position does not carry over"
+                );
+            }
+        }
+        // Removed markets holding inventory stay configured while they unwind
+        let (retiring, removed): (Vec<StrategyKey>, Vec<StrategyKey>) =
+            removed.into_iter().partition(|key| {
+                new_config.risk.unwind_cycles.is_some()
+                    && self
+                        .positions
+                        .get(key)
+                        .is_some_and(|p| !p.net_position.is_zero())
+            });
+        for key in &retiring {
+            info!(token = %key, "market removed on reload — unwinding its inventory first");
+            new_market_configs.insert(key.clone(), self.market_configs[key].clone());
+        }
+        if !removed.is_empty() {
+            let open = self.executor.open_orders().await?;
+            for key in &removed {
+                let resting: Vec<&OpenOrder> = open.iter().filter(|o| o.key() == *key).collect();
+                for order in &resting {
+                    self.executor.cancel_order(&order.id).await?;
+                }
+                let held = self
+                    .positions
+                    .get(key)
+                    .map_or(Decimal::ZERO, |p| p.net_position);
+                if !held.is_zero() {
+                    warn!(
+                        token = %key,
+                        position = %held,
+                        cancelled = resting.len(),
+                        "market removed on reload while holding inventory — it is no longer \
+                         quoted or unwound (set risk.unwind_cycles to work it off first)"
+                    );
+                } else if !resting.is_empty() {
+                    warn!(
+                        token = %key,
+                        cancelled = resting.len(),
+                        "market removed on reload — its resting orders were cancelled"
+                    );
+                } else {
+                    info!(token = %key, "market removed on reload");
+                }
+                self.open_counts.remove(key);
+                self.remove_from_dashboard(key);
+            }
         }
 
-        // Place new bid
-        if target.bid_price > Decimal::ZERO && target.size > Decimal::ZERO {
-            self.executor
-                .place_order(token_id, Side::Buy, target.bid_price, target.size)
-                .await?;
+        for token_id in new_market_configs.keys() {
+            if !self.market_configs.contains_key(token_id) {
+                warn!(
+                    token = %token_id,
+                    "market added on reload — it will only be quoted if the feed polls it"
+                );
+            }
         }
 
-        // Place new ask
-        if target.ask_price > Decimal::ZERO && target.size > Decimal::ZERO {
-            self.executor
-                .place_order(token_id, Side::Sell, target.ask_price, target.size)
-                .await?;
+        // Existing positions keep their history but pick up new fee/edge settings
+        for (token_id, position) in self.positions.iter_mut() {
+            if let Some(cfg) = new_market_configs.get(token_id) {
+                position.fee_bps = cfg.effective_fee_bps(new_config.fee_bps);
+                position.min_round_trip_edge = cfg.min_round_trip_edge;
+            }
         }
 
+        // Markout history survives a reload unless the feature is switched off
+        self.adverse = match (self.adverse.take(), new_config.adverse_selection.clone()) {
+            (Some(mut tracker), Some(cfg)) => {
+                tracker.set_config(cfg);
+                Some(tracker)
+            }
+            (None, Some(cfg)) => Some(AdverseSelectionTracker::new(cfg)),
+            (_, None) => None,
+        };
+
+        self.config = new_config;
+        self.market_configs = new_market_configs;
+        if let Some(cycles) = self.config.risk.unwind_cycles {
+            for key in retiring {
+                self.start_unwind(&key, cycles);
+                self.retiring.insert(key);
+            }
+        }
+        info!(markets = self.market_configs.len(), "config reloaded");
         Ok(())
     }
 
-    /// Apply simulated fills from the paper executor to inventory positions.
-    pub fn apply_fills(&mut self, fills: &[Fill]) {
-        for fill in fills {
-            let position = self
-                .positions
-                .entry(fill.token_id.clone())
-                .or_insert_with(|| InventoryPosition::new(fill.token_id.clone()));
-            position.apply_fill(fill);
+    /// Process a single market snapshot.
+    #[instrument(
+        name = "quote_cycle",
+        skip_all,
+        fields(token = %snapshot.token_id, mid = %snapshot.midpoint)
+    )]
+    async fn handle_snapshot(&mut self, snapshot: &MarketSnapshot) -> eutrader_core::Result<()> {
+        let started = Instant::now();
+        self.record_snapshot(snapshot);
+        // Only sane books are kept for marking positions and pricing flattens
+        if snapshot.is_quotable() {
+            self.last_snapshots
+                .insert(snapshot.token_id.clone(), snapshot.clone());
+            if let Some(ref mut tracker) = self.adverse {
+                tracker.observe(snapshot);
+            }
+        }
+        let keys = self.strategy_keys(&snapshot.token_id);
+        if keys.is_empty() {
+            debug!(token = %snapshot.token_id, "ignoring snapshot for unconfigured token");
+        }
+        // Each strategy on the token quotes the same book
+        let mut result = Ok(());
+        for key in keys {
+            result = result.and(self.quote_cycle(&key, snapshot).await);
+        }
+        let elapsed = started.elapsed();
+        self.cycle_latency.record(elapsed);
+        debug!(
+            elapsed_us = elapsed.as_micros() as u64,
+            "quote cycle complete"
+        );
+        self.publish_latency();
+        self.settle_unwinds().await;
+        if self.sample_pnl(self.clock.now()) {
+            self.publish_metrics();
+        }
+        self.count_cycle();
+        // Keep the dashboard's subscription from filling up with quote events
+        self.sync_dashboard();
+        result
+    }
 
-            info!(
-                token = %fill.token_id,
-                side = %fill.side,
-                price = %fill.price,
-                size = %fill.size,
-                "FILL"
-            );
+    /// Count a quote cycle and log a heartbeat every `heartbeat_every_cycles`.
+    fn count_cycle(&mut self) {
+        self.cycles += 1;
+        let Some(every) = self.config.heartbeat_every_cycles.filter(|n| *n > 0) else {
+            return;
+        };
+        if !self.cycles.is_multiple_of(every) {
+            return;
+        }
+        let mut inventory: Vec<String> = self
+            .positions
+            .iter()
+            .map(|(key, pos)| format!("{}={}", self.market_name(key), pos.net_position.normalize()))
+            .collect();
+        inventory.sort();
+        self.heartbeats += 1;
+        info!(
+            heartbeat = self.heartbeats,
+            uptime_secs = self.uptime().num_seconds(),
+            cycles = self.cycles,
+            inventory = %inventory.join(" "),
+            "heartbeat — engine alive"
+        );
+    }
 
-            if let Some(ref dash) = self.dashboard {
-                let market_name = self
-                    .market_configs
-                    .get(&fill.token_id)
-                    .map(|c| c.name.clone())
-                    .unwrap_or_else(|| fill.token_id[..8].to_string());
-
-                if let Ok(mut state) = dash.write() {
-                    state.add_fill(FillRow {
-                        timestamp: fill.timestamp,
-                        market_name,
-                        side: fill.side,
-                        price: fill.price,
-                        size: fill.size,
-                        pnl_after: position.realized_pnl,
-                    });
-                }
-            }
+    /// Time since the manager was built.
+    fn uptime(&self) -> chrono::Duration {
+        self.clock.now() - self.started
+    }
+
+    /// Record total PnL, at most once per `quote_refresh_interval_ms` times
+    /// `pnl_stride`. Returns whether a sample was taken.
+    fn sample_pnl(&mut self, now: DateTime<Utc>) -> bool {
+        let interval = chrono::Duration::milliseconds(
+            self.config.risk.quote_refresh_interval_ms as i64 * i64::from(self.pnl_stride),
+        );
+        if self
+            .pnl_series
+            .last()
+            .is_some_and(|last| now - last.timestamp < interval)
+        {
+            return false;
+        }
+        let pnl = self
+            .positions
+            .iter()
+            .map(|(key, pos)| {
+                let unrealized = self
+                    .last_snapshots
+                    .get(&key.token_id)
+                    .map_or(Decimal::ZERO, |s| pos.unrealized_pnl(s.midpoint));
+                pos.realized_pnl + unrealized
+            })
+            .sum();
+        self.pnl_series.push(PnlSample {
+            timestamp: now,
+            pnl,
+        });
+        // Keep long sessions bounded: every other sample, at half the rate
+        if self.pnl_series.len() >= MAX_PNL_SAMPLES {
+            let mut index = 0;
+            self.pnl_series.retain(|_| {
+                index += 1;
+                index % 2 == 1
+            });
+            self.pnl_stride *= 2;
         }
+        true
     }
 
-    /// Cancel all orders and print final PnL summary.
-    async fn shutdown(&mut self) {
-        info!("cancelling all open orders...");
-        if let Err(e) = self.executor.cancel_all().await {
-            error!(error = %e, "failed to cancel orders during shutdown");
+    /// Send PnL, per-market inventory and open-order gauges to the metrics sink.
+    fn publish_metrics(&self) {
+        let mut realized = Decimal::ZERO;
+        let mut unrealized = Decimal::ZERO;
+        for (key, pos) in &self.positions {
+            realized += pos.realized_pnl;
+            if let Some(snapshot) = self.last_snapshots.get(&key.token_id) {
+                unrealized += pos.unrealized_pnl(snapshot.midpoint);
+            }
+            let market = self.market_name(key);
+            let inventory = pos.net_position.to_f64().unwrap_or_default();
+            self.metrics
+                .gauge("inventory", inventory, &[("market", market)]);
         }
+        self.metrics
+            .gauge("realized_pnl", realized.to_f64().unwrap_or_default(), &[]);
+        self.metrics.gauge(
+            "unrealized_pnl",
+            unrealized.to_f64().unwrap_or_default(),
+            &[],
+        );
+        let open: usize = self.open_counts.values().sum();
+        self.metrics.gauge("open_orders", open as f64, &[]);
+    }
 
-        self.print_pnl_summary();
+    /// Display name of the market keyed `key`, or its venue token.
+    fn market_name<'a>(&'a self, key: &'a StrategyKey) -> &'a str {
+        self.market_configs
+            .get(key)
+            .map_or(key.token_id.as_str(), |m| m.name.as_str())
     }
 
-    /// Print a summary of realised PnL across all positions.
-    pub fn print_pnl_summary(&self) {
-        info!("=== Final PnL Summary ===");
-        let mut total_realized = Decimal::ZERO;
-        let mut total_fills: u64 = 0;
+    /// Sampling intervals per year, used to annualize the Sharpe ratio. A
+    /// thinned series has proportionally fewer.
+    fn periods_per_year(&self) -> f64 {
+        let periods = self.periods_per_year.unwrap_or_else(|| {
+            let interval_ms = self.config.risk.quote_refresh_interval_ms.max(1) as f64;
+            365.0 * 24.0 * 60.0 * 60.0 * 1000.0 / interval_ms
+        });
+        periods / f64::from(self.pnl_stride)
+    }
 
-        for (token_id, pos) in &self.positions {
-            info!(
-                token = %token_id,
-                net_position = %pos.net_position,
-                avg_entry = %pos.avg_entry,
-                realized_pnl = %pos.realized_pnl,
-                fills = pos.fill_count,
+    /// Strategy keys configured on `token_id`, sorted.
+    fn strategy_keys(&self, token_id: &str) -> Vec<StrategyKey> {
+        let mut keys: Vec<StrategyKey> = self
+            .market_configs
+            .keys()
+            .filter(|key| key.token_id == token_id)
+            .cloned()
+            .collect();
+        keys.sort();
+        keys
+    }
+
+    /// Cancel the resting orders of strategy `key`, leaving any other
+    /// strategy on the same token alone.
+    async fn pull_quotes(&mut self, key: &StrategyKey, reason: &str) -> eutrader_core::Result<()> {
+        if self.strategy_keys(&key.token_id).len() > 1 {
+            self.executor
+                .cancel_strategy(&key.token_id, &key.strategy_id)
+                .await?;
+        } else {
+            self.executor.cancel_token(&key.token_id).await?;
+        }
+        self.open_counts.remove(key);
+        if self.quoting.remove(key) {
+            self.emit(EngineEvent::QuotePulled {
+                key: key.clone(),
+                reason: reason.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Quote strategy `key` on a snapshot of its token: risk checks, quoting
+    /// and order reconciliation.
+    async fn quote_cycle(
+        &mut self,
+        key: &StrategyKey,
+        snapshot: &MarketSnapshot,
+    ) -> eutrader_core::Result<()> {
+        if self.killed {
+            return Ok(());
+        }
+
+        let Some(mut market_cfg) = self.market_configs.get(key).cloned() else {
+            return Ok(());
+        };
+
+        if self.closed_markets.contains_key(key) {
+            return Ok(());
+        }
+
+        // A crossed, locked or empty book gives no fair price: pull quotes
+        // rather than leave them resting to be picked off
+        if !snapshot.is_quotable() {
+            let status = snapshot.book_status;
+            warn!(token = %key, %status, "unquotable book — pulling quotes");
+            let reason = format!("{status} book — not quoting");
+            self.pull_quotes(key, &reason).await?;
+            // Keep showing the last good prices, refreshed to this snapshot's time
+            let shown = match self.last_snapshots.get(&snapshot.token_id) {
+                Some(last) => MarketSnapshot {
+                    timestamp: snapshot.timestamp,
+                    ..last.clone()
+                },
+                None => snapshot.clone(),
+            };
+            self.update_dashboard(&market_cfg, &shown, None, Some(reason));
+            return Ok(());
+        }
+
+        // Widen the spread if recent fills on the token have been picked off
+        if let Some(ref tracker) = self.adverse {
+            let widened = tracker.effective_spread_bps(&key.token_id, market_cfg.spread_bps);
+            if widened != market_cfg.spread_bps {
+                debug!(
+                    token = %key,
+                    configured = market_cfg.spread_bps,
+                    widened,
+                    "adverse selection — widening spread"
+                );
+                market_cfg.spread_bps = widened;
+            }
+        }
+
+        // Let prices settle before the first quote
+        let warmup = self.config.risk.warmup_ticks;
+        let seen = self.warmup_seen.entry(key.clone()).or_insert(0);
+        if *seen < warmup {
+            *seen += 1;
+            debug!(token = %key, seen = *seen, warmup, "warming up — not quoting");
+            let reason = format!("warming up ({}/{warmup})", *seen);
+            self.update_dashboard(&market_cfg, snapshot, None, Some(reason));
+            return Ok(());
+        }
+
+        // Ensure we have a position tracker for this token
+        let global_fee_bps = self.config.fee_bps;
+        self.positions
+            .entry(key.clone())
+            .or_insert_with(|| InventoryPosition::for_market(&market_cfg, global_fee_bps));
+
+        if self.check_kill_switch() {
+            self.trigger_kill_switch().await;
+            return Ok(());
+        }
+
+        // Unwinding markets keep quoting through a stop until their unwind ends
+        let unwinding = self.unwinds.contains_key(key);
+        if self.winding_down && !unwinding {
+            self.pull_quotes(key, "session winding down").await?;
+            self.update_dashboard(
+                &market_cfg,
+                snapshot,
+                None,
+                Some("winding down — not quoting".into()),
             );
-            total_realized += pos.realized_pnl;
-            total_fills += pos.fill_count;
+            return Ok(());
+        }
+        if !unwinding && self.stopped_markets.contains(key) {
+            self.update_dashboard(
+                &market_cfg,
+                snapshot,
+                None,
+                Some("market stop — not quoting".into()),
+            );
+            return Ok(());
+        }
+        if unwinding {
+            // Drawdown during an unwind is what the unwind is closing out
+        } else if let Some(drawdown) = self.market_drawdown_breach(&market_cfg, snapshot) {
+            self.stop_market(&market_cfg, snapshot, drawdown).await?;
+            return Ok(());
         }
 
-        info!(
-            total_realized_pnl = %total_realized,
-            total_fills = total_fills,
-            "session complete"
+        // --- Step 0: Skip stale or nonsensical books ---
+        if let Some(reason) = Quoter::skip_reason(
+            snapshot,
+            &market_cfg,
+            self.config.risk.max_snapshot_age_ms,
+            self.clock.now(),
+        ) {
+            warn!(token = %key, reason = %reason, "market unfit for quoting — pulling quotes");
+            self.pull_quotes(key, &reason.to_string()).await?;
+            self.update_dashboard(&market_cfg, snapshot, None, Some(reason.to_string()));
+            return Ok(());
+        }
+
+        // --- Step 1: Compute target quote ---
+        let unwind_step = self.unwinds.get_mut(key).map(|unwind| {
+            unwind.step += 1;
+            (unwind.step, unwind.cycles)
+        });
+        // Borrow position temporarily for quote computation
+        let target_quote = if let Some((step, cycles)) = unwind_step {
+            let position = &self.positions[key];
+            let progress = Decimal::from(step) / Decimal::from(cycles);
+            debug!(token = %key, step, cycles, inv = %position.net_position, "unwinding");
+            if step <= cycles {
+                Quoter::unwind(snapshot, position, &market_cfg, progress)
+            } else {
+                None
+            }
+        } else {
+            let position = &self.positions[key];
+            let fair_value = self.fair_values.fair_value(&key.token_id);
+            if let Some(fair) = fair_value {
+                debug!(token = %key, %fair, mid = %snapshot.midpoint, "quoting around external fair value");
+            }
+            let center = fair_value.or_else(|| {
+                let last_fill = self.last_fills.get(key);
+                let anchor = Quoter::anchor(snapshot.midpoint, last_fill, &market_cfg, self.clock.now());
+                if let Some(anchor) = anchor {
+                    debug!(token = %key, %anchor, mid = %snapshot.midpoint, "quoting around last fill");
+                }
+                anchor
+            });
+            Quoter::quote_around(snapshot, center, position, &market_cfg).map(|mut quote| {
+                Quoter::jitter_sizes(&mut quote, &market_cfg, self.size_jitter_seed);
+                quote
+            })
+        };
+        let target_quote = match target_quote {
+            Some(q) => q,
+            None => {
+                debug!(token = %key, "quoter returned None — spread too tight, pulling quotes");
+                self.pull_quotes(key, "no quote — spread too tight").await?;
+                return Ok(());
+            }
+        };
+
+        // --- Step 2: Risk checks ---
+        let risk_check =
+            RiskManager::check_order(&self.positions[key], &target_quote, &self.config.risk);
+        if let Err(e) = risk_check {
+            warn!(
+                token = %key,
+                reason = %e,
+                "risk check failed — pulling quotes"
+            );
+            let reason = e.to_string();
+            self.emit(EngineEvent::RiskBreach {
+                key: key.clone(),
+                reason: reason.clone(),
+            });
+            self.pull_quotes(key, &reason).await?;
+            return Ok(());
+        }
+
+        // --- Step 3: Reconcile orders ---
+        self.reconcile_orders(key, &target_quote, snapshot.midpoint)
+            .await?;
+        if target_quote.bid_active() || target_quote.ask_active() {
+            self.quoting.insert(key.clone());
+        }
+
+        // --- Step 4: Update dashboard + log state ---
+        self.update_dashboard(&market_cfg, snapshot, Some(&target_quote), None);
+
+        let position = &self.positions[key];
+        debug!(
+            token = %key,
+            mid = %snapshot.midpoint,
+            bid = %target_quote.bid_price,
+            ask = %target_quote.ask_price,
+            inv = %position.net_position,
+            pnl = %position.realized_pnl,
+            "tick"
         );
-    }
 
-    /// Return a reference to all tracked positions.
-    pub fn positions(&self) -> &HashMap<String, InventoryPosition> {
-        &self.positions
+        Ok(())
     }
-}
 
-/// Specialised `OrderManager` that also handles paper fills on each tick.
-impl OrderManager<PaperExecutor> {
-    /// Run the main loop with paper fill detection.
+    /// Track this market's PnL peak and return the drawdown from it if it
+    /// exceeds `max_market_loss`.
     ///
-    /// Before computing quotes on each snapshot, this checks whether any
-    /// virtual orders have been filled by the market moving through them.
-    pub async fn run_paper(
+    /// PnL is realized plus unrealized at the snapshot midpoint, so locking in
+    /// gains raises the peak rather than looking like a loss.
+    fn market_drawdown_breach(
         &mut self,
-        mut snapshots: impl futures::Stream<Item = MarketSnapshot> + Unpin,
-    ) {
-        info!("order manager started in PAPER mode — waiting for market data");
+        market_cfg: &MarketConfig,
+        snapshot: &MarketSnapshot,
+    ) -> Option<Decimal> {
+        let max_loss = market_cfg.max_market_loss?;
+        let key = market_cfg.key();
+        let position = self.positions.get(&key)?;
+        let pnl = position.realized_pnl + position.unrealized_pnl(snapshot.midpoint);
+        let peak = self.market_peaks.entry(key).or_insert(Decimal::ZERO);
+        *peak = (*peak).max(pnl);
+        let drawdown = *peak - pnl;
+        (drawdown > max_loss).then_some(drawdown)
+    }
 
-        let shutdown = tokio::signal::ctrl_c();
-        tokio::pin!(shutdown);
+    /// Pull quotes for one market after its trailing stop trips and, if
+    /// `flatten_on_stop` is set, close its position. Other markets keep quoting.
+    async fn stop_market(
+        &mut self,
+        market_cfg: &MarketConfig,
+        snapshot: &MarketSnapshot,
+        drawdown: Decimal,
+    ) -> eutrader_core::Result<()> {
+        let key = market_cfg.key();
+        error!(
+            token = %key,
+            drawdown = %drawdown,
+            max_market_loss = ?market_cfg.max_market_loss,
+            "MARKET STOP — pulling quotes for this market"
+        );
+        self.stopped_markets.insert(key.clone());
+        let reason = format!("market stop: down {drawdown} from peak");
+        self.emit(EngineEvent::RiskBreach {
+            key: key.clone(),
+            reason: reason.clone(),
+        });
+        self.pull_quotes(&key, &reason).await?;
+        if market_cfg.flatten_on_stop {
+            // With unwinding on, the position is worked off before the flatten
+            match self.config.risk.unwind_cycles {
+                Some(cycles) => self.start_unwind(&key, cycles),
+                None => self.flatten_position(&key).await,
+            }
+        }
+        self.update_dashboard(market_cfg, snapshot, None, Some(reason));
+        Ok(())
+    }
 
-        loop {
-            tokio::select! {
-                maybe_snap = snapshots.next() => {
-                    match maybe_snap {
-                        Some(snapshot) => {
-                            // Check for paper fills before processing the snapshot
-                            let fills = self.executor.check_fills(&snapshot).await;
-                            if !fills.is_empty() {
-                                self.apply_fills(&fills);
-                            }
+    /// Start polling Gamma for market statuses in the background, if
+    /// `market_status_interval_ms` is set.
+    fn spawn_status_poller(&self) -> Option<StatusPoller> {
+        let ms = self.config.risk.market_status_interval_ms?;
+        Some(StatusPoller::spawn(
+            self.gamma.clone(),
+            Duration::from_millis(ms),
+            self.status_tokens(),
+        ))
+    }
 
-                            if let Err(e) = self.handle_snapshot(&snapshot).await {
-                                error!(
-                                    token = %snapshot.token_id,
-                                    error = %e,
-                                    "error handling snapshot"
-                                );
-                            }
-                        }
-                        None => {
-                            info!("snapshot stream ended — shutting down");
-                            break;
-                        }
-                    }
-                }
-                _ = &mut shutdown => {
-                    info!("ctrl+c received — shutting down gracefully");
-                    break;
+    /// Point the status poller at the markets not yet settled.
+    fn refresh_status_tokens(&self, poller: &Option<StatusPoller>) {
+        if let Some(poller) = poller {
+            poller.set_tokens(self.status_tokens());
+        }
+    }
+
+    /// Venue tokens of every market not yet settled.
+    fn status_tokens(&self) -> Vec<String> {
+        let mut tokens: Vec<String> = self
+            .market_configs
+            .keys()
+            .filter(|key| !matches!(self.closed_markets.get(*key), Some(Some(_))))
+            .map(|key| key.token_id.clone())
+            .collect();
+        tokens.sort();
+        tokens.dedup();
+        tokens
+    }
+
+    /// Stop quoting markets that `markets` reports as closed and settle them.
+    ///
+    /// A closed market's orders are cancelled and it is not quoted again this
+    /// session. Its position is settled at the resolution price once Gamma
+    /// reports one (0 or 1); until then it stays open and is re-checked on
+    /// the next poll.
+    pub async fn apply_market_statuses(&mut self, markets: &[GammaMarket]) {
+        let keys: Vec<StrategyKey> = self.market_configs.keys().cloned().collect();
+        for key in keys {
+            if matches!(self.closed_markets.get(&key), Some(Some(_))) {
+                continue;
+            }
+            let Some(market) = markets
+                .iter()
+                .find(|m| m.all_token_ids().any(|t| t == key.token_id))
+            else {
+                continue;
+            };
+            if !market.is_closed() {
+                continue;
+            }
+
+            if !self.closed_markets.contains_key(&key) {
+                warn!(token = %key, question = %market.question, "market closed — pulling quotes");
+                if let Err(e) = self.pull_quotes(&key, "market closed").await {
+                    error!(token = %key, error = %e, "failed to cancel orders for closed market");
                 }
             }
+            let resolution = market.resolution_price(&key.token_id);
+            if self.closed_markets.insert(key.clone(), resolution) != Some(resolution) {
+                self.emit(EngineEvent::MarketClosed {
+                    key: key.clone(),
+                    resolution,
+                });
+            }
+
+            if let Some(price) = resolution {
+                let pnl = self
+                    .positions
+                    .get_mut(&key)
+                    .map_or(Decimal::ZERO, |p| p.settle(price));
+                info!(token = %key, %price, %pnl, "market resolved — position settled");
+            }
+            self.show_closed_market(&key, resolution);
         }
+    }
 
-        self.shutdown().await;
+    /// Show a closed market in the dashboard, marked at its resolution price.
+    fn show_closed_market(&self, key: &StrategyKey, resolution: Option<Decimal>) {
+        let (Some(market_cfg), Some(last)) = (
+            self.market_configs.get(key),
+            self.last_snapshots.get(&key.token_id),
+        ) else {
+            return;
+        };
+        let (shown, reason) = match resolution {
+            Some(price) => (
+                MarketSnapshot {
+                    midpoint: price,
+                    ..last.clone()
+                },
+                format!("resolved at {price} — not quoting"),
+            ),
+            None => (last.clone(), "market closed — not quoting".to_string()),
+        };
+        self.update_dashboard(market_cfg, &shown, None, Some(reason));
+    }
+
+    /// Whether `key`'s market has closed this session.
+    pub fn is_market_closed(&self, key: &StrategyKey) -> bool {
+        self.closed_markets.contains_key(key)
+    }
+
+    /// Whether the trailing stop has tripped for `key`.
+    pub fn is_market_stopped(&self, key: &StrategyKey) -> bool {
+        self.stopped_markets.contains(key)
+    }
+
+    /// Whether unrealized losses across all positions exceed `max_unrealized_loss`.
+    ///
+    /// Each position is marked at its market's latest midpoint; positions
+    /// without market data yet are marked at their entry price.
+    fn check_kill_switch(&self) -> bool {
+        let (positions, mids): (Vec<InventoryPosition>, Vec<Decimal>) = self
+            .positions
+            .values()
+            .map(|p| {
+                let mid = self
+                    .last_snapshots
+                    .get(&p.token_id)
+                    .map(|s| s.midpoint)
+                    .unwrap_or(p.avg_entry);
+                (p.clone(), mid)
+            })
+            .unzip();
+        RiskManager::should_kill_switch_with_prices(&positions, &mids, &self.config.risk)
+    }
+
+    /// Cancel every order and latch the kill switch so no more quotes go out.
+    async fn trigger_kill_switch(&mut self) {
+        self.killed = true;
+        error!(
+            max_unrealized_loss = %self.config.risk.max_unrealized_loss,
+            "KILL SWITCH — cancelling all orders and halting quoting for this session"
+        );
+        if let Err(e) = self.executor.cancel_all().await {
+            error!(error = %e, "failed to cancel orders after kill switch");
+        }
+        self.emit(EngineEvent::KillSwitch);
+        self.sync_dashboard();
+    }
+
+    /// Whether the kill switch has tripped this session.
+    pub fn is_killed(&self) -> bool {
+        self.killed
+    }
+
+    /// Rolling percentiles of quote cycle durations.
+    pub fn cycle_latency(&self) -> LatencyStats {
+        self.cycle_latency.stats()
+    }
+
+    /// Rolling percentiles of `place_order` round trips.
+    pub fn order_latency(&self) -> LatencyStats {
+        self.order_latency.stats()
+    }
+
+    /// Drop `key`'s row from the dashboard, if attached.
+    fn remove_from_dashboard(&self, key: &StrategyKey) {
+        if let Some(ref dash) = self.dashboard {
+            if let Ok(mut state) = dash.write() {
+                state.markets.remove(&key.to_string());
+            }
+        }
+    }
+
+    /// Keep the dashboard's feed freshness current, whatever the quote cycle
+    /// then makes of `snapshot`.
+    fn record_snapshot(&self, snapshot: &MarketSnapshot) {
+        if let Some(ref dash) = self.dashboard {
+            if let Ok(mut state) = dash.write() {
+                state.record_snapshot(snapshot.timestamp);
+            }
+        }
+    }
+
+    /// Copy the latest latency percentiles to the dashboard, if attached.
+    fn publish_latency(&self) {
+        let Some(ref dash) = self.dashboard else {
+            return;
+        };
+        if let Ok(mut state) = dash.write() {
+            state.cycle_latency = self.cycle_latency.stats();
+            state.order_latency = self.order_latency.stats();
+        }
+    }
+
+    /// Publish the latest state of a market to the dashboard, if attached.
+    ///
+    /// `quote` is `None` when the market is not being quoted, in which case
+    /// `status` should explain why.
+    fn update_dashboard(
+        &self,
+        market_cfg: &MarketConfig,
+        snapshot: &MarketSnapshot,
+        quote: Option<&Quote>,
+        status: Option<String>,
+    ) {
+        let Some(ref dash) = self.dashboard else {
+            return;
+        };
+        let key = market_cfg.key();
+        let Some(position) = self.positions.get(&key) else {
+            return;
+        };
+
+        // Ticks each resting quote sits behind the touch
+        let tick = market_cfg.tick_size.max(Decimal::new(1, 4));
+        let now = self.clock.now();
+        let fill_prob = |side: Side, behind: Decimal| {
+            let ticks = (behind / tick).to_f64().unwrap_or(0.0);
+            self.fill_rates
+                .fill_probability(&key, side, ticks, FILL_PROB_HORIZON, now)
+        };
+        let bid_fill_prob = quote
+            .filter(|q| q.bid_active())
+            .map(|q| fill_prob(Side::Buy, snapshot.best_bid - q.bid_price));
+        let ask_fill_prob = quote
+            .filter(|q| q.ask_active())
+            .map(|q| fill_prob(Side::Sell, q.ask_price - snapshot.best_ask));
+
+        if let Ok(mut state) = dash.write() {
+            state.pnl_rounding = self.config.pnl_rounding;
+            state.update_market(MarketRow {
+                name: market_cfg.name.clone(),
+                token_id: key.to_string(),
+                midpoint: snapshot.midpoint,
+                our_bid: quote
+                    .filter(|q| q.bid_active())
+                    .map(|q| q.bid_price)
+                    .unwrap_or(Decimal::ZERO),
+                our_ask: quote
+                    .filter(|q| q.ask_active())
+                    .map(|q| q.ask_price)
+                    .unwrap_or(Decimal::ZERO),
+                spread: quote.map(|q| q.spread()).unwrap_or(Decimal::ZERO),
+                inventory: position.net_position,
+                realized_pnl: position.realized_pnl,
+                unrealized_pnl: position.unrealized_pnl(snapshot.midpoint),
+                fill_count: position.fill_count,
+                last_update: snapshot.timestamp,
+                quote_status: status,
+                open_orders: quote
+                    .map(|q| q.bid_active() as usize + q.ask_active() as usize)
+                    .unwrap_or(0),
+                tick_size: market_cfg.tick_size,
+                spread_pnl: position.spread_pnl,
+                inventory_pnl: position.inventory_pnl,
+                bid_fill_prob,
+                ask_fill_prob,
+            });
+            state.refresh_totals();
+        }
+    }
+
+    /// Cancel stale orders and place new ones to match the target quote.
+    ///
+    /// With `min_requote_move` or `min_requote_bps` set, resting orders whose
+    /// only difference from the target is a small price move are kept.
+    async fn reconcile_orders(
+        &mut self,
+        key: &StrategyKey,
+        target: &Quote,
+        mid: Decimal,
+    ) -> eutrader_core::Result<()> {
+        let current_orders = self.executor.open_orders().await?;
+        let market_cfg = self.market_configs.get(key);
+        let max_age_ms = market_cfg.and_then(|m| m.max_order_age_ms);
+        let min_move = market_cfg.and_then(|m| {
+            m.min_requote_move
+                .map(|ticks| m.tick_size * Decimal::from(ticks))
+        });
+        let min_mid_bps = market_cfg.and_then(|m| m.min_requote_bps);
+        let notional_sizing = market_cfg.is_some_and(|m| m.size_mode == SizeMode::Notional);
+        let now = self.clock.now();
+
+        // Filter to orders for this token and strategy
+        let my_orders: Vec<&OpenOrder> =
+            current_orders.iter().filter(|o| o.key() == *key).collect();
+        self.open_counts.insert(key.clone(), my_orders.len());
+
+        // Check if current orders already match target
+        let has_matching_bid = my_orders.iter().any(|o| {
+            o.side == Side::Buy
+                && o.price == target.bid_price
+                && same_size(o.size, target.bid_size)
+                && o.reduce_only == (target.reduce_only == Some(Side::Buy))
+                && !order_expired(o, max_age_ms, now)
+        });
+        let has_matching_ask = my_orders.iter().any(|o| {
+            o.side == Side::Sell
+                && o.price == target.ask_price
+                && same_size(o.size, target.ask_size)
+                && o.reduce_only == (target.reduce_only == Some(Side::Sell))
+                && !order_expired(o, max_age_ms, now)
+        });
+
+        // Suppressed sides must have no resting order
+        let bid_ok = has_matching_bid == target.bid_active();
+        let ask_ok = has_matching_ask == target.ask_active();
+        let expected_orders = target.bid_active() as usize + target.ask_active() as usize;
+
+        if bid_ok && ask_ok && my_orders.len() == expected_orders {
+            debug!(token = %key, "orders already match target — no action");
+            return Ok(());
+        }
+
+        if min_move.is_some() || min_mid_bps.is_some() {
+            let mid_moved = match (self.requote_mids.get(key), min_mid_bps) {
+                (Some(&last), Some(bps)) if last > Decimal::ZERO => {
+                    (mid - last).abs() / last * Decimal::from(10_000) >= Decimal::from(bps)
+                }
+                (None, _) => true,
+                _ => false,
+            };
+            // A side is within threshold when its one resting order differs
+            // from the target by less than `min_move` in price and nothing
+            // else. Notional sizing compares value, as the share count moves
+            // with every tick of price.
+            let within = |side: Side, active: bool, price: Decimal, size: Decimal| {
+                let resting: Vec<&&OpenOrder> =
+                    my_orders.iter().filter(|o| o.side == side).collect();
+                match (active, resting.as_slice()) {
+                    (false, []) => true,
+                    (true, [o]) => {
+                        let size_ok = if notional_sizing {
+                            (o.size * o.price - size * price).abs() < SIZE_LOT * o.price.max(price)
+                        } else {
+                            same_size(o.size, size)
+                        };
+                        size_ok
+                            && o.reduce_only == (target.reduce_only == Some(side))
+                            && !order_expired(o, max_age_ms, now)
+                            && min_move.is_none_or(|m| (o.price - price).abs() < m)
+                    }
+                    _ => false,
+                }
+            };
+            if !mid_moved
+                && within(
+                    Side::Buy,
+                    target.bid_active(),
+                    target.bid_price,
+                    target.bid_size,
+                )
+                && within(
+                    Side::Sell,
+                    target.ask_active(),
+                    target.ask_price,
+                    target.ask_size,
+                )
+            {
+                debug!(token = %key, %mid, "target moved less than requote threshold — keeping orders");
+                return Ok(());
+            }
+        }
+        self.requote_mids.insert(key.clone(), mid);
+
+        let bid_target = (target.bid_active()
+            && target.bid_price > Decimal::ZERO
+            && target.bid_size > Decimal::ZERO)
+            .then_some((target.bid_price, target.bid_size));
+        let ask_target = (target.ask_active()
+            && target.ask_price > Decimal::ZERO
+            && target.ask_size > Decimal::ZERO)
+            .then_some((target.ask_price, target.ask_size));
+        let (bids, asks): (Vec<OpenOrder>, Vec<OpenOrder>) = my_orders
+            .into_iter()
+            .cloned()
+            .partition(|o| o.side == Side::Buy);
+
+        let bid_reduce_only = target.reduce_only == Some(Side::Buy);
+        let ask_reduce_only = target.reduce_only == Some(Side::Sell);
+        let expired: HashSet<OrderId> = bids
+            .iter()
+            .chain(&asks)
+            .filter(|o| order_expired(o, max_age_ms, now))
+            .map(|o| o.id.clone())
+            .collect();
+        self.reconcile_side(key, Side::Buy, bids, bid_target, bid_reduce_only, &expired)
+            .await?;
+        self.reconcile_side(key, Side::Sell, asks, ask_target, ask_reduce_only, &expired)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Bring one side's resting orders in line with `target`: keep an exact
+    /// match, otherwise amend an existing order via `replace_order` so the
+    /// side is never left empty, and cancel any extras. An order whose
+    /// reduce-only flag is wrong cannot be amended and is replaced outright;
+    /// an `expired` order is re-posted even when it matches.
+    async fn reconcile_side(
+        &mut self,
+        key: &StrategyKey,
+        side: Side,
+        orders: Vec<OpenOrder>,
+        target: Option<(Decimal, Decimal)>,
+        reduce_only: bool,
+        expired: &HashSet<OrderId>,
+    ) -> eutrader_core::Result<()> {
+        let Some((price, size)) = target else {
+            for order in &orders {
+                self.cancel_counted(key, &order.id).await?;
+            }
+            return Ok(());
+        };
+
+        let (mut orders, flagged_wrong): (Vec<OpenOrder>, Vec<OpenOrder>) = orders
+            .into_iter()
+            .partition(|o| o.reduce_only == reduce_only);
+        let current =
+            |o: &OpenOrder| o.price == price && same_size(o.size, size) && !expired.contains(&o.id);
+        let keep = orders
+            .iter()
+            .position(current)
+            .or((!orders.is_empty()).then_some(0));
+        let kept = keep.map(|i| orders.swap_remove(i));
+        for order in orders.iter().chain(&flagged_wrong) {
+            self.cancel_counted(key, &order.id).await?;
+        }
+
+        match kept {
+            Some(order) if current(&order) => {}
+            Some(order) => {
+                if expired.contains(&order.id) {
+                    debug!(order_id = %order.id, "order exceeded max_order_age_ms — refreshing");
+                }
+                self.replace_order_checked(&order, price, size).await?;
+            }
+            None => {
+                self.place_order_checked(key, side, price, size, reduce_only)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Cancel one of `key`'s orders and drop it from its open count.
+    async fn cancel_counted(
+        &mut self,
+        key: &StrategyKey,
+        id: &OrderId,
+    ) -> eutrader_core::Result<()> {
+        self.executor.cancel_order(id).await?;
+        if let Some(count) = self.open_counts.get_mut(key) {
+            *count = count.saturating_sub(1);
+        }
+        Ok(())
+    }
+
+    /// Amend `old` to `price`/`size`, recording latency and treating a venue
+    /// rejection as an error.
+    async fn replace_order_checked(
+        &mut self,
+        old: &OpenOrder,
+        price: Decimal,
+        size: Decimal,
+    ) -> eutrader_core::Result<OrderAck> {
+        let started = Instant::now();
+        let ack = self.executor.replace_order(old, price, size).await?;
+        self.order_latency.record(started.elapsed());
+        if !ack.is_accepted() {
+            return Err(eutrader_core::Error::Execution(format!(
+                "replace of {} to {size} @ {price} on {} rejected by venue",
+                old.id, old.token_id
+            )));
+        }
+        debug!(old_id = %old.id, order_id = %ack.id, price = %price, size = %size, "order replaced");
+        self.emit(EngineEvent::QuotePlaced {
+            key: old.key(),
+            side: old.side,
+            price,
+            size,
+        });
+        Ok(ack)
+    }
+
+    /// Place an order unless the market already has
+    /// `max_open_orders_per_market` orders resting.
+    ///
+    /// An order the venue rejects is returned as an `Execution` error so
+    /// callers treat it like any other failed placement.
+    async fn place_order_checked(
+        &mut self,
+        key: &StrategyKey,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+        reduce_only: bool,
+    ) -> eutrader_core::Result<OrderAck> {
+        if let Some(max) = self.config.risk.max_open_orders_per_market {
+            let open = self.open_counts.get(key).copied().unwrap_or(0);
+            if open >= max {
+                return Err(eutrader_core::Error::RiskBreach(format!(
+                    "{key} already has {open} open orders (max {max}) — refusing {side} order"
+                )));
+            }
+        }
+        let started = Instant::now();
+        let ack = self
+            .executor
+            .place_strategy_order(
+                &key.strategy_id,
+                &key.token_id,
+                side,
+                price,
+                size,
+                reduce_only,
+            )
+            .await?;
+        let elapsed = started.elapsed();
+        self.order_latency.record(elapsed);
+        if !ack.is_accepted() {
+            return Err(eutrader_core::Error::Execution(format!(
+                "{side} {size} @ {price} on {key} rejected by venue (order {})",
+                ack.id
+            )));
+        }
+        *self.open_counts.entry(key.clone()).or_default() += 1;
+        debug!(
+            order_id = %ack.id,
+            status = ?ack.status,
+            elapsed_us = elapsed.as_micros() as u64,
+            "order acknowledged"
+        );
+        self.emit(EngineEvent::QuotePlaced {
+            key: key.clone(),
+            side,
+            price,
+            size,
+        });
+        Ok(ack)
+    }
+
+    /// Apply simulated fills from the paper executor to inventory positions.
+    pub fn apply_fills(&mut self, fills: &[Fill]) {
+        for fill in fills {
+            // Tracked under the key of the strategy whose order filled
+            let key = fill.key();
+            let global_fee_bps = self.config.fee_bps;
+            let market_cfg = self.market_configs.get(&key);
+            let position = self
+                .positions
+                .entry(key.clone())
+                .or_insert_with(|| new_position(&key, market_cfg, global_fee_bps));
+            position.apply_fill(fill);
+            let market = self.market_name(&key);
+            self.metrics.count("fills", 1, &[("market", market)]);
+            self.session_stats.record(fill);
+            self.last_fills.insert(key.clone(), fill.clone());
+            self.fill_rates.record_fill(&key, fill.side, fill.timestamp);
+
+            if let Some(ref mut tracker) = self.adverse {
+                let mid = if fill.mid_at_fill > Decimal::ZERO {
+                    fill.mid_at_fill
+                } else {
+                    self.last_snapshots
+                        .get(&fill.token_id)
+                        .map(|s| s.midpoint)
+                        .unwrap_or(fill.price)
+                };
+                tracker.record_fill(fill, mid);
+            }
+
+            info!(
+                token = %key,
+                side = %fill.side,
+                price = %fill.price,
+                size = %fill.size,
+                "FILL"
+            );
+
+            let market = self
+                .market_configs
+                .get(&key)
+                .map(|c| c.name.clone())
+                .unwrap_or_else(|| fill.token_id.chars().take(8).collect());
+            let realized_pnl = self.positions[&key].realized_pnl;
+            self.emit(EngineEvent::Fill {
+                fill: fill.clone(),
+                market,
+                realized_pnl,
+            });
+        }
+        self.sync_dashboard();
+    }
+
+    /// Start winding the session down: each market holding inventory gets
+    /// `unwind_cycles` quote cycles of passive, reduce-only quotes to work it
+    /// off. Returns how long the unwind may take before the rest is
+    /// flattened, or `None` when unwinding is off or there is nothing to
+    /// unwind.
+    fn begin_unwind(&mut self) -> Option<Duration> {
+        let cycles = self.config.risk.unwind_cycles?;
+        if self.killed {
+            return None;
+        }
+        let keys: Vec<StrategyKey> = self
+            .positions
+            .iter()
+            .filter(|(key, p)| {
+                !p.net_position.is_zero()
+                    && self.market_configs.contains_key(*key)
+                    && !self.closed_markets.contains_key(*key)
+            })
+            .map(|(key, _)| key.clone())
+            .collect();
+        if keys.is_empty() {
+            return None;
+        }
+        info!(
+            cycles,
+            markets = keys.len(),
+            "max runtime reached — unwinding inventory passively"
+        );
+        self.winding_down = true;
+        for key in &keys {
+            self.start_unwind(key, cycles);
+        }
+        Some(self.unwind_limit(cycles))
+    }
+
+    /// Start unwinding one market over `cycles` quote cycles, unless it
+    /// already is.
+    fn start_unwind(&mut self, key: &StrategyKey, cycles: u32) {
+        let cycles = cycles.max(1);
+        let limit =
+            chrono::Duration::from_std(self.unwind_limit(cycles)).unwrap_or(chrono::Duration::MAX);
+        let deadline = self.clock.now() + limit;
+        self.unwinds.entry(key.clone()).or_insert_with(|| {
+            info!(token = %key, cycles, "unwinding inventory passively");
+            Unwind {
+                cycles,
+                step: 0,
+                deadline,
+            }
+        });
+    }
+
+    /// Longest an unwind of `cycles` quote cycles may run: one refresh
+    /// interval per cycle.
+    fn unwind_limit(&self, cycles: u32) -> Duration {
+        Duration::from_millis(u64::from(cycles) * self.config.risk.quote_refresh_interval_ms)
+    }
+
+    /// End the unwinds that are flat, out of cycles or past their deadline.
+    ///
+    /// A market unwinding on its own has what is left flattened, and if it
+    /// was removed on reload it is dropped for good. The session-wide
+    /// unwind leaves the flatten to `shutdown`.
+    async fn settle_unwinds(&mut self) {
+        let now = self.clock.now();
+        let mut ended: Vec<StrategyKey> = self
+            .unwinds
+            .iter()
+            .filter(|(key, unwind)| {
+                unwind.step >= unwind.cycles
+                    || now >= unwind.deadline
+                    || self
+                        .positions
+                        .get(*key)
+                        .is_none_or(|p| p.net_position.is_zero())
+            })
+            .map(|(key, _)| key.clone())
+            .collect();
+        ended.sort();
+        for key in ended {
+            self.unwinds.remove(&key);
+            if self.winding_down {
+                continue;
+            }
+            info!(token = %key, "unwind over — flattening what is left");
+            if let Err(e) = self.pull_quotes(&key, "unwind over").await {
+                warn!(token = %key, error = %e, "failed to pull quotes after unwind");
+            }
+            self.flatten_position(&key).await;
+            if self.retiring.remove(&key) {
+                self.market_configs.remove(&key);
+                self.remove_from_dashboard(&key);
+            }
+        }
+    }
+
+    /// Whether the session-wide unwind is under way and every market in it
+    /// is done.
+    fn unwind_finished(&self) -> bool {
+        self.winding_down && (self.killed || self.unwinds.is_empty())
+    }
+
+    /// Whether shutdown closes what is left of every position: always after
+    /// a session-wide unwind, whose deadline forces the flatten.
+    fn flattens_on_shutdown(&self) -> bool {
+        self.config.risk.flatten_on_shutdown || self.winding_down
+    }
+
+    /// Cancel all orders and, if `flatten_on_shutdown` is set or an unwind
+    /// ran, send marketable orders to close every open position. Markets cut
+    /// short mid-unwind are flattened either way.
+    async fn shutdown(&mut self) {
+        info!("cancelling all open orders...");
+        if let Err(e) = self.executor.cancel_all().await {
+            error!(error = %e, "failed to cancel orders during shutdown");
+        }
+
+        if self.flattens_on_shutdown() {
+            self.place_flatten_orders().await;
+        } else {
+            let mut unwinding: Vec<&StrategyKey> = self.unwinds.keys().collect();
+            unwinding.sort();
+            for key in unwinding {
+                self.flatten_position(key).await;
+            }
+        }
+    }
+
+    /// Place an order to close each non-flat position, priced by
+    /// `flatten_aggression` against the last seen book.
+    async fn place_flatten_orders(&self) {
+        for key in self.positions.keys() {
+            self.flatten_position(key).await;
+        }
+    }
+
+    /// Place one order to close `key`'s position, priced by
+    /// `flatten_aggression`: crossing the touch by default.
+    async fn flatten_position(&self, key: &StrategyKey) {
+        let Some(pos) = self.positions.get(key) else {
+            return;
+        };
+        if pos.net_position == Decimal::ZERO {
+            return;
+        }
+        let Some(snapshot) = self.last_snapshots.get(&key.token_id) else {
+            warn!(token = %key, net_position = %pos.net_position, "no market data — cannot flatten");
+            return;
+        };
+
+        let side = if pos.net_position > Decimal::ZERO {
+            Side::Sell
+        } else {
+            Side::Buy
+        };
+        let tick = self
+            .market_configs
+            .get(key)
+            .map_or(DEFAULT_TICK_SIZE, |m| m.tick_size);
+        let price = self
+            .config
+            .risk
+            .flatten_aggression
+            .price(side, snapshot, tick);
+        let size = pos.net_position.abs();
+
+        match self
+            .executor
+            .place_strategy_order(&key.strategy_id, &key.token_id, side, price, size, false)
+            .await
+        {
+            Ok(ack) if !ack.is_accepted() => {
+                error!(token = %key, order_id = %ack.id, "flatten order rejected by venue")
+            }
+            Ok(ack) => info!(
+                token = %key,
+                order_id = %ack.id,
+                side = %side,
+                price = %price,
+                size = %size,
+                "FLATTEN order placed"
+            ),
+            Err(e) => error!(token = %key, error = %e, "failed to place flatten order"),
+        }
+    }
+
+    /// Print a summary of realised PnL across all positions.
+    pub fn print_pnl_summary(&self) {
+        info!("=== Final PnL Summary ===");
+        let mut total_realized = Decimal::ZERO;
+        let mut total_fees = Decimal::ZERO;
+        let mut total_fills: u64 = 0;
+        let mut total_round_trips: u64 = 0;
+        let mut total_profitable: u64 = 0;
+
+        let rounding = self.config.pnl_rounding;
+
+        for (token_id, pos) in &self.positions {
+            info!(
+                token = %token_id,
+                net_position = %pos.net_position,
+                avg_entry = %pos.avg_entry,
+                realized_pnl = %rounding.round(pos.realized_pnl),
+                fees_paid = %rounding.round(pos.fees_paid),
+                fills = pos.fill_count,
+                round_trips = pos.round_trips,
+                profitable_round_trips = pos.profitable_round_trips,
+            );
+            total_realized += pos.realized_pnl;
+            total_fees += pos.fees_paid;
+            total_round_trips += pos.round_trips;
+            total_profitable += pos.profitable_round_trips;
+            total_fills += pos.fill_count;
+        }
+
+        info!(
+            total_realized_pnl = %rounding.round(total_realized),
+            total_fees = %rounding.round(total_fees),
+            total_fills = total_fills,
+            "session complete"
+        );
+
+        let performance = self.session_report().performance;
+        info!(
+            samples = self.pnl_series.len(),
+            sharpe = ?performance.sharpe.map(|s| (s * 100.0).round() / 100.0),
+            max_drawdown = %rounding.round(performance.max_drawdown),
+            total_return = %rounding.round(performance.total_return),
+            "risk-adjusted performance"
+        );
+
+        for (name, stats) in [
+            ("quote cycle", self.cycle_latency.stats()),
+            ("place_order", self.order_latency.stats()),
+        ] {
+            info!(
+                samples = stats.samples,
+                p50_ms = format_args!("{:.2}", stats.p50_ms),
+                p95_ms = format_args!("{:.2}", stats.p95_ms),
+                p99_ms = format_args!("{:.2}", stats.p99_ms),
+                "{name} latency"
+            );
+        }
+
+        for fill in &self.flatten_fills {
+            info!(
+                token = %fill.token_id,
+                side = %fill.side,
+                price = %fill.price,
+                size = %fill.size,
+                "flattened on shutdown"
+            );
+        }
+
+        if total_round_trips > 0 {
+            let rate = Decimal::from(total_profitable) / Decimal::from(total_round_trips);
+            info!(
+                round_trips = total_round_trips,
+                profitable = total_profitable,
+                profitable_rate = %rate.round_dp(4),
+                "round-trip quality"
+            );
+        }
+    }
+
+    /// Summarise the session so far from its fills and current positions.
+    pub fn session_report(&self) -> SessionReport {
+        let positions: Vec<InventoryPosition> = self.positions.values().cloned().collect();
+        SessionReport::from_stats(&self.session_stats, &positions)
+            .with_pnl_series(self.pnl_series.clone(), self.periods_per_year())
+    }
+
+    /// Write the session report to the configured path, if any.
+    fn write_report(&self) {
+        let Some(ref path) = self.report_path else {
+            return;
+        };
+        let report = self.session_report().rounded(self.config.pnl_rounding);
+        match report.write_to(path) {
+            Ok(()) => info!(path = %path.display(), "session report written"),
+            Err(e) => error!(path = %path.display(), error = %e, "failed to write session report"),
+        }
+    }
+
+    /// Save positions to the state file, if one is set.
+    fn write_state(&self) {
+        let Some(ref store) = self.state_store else {
+            return;
+        };
+        let mut positions: Vec<SavedPosition> =
+            self.positions.values().map(SavedPosition::from).collect();
+        positions.sort_by_key(|p| p.key());
+        match store.save(&positions) {
+            Ok(()) => info!(path = %store.path().display(), "positions saved"),
+            Err(e) => {
+                error!(path = %store.path().display(), error = %e, "failed to save positions")
+            }
+        }
+    }
+
+    /// Return a reference to all tracked positions.
+    pub fn positions(&self) -> &HashMap<StrategyKey, InventoryPosition> {
+        &self.positions
+    }
+}
+
+/// Specialised `OrderManager` that also handles paper fills on each tick.
+impl OrderManager<PaperExecutor> {
+    /// Run the main loop with paper fill detection.
+    ///
+    /// Before computing quotes on each snapshot, this checks whether any
+    /// virtual orders have been filled by the market moving through them.
+    pub async fn run_paper(
+        &mut self,
+        mut snapshots: impl futures::Stream<Item = MarketSnapshot> + Unpin,
+    ) {
+        info!("order manager started in PAPER mode — waiting for market data");
+
+        let shutdown = tokio::signal::ctrl_c();
+        tokio::pin!(shutdown);
+        let expiry = run_deadline(self.max_runtime);
+        tokio::pin!(expiry);
+        let mut unwind_expiry = Box::pin(run_deadline(None));
+        let mut hangup = HangupSignal::new();
+        let mut status_poller = self.spawn_status_poller();
+        let mut stop = self.stop.take();
+
+        loop {
+            tokio::select! {
+                maybe_snap = snapshots.next() => {
+                    match maybe_snap {
+                        Some(snapshot) => {
+                            self.process_paper_snapshot(&snapshot).await;
+                            if self.unwind_finished() {
+                                info!("unwind finished — shutting down");
+                                break;
+                            }
+                        }
+                        None => {
+                            info!("snapshot stream ended — shutting down");
+                            break;
+                        }
+                    }
+                }
+                _ = hangup.recv() => {
+                    self.reload_from_disk().await;
+                    self.refresh_status_tokens(&status_poller);
+                }
+                markets = next_statuses(&mut status_poller) => {
+                    self.apply_market_statuses(&markets).await;
+                    self.refresh_status_tokens(&status_poller);
+                }
+                _ = &mut shutdown => {
+                    info!("ctrl+c received — shutting down gracefully");
+                    break;
+                }
+                _ = stop_requested(&mut stop) => {
+                    info!("stop requested — shutting down gracefully");
+                    break;
+                }
+                _ = &mut expiry, if !self.winding_down => {
+                    if let Some(limit) = self.begin_unwind() {
+                        unwind_expiry = Box::pin(run_deadline(Some(limit)));
+                        continue;
+                    }
+                    info!("max runtime reached — shutting down gracefully");
+                    break;
+                }
+                _ = &mut unwind_expiry => {
+                    info!("unwind deadline reached — flattening what is left");
+                    break;
+                }
+            }
+        }
+
+        self.shutdown().await;
+        self.settle_flatten_orders().await;
+        self.print_pnl_summary();
+        self.write_report();
+        self.write_state();
+    }
+
+    /// Apply any paper fills the snapshot triggers, then run a quote cycle on it.
+    pub async fn process_paper_snapshot(&mut self, snapshot: &MarketSnapshot) {
+        let fills = self.executor.check_fills(snapshot).await;
+        if !fills.is_empty() {
+            self.apply_fills(&fills);
+        }
+
+        if let Err(e) = self.handle_snapshot(snapshot).await {
+            error!(
+                token = %snapshot.token_id,
+                error = %e,
+                "error handling snapshot"
+            );
+        }
+    }
+
+    /// Evaluate flattening orders against the last snapshot of their market
+    /// with the configured fill model.
+    ///
+    /// Only flatten orders are open at this point since `shutdown` cancels
+    /// everything first. Ones the book would not fill, like a passive
+    /// flatten, leave their position open.
+    async fn settle_flatten_orders(&mut self) {
+        if !self.flattens_on_shutdown() && self.unwinds.is_empty() {
+            return;
+        }
+        let snapshots: Vec<MarketSnapshot> = self.last_snapshots.values().cloned().collect();
+        for snapshot in &snapshots {
+            let fills = self.executor.settle_at(snapshot).await;
+            for fill in &fills {
+                info!(
+                    token = %fill.token_id,
+                    side = %fill.side,
+                    price = %fill.price,
+                    size = %fill.size,
+                    "FLATTEN fill"
+                );
+            }
+            self.apply_fills(&fills);
+            self.flatten_fills.extend(fills);
+        }
+        let mut open: Vec<(&StrategyKey, Decimal)> = self
+            .positions
+            .iter()
+            .filter(|(_, p)| !p.net_position.is_zero())
+            .map(|(key, p)| (key, p.net_position))
+            .collect();
+        open.sort();
+        for (key, net_position) in open {
+            warn!(token = %key, %net_position, "flatten order did not fill — position left open");
+        }
+    }
+}
+
+/// Tighten each market's order size limits to its token's venue limits.
+fn apply_venue_limits(
+    market_configs: &mut HashMap<StrategyKey, MarketConfig>,
+    limits: &HashMap<String, MarketLimits>,
+) {
+    for market in market_configs.values_mut() {
+        if let Some(limits) = limits.get(&market.token_id) {
+            limits.apply(market);
+        }
+    }
+}
+
+/// A fresh position for `key`, with its market's fee and round-trip
+/// settings if it is configured.
+fn new_position(
+    key: &StrategyKey,
+    market_cfg: Option<&MarketConfig>,
+    global_fee_bps: u32,
+) -> InventoryPosition {
+    match market_cfg {
+        Some(cfg) => InventoryPosition::for_market(cfg, global_fee_bps),
+        None => InventoryPosition {
+            strategy_id: key.strategy_id.clone(),
+            ..InventoryPosition::with_fee_bps(key.token_id.clone(), global_fee_bps)
+        },
+    }
+}
+
+/// Positions for `config.seed_positions`, keyed like `market_configs`.
+fn seed_positions(
+    config: &Config,
+    market_configs: &HashMap<StrategyKey, MarketConfig>,
+) -> HashMap<StrategyKey, InventoryPosition> {
+    config
+        .seed_positions
+        .iter()
+        .map(|seed| {
+            let key = seed.key();
+            let mut position = new_position(&key, market_configs.get(&key), config.fee_bps);
+            position.net_position = seed.net_position;
+            position.avg_entry = seed.avg_entry;
+            position.avg_entry_mid = seed.avg_entry;
+            info!(token = %key, net_position = %seed.net_position, avg_entry = %seed.avg_entry, "seeded position");
+            (key, position)
+        })
+        .collect()
+}
+
+/// Whether `order` has rested longer than `max_age_ms`.
+fn order_expired(order: &OpenOrder, max_age_ms: Option<u64>, now: DateTime<Utc>) -> bool {
+    max_age_ms.is_some_and(|ms| (now - order.placed_at).num_milliseconds() > ms as i64)
+}
+
+/// Whether a resting `size` is within a lot of `target`, so not worth
+/// replacing.
+fn same_size(size: Decimal, target: Decimal) -> bool {
+    (size - target).abs() < SIZE_LOT
+}
+
+/// Resolve once `limit` has elapsed, or never without one.
+async fn run_deadline(limit: Option<Duration>) {
+    match limit {
+        Some(limit) => tokio::time::sleep(limit).await,
+        None => std::future::pending::<()>().await,
+    }
+}
+
+/// Resolve once `stop` reads `true`, or never without one or once its
+/// sender is gone.
+async fn stop_requested(stop: &mut Option<watch::Receiver<bool>>) {
+    if let Some(rx) = stop {
+        if rx.wait_for(|stopped| *stopped).await.is_ok() {
+            return;
+        }
+    }
+    std::future::pending::<()>().await
+}
+
+/// Wait for the next tick of an optional interval; pends forever if `None`.
+async fn next_tick(interval: &mut Option<tokio::time::Interval>) {
+    match interval {
+        Some(i) => {
+            i.tick().await;
+        }
+        None => std::future::pending::<()>().await,
+    }
+}
+
+/// Resolves each time SIGHUP is received. Never resolves on non-unix platforms
+/// or if the signal handler could not be installed.
+struct HangupSignal {
+    #[cfg(unix)]
+    inner: Option<tokio::signal::unix::Signal>,
+}
+
+impl HangupSignal {
+    fn new() -> Self {
+        #[cfg(unix)]
+        {
+            use tokio::signal::unix::{signal, SignalKind};
+            let inner = match signal(SignalKind::hangup()) {
+                Ok(sig) => Some(sig),
+                Err(e) => {
+                    warn!(error = %e, "failed to install SIGHUP handler — config reload disabled");
+                    None
+                }
+            };
+            Self { inner }
+        }
+        #[cfg(not(unix))]
+        {
+            Self {}
+        }
+    }
+
+    async fn recv(&mut self) {
+        #[cfg(unix)]
+        if let Some(ref mut sig) = self.inner {
+            if sig.recv().await.is_some() {
+                return;
+            }
+        }
+        std::future::pending::<()>().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use eutrader_core::{QuoteAnchor, QuoteWindow, RiskConfig, SeedPosition};
+    use rust_decimal_macros::dec;
+
+    /// The key written `name`, as `StrategyKey`'s `Display` prints it.
+    fn key(name: &str) -> StrategyKey {
+        match name.split_once('/') {
+            Some((token_id, strategy_id)) => StrategyKey::new(token_id, strategy_id),
+            None => name.into(),
+        }
+    }
+
+    fn make_market(token_id: &str, spread_bps: u32) -> MarketConfig {
+        MarketConfig {
+            name: token_id.into(),
+            token_id: token_id.into(),
+            spread_bps,
+            size: dec!(10),
+            max_inventory: dec!(50),
+            skew_factor: dec!(0.001),
+            ..Default::default()
+        }
+    }
+
+    fn make_config(markets: Vec<MarketConfig>) -> Config {
+        Config {
+            mode: Mode::Paper,
+            risk: RiskConfig {
+                max_position_per_market: dec!(100),
+                max_total_exposure: dec!(500),
+                max_unrealized_loss: dec!(50),
+                quote_refresh_interval_ms: 1000,
+                quote_jitter_ms: 0,
+                max_snapshot_age_ms: None,
+                flatten_on_shutdown: false,
+                flatten_aggression: Default::default(),
+                max_open_orders_per_market: None,
+                warmup_ticks: 0,
+                market_status_interval_ms: None,
+                unwind_cycles: None,
+            },
+            auto_discover: None,
+            markets,
+            fee_bps: 0,
+            midpoint_mode: Default::default(),
+            min_level_size: Decimal::ZERO,
+            feed_startup_timeout_ms: 30_000,
+            last_trade_max_age_ms: 60_000,
+            execution: Default::default(),
+            adverse_selection: None,
+            pnl_rounding: Default::default(),
+            heartbeat_every_cycles: None,
+            metrics: Default::default(),
+            seed_positions: Vec::new(),
+        }
+    }
+
+    fn snapshot(token_id: &str, mid: Decimal) -> MarketSnapshot {
+        MarketSnapshot {
+            token_id: token_id.into(),
+            best_bid: mid - dec!(0.01),
+            best_ask: mid + dec!(0.01),
+            midpoint: mid,
+            micro_price: mid,
+            spread: dec!(0.02),
+            bid_size: dec!(100),
+            ask_size: dec!(100),
+            timestamp: Utc::now(),
+            book_status: Default::default(),
+        }
+    }
+
+    async fn quoted_prices<E: Executor>(
+        manager: &OrderManager<E>,
+        token_id: &str,
+    ) -> (Decimal, Decimal) {
+        let orders = manager.executor.open_orders().await.unwrap();
+        let bid = orders
+            .iter()
+            .find(|o| o.key() == key(token_id) && o.side == Side::Buy)
+            .map(|o| o.price)
+            .unwrap();
+        let ask = orders
+            .iter()
+            .find(|o| o.key() == key(token_id) && o.side == Side::Sell)
+            .map(|o| o.price)
+            .unwrap();
+        (bid, ask)
+    }
+
+    #[tokio::test]
+    async fn reload_with_tighter_spread_tightens_quotes() {
+        let config = make_config(vec![make_market("tok1", 600)]);
+        let mut manager = OrderManager::new(
+            PaperExecutor::new(),
+            Quoter::new(),
+            RiskManager::new(),
+            config,
+        );
+
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.50)))
+            .await
+            .unwrap();
+        assert_eq!(
+            quoted_prices(&manager, "tok1").await,
+            (dec!(0.47), dec!(0.53))
+        );
+
+        manager
+            .reload_config(make_config(vec![make_market("tok1", 200)]))
+            .await
+            .unwrap();
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.50)))
+            .await
+            .unwrap();
+        assert_eq!(
+            quoted_prices(&manager, "tok1").await,
+            (dec!(0.49), dec!(0.51))
+        );
+    }
+
+    struct FixedFairValues(HashMap<String, Decimal>);
+
+    impl FairValueProvider for FixedFairValues {
+        fn fair_value(&self, token_id: &str) -> Option<Decimal> {
+            self.0.get(token_id).copied()
+        }
+    }
+
+    #[tokio::test]
+    async fn external_fair_value_shifts_quotes_off_book_mid() {
+        let config = make_config(vec![make_market("tok1", 200), make_market("tok2", 200)]);
+        let fair = FixedFairValues(HashMap::from([("tok1".to_string(), dec!(0.60))]));
+        let mut manager = OrderManager::new(
+            PaperExecutor::new(),
+            Quoter::new(),
+            RiskManager::new(),
+            config,
+        )
+        .with_fair_values(fair);
+
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.50)))
+            .await
+            .unwrap();
+        manager
+            .handle_snapshot(&snapshot("tok2", dec!(0.50)))
+            .await
+            .unwrap();
+        // Centered on 0.60, with the bid held passive below the 0.51 ask
+        assert_eq!(
+            quoted_prices(&manager, "tok1").await,
+            (dec!(0.50), dec!(0.61))
+        );
+        assert_eq!(
+            quoted_prices(&manager, "tok2").await,
+            (dec!(0.49), dec!(0.51))
+        );
+    }
+
+    #[tokio::test]
+    async fn builder_assembles_a_working_manager() {
+        let dashboard = eutrader_core::dashboard::new_shared_dashboard("Paper");
+        let clock = eutrader_core::MockClock::new(Utc::now());
+        let mut manager = OrderManagerBuilder::new()
+            .quoter(Quoter::new())
+            .risk_manager(RiskManager::new())
+            .config(make_config(vec![make_market("tok1", 400)]))
+            .dashboard(dashboard.clone())
+            .clock(Arc::new(clock))
+            .size_jitter_seed(7)
+            .build(PaperExecutor::new())
+            .unwrap();
+        assert_eq!(manager.size_jitter_seed, 7);
+
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.50)))
+            .await
+            .unwrap();
+        assert_eq!(
+            quoted_prices(&manager, "tok1").await,
+            (dec!(0.48), dec!(0.52))
+        );
+        assert!(dashboard.read().unwrap().markets.contains_key("tok1"));
+
+        let missing = OrderManagerBuilder::new().build(PaperExecutor::new());
+        assert!(matches!(missing, Err(eutrader_core::Error::Config(_))));
+    }
+
+    #[test]
+    fn pnl_series_is_thinned_once_full() {
+        let config = make_config(vec![make_market("tok1", 400)]);
+        let mut manager = OrderManager::new(
+            PaperExecutor::new(),
+            Quoter::new(),
+            RiskManager::new(),
+            config,
+        );
+        let periods = manager.periods_per_year();
+        let start = Utc::now();
+        let interval = manager.config.risk.quote_refresh_interval_ms as i64;
+        for i in 0..MAX_PNL_SAMPLES as i64 {
+            manager.sample_pnl(start + chrono::Duration::milliseconds(i * interval));
+        }
+
+        assert_eq!(manager.pnl_series.len(), MAX_PNL_SAMPLES / 2);
+        assert_eq!(
+            manager.pnl_series[1].timestamp - manager.pnl_series[0].timestamp,
+            chrono::Duration::milliseconds(2 * interval)
+        );
+        assert_eq!(manager.periods_per_year(), periods / 2.0);
+        // Sampling continues at the halved rate
+        let last = manager.pnl_series.last().unwrap().timestamp;
+        assert!(!manager.sample_pnl(last + chrono::Duration::milliseconds(interval)));
+        assert!(manager.sample_pnl(last + chrono::Duration::milliseconds(2 * interval)));
+    }
+
+    #[tokio::test]
+    async fn unquoted_snapshots_still_refresh_feed_freshness() {
+        let dashboard = eutrader_core::dashboard::new_shared_dashboard("Paper");
+        let mut config = make_config(vec![make_market("tok1", 400)]);
+        config.risk.warmup_ticks = 5;
+        let mut manager = OrderManager::new(
+            PaperExecutor::new(),
+            Quoter::new(),
+            RiskManager::new(),
+            config,
+        )
+        .with_dashboard(dashboard.clone());
+
+        // Warming up: no position yet, so no market row is written
+        let first = snapshot("tok1", dec!(0.50));
+        manager.handle_snapshot(&first).await.unwrap();
+        assert!(dashboard.read().unwrap().markets.is_empty());
+        assert_eq!(
+            dashboard.read().unwrap().last_snapshot_at(),
+            Some(first.timestamp)
+        );
+
+        // Neither does a killed engine, nor a token nobody quotes
+        manager.killed = true;
+        let later = MarketSnapshot {
+            timestamp: first.timestamp + chrono::Duration::seconds(5),
+            ..snapshot("other", dec!(0.50))
+        };
+        manager.handle_snapshot(&later).await.unwrap();
+        assert_eq!(
+            dashboard.read().unwrap().last_snapshot_at(),
+            Some(later.timestamp)
+        );
+    }
+
+    #[tokio::test]
+    async fn fills_and_quotes_are_published_on_the_event_bus() {
+        let dashboard = eutrader_core::dashboard::new_shared_dashboard("Paper");
+        let config = make_config(vec![make_market("tok1", 400)]);
+        let mut manager = OrderManager::new(
+            PaperExecutor::new(),
+            Quoter::new(),
+            RiskManager::new(),
+            config,
+        )
+        .with_dashboard(dashboard.clone());
+        let mut events = manager.subscribe();
+
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.50)))
+            .await
+            .unwrap();
+        let mut placed = Vec::new();
+        while let Ok(EngineEvent::QuotePlaced {
+            key, side, price, ..
+        }) = events.try_recv()
+        {
+            placed.push((key, side, price));
+        }
+        placed.sort_by_key(|(_, side, _)| *side == Side::Sell);
+        assert_eq!(
+            placed,
+            [
+                (key("tok1"), Side::Buy, dec!(0.48)),
+                (key("tok1"), Side::Sell, dec!(0.52))
+            ]
+        );
+
+        manager.apply_fills(&[Fill {
+            token_id: "tok1".into(),
+            strategy_id: String::new(),
+            side: Side::Buy,
+            price: dec!(0.48),
+            size: dec!(10),
+            timestamp: Utc::now(),
+            is_simulated: true,
+            mid_at_fill: Decimal::ZERO,
+            edge: Decimal::ZERO,
+        }]);
+        match events.try_recv().unwrap() {
+            EngineEvent::Fill {
+                fill,
+                market,
+                realized_pnl,
+            } => {
+                assert_eq!(
+                    (fill.token_id.as_str(), fill.side, fill.size),
+                    ("tok1", Side::Buy, dec!(10))
+                );
+                assert_eq!(market, "tok1");
+                assert_eq!(realized_pnl, Decimal::ZERO);
+            }
+            other => panic!("expected a fill, got {other:?}"),
+        }
+        assert!(events.try_recv().is_err());
+        // The dashboard's fill log is fed from the same bus
+        assert_eq!(dashboard.read().unwrap().recent_fills.len(), 1);
+
+        // A pull is published once, however many cycles it lasts
+        let mut empty = snapshot("tok1", Decimal::ZERO);
+        empty.book_status = eutrader_core::BookStatus::Empty;
+        manager.handle_snapshot(&empty).await.unwrap();
+        manager.handle_snapshot(&empty).await.unwrap();
+        assert!(matches!(
+            events.try_recv().unwrap(),
+            EngineEvent::QuotePulled { key: pulled, .. } if pulled == key("tok1")
+        ));
+        assert!(events.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn nothing_resting_publishes_no_pull() {
+        let config = make_config(vec![make_market("tok1", 400)]);
+        let mut manager = OrderManager::new(
+            PaperExecutor::new(),
+            Quoter::new(),
+            RiskManager::new(),
+            config,
+        );
+        let mut events = manager.subscribe();
+
+        let mut empty = snapshot("tok1", Decimal::ZERO);
+        empty.book_status = eutrader_core::BookStatus::Empty;
+        manager.handle_snapshot(&empty).await.unwrap();
+        assert!(events.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn dashboard_keeps_up_with_quote_events() {
+        let dashboard = eutrader_core::dashboard::new_shared_dashboard("DryRun");
+        let config = make_config(vec![make_market("tok1", 400)]);
+        let mut manager = OrderManager::new(
+            crate::dry_run::DryRunExecutor::new(),
+            Quoter::new(),
+            RiskManager::new(),
+            config,
+        )
+        .with_dashboard(dashboard);
+
+        // Alternate books so every cycle requotes, without a single fill
+        for i in 0..EVENT_BUS_CAPACITY {
+            let mid = if i % 2 == 0 { dec!(0.50) } else { dec!(0.60) };
+            manager
+                .handle_snapshot(&snapshot("tok1", mid))
+                .await
+                .unwrap();
+        }
+        assert_eq!(manager.dashboard_events.as_ref().unwrap().backlog(), 0);
+    }
+
+    #[tokio::test]
+    async fn adverse_fills_widen_effective_spread() {
+        let mut config = make_config(vec![make_market("tok1", 400)]);
+        config.adverse_selection = Some(eutrader_core::AdverseSelectionConfig {
+            lookback_fills: 5,
+            markout_ms: 1000,
+            sensitivity: Decimal::ONE,
+            max_multiplier: dec!(3),
+        });
+        let mut manager = OrderManager::new(
+            PaperExecutor::new(),
+            Quoter::new(),
+            RiskManager::new(),
+            config,
+        );
+
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.50)))
+            .await
+            .unwrap();
+        assert_eq!(
+            quoted_prices(&manager, "tok1").await,
+            (dec!(0.48), dec!(0.52))
+        );
+
+        // Alternating buys and sells, each followed by the mid moving 0.01 against us
+        let mut mid = dec!(0.50);
+        for i in 0..4 {
+            let side = if i % 2 == 0 { Side::Buy } else { Side::Sell };
+            manager.apply_fills(&[Fill {
+                token_id: "tok1".into(),
+                strategy_id: String::new(),
+                side,
+                price: mid,
+                size: dec!(10),
+                timestamp: Utc::now() - chrono::Duration::seconds(2),
+                is_simulated: true,
+                mid_at_fill: mid,
+                edge: Decimal::ZERO,
+            }]);
+            mid = match side {
+                Side::Buy => mid - dec!(0.01),
+                Side::Sell => mid + dec!(0.01),
+            };
+            manager
+                .handle_snapshot(&snapshot("tok1", mid))
+                .await
+                .unwrap();
+        }
+
+        // Flat again at 0.50 after being picked off four times by ~200bps:
+        // spread widens from 400 to ~800bps
+        let (bid, ask) = quoted_prices(&manager, "tok1").await;
+        assert_eq!(
+            manager.positions()[&key("tok1")].net_position,
+            Decimal::ZERO
+        );
+        assert!(
+            ask - bid >= dec!(0.08),
+            "expected a widened spread, got {bid}/{ask}"
+        );
+    }
+
+    #[tokio::test]
+    async fn crossed_or_empty_book_pulls_quotes() {
+        let config = make_config(vec![make_market("tok1", 400), make_market("tok2", 400)]);
+        let mut manager = OrderManager::new(
+            PaperExecutor::new(),
+            Quoter::new(),
+            RiskManager::new(),
+            config,
+        );
+
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.50)))
+            .await
+            .unwrap();
+        manager
+            .handle_snapshot(&snapshot("tok2", dec!(0.50)))
+            .await
+            .unwrap();
+        assert_eq!(manager.executor.open_orders().await.unwrap().len(), 4);
+
+        let mut crossed = snapshot("tok1", dec!(0.50));
+        crossed.best_bid = dec!(0.55);
+        crossed.best_ask = dec!(0.45);
+        crossed.book_status = eutrader_core::BookStatus::Crossed;
+        manager.handle_snapshot(&crossed).await.unwrap();
+
+        let orders = manager.executor.open_orders().await.unwrap();
+        assert_eq!(orders.len(), 2);
+        assert!(orders.iter().all(|o| o.token_id == "tok2"));
+        // The crossed book is not kept as the market's last price
+        assert_eq!(manager.last_snapshots["tok1"].best_bid, dec!(0.49));
+
+        let mut empty = snapshot("tok2", Decimal::ZERO);
+        empty.book_status = eutrader_core::BookStatus::Empty;
+        manager.handle_snapshot(&empty).await.unwrap();
+        assert!(manager.executor.open_orders().await.unwrap().is_empty());
+
+        // Quoting resumes once the book is sane again
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.50)))
+            .await
+            .unwrap();
+        assert_eq!(
+            quoted_prices(&manager, "tok1").await,
+            (dec!(0.48), dec!(0.52))
+        );
+    }
+
+    #[tokio::test]
+    async fn reload_pulls_quotes_for_removed_markets() {
+        let config = make_config(vec![make_market("tok1", 400), make_market("tok2", 400)]);
+        let mut manager = OrderManager::new(
+            PaperExecutor::new(),
+            Quoter::new(),
+            RiskManager::new(),
+            config,
+        );
+
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.50)))
+            .await
+            .unwrap();
+        manager
+            .handle_snapshot(&snapshot("tok2", dec!(0.50)))
+            .await
+            .unwrap();
+        assert_eq!(manager.executor.open_orders().await.unwrap().len(), 4);
+
+        manager
+            .reload_config(make_config(vec![make_market("tok1", 400)]))
+            .await
+            .unwrap();
+
+        let orders = manager.executor.open_orders().await.unwrap();
+        assert_eq!(orders.len(), 2);
+        assert!(orders.iter().all(|o| o.token_id == "tok1"));
+
+        // Snapshots for the removed market are now ignored
+        manager
+            .handle_snapshot(&snapshot("tok2", dec!(0.50)))
+            .await
+            .unwrap();
+        assert_eq!(manager.executor.open_orders().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn reload_treats_a_changed_token_as_a_new_market() {
+        let config = make_config(vec![make_market("tok1", 400)]);
+        let mut manager = OrderManager::new(
+            PaperExecutor::new(),
+            Quoter::new(),
+            RiskManager::new(),
+            config,
+        );
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.50)))
+            .await
+            .unwrap();
+        manager.apply_fills(&[Fill {
+            token_id: "tok1".into(),
+            strategy_id: String::new(),
+            side: Side::Buy,
+            price: dec!(0.48),
+            size: dec!(10),
+            timestamp: Utc::now(),
+            is_simulated: true,
+            mid_at_fill: dec!(0.50),
+            edge: Decimal::ZERO,
+        }]);
+
+        // Same market name, pointed at another token
+        let mut moved = make_market("tok9", 400);
+        moved.name = "tok1".into();
+        manager
+            .reload_config(make_config(vec![moved]))
+            .await
+            .unwrap();
+
+        assert!(manager.executor.open_orders().await.unwrap().is_empty());
+        assert!(!manager.positions.contains_key(&key("tok9")));
+        manager
+            .handle_snapshot(&snapshot("tok9", dec!(0.50)))
+            .await
+            .unwrap();
+        assert_eq!(manager.positions[&key("tok9")].net_position, Decimal::ZERO);
+    }
+
+    #[tokio::test]
+    async fn wide_market_spread_pulls_quotes_for_that_market_only() {
+        let mut wide = make_market("tok1", 400);
+        wide.max_market_spread_bps = Some(1000);
+        let config = make_config(vec![wide, make_market("tok2", 400)]);
+        let dashboard = eutrader_core::dashboard::new_shared_dashboard("Paper");
+        let mut manager = OrderManager::new(
+            PaperExecutor::new(),
+            Quoter::new(),
+            RiskManager::new(),
+            config,
+        )
+        .with_dashboard(dashboard.clone());
+
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.50)))
+            .await
+            .unwrap();
+        manager
+            .handle_snapshot(&snapshot("tok2", dec!(0.50)))
+            .await
+            .unwrap();
+        assert_eq!(manager.executor.open_orders().await.unwrap().len(), 4);
+
+        // Book blows out to 0.10 / 0.90 — 8000 bps wide
+        let mut blown = snapshot("tok1", dec!(0.50));
+        blown.best_bid = dec!(0.10);
+        blown.best_ask = dec!(0.90);
+        blown.spread = dec!(0.80);
+        manager.handle_snapshot(&blown).await.unwrap();
+
+        let orders = manager.executor.open_orders().await.unwrap();
+        assert_eq!(orders.len(), 2);
+        assert!(orders.iter().all(|o| o.token_id == "tok2"));
+
+        let state = dashboard.read().unwrap();
+        assert!(state.markets["tok1"].quote_status.is_some());
+        assert!(state.markets["tok2"].quote_status.is_none());
+    }
+
+    #[tokio::test]
+    async fn one_sided_quote_places_only_active_side() {
+        let mut market = make_market("tok1", 400);
+        market.one_sided_threshold = Some(dec!(0.5));
+        let config = make_config(vec![market]);
+        let mut manager = OrderManager::new(
+            PaperExecutor::new(),
+            Quoter::new(),
+            RiskManager::new(),
+            config,
+        );
+
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.50)))
+            .await
+            .unwrap();
+        assert_eq!(manager.executor.open_orders().await.unwrap().len(), 2);
+
+        // Get long beyond 50% of max_inventory (50) => distribute only
+        manager.apply_fills(&[Fill {
+            token_id: "tok1".into(),
+            strategy_id: String::new(),
+            side: Side::Buy,
+            price: dec!(0.48),
+            size: dec!(30),
+            timestamp: Utc::now(),
+            is_simulated: true,
+            mid_at_fill: Decimal::ZERO,
+            edge: Decimal::ZERO,
+        }]);
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.50)))
+            .await
+            .unwrap();
+
+        let orders = manager.executor.open_orders().await.unwrap();
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].side, Side::Sell);
+
+        // Reconciling again leaves the single ask alone
+        let id = orders[0].id.clone();
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.50)))
+            .await
+            .unwrap();
+        let orders = manager.executor.open_orders().await.unwrap();
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].id, id);
+    }
+
+    #[tokio::test]
+    async fn shutdown_flattens_long_position() {
+        let mut config = make_config(vec![make_market("tok1", 400)]);
+        config.risk.flatten_on_shutdown = true;
+        let mut manager = OrderManager::new(
+            PaperExecutor::new(),
+            Quoter::new(),
+            RiskManager::new(),
+            config,
+        );
+
+        // As in run_paper, the executor has already evaluated the last snapshot
+        let last = snapshot("tok1", dec!(0.50));
+        manager.executor.check_fills(&last).await;
+        manager.handle_snapshot(&last).await.unwrap();
+        manager.apply_fills(&[Fill {
+            token_id: "tok1".into(),
+            strategy_id: String::new(),
+            side: Side::Buy,
+            price: dec!(0.48),
+            size: dec!(10),
+            timestamp: Utc::now(),
+            is_simulated: true,
+            mid_at_fill: Decimal::ZERO,
+            edge: Decimal::ZERO,
+        }]);
+
+        manager.shutdown().await;
+        manager.settle_flatten_orders().await;
+
+        assert_eq!(manager.flatten_fills.len(), 1);
+        let fill = &manager.flatten_fills[0];
+        assert_eq!(fill.side, Side::Sell);
+        assert_eq!(fill.price, dec!(0.49));
+        assert_eq!(fill.size, dec!(10));
+        assert_eq!(manager.positions[&key("tok1")].net_position, Decimal::ZERO);
+        assert!(manager.executor.open_orders().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn flatten_aggression_sets_fill_price() {
+        use eutrader_core::{FillModel, FlattenAggression};
+
+        let book = MarketSnapshot {
+            best_bid: dec!(0.48),
+            best_ask: dec!(0.52),
+            spread: dec!(0.04),
+            ..snapshot("tok1", dec!(0.50))
+        };
+        // Only a flatten that crosses the touch fills against the closing book
+        for fill_model in [FillModel::Touch, FillModel::QueuePosition] {
+            for (aggression, net, side, price, fills) in [
+                (
+                    FlattenAggression::Passive,
+                    dec!(10),
+                    Side::Sell,
+                    dec!(0.52),
+                    false,
+                ),
+                (
+                    FlattenAggression::Mid,
+                    dec!(10),
+                    Side::Sell,
+                    dec!(0.50),
+                    false,
+                ),
+                (
+                    FlattenAggression::Cross,
+                    dec!(10),
+                    Side::Sell,
+                    dec!(0.48),
+                    true,
+                ),
+                (
+                    FlattenAggression::Passive,
+                    dec!(-10),
+                    Side::Buy,
+                    dec!(0.48),
+                    false,
+                ),
+                (
+                    FlattenAggression::Mid,
+                    dec!(-10),
+                    Side::Buy,
+                    dec!(0.50),
+                    false,
+                ),
+                (
+                    FlattenAggression::Cross,
+                    dec!(-10),
+                    Side::Buy,
+                    dec!(0.52),
+                    true,
+                ),
+            ] {
+                let mut config = make_config(vec![make_market("tok1", 400)]);
+                config.risk.flatten_on_shutdown = true;
+                config.risk.flatten_aggression = aggression;
+                let mut manager = OrderManager::new(
+                    PaperExecutor::new().with_fill_model(fill_model),
+                    Quoter::new(),
+                    RiskManager::new(),
+                    config,
+                );
+                manager.last_snapshots.insert("tok1".into(), book.clone());
+                manager.positions.insert(
+                    key("tok1"),
+                    InventoryPosition {
+                        net_position: net,
+                        ..InventoryPosition::new("tok1".into())
+                    },
+                );
+
+                manager.shutdown().await;
+                let resting = manager.executor.open_orders().await.unwrap();
+                assert_eq!(resting.len(), 1);
+                assert_eq!((resting[0].side, resting[0].price), (side, price));
+                manager.settle_flatten_orders().await;
+
+                let case = format!("{fill_model:?} {aggression:?} {side}");
+                if fills {
+                    let fill = &manager.flatten_fills[0];
+                    assert_eq!((fill.side, fill.price), (side, price), "{case}");
+                    assert!(manager.positions[&key("tok1")].net_position.is_zero());
+                } else {
+                    assert!(manager.flatten_fills.is_empty(), "{case}");
+                    assert_eq!(manager.positions[&key("tok1")].net_position, net, "{case}");
+                    assert_eq!(manager.executor.open_orders().await.unwrap().len(), 1);
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn startup_cancels_orders_from_previous_session() {
+        let executor = PaperExecutor::new();
+        executor
+            .place_order("tok1", Side::Buy, dec!(0.45), dec!(10))
+            .await
+            .unwrap();
+        executor
+            .place_order("tok2", Side::Sell, dec!(0.60), dec!(5))
+            .await
+            .unwrap();
+
+        let mut config = make_config(vec![make_market("tok1", 400)]);
+        config.mode = Mode::Live;
+        let manager = OrderManager::new(executor, Quoter::new(), RiskManager::new(), config);
+
+        assert_eq!(manager.reconcile_on_startup().await.unwrap(), 2);
+        assert!(manager.executor.open_orders().await.unwrap().is_empty());
+        assert_eq!(manager.reconcile_on_startup().await.unwrap(), 0);
+    }
+
+    /// Paper executor that refuses to cancel one order.
+    struct StuckOrderExecutor {
+        inner: PaperExecutor,
+        stuck: eutrader_core::OrderId,
+    }
+
+    #[async_trait::async_trait]
+    impl Executor for StuckOrderExecutor {
+        async fn place_order(
+            &self,
+            token_id: &str,
+            side: Side,
+            price: Decimal,
+            size: Decimal,
+        ) -> eutrader_core::Result<OrderAck> {
+            self.inner.place_order(token_id, side, price, size).await
+        }
+
+        async fn cancel_order(&self, id: &eutrader_core::OrderId) -> eutrader_core::Result<()> {
+            if *id == self.stuck {
+                return Err(eutrader_core::Error::Execution("venue refused".into()));
+            }
+            self.inner.cancel_order(id).await
+        }
+
+        async fn cancel_all(&self) -> eutrader_core::Result<()> {
+            self.inner.cancel_all().await
+        }
+
+        async fn open_orders(&self) -> eutrader_core::Result<Vec<OpenOrder>> {
+            self.inner.open_orders().await
+        }
+    }
+
+    #[tokio::test]
+    async fn startup_cancel_tries_every_order_before_failing() {
+        let inner = PaperExecutor::new();
+        let mut ids = Vec::new();
+        for token in ["tok1", "tok2", "tok3"] {
+            let ack = inner
+                .place_order(token, Side::Buy, dec!(0.45), dec!(10))
+                .await
+                .unwrap();
+            ids.push(ack.id);
+        }
+        let executor = StuckOrderExecutor {
+            inner,
+            stuck: ids[0].clone(),
+        };
+
+        let mut config = make_config(vec![make_market("tok1", 400)]);
+        config.mode = Mode::Live;
+        let manager = OrderManager::new(executor, Quoter::new(), RiskManager::new(), config);
+
+        let err = manager.reconcile_on_startup().await.unwrap_err();
+        assert!(err.to_string().contains("1 of 3"), "{err}");
+        // The stuck order did not stop the others from being cancelled
+        let left = manager.executor.open_orders().await.unwrap();
+        assert_eq!(left.len(), 1);
+        assert_eq!(left[0].id, ids[0]);
+    }
+
+    #[tokio::test]
+    async fn order_beyond_market_cap_is_rejected() {
+        let mut config = make_config(vec![make_market("tok1", 400)]);
+        config.risk.max_open_orders_per_market = Some(2);
+        let mut manager = OrderManager::new(
+            PaperExecutor::new(),
+            Quoter::new(),
+            RiskManager::new(),
+            config,
+        );
+
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.50)))
+            .await
+            .unwrap();
+        assert_eq!(manager.executor.open_orders().await.unwrap().len(), 2);
+
+        let err = manager
+            .place_order_checked(&key("tok1"), Side::Buy, dec!(0.40), dec!(10), false)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, eutrader_core::Error::RiskBreach(_)));
+        assert_eq!(manager.executor.open_orders().await.unwrap().len(), 2);
+
+        // Other markets are unaffected
+        manager
+            .place_order_checked(&key("tok2"), Side::Buy, dec!(0.40), dec!(10), false)
+            .await
+            .unwrap();
+
+        // Pulled quotes free up the market's slots
+        manager.pull_quotes(&key("tok1"), "test").await.unwrap();
+        manager
+            .place_order_checked(&key("tok1"), Side::Buy, dec!(0.40), dec!(10), false)
+            .await
+            .unwrap();
+    }
+
+    /// Paper executor whose `place_order` takes `delay` to answer.
+    struct SlowExecutor {
+        inner: PaperExecutor,
+        delay: Duration,
+    }
+
+    #[async_trait::async_trait]
+    impl Executor for SlowExecutor {
+        async fn place_order(
+            &self,
+            token_id: &str,
+            side: Side,
+            price: Decimal,
+            size: Decimal,
+        ) -> eutrader_core::Result<OrderAck> {
+            tokio::time::sleep(self.delay).await;
+            self.inner.place_order(token_id, side, price, size).await
+        }
+
+        async fn cancel_order(&self, id: &eutrader_core::OrderId) -> eutrader_core::Result<()> {
+            self.inner.cancel_order(id).await
+        }
+
+        async fn cancel_all(&self) -> eutrader_core::Result<()> {
+            self.inner.cancel_all().await
+        }
+
+        async fn open_orders(&self) -> eutrader_core::Result<Vec<OpenOrder>> {
+            self.inner.open_orders().await
+        }
+    }
+
+    #[tokio::test]
+    async fn latency_reflects_slow_order_placement() {
+        let executor = SlowExecutor {
+            inner: PaperExecutor::new(),
+            delay: Duration::from_millis(20),
+        };
+        let dashboard = eutrader_core::dashboard::new_shared_dashboard("Paper");
+        let mut manager = OrderManager::new(
+            executor,
+            Quoter::new(),
+            RiskManager::new(),
+            make_config(vec![make_market("tok1", 400)]),
+        )
+        .with_dashboard(dashboard.clone());
+
+        // Each new mid re-quotes both sides: two slow placements per cycle
+        for mid in [dec!(0.40), dec!(0.50), dec!(0.60)] {
+            manager
+                .handle_snapshot(&snapshot("tok1", mid))
+                .await
+                .unwrap();
+        }
+
+        let orders = manager.order_latency();
+        assert_eq!(orders.samples, 6);
+        assert!(orders.p50_ms >= 20.0, "{orders:?}");
+
+        let cycles = manager.cycle_latency();
+        assert_eq!(cycles.samples, 3);
+        assert!(cycles.p50_ms >= 40.0, "{cycles:?}");
+
+        let state = dashboard.read().unwrap();
+        assert_eq!(state.cycle_latency, cycles);
+        assert_eq!(state.order_latency, orders);
+    }
+
+    /// Paper executor that reports a fixed set of venue positions.
+    struct VenueExecutor {
+        inner: PaperExecutor,
+        positions: HashMap<String, Decimal>,
+    }
+
+    #[async_trait::async_trait]
+    impl Executor for VenueExecutor {
+        async fn place_order(
+            &self,
+            token_id: &str,
+            side: Side,
+            price: Decimal,
+            size: Decimal,
+        ) -> eutrader_core::Result<OrderAck> {
+            self.inner.place_order(token_id, side, price, size).await
+        }
+
+        async fn cancel_order(&self, id: &eutrader_core::OrderId) -> eutrader_core::Result<()> {
+            self.inner.cancel_order(id).await
+        }
+
+        async fn cancel_all(&self) -> eutrader_core::Result<()> {
+            self.inner.cancel_all().await
+        }
+
+        async fn open_orders(&self) -> eutrader_core::Result<Vec<OpenOrder>> {
+            self.inner.open_orders().await
+        }
+
+        async fn fetch_positions(&self) -> eutrader_core::Result<Option<HashMap<String, Decimal>>> {
+            Ok(Some(self.positions.clone()))
+        }
+    }
+
+    #[tokio::test]
+    async fn position_drift_is_detected_and_optionally_corrected() {
+        let executor = VenueExecutor {
+            inner: PaperExecutor::new(),
+            positions: HashMap::from([("tok1".to_string(), dec!(25))]),
+        };
+        let mut config = make_config(vec![make_market("tok1", 200), make_market("tok2", 200)]);
+        let mut manager =
+            OrderManager::new(executor, Quoter::new(), RiskManager::new(), config.clone());
+        manager.apply_fills(&[Fill {
+            token_id: "tok1".into(),
+            strategy_id: String::new(),
+            side: Side::Buy,
+            price: dec!(0.48),
+            size: dec!(10),
+            timestamp: Utc::now(),
+            is_simulated: true,
+            mid_at_fill: Decimal::ZERO,
+            edge: Decimal::ZERO,
+        }]);
+
+        // A missed fill leaves us 15 short of the venue; flat tok2 agrees
+        let drifts = manager.reconcile_positions().await.unwrap();
+        assert_eq!(
+            drifts,
+            vec![PositionDrift {
+                token_id: "tok1".into(),
+                local: dec!(10),
+                venue: dec!(25)
+            }]
+        );
+        // Detection alone leaves local state untouched
+        assert_eq!(manager.positions()[&key("tok1")].net_position, dec!(10));
+
+        config.execution.correct_position_drift = true;
+        manager.config = config;
+        manager.reconcile_positions().await.unwrap();
+        assert_eq!(manager.positions()[&key("tok1")].net_position, dec!(25));
+        assert!(manager.reconcile_positions().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn market_stop_pulls_one_market_and_flattens_it() {
+        let mut stopped = make_market("tok1", 400);
+        stopped.max_market_loss = Some(dec!(2));
+        stopped.flatten_on_stop = true;
+        let config = make_config(vec![stopped, make_market("tok2", 400)]);
+        let mut manager = OrderManager::new(
+            PaperExecutor::new(),
+            Quoter::new(),
+            RiskManager::new(),
+            config,
+        );
+
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.50)))
+            .await
+            .unwrap();
+        manager
+            .handle_snapshot(&snapshot("tok2", dec!(0.50)))
+            .await
+            .unwrap();
+        manager.apply_fills(&[Fill {
+            token_id: "tok1".into(),
+            strategy_id: String::new(),
+            side: Side::Buy,
+            price: dec!(0.50),
+            size: dec!(20),
+            timestamp: Utc::now(),
+            is_simulated: true,
+            mid_at_fill: dec!(0.50),
+            edge: Decimal::ZERO,
+        }]);
+
+        // Up 2 at 0.60 sets the peak; back to 0.45 is 3 below it, beyond the limit of 2
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.60)))
+            .await
+            .unwrap();
+        assert!(!manager.is_market_stopped(&key("tok1")));
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.45)))
+            .await
+            .unwrap();
+        assert!(manager.is_market_stopped(&key("tok1")));
+        assert!(!manager.is_killed());
+
+        let orders = manager.executor.open_orders().await.unwrap();
+        let tok1: Vec<_> = orders.iter().filter(|o| o.token_id == "tok1").collect();
+        assert_eq!(tok1.len(), 1, "only the flatten order should remain");
+        assert_eq!(tok1[0].side, Side::Sell);
+        assert_eq!(tok1[0].price, dec!(0.44));
+        assert_eq!(tok1[0].size, dec!(20));
+
+        // tok2 keeps quoting; tok1 stays stopped after a recovery
+        manager
+            .handle_snapshot(&snapshot("tok2", dec!(0.52)))
+            .await
+            .unwrap();
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.60)))
+            .await
+            .unwrap();
+        let orders = manager.executor.open_orders().await.unwrap();
+        assert_eq!(orders.iter().filter(|o| o.token_id == "tok2").count(), 2);
+        assert_eq!(orders.iter().filter(|o| o.token_id == "tok1").count(), 1);
+    }
+
+    #[tokio::test]
+    async fn closed_market_stops_quoting_and_settles_at_resolution() {
+        let config = make_config(vec![make_market("tok1", 200), make_market("tok2", 200)]);
+        let mut manager = OrderManager::new(
+            PaperExecutor::new(),
+            Quoter::new(),
+            RiskManager::new(),
+            config,
+        );
+        manager.apply_fills(&[Fill {
+            token_id: "tok1".into(),
+            strategy_id: String::new(),
+            side: Side::Buy,
+            price: dec!(0.48),
+            size: dec!(10),
+            timestamp: Utc::now(),
+            is_simulated: true,
+            mid_at_fill: Decimal::ZERO,
+            edge: Decimal::ZERO,
+        }]);
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.50)))
+            .await
+            .unwrap();
+        manager
+            .handle_snapshot(&snapshot("tok2", dec!(0.50)))
+            .await
+            .unwrap();
+
+        let market = |closed: bool, prices: &str| -> GammaMarket {
+            serde_json::from_value(serde_json::json!({
+                "conditionId": "0xabc",
+                "question": "Will it rain?",
+                "clobTokenIds": r#"["tok1", "tok1_no"]"#,
+                "outcomePrices": prices,
+                "active": true,
+                "closed": closed,
+            }))
+            .unwrap()
+        };
+
+        // Still open: nothing changes
+        manager
+            .apply_market_statuses(&[market(false, r#"["0.5", "0.5"]"#)])
+            .await;
+        assert!(!manager.is_market_closed(&key("tok1")));
+
+        // Closed and resolved YES: orders pulled, long 10 from 0.48 settles at 1
+        manager
+            .apply_market_statuses(&[market(true, r#"["1", "0"]"#)])
+            .await;
+        assert!(manager.is_market_closed(&key("tok1")));
+        let position = &manager.positions()[&key("tok1")];
+        assert_eq!(position.net_position, Decimal::ZERO);
+        assert_eq!(position.realized_pnl, dec!(5.2));
+
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.99)))
+            .await
+            .unwrap();
+        let orders = manager.executor.open_orders().await.unwrap();
+        assert!(orders.iter().all(|o| o.token_id == "tok2"));
+        assert_eq!(orders.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn market_status_arrives_from_background_poller() {
+        let base = crate::test_support::serve_json(|_| {
+            Some(
+                r#"[{"conditionId":"0xabc","question":"Q","clobTokenIds":"[\"tok1\", \"tok1_no\"]","outcomePrices":"[\"1\", \"0\"]","active":true,"closed":true}]"#
+                    .to_string(),
+            )
+        })
+        .await;
+        let mut config = make_config(vec![make_market("tok1", 400)]);
+        config.risk.market_status_interval_ms = Some(10);
+        let (stop_tx, stop_rx) = watch::channel(false);
+        let mut manager = OrderManager::new(
+            PaperExecutor::new(),
+            Quoter::new(),
+            RiskManager::new(),
+            config,
+        )
+        .with_gamma(GammaClient::with_base_url(base))
+        .with_stop_signal(stop_rx);
+        let mut events = manager.subscribe();
+        tokio::spawn(async move {
+            while let Ok(event) = events.recv().await {
+                if matches!(event, EngineEvent::MarketClosed { .. }) {
+                    let _ = stop_tx.send(true);
+                }
+            }
+        });
+
+        let snapshots = futures::stream::pending::<MarketSnapshot>();
+        tokio::time::timeout(Duration::from_secs(5), manager.run_paper(snapshots))
+            .await
+            .unwrap();
+        assert!(manager.is_market_closed(&key("tok1")));
+    }
+
+    #[tokio::test]
+    async fn hanging_market_status_check_does_not_stall_quoting() {
+        let base = crate::test_support::serve_silent().await;
+        let mut config = make_config(vec![make_market("tok1", 400)]);
+        config.risk.market_status_interval_ms = Some(1);
+        let mut manager = OrderManager::new(
+            PaperExecutor::new(),
+            Quoter::new(),
+            RiskManager::new(),
+            config,
+        )
+        .with_gamma(GammaClient::with_base_url(base));
+
+        let snapshots = futures::stream::iter(vec![
+            snapshot("tok1", dec!(0.50)),
+            snapshot("tok1", dec!(0.51)),
+        ]);
+        tokio::time::timeout(Duration::from_secs(2), manager.run_paper(snapshots))
+            .await
+            .unwrap();
+        assert_eq!(manager.cycles, 2);
+    }
+
+    #[tokio::test]
+    async fn warmup_delays_first_quote() {
+        let mut config = make_config(vec![make_market("tok1", 400), make_market("tok2", 400)]);
+        config.risk.warmup_ticks = 3;
+        let mut manager = OrderManager::new(
+            PaperExecutor::new(),
+            Quoter::new(),
+            RiskManager::new(),
+            config,
+        );
+
+        for _ in 0..3 {
+            manager
+                .handle_snapshot(&snapshot("tok1", dec!(0.50)))
+                .await
+                .unwrap();
+        }
+        manager
+            .handle_snapshot(&snapshot("tok2", dec!(0.50)))
+            .await
+            .unwrap();
+        assert!(manager.executor.open_orders().await.unwrap().is_empty());
+
+        // Warmup is per market: tok1 quotes on its fourth snapshot, tok2 still waits
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.50)))
+            .await
+            .unwrap();
+        manager
+            .handle_snapshot(&snapshot("tok2", dec!(0.50)))
+            .await
+            .unwrap();
+        let orders = manager.executor.open_orders().await.unwrap();
+        assert_eq!(orders.len(), 2);
+        assert!(orders.iter().all(|o| o.token_id == "tok1"));
+    }
+
+    #[tokio::test]
+    async fn aged_orders_are_refreshed_despite_matching_target() {
+        let mut aging = make_market("tok1", 400);
+        aging.max_order_age_ms = Some(10);
+        let config = make_config(vec![aging, make_market("tok2", 400)]);
+        let clock = eutrader_core::MockClock::new(Utc::now());
+        let executor = PaperExecutor::new().with_clock(Arc::new(clock.clone()));
+        let mut manager = OrderManager::new(executor, Quoter::new(), RiskManager::new(), config)
+            .with_clock(Arc::new(clock.clone()));
+
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.50)))
+            .await
+            .unwrap();
+        manager
+            .handle_snapshot(&snapshot("tok2", dec!(0.50)))
+            .await
+            .unwrap();
+        let placed = |orders: &[OpenOrder], token: &str| {
+            let mut times: Vec<_> = orders
+                .iter()
+                .filter(|o| o.token_id == token)
+                .map(|o| (o.side, o.price, o.placed_at))
+                .collect();
+            times.sort_by_key(|(side, _, _)| *side == Side::Sell);
+            times
+        };
+        let before = manager.executor.open_orders().await.unwrap();
+
+        clock.advance(chrono::Duration::milliseconds(20));
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.50)))
+            .await
+            .unwrap();
+        manager
+            .handle_snapshot(&snapshot("tok2", dec!(0.50)))
+            .await
+            .unwrap();
+        let after = manager.executor.open_orders().await.unwrap();
+
+        // Same prices on tok1, but both orders were re-posted
+        let (old, new) = (placed(&before, "tok1"), placed(&after, "tok1"));
+        assert_eq!(new.len(), 2);
+        for ((_, old_price, old_at), (_, new_price, new_at)) in old.iter().zip(&new) {
+            assert_eq!(old_price, new_price);
+            assert!(new_at > old_at);
+        }
+        // tok2 has no age limit and is left alone
+        assert_eq!(placed(&before, "tok2"), placed(&after, "tok2"));
+    }
+
+    #[tokio::test]
+    async fn sub_threshold_moves_keep_notional_sized_orders() {
+        let mut market = make_market("tok1", 400);
+        market.size_mode = SizeMode::Notional;
+        market.size = dec!(5);
+        market.min_requote_move = Some(2);
+        let config = make_config(vec![market]);
+        let mut manager = OrderManager::new(
+            PaperExecutor::new(),
+            Quoter::new(),
+            RiskManager::new(),
+            config,
+        );
+
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.50)))
+            .await
+            .unwrap();
+        let before = manager.executor.open_orders().await.unwrap();
+
+        // A tick changes the share count for $5, but not what the order is worth
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.51)))
+            .await
+            .unwrap();
+        let after = manager.executor.open_orders().await.unwrap();
+        let ids = |orders: &[OpenOrder]| -> HashSet<OrderId> {
+            orders.iter().map(|o| o.id.clone()).collect()
+        };
+        assert_eq!(ids(&before), ids(&after));
+    }
+
+    #[tokio::test]
+    async fn sub_threshold_moves_keep_resting_orders() {
+        let mut throttled = make_market("tok1", 400);
+        throttled.min_requote_move = Some(2);
+        throttled.min_requote_bps = Some(500);
+        let config = make_config(vec![throttled, make_market("tok2", 400)]);
+        let mut manager = OrderManager::new(
+            PaperExecutor::new(),
+            Quoter::new(),
+            RiskManager::new(),
+            config,
+        );
+
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.50)))
+            .await
+            .unwrap();
+        manager
+            .handle_snapshot(&snapshot("tok2", dec!(0.50)))
+            .await
+            .unwrap();
+        let before = manager.executor.open_orders().await.unwrap();
+
+        // A one-tick move is below both thresholds on tok1 but requotes tok2
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.51)))
+            .await
+            .unwrap();
+        manager
+            .handle_snapshot(&snapshot("tok2", dec!(0.51)))
+            .await
+            .unwrap();
+        let after = manager.executor.open_orders().await.unwrap();
+        let tok1 = |orders: &[OpenOrder]| -> HashSet<OrderId> {
+            orders
+                .iter()
+                .filter(|o| o.token_id == "tok1")
+                .map(|o| o.id.clone())
+                .collect()
+        };
+        assert_eq!(tok1(&before), tok1(&after));
+        assert_eq!(
+            quoted_prices(&manager, "tok1").await,
+            (dec!(0.48), dec!(0.52))
+        );
+        assert_eq!(
+            quoted_prices(&manager, "tok2").await,
+            (dec!(0.49), dec!(0.53))
+        );
+
+        // Moving three ticks from the resting quotes crosses min_requote_move
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.53)))
+            .await
+            .unwrap();
+        assert_eq!(
+            quoted_prices(&manager, "tok1").await,
+            (dec!(0.51), dec!(0.55))
+        );
+    }
+
+    #[tokio::test]
+    async fn mid_outside_price_band_pulls_quotes() {
+        let mut banded = make_market("tok1", 400);
+        banded.quote_price_band = Some((dec!(0.10), dec!(0.90)));
+        let config = make_config(vec![banded]);
+        let dashboard = eutrader_core::dashboard::new_shared_dashboard("Paper");
+        let mut manager = OrderManager::new(
+            PaperExecutor::new(),
+            Quoter::new(),
+            RiskManager::new(),
+            config,
+        )
+        .with_dashboard(dashboard.clone());
+
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.50)))
+            .await
+            .unwrap();
+        assert_eq!(manager.executor.open_orders().await.unwrap().len(), 2);
+
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.97)))
+            .await
+            .unwrap();
+        assert!(manager.executor.open_orders().await.unwrap().is_empty());
+        let status = dashboard.read().unwrap().markets["tok1"]
+            .quote_status
+            .clone();
+        assert!(status.unwrap().starts_with("out of band"));
+    }
+
+    #[tokio::test]
+    async fn orders_are_clamped_to_venue_max_size() {
+        let config = make_config(vec![make_market("tok1", 400), make_market("tok2", 400)]);
+        let limits = MarketLimits {
+            min_order_size: None,
+            max_order_size: Some(dec!(4)),
+        };
+        let mut manager = OrderManager::new(
+            PaperExecutor::new(),
+            Quoter::new(),
+            RiskManager::new(),
+            config,
+        )
+        .with_venue_limits(HashMap::from([("tok1".to_string(), limits)]));
+        let sizes = |orders: Vec<OpenOrder>, token: &str| -> Vec<Decimal> {
+            orders
+                .iter()
+                .filter(|o| o.token_id == token)
+                .map(|o| o.size)
+                .collect()
+        };
+
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.50)))
+            .await
+            .unwrap();
+        manager
+            .handle_snapshot(&snapshot("tok2", dec!(0.50)))
+            .await
+            .unwrap();
+        let open = manager.executor.open_orders().await.unwrap();
+        assert_eq!(sizes(open.clone(), "tok1"), vec![dec!(4), dec!(4)]);
+        assert_eq!(sizes(open, "tok2"), vec![dec!(10), dec!(10)]);
+
+        // A reloaded config is still held to the venue limit
+        manager
+            .reload_config(make_config(vec![
+                make_market("tok1", 200),
+                make_market("tok2", 400),
+            ]))
+            .await
+            .unwrap();
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.50)))
+            .await
+            .unwrap();
+        let open = manager.executor.open_orders().await.unwrap();
+        assert_eq!(sizes(open, "tok1"), vec![dec!(4), dec!(4)]);
+    }
+
+    #[tokio::test]
+    async fn quotes_are_pulled_outside_the_schedule() {
+        let mut scheduled = make_market("tok1", 400);
+        scheduled.schedule = vec![QuoteWindow {
+            start: "13:30".parse().unwrap(),
+            end: "20:00".parse().unwrap(),
+        }];
+        let config = make_config(vec![scheduled]);
+        let clock = eutrader_core::MockClock::new("2026-03-02T12:00:00Z".parse().unwrap());
+        let mut manager = OrderManager::new(
+            PaperExecutor::new(),
+            Quoter::new(),
+            RiskManager::new(),
+            config,
+        )
+        .with_clock(Arc::new(clock.clone()));
+
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.50)))
+            .await
+            .unwrap();
+        assert!(manager.executor.open_orders().await.unwrap().is_empty());
+
+        clock.advance(chrono::Duration::hours(2));
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.50)))
+            .await
+            .unwrap();
+        assert_eq!(manager.executor.open_orders().await.unwrap().len(), 2);
+
+        clock.advance(chrono::Duration::hours(7));
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.50)))
+            .await
+            .unwrap();
+        assert!(manager.executor.open_orders().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn pulling_one_market_leaves_others_quoted() {
+        let config = make_config(vec![make_market("tok1", 400), make_market("tok2", 400)]);
+        let mut manager = OrderManager::new(
+            PaperExecutor::new(),
+            Quoter::new(),
+            RiskManager::new(),
+            config,
+        );
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.50)))
+            .await
+            .unwrap();
+        manager
+            .handle_snapshot(&snapshot("tok2", dec!(0.50)))
+            .await
+            .unwrap();
+
+        // Fees no spread can cover: the quoter gives up on tok1
+        manager.positions.get_mut(&key("tok1")).unwrap().fee_bps = 10_000;
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.50)))
+            .await
+            .unwrap();
+
+        let open = manager.executor.open_orders().await.unwrap();
+        assert!(open.iter().all(|o| o.token_id == "tok2"));
+        assert_eq!(
+            quoted_prices(&manager, "tok2").await,
+            (dec!(0.48), dec!(0.52))
+        );
+    }
+
+    #[tokio::test]
+    async fn strategies_on_one_token_keep_separate_orders() {
+        let tight = MarketConfig {
+            strategy_id: "tight".into(),
+            ..make_market("tok1", 200)
+        };
+        let wide = MarketConfig {
+            strategy_id: "wide".into(),
+            ..make_market("tok1", 800)
+        };
+        let config = make_config(vec![tight, wide]);
+        let mut manager = OrderManager::new(
+            PaperExecutor::new(),
+            Quoter::new(),
+            RiskManager::new(),
+            config,
+        );
+
+        manager
+            .process_paper_snapshot(&snapshot("tok1", dec!(0.50)))
+            .await;
+        assert_eq!(manager.executor.open_orders().await.unwrap().len(), 4);
+        assert_eq!(
+            quoted_prices(&manager, "tok1/tight").await,
+            (dec!(0.49), dec!(0.51))
+        );
+        assert_eq!(
+            quoted_prices(&manager, "tok1/wide").await,
+            (dec!(0.46), dec!(0.54))
+        );
+
+        // The book trades through the tight bid only
+        manager
+            .process_paper_snapshot(&snapshot("tok1", dec!(0.47)))
+            .await;
+        assert!(manager.positions[&key("tok1/tight")].net_position > Decimal::ZERO);
+        // Fills keep the venue token, with the strategy alongside
+        let fill = &manager.last_fills[&key("tok1/tight")];
+        assert_eq!(
+            (fill.token_id.as_str(), fill.strategy_id.as_str()),
+            ("tok1", "tight")
+        );
+        assert!(manager
+            .positions
+            .get(&key("tok1/wide"))
+            .is_none_or(|p| p.net_position.is_zero()));
+
+        // Pulling one strategy leaves the other's orders resting
+        manager
+            .positions
+            .get_mut(&key("tok1/tight"))
+            .unwrap()
+            .fee_bps = 10_000;
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.47)))
+            .await
+            .unwrap();
+        let open = manager.executor.open_orders().await.unwrap();
+        assert_eq!(open.len(), 2);
+        assert!(open
+            .iter()
+            .all(|o| o.token_id == "tok1" && o.strategy_id == "wide"));
+    }
+
+    #[tokio::test]
+    async fn last_fill_anchor_holds_quotes_near_the_fill() {
+        let mut quoted = Vec::new();
+        for anchor in [QuoteAnchor::Mid, QuoteAnchor::LastFill] {
+            let market = MarketConfig {
+                quote_anchor: anchor,
+                ..make_market("tok1", 400)
+            };
+            let mut manager = OrderManager::new(
+                PaperExecutor::new(),
+                Quoter::new(),
+                RiskManager::new(),
+                make_config(vec![market]),
+            );
+            manager
+                .process_paper_snapshot(&snapshot("tok1", dec!(0.50)))
+                .await;
+            // The market drops through our 0.48 bid
+            manager
+                .process_paper_snapshot(&snapshot("tok1", dec!(0.47)))
+                .await;
+            assert_eq!(manager.last_fills[&key("tok1")].price, dec!(0.48));
+            quoted.push(quoted_prices(&manager, "tok1").await);
+        }
+
+        // Anchored quotes stay up by the fresh fill instead of chasing the mid down
+        let (mid_bid, mid_ask) = quoted[0];
+        let (anchored_bid, anchored_ask) = quoted[1];
+        assert!(anchored_bid > mid_bid && anchored_ask > mid_ask);
+    }
+
+    #[tokio::test]
+    async fn fills_pnl_and_open_orders_are_sent_to_statsd() {
+        let agent = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+        agent
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        let port = agent.local_addr().unwrap().port();
+        let statsd = crate::metrics::StatsdSink::connect("127.0.0.1", port).unwrap();
+        let config = make_config(vec![make_market("tok1", 400)]);
+        let mut manager = OrderManager::new(
+            PaperExecutor::new(),
+            Quoter::new(),
+            RiskManager::new(),
+            config,
+        )
+        .with_metrics(Arc::new(statsd));
+
+        manager
+            .process_paper_snapshot(&snapshot("tok1", dec!(0.50)))
+            .await;
+        // Drops through the 0.48 bid; the next PnL sample is a refresh interval away
+        manager
+            .process_paper_snapshot(&snapshot("tok1", dec!(0.47)))
+            .await;
+
+        let mut lines = Vec::new();
+        let mut buf = [0u8; 512];
+        while lines.len() < 5 {
+            let n = agent.recv(&mut buf).unwrap();
+            lines.push(String::from_utf8_lossy(&buf[..n]).into_owned());
+        }
+        assert_eq!(
+            lines,
+            [
+                "eutrader.inventory:0|g|#market:tok1",
+                "eutrader.realized_pnl:0|g",
+                "eutrader.unrealized_pnl:0|g",
+                "eutrader.open_orders:2|g",
+                "eutrader.fills:1|c|#market:tok1",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn seeded_long_skews_the_first_quote_down() {
+        let mut quoted = Vec::new();
+        for seeds in [
+            vec![],
+            vec![SeedPosition {
+                token_id: "tok1".into(),
+                strategy_id: String::new(),
+                net_position: dec!(20),
+                avg_entry: dec!(0.45),
+            }],
+        ] {
+            let mut config = make_config(vec![make_market("tok1", 400)]);
+            config.seed_positions = seeds;
+            let mut manager = OrderManager::new(
+                PaperExecutor::new(),
+                Quoter::new(),
+                RiskManager::new(),
+                config,
+            );
+            manager
+                .handle_snapshot(&snapshot("tok1", dec!(0.50)))
+                .await
+                .unwrap();
+            quoted.push(quoted_prices(&manager, "tok1").await);
+        }
+
+        assert_eq!(quoted[0], (dec!(0.48), dec!(0.52)));
+        // 20 long at skew_factor 0.001 shifts both sides down two cents
+        assert_eq!(quoted[1], (dec!(0.46), dec!(0.50)));
+    }
+
+    #[tokio::test]
+    async fn unwind_works_a_long_off_passively() {
+        let mut config = make_config(vec![make_market("tok1", 400)]);
+        config.risk.unwind_cycles = Some(8);
+        config.seed_positions = vec![SeedPosition {
+            token_id: "tok1".into(),
+            strategy_id: String::new(),
+            net_position: dec!(30),
+            avg_entry: dec!(0.45),
+        }];
+        let executor = PaperExecutor::new()
+            .with_seed_positions(&config.seed_positions)
+            .without_trade_log();
+        let mut manager = OrderManager::new(executor, Quoter::new(), RiskManager::new(), config);
+        assert_eq!(manager.begin_unwind(), Some(Duration::from_secs(8)));
+
+        // Bids climb two cents a cycle, meeting our asks as they walk down
+        let mut held = vec![dec!(30)];
+        let mut mid = dec!(0.50);
+        while !manager.unwind_finished() {
+            let snap = snapshot("tok1", mid);
+            manager.process_paper_snapshot(&snap).await;
+            for order in manager.executor.open_orders().await.unwrap() {
+                assert_eq!(order.side, Side::Sell);
+                assert!(order.reduce_only && order.price > snap.best_bid);
+            }
+            held.push(manager.positions[&key("tok1")].net_position);
+            mid += dec!(0.02);
+        }
+
+        assert!(held.len() <= 9);
+        assert!(held.windows(2).all(|w| w[1] <= w[0]), "{held:?}");
+        assert_eq!(*held.last().unwrap(), Decimal::ZERO, "{held:?}");
+        let report = manager.session_report();
+        assert_eq!(report.markets[0].buy_volume, Decimal::ZERO);
+        assert_eq!(
+            report.markets[0].sell_volume,
+            dec!(10) * Decimal::from(report.markets[0].fill_count)
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn unwind_deadline_flattens_a_market_that_stops_ticking() {
+        let mut config = make_config(vec![make_market("tok1", 400)]);
+        config.risk.unwind_cycles = Some(5);
+        let mut manager = OrderManager::new(
+            PaperExecutor::new().without_trade_log(),
+            Quoter::new(),
+            RiskManager::new(),
+            config,
+        )
+        .with_max_runtime(Duration::from_millis(20));
+        manager.apply_fills(&[Fill {
+            token_id: "tok1".into(),
+            strategy_id: String::new(),
+            side: Side::Buy,
+            price: dec!(0.48),
+            size: dec!(10),
+            timestamp: Utc::now(),
+            is_simulated: true,
+            mid_at_fill: Decimal::ZERO,
+            edge: Decimal::ZERO,
+        }]);
+
+        // No snapshot arrives once the unwind starts, so no cycle ever runs
+        let started = tokio::time::Instant::now();
+        let snapshots =
+            futures::stream::iter([snapshot("tok1", dec!(0.50))]).chain(futures::stream::pending());
+        tokio::time::timeout(Duration::from_secs(60), manager.run_paper(snapshots))
+            .await
+            .expect("the unwind deadline should end the session");
+
+        // Five cycles at the 1s refresh interval, then the flatten
+        assert!(started.elapsed() >= Duration::from_secs(5));
+        assert!(manager.executor.open_orders().await.unwrap().is_empty());
+        assert_eq!(
+            manager.positions()[&key("tok1")].net_position,
+            Decimal::ZERO
+        );
+    }
+
+    #[tokio::test]
+    async fn removed_market_unwinds_before_it_goes() {
+        let mut config = make_config(vec![make_market("tok1", 400), make_market("tok2", 400)]);
+        config.risk.unwind_cycles = Some(3);
+        config.seed_positions = vec![SeedPosition {
+            token_id: "tok2".into(),
+            strategy_id: String::new(),
+            net_position: dec!(30),
+            avg_entry: dec!(0.45),
+        }];
+        let executor = PaperExecutor::new()
+            .with_seed_positions(&config.seed_positions)
+            .without_trade_log();
+        let mut manager =
+            OrderManager::new(executor, Quoter::new(), RiskManager::new(), config.clone());
+        manager
+            .process_paper_snapshot(&snapshot("tok2", dec!(0.50)))
+            .await;
+
+        config.markets.retain(|m| m.token_id == "tok1");
+        manager.reload_config(config).await.unwrap();
+
+        // Still configured, quoting only the passive reducing side
+        for _ in 0..2 {
+            let snap = snapshot("tok2", dec!(0.50));
+            manager.process_paper_snapshot(&snap).await;
+            let orders = manager.executor.open_orders().await.unwrap();
+            assert!(!orders.is_empty());
+            assert!(orders
+                .iter()
+                .all(|o| o.side == Side::Sell && o.reduce_only && o.price > snap.best_bid));
+        }
+        assert!(manager.market_configs.contains_key(&key("tok2")));
+
+        // The last cycle ends the unwind: the rest is flattened and the market dropped
+        manager
+            .process_paper_snapshot(&snapshot("tok2", dec!(0.50)))
+            .await;
+        assert!(!manager.market_configs.contains_key(&key("tok2")));
+        manager
+            .process_paper_snapshot(&snapshot("tok2", dec!(0.50)))
+            .await;
+        assert_eq!(
+            manager.positions()[&key("tok2")].net_position,
+            Decimal::ZERO
+        );
+    }
+
+    #[tokio::test]
+    async fn heartbeat_logs_every_configured_cycles() {
+        let mut config = make_config(vec![make_market("tok1", 400)]);
+        config.heartbeat_every_cycles = Some(5);
+        let mut manager = OrderManager::new(
+            PaperExecutor::new(),
+            Quoter::new(),
+            RiskManager::new(),
+            config,
+        );
+
+        for _ in 0..9 {
+            manager
+                .handle_snapshot(&snapshot("tok1", dec!(0.50)))
+                .await
+                .unwrap();
+        }
+        assert_eq!((manager.cycles, manager.heartbeats), (9, 1));
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.50)))
+            .await
+            .unwrap();
+        assert_eq!(manager.heartbeats, 2);
+    }
+
+    #[test]
+    fn positions_are_saved_per_strategy() {
+        let path = std::env::temp_dir().join(format!("eutrader_saved_{}.json", std::process::id()));
+        let mut manager = OrderManager::new(
+            PaperExecutor::new(),
+            Quoter::new(),
+            RiskManager::new(),
+            make_config(vec![make_market("tok1", 400)]),
+        )
+        .with_state_path(path.clone());
+        for (strategy_id, side) in [("", Side::Buy), ("wide", Side::Sell)] {
+            manager.apply_fills(&[Fill {
+                token_id: "tok1".into(),
+                strategy_id: strategy_id.into(),
+                side,
+                price: dec!(0.48),
+                size: dec!(10),
+                timestamp: Utc::now(),
+                is_simulated: true,
+                mid_at_fill: Decimal::ZERO,
+                edge: Decimal::ZERO,
+            }]);
+        }
+
+        manager.write_state();
+        let saved = StateStore::new(&path).load().unwrap();
+        std::fs::remove_file(&path).unwrap();
+        // Two strategies on one token keep separate positions
+        let keys: Vec<String> = saved.iter().map(|p| p.key().to_string()).collect();
+        assert_eq!(keys, ["tok1", "tok1/wide"]);
+        assert_eq!(saved[0].net_position, dec!(10));
+        assert_eq!(saved[1].net_position, dec!(-10));
+    }
+
+    #[test]
+    fn uptime_follows_the_injected_clock() {
+        let clock = eutrader_core::MockClock::new(Utc::now());
+        let manager = OrderManager::new(
+            PaperExecutor::new(),
+            Quoter::new(),
+            RiskManager::new(),
+            make_config(vec![make_market("tok1", 400)]),
+        )
+        .with_clock(Arc::new(clock.clone()));
+
+        assert_eq!(manager.uptime(), chrono::Duration::zero());
+        clock.advance(chrono::Duration::seconds(90));
+        assert_eq!(manager.uptime(), chrono::Duration::seconds(90));
+    }
+
+    #[tokio::test]
+    async fn max_runtime_ends_paper_run() {
+        let config = make_config(vec![make_market("tok1", 400)]);
+        let mut manager = OrderManager::new(
+            PaperExecutor::new(),
+            Quoter::new(),
+            RiskManager::new(),
+            config,
+        )
+        .with_max_runtime(Duration::from_millis(20));
+
+        // A feed that never ends: only the deadline can stop the loop
+        let snapshots =
+            futures::stream::iter([snapshot("tok1", dec!(0.50))]).chain(futures::stream::pending());
+        tokio::time::timeout(Duration::from_secs(5), manager.run_paper(snapshots))
+            .await
+            .expect("run_paper should stop at max_runtime");
+
+        // shutdown() ran and pulled the quotes
+        assert!(manager.executor.open_orders().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn stop_signal_shuts_down_gracefully() {
+        let mut config = make_config(vec![make_market("tok1", 400)]);
+        config.risk.flatten_on_shutdown = true;
+        let (stop_tx, stop_rx) = watch::channel(false);
+        let mut manager = OrderManager::new(
+            PaperExecutor::new().without_trade_log(),
+            Quoter::new(),
+            RiskManager::new(),
+            config,
+        )
+        .with_stop_signal(stop_rx);
+        manager.apply_fills(&[Fill {
+            token_id: "tok1".into(),
+            strategy_id: String::new(),
+            side: Side::Buy,
+            price: dec!(0.48),
+            size: dec!(10),
+            timestamp: Utc::now(),
+            is_simulated: true,
+            mid_at_fill: Decimal::ZERO,
+            edge: Decimal::ZERO,
+        }]);
+
+        let snapshots =
+            futures::stream::iter([snapshot("tok1", dec!(0.50))]).chain(futures::stream::pending());
+        let run = async {
+            manager.run_paper(snapshots).await;
+        };
+        let stop = async {
+            tokio::task::yield_now().await;
+            stop_tx.send(true).unwrap();
+        };
+        tokio::time::timeout(Duration::from_secs(5), futures::future::join(run, stop))
+            .await
+            .expect("run_paper should stop when signalled");
+
+        // The shutdown path ran: quotes pulled and the long flattened
+        assert!(manager.executor.open_orders().await.unwrap().is_empty());
+        assert_eq!(
+            manager.positions()[&key("tok1")].net_position,
+            Decimal::ZERO
+        );
+    }
+
+    #[tokio::test]
+    async fn kill_switch_halts_quoting_after_large_loss() {
+        let mut config = make_config(vec![make_market("tok1", 200)]);
+        config.risk.max_position_per_market = dec!(1000);
+        let mut manager = OrderManager::new(
+            PaperExecutor::new(),
+            Quoter::new(),
+            RiskManager::new(),
+            config,
+        );
+
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.50)))
+            .await
+            .unwrap();
+        assert!(!manager.executor.open_orders().await.unwrap().is_empty());
+
+        // Long 200 from 0.50; a drop to 0.20 is a 60 loss, beyond the 50 limit
+        manager.apply_fills(&[Fill {
+            token_id: "tok1".into(),
+            strategy_id: String::new(),
+            side: Side::Buy,
+            price: dec!(0.50),
+            size: dec!(200),
+            timestamp: Utc::now(),
+            is_simulated: true,
+            mid_at_fill: dec!(0.50),
+            edge: Decimal::ZERO,
+        }]);
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.20)))
+            .await
+            .unwrap();
+        assert!(manager.is_killed());
+        assert!(manager.executor.open_orders().await.unwrap().is_empty());
+
+        // Latched: a recovery does not resume quoting
+        manager
+            .handle_snapshot(&snapshot("tok1", dec!(0.50)))
+            .await
+            .unwrap();
+        assert!(manager.executor.open_orders().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn reload_keeps_mode() {
+        let config = make_config(vec![make_market("tok1", 400)]);
+        let mut manager = OrderManager::new(
+            PaperExecutor::new(),
+            Quoter::new(),
+            RiskManager::new(),
+            config,
+        );
+
+        let mut live = make_config(vec![make_market("tok1", 400)]);
+        live.mode = Mode::Live;
+        manager.reload_config(live).await.unwrap();
+        assert_eq!(manager.config.mode, Mode::Paper);
     }
 }
@@ -0,0 +1,72 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::{mpsc, watch};
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+use eutrader_feed::gamma::GammaMarket;
+use eutrader_feed::GammaClient;
+
+/// Polls Gamma for market statuses on a task of its own, so a slow or
+/// hanging response never holds up quoting. The task stops when the poller
+/// is dropped.
+pub(crate) struct StatusPoller {
+    updates: mpsc::Receiver<Vec<GammaMarket>>,
+    tokens: watch::Sender<Vec<String>>,
+    task: JoinHandle<()>,
+}
+
+impl StatusPoller {
+    /// Fetch the status of `tokens` every `interval`, first right away.
+    pub(crate) fn spawn(gamma: Arc<GammaClient>, interval: Duration, tokens: Vec<String>) -> Self {
+        let (tokens, mut watched) = watch::channel(tokens);
+        // One poll in flight and one waiting is plenty
+        let (tx, updates) = mpsc::channel(1);
+        let task = tokio::spawn(async move {
+            let mut ticks = tokio::time::interval(interval);
+            ticks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticks.tick().await;
+                let tokens = watched.borrow_and_update().clone();
+                if tokens.is_empty() {
+                    continue;
+                }
+                match gamma.fetch_markets_by_token_ids(&tokens).await {
+                    Ok(markets) => {
+                        if tx.send(markets).await.is_err() {
+                            return;
+                        }
+                    }
+                    Err(e) => warn!(error = %e, "market status check failed"),
+                }
+            }
+        });
+        Self {
+            updates,
+            tokens,
+            task,
+        }
+    }
+
+    /// Poll `tokens` from now on instead.
+    pub(crate) fn set_tokens(&self, tokens: Vec<String>) {
+        self.tokens.send_replace(tokens);
+    }
+}
+
+impl Drop for StatusPoller {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// The next batch of statuses from `poller`; pends forever without one.
+pub(crate) async fn next_statuses(poller: &mut Option<StatusPoller>) -> Vec<GammaMarket> {
+    if let Some(p) = poller {
+        if let Some(markets) = p.updates.recv().await {
+            return markets;
+        }
+    }
+    std::future::pending().await
+}
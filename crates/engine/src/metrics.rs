@@ -0,0 +1,158 @@
+use std::collections::BTreeMap;
+use std::net::UdpSocket;
+use std::sync::{Arc, Mutex};
+
+use tracing::debug;
+
+/// Prefix shared by every metric the engine emits.
+pub const METRIC_PREFIX: &str = "eutrader";
+
+/// Destination for engine metrics. Instrumentation calls these without
+/// knowing which backend, if any, is listening.
+pub trait MetricsSink: Send + Sync {
+    /// Record the current value of `name`.
+    fn gauge(&self, name: &str, value: f64, tags: &[(&str, &str)]);
+
+    /// Add `value` to the running total of `name`.
+    fn count(&self, name: &str, value: u64, tags: &[(&str, &str)]);
+}
+
+/// A metrics sink shared between the engine and whatever exports it.
+pub type SharedMetrics = Arc<dyn MetricsSink>;
+
+/// Discards every metric.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoMetrics;
+
+impl MetricsSink for NoMetrics {
+    fn gauge(&self, _name: &str, _value: f64, _tags: &[(&str, &str)]) {}
+
+    fn count(&self, _name: &str, _value: u64, _tags: &[(&str, &str)]) {}
+}
+
+/// Sends each metric as a StatsD line over UDP, with DogStatsD `|#tag:value`
+/// tags. Send failures are logged and dropped, as StatsD is best-effort.
+#[derive(Debug)]
+pub struct StatsdSink {
+    socket: UdpSocket,
+}
+
+impl StatsdSink {
+    /// Send to the agent at `host:port`.
+    pub fn connect(host: &str, port: u16) -> std::io::Result<Self> {
+        let socket = UdpSocket::bind(("0.0.0.0", 0))?;
+        socket.connect((host, port))?;
+        socket.set_nonblocking(true)?;
+        Ok(Self { socket })
+    }
+
+    fn send(&self, name: &str, value: &str, kind: &str, tags: &[(&str, &str)]) {
+        let mut line = format!("{METRIC_PREFIX}.{name}:{value}|{kind}");
+        if !tags.is_empty() {
+            let tags: Vec<String> = tags.iter().map(|(k, v)| format!("{k}:{v}")).collect();
+            line.push_str("|#");
+            line.push_str(&tags.join(","));
+        }
+        if let Err(e) = self.socket.send(line.as_bytes()) {
+            debug!(error = %e, metric = name, "statsd send failed");
+        }
+    }
+}
+
+impl MetricsSink for StatsdSink {
+    fn gauge(&self, name: &str, value: f64, tags: &[(&str, &str)]) {
+        self.send(name, &value.to_string(), "g", tags);
+    }
+
+    fn count(&self, name: &str, value: u64, tags: &[(&str, &str)]) {
+        self.send(name, &value.to_string(), "c", tags);
+    }
+}
+
+/// One Prometheus metric: its type and the value per rendered label set.
+#[derive(Debug)]
+struct Series {
+    kind: &'static str,
+    values: BTreeMap<String, f64>,
+}
+
+/// Keeps the latest value of every series for a Prometheus scrape.
+#[derive(Debug, Default)]
+pub struct PrometheusSink {
+    /// Keyed by full metric name
+    series: Mutex<BTreeMap<String, Series>>,
+}
+
+impl PrometheusSink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn record(
+        &self,
+        name: &str,
+        kind: &'static str,
+        tags: &[(&str, &str)],
+        apply: impl FnOnce(&mut f64),
+    ) {
+        let labels: Vec<String> = tags
+            .iter()
+            .map(|(k, v)| format!("{k}=\"{}\"", v.replace('\\', "\\\\").replace('"', "\\\"")))
+            .collect();
+        let mut series = self.series.lock().unwrap_or_else(|e| e.into_inner());
+        let series = series
+            .entry(format!("{METRIC_PREFIX}_{name}"))
+            .or_insert_with(|| Series {
+                kind,
+                values: BTreeMap::new(),
+            });
+        apply(series.values.entry(labels.join(",")).or_default());
+    }
+
+    /// Every series in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let series = self.series.lock().unwrap_or_else(|e| e.into_inner());
+        let mut out = String::new();
+        for (name, Series { kind, values }) in series.iter() {
+            out.push_str(&format!("# TYPE {name} {kind}\n"));
+            for (labels, value) in values {
+                if labels.is_empty() {
+                    out.push_str(&format!("{name} {value}\n"));
+                } else {
+                    out.push_str(&format!("{name}{{{labels}}} {value}\n"));
+                }
+            }
+        }
+        out
+    }
+}
+
+impl MetricsSink for PrometheusSink {
+    fn gauge(&self, name: &str, value: f64, tags: &[(&str, &str)]) {
+        self.record(name, "gauge", tags, |v| *v = value);
+    }
+
+    fn count(&self, name: &str, value: u64, tags: &[(&str, &str)]) {
+        self.record(name, "counter", tags, |v| *v += value as f64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prometheus_accumulates_counters_and_replaces_gauges() {
+        let sink = PrometheusSink::new();
+        sink.count("fills", 1, &[("market", "Rain")]);
+        sink.count("fills", 2, &[("market", "Rain")]);
+        sink.gauge("realized_pnl", 1.5, &[]);
+        sink.gauge("realized_pnl", -0.25, &[]);
+
+        assert_eq!(
+            sink.render(),
+            "# TYPE eutrader_fills counter\neutrader_fills{market=\"Rain\"} 3\n\
+             # TYPE eutrader_realized_pnl gauge\neutrader_realized_pnl -0.25\n"
+        );
+    }
+}
@@ -1,25 +1,42 @@
 use std::collections::HashMap;
-use std::io::Write;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use tokio::sync::Mutex;
-use tracing::{debug, info};
+use tracing::{debug, info, instrument, warn};
 
-use eutrader_core::{Fill, MarketSnapshot, OpenOrder, OrderId, Result, Side};
+use eutrader_core::{
+    system_clock, Fill, FillModel, MarketSnapshot, OpenOrder, OrderAck, OrderId, OrderStatus,
+    Result, SeedPosition, SharedClock, Side, StrategyKey, TradeLogFormat,
+};
 
 use crate::executor::Executor;
+use crate::trade_log::{FileTradeLogger, SharedTradeLogger};
+
+/// A virtual resting order and its estimated place in the queue.
+struct PaperOrder {
+    order: OpenOrder,
+    /// Size resting ahead of us at our price when we joined.
+    queue_ahead: Decimal,
+    /// Volume estimated to have traded at our price since we joined.
+    traded: Decimal,
+}
 
 /// Internal mutable state for the paper executor.
 struct PaperState {
     /// Virtual open orders keyed by OrderId.
-    orders: HashMap<OrderId, OpenOrder>,
+    orders: HashMap<OrderId, PaperOrder>,
     /// Complete log of simulated fills.
     fills: Vec<Fill>,
     /// Monotonic counter for generating order IDs.
     next_id: u64,
+    /// Last snapshot seen per token, used to size the queue at placement.
+    last_snapshots: HashMap<String, MarketSnapshot>,
+    /// Net position per token built from simulated fills, for reduce-only orders.
+    positions: HashMap<StrategyKey, Decimal>,
 }
 
 impl PaperState {
@@ -28,6 +45,8 @@ impl PaperState {
             orders: HashMap::new(),
             fills: Vec::new(),
             next_id: 1,
+            last_snapshots: HashMap::new(),
+            positions: HashMap::new(),
         }
     }
 
@@ -42,49 +61,197 @@ impl PaperState {
 /// real orders on Polymarket. Useful for back-testing and paper trading.
 pub struct PaperExecutor {
     state: Arc<Mutex<PaperState>>,
+    fill_model: FillModel,
+    max_snapshot_age_ms: Option<u64>,
+    order_ttl_ms: Option<u64>,
+    trade_logger: Option<SharedTradeLogger>,
+    clock: SharedClock,
 }
 
 impl PaperExecutor {
-    /// Create a new paper executor with empty state.
+    /// Create a new paper executor with empty state. Fills are kept in
+    /// memory only until a trade logger is set.
     pub fn new() -> Self {
         Self {
             state: Arc::new(Mutex::new(PaperState::new())),
+            fill_model: FillModel::default(),
+            max_snapshot_age_ms: None,
+            order_ttl_ms: None,
+            trade_logger: None,
+            clock: system_clock(),
         }
     }
 
+    /// Choose how resting orders are filled (`Touch` by default).
+    pub fn with_fill_model(mut self, fill_model: FillModel) -> Self {
+        self.fill_model = fill_model;
+        self
+    }
+
+    /// Skip fill evaluation for snapshots older than `max_age_ms`.
+    pub fn with_max_snapshot_age_ms(mut self, max_age_ms: Option<u64>) -> Self {
+        self.max_snapshot_age_ms = max_age_ms;
+        self
+    }
+
+    /// Expire every order `ttl_ms` after it is placed or amended, as a
+    /// good-till-date order would be on the venue.
+    pub fn with_order_ttl_ms(mut self, ttl_ms: Option<u64>) -> Self {
+        self.order_ttl_ms = ttl_ms;
+        self
+    }
+
+    /// Stamp orders and fills, and judge expiry and staleness, by `clock`.
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Append simulated fills to `path`, one JSON object per line.
+    pub fn with_trade_log_path(self, path: impl Into<PathBuf>) -> Self {
+        self.with_trade_logger(Arc::new(FileTradeLogger::new(path, TradeLogFormat::Jsonl)))
+    }
+
+    /// Send simulated fills to `logger`.
+    pub fn with_trade_logger(mut self, logger: SharedTradeLogger) -> Self {
+        self.trade_logger = Some(logger);
+        self
+    }
+
+    /// Start out holding the config's `seed_positions`, so reduce-only
+    /// orders can work them off.
+    pub fn with_seed_positions(self, seeds: &[SeedPosition]) -> Self {
+        if let Ok(mut state) = self.state.try_lock() {
+            state
+                .positions
+                .extend(seeds.iter().map(|s| (s.key(), s.net_position)));
+        }
+        self
+    }
+
+    /// Drop any trade logger, keeping fills in memory only.
+    pub fn without_trade_log(mut self) -> Self {
+        self.trade_logger = None;
+        self
+    }
+
     /// Check whether any virtual open orders would have been filled
     /// by the current market prices in the snapshot.
     ///
+    /// Under `FillModel::Touch`:
     /// - Buy orders fill when `best_ask <= our bid price`
     /// - Sell orders fill when `best_bid >= our ask price`
     ///
+    /// Under `FillModel::QueuePosition` an order fills when the market trades
+    /// strictly through its price, or once the size that was ahead of it has
+    /// traded. Traded volume is estimated from shrinkage of the displayed size
+    /// at our price while it stays the best level; a level that disappears is
+    /// not counted since cancels and trades are indistinguishable.
+    ///
+    /// Orders past their `expires_at` are cancelled before anything fills.
+    ///
+    /// Snapshots older than the configured max age, or carrying the same
+    /// timestamp as the previous one for the token (a replayed duplicate), are
+    /// skipped so a lagging feed cannot produce phantom fills.
+    ///
+    /// A reduce-only order fills at most the size that brings the position
+    /// back to flat; one that could only grow the position is cancelled
+    /// without filling.
+    ///
     /// Filled orders are removed from the internal map and returned
     /// as `Fill` structs.
+    #[instrument(name = "paper_fills", skip_all, fields(token = %snapshot.token_id))]
     pub async fn check_fills(&self, snapshot: &MarketSnapshot) -> Vec<Fill> {
         let mut state = self.state.lock().await;
+        expire_orders(&mut state, self.clock.now());
+
+        if !snapshot.is_quotable() {
+            warn!(status = %snapshot.book_status, "unquotable book — skipping fill evaluation");
+            return Vec::new();
+        }
+        if let Some(max_ms) = self.max_snapshot_age_ms {
+            let age_ms = (self.clock.now() - snapshot.timestamp).num_milliseconds();
+            if age_ms > max_ms as i64 {
+                warn!(age_ms, max_ms, "stale snapshot — skipping fill evaluation");
+                return Vec::new();
+            }
+        }
+        if state
+            .last_snapshots
+            .get(&snapshot.token_id)
+            .is_some_and(|prev| prev.timestamp == snapshot.timestamp)
+        {
+            warn!(timestamp = %snapshot.timestamp, "duplicate snapshot — skipping fill evaluation");
+            return Vec::new();
+        }
+
+        self.evaluate_fills(&mut state, snapshot)
+    }
+
+    /// Evaluate open orders on `snapshot`'s token with the configured fill
+    /// model but without the freshness checks: the session is ending and this
+    /// is the last book seen. Orders the book would not fill, like a passive
+    /// flatten, stay open.
+    pub async fn settle_at(&self, snapshot: &MarketSnapshot) -> Vec<Fill> {
+        let mut state = self.state.lock().await;
+        self.evaluate_fills(&mut state, snapshot)
+    }
+
+    fn evaluate_fills(&self, state: &mut PaperState, snapshot: &MarketSnapshot) -> Vec<Fill> {
+        let previous = state
+            .last_snapshots
+            .insert(snapshot.token_id.clone(), snapshot.clone());
         let mut filled_ids = Vec::new();
         let mut fills = Vec::new();
+        let PaperState {
+            orders, positions, ..
+        } = state;
 
-        for (id, order) in state.orders.iter() {
-            if order.token_id != snapshot.token_id {
+        for (id, paper) in orders.iter_mut() {
+            if paper.order.token_id != snapshot.token_id {
                 continue;
             }
 
-            let should_fill = match order.side {
-                // Our bid gets lifted: market ask <= our bid price
-                Side::Buy => snapshot.best_ask <= order.price,
-                // Our ask gets hit: market bid >= our ask price
-                Side::Sell => snapshot.best_bid >= order.price,
+            let should_fill = match self.fill_model {
+                FillModel::Touch => touched(&paper.order, snapshot),
+                FillModel::QueuePosition => {
+                    if let Some(ref prev) = previous {
+                        paper.traded += traded_at_level(&paper.order, prev, snapshot);
+                    }
+                    traded_through(&paper.order, snapshot)
+                        || paper.traded > paper.queue_ahead
+                        || (touched(&paper.order, snapshot) && paper.traded >= paper.queue_ahead)
+                }
             };
 
             if should_fill {
+                let order = &paper.order;
+                let position = positions.entry(order.key()).or_default();
+                let size = if order.reduce_only {
+                    reducible(order.side, *position).min(order.size)
+                } else {
+                    order.size
+                };
+                filled_ids.push(id.clone());
+                if size <= Decimal::ZERO {
+                    info!(order_id = %id, side = %order.side, position = %position, "reduce-only order would grow position — cancelled");
+                    continue;
+                }
+                *position += match order.side {
+                    Side::Buy => size,
+                    Side::Sell => -size,
+                };
+
                 let fill = Fill {
                     token_id: order.token_id.clone(),
+                    strategy_id: order.strategy_id.clone(),
                     side: order.side,
                     price: order.price,
-                    size: order.size,
-                    timestamp: Utc::now(),
+                    size,
+                    timestamp: self.clock.now(),
                     is_simulated: true,
+                    mid_at_fill: snapshot.midpoint,
+                    edge: Fill::edge_vs_mid(order.side, order.price, snapshot.midpoint),
                 };
 
                 info!(
@@ -92,11 +259,12 @@ impl PaperExecutor {
                     price = %fill.price,
                     size = %fill.size,
                     token = %fill.token_id,
+                    mid = %fill.mid_at_fill,
+                    edge = %fill.edge,
                     "paper fill"
                 );
 
                 fills.push(fill);
-                filled_ids.push(id.clone());
             }
         }
 
@@ -108,33 +276,76 @@ impl PaperExecutor {
         // Record fills in the trade log
         for fill in &fills {
             state.fills.push(fill.clone());
-            Self::write_fill_log(fill);
+            if let Some(ref logger) = self.trade_logger {
+                logger.log_fill(fill);
+            }
         }
 
         fills
     }
 
-    /// Append a single fill record to `paper_trades.jsonl` for post-session analysis.
-    fn write_fill_log(fill: &Fill) {
-        let line = match serde_json::to_string(fill) {
-            Ok(json) => json,
-            Err(e) => {
-                tracing::warn!(error = %e, "failed to serialize fill for log");
-                return;
-            }
+    /// Rest a new virtual order behind the size displayed at its price.
+    async fn insert_order(
+        &self,
+        strategy_id: &str,
+        token_id: &str,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+        reduce_only: bool,
+    ) -> OrderAck {
+        let mut state = self.state.lock().await;
+        let id = state.next_order_id();
+        let placed_at = self.clock.now();
+        let queue_ahead = state
+            .last_snapshots
+            .get(token_id)
+            .map(|snap| queue_ahead(side, price, snap))
+            .unwrap_or(Decimal::ZERO);
+
+        let order = OpenOrder {
+            id: id.clone(),
+            token_id: token_id.to_string(),
+            strategy_id: strategy_id.to_string(),
+            side,
+            price,
+            size,
+            reduce_only,
+            placed_at,
+            expires_at: self.expiry_from(placed_at),
         };
 
-        let result = std::fs::OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open("paper_trades.jsonl")
-            .and_then(|mut f| writeln!(f, "{}", line));
+        debug!(
+            order_id = %id,
+            side = %side,
+            price = %price,
+            size = %size,
+            token = token_id,
+            reduce_only,
+            "paper order placed"
+        );
 
-        if let Err(e) = result {
-            tracing::warn!(error = %e, "failed to write paper trade log");
+        state.orders.insert(
+            id.clone(),
+            PaperOrder {
+                order,
+                queue_ahead,
+                traded: Decimal::ZERO,
+            },
+        );
+        OrderAck {
+            id,
+            status: OrderStatus::Live,
+            placed_at,
         }
     }
 
+    /// Expiry for an order placed at `placed_at` under the configured TTL.
+    fn expiry_from(&self, placed_at: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.order_ttl_ms
+            .map(|ms| placed_at + chrono::Duration::milliseconds(ms as i64))
+    }
+
     /// Return a copy of all recorded fills.
     pub async fn fill_log(&self) -> Vec<Fill> {
         let state = self.state.lock().await;
@@ -148,6 +359,78 @@ impl PaperExecutor {
     }
 }
 
+/// Cancel every order whose `expires_at` has passed by `now`.
+fn expire_orders(state: &mut PaperState, now: DateTime<Utc>) {
+    state.orders.retain(|id, paper| {
+        let expired = paper.order.expires_at.is_some_and(|at| at <= now);
+        if expired {
+            info!(order_id = %id, side = %paper.order.side, price = %paper.order.price, "paper order expired — cancelled");
+        }
+        !expired
+    });
+}
+
+/// Whether the opposite side of the book has reached our price.
+fn touched(order: &OpenOrder, snapshot: &MarketSnapshot) -> bool {
+    match order.side {
+        // Our bid gets lifted: market ask <= our bid price
+        Side::Buy => snapshot.best_ask <= order.price,
+        // Our ask gets hit: market bid >= our ask price
+        Side::Sell => snapshot.best_bid >= order.price,
+    }
+}
+
+/// Whether the opposite side of the book has moved strictly past our price.
+fn traded_through(order: &OpenOrder, snapshot: &MarketSnapshot) -> bool {
+    match order.side {
+        Side::Buy => snapshot.best_ask < order.price,
+        Side::Sell => snapshot.best_bid > order.price,
+    }
+}
+
+/// Best price and displayed size on our side of the book.
+fn own_side(side: Side, snapshot: &MarketSnapshot) -> (Decimal, Decimal) {
+    match side {
+        Side::Buy => (snapshot.best_bid, snapshot.bid_size),
+        Side::Sell => (snapshot.best_ask, snapshot.ask_size),
+    }
+}
+
+/// How much a `side` order can trade before `position` passes through flat.
+fn reducible(side: Side, position: Decimal) -> Decimal {
+    match side {
+        Side::Buy => (-position).max(Decimal::ZERO),
+        Side::Sell => position.max(Decimal::ZERO),
+    }
+}
+
+/// Size queued ahead of a new order: the displayed best-level size when
+/// joining or sitting behind it, nothing when improving on it.
+fn queue_ahead(side: Side, price: Decimal, snapshot: &MarketSnapshot) -> Decimal {
+    let (best, size) = own_side(side, snapshot);
+    let improves = match side {
+        Side::Buy => price > best,
+        Side::Sell => price < best,
+    };
+    if improves {
+        Decimal::ZERO
+    } else {
+        size
+    }
+}
+
+/// Volume traded at our price between two snapshots, estimated as the drop in
+/// displayed size while our price stayed the best level.
+fn traded_at_level(order: &OpenOrder, prev: &MarketSnapshot, snapshot: &MarketSnapshot) -> Decimal {
+    let (prev_best, prev_size) = own_side(order.side, prev);
+    let (best, size) = own_side(order.side, snapshot);
+    if prev_best == order.price && best == order.price {
+        (prev_size - size).max(Decimal::ZERO)
+    } else {
+        Decimal::ZERO
+    }
+}
+
 impl Default for PaperExecutor {
     fn default() -> Self {
         Self::new()
@@ -162,29 +445,85 @@ impl Executor for PaperExecutor {
         side: Side,
         price: Decimal,
         size: Decimal,
-    ) -> Result<OrderId> {
-        let mut state = self.state.lock().await;
-        let id = state.next_order_id();
+    ) -> Result<OrderAck> {
+        Ok(self
+            .insert_order("", token_id, side, price, size, false)
+            .await)
+    }
 
-        let order = OpenOrder {
-            id: id.clone(),
-            token_id: token_id.to_string(),
-            side,
-            price,
-            size,
+    async fn place_reduce_only_order(
+        &self,
+        token_id: &str,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+    ) -> Result<OrderAck> {
+        Ok(self
+            .insert_order("", token_id, side, price, size, true)
+            .await)
+    }
+
+    async fn place_strategy_order(
+        &self,
+        strategy_id: &str,
+        token_id: &str,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+        reduce_only: bool,
+    ) -> Result<OrderAck> {
+        Ok(self
+            .insert_order(strategy_id, token_id, side, price, size, reduce_only)
+            .await)
+    }
+
+    /// Amend the order in place, keeping its ID. Shrinking at the same price
+    /// keeps its queue position; a new price or a larger size goes to the
+    /// back of the queue, as on the venue. An order that has already filled
+    /// is replaced by a fresh one.
+    async fn replace_order(
+        &self,
+        old: &OpenOrder,
+        price: Decimal,
+        size: Decimal,
+    ) -> Result<OrderAck> {
+        let mut state = self.state.lock().await;
+        let queue_ahead = state
+            .last_snapshots
+            .get(&old.token_id)
+            .map(|snap| queue_ahead(old.side, price, snap))
+            .unwrap_or(Decimal::ZERO);
+        let Some(paper) = state.orders.get_mut(&old.id) else {
+            drop(state);
+            debug!(order_id = %old.id, "replace: order not found — placing anew");
+            return Ok(self
+                .insert_order(
+                    &old.strategy_id,
+                    &old.token_id,
+                    old.side,
+                    price,
+                    size,
+                    old.reduce_only,
+                )
+                .await);
         };
 
-        debug!(
-            order_id = %id,
-            side = %side,
-            price = %price,
-            size = %size,
-            token = token_id,
-            "paper order placed"
-        );
+        if price != paper.order.price || size > paper.order.size {
+            paper.queue_ahead = queue_ahead;
+            paper.traded = Decimal::ZERO;
+        }
+        let placed_at = self.clock.now();
+        paper.order.price = price;
+        paper.order.size = size;
+        paper.order.placed_at = placed_at;
+        paper.order.expires_at = self.expiry_from(placed_at);
+        debug!(order_id = %old.id, price = %price, size = %size, "paper order replaced");
 
-        state.orders.insert(id.clone(), order);
-        Ok(id)
+        Ok(OrderAck {
+            id: old.id.clone(),
+            status: OrderStatus::Live,
+            placed_at,
+        })
     }
 
     async fn cancel_order(&self, id: &OrderId) -> Result<()> {
@@ -205,15 +544,43 @@ impl Executor for PaperExecutor {
         Ok(())
     }
 
+    async fn cancel_token(&self, token_id: &str) -> Result<()> {
+        let mut state = self.state.lock().await;
+        let before = state.orders.len();
+        state.orders.retain(|_, p| p.order.token_id != token_id);
+        debug!(
+            token_id,
+            count = before - state.orders.len(),
+            "cancelled paper orders for token"
+        );
+        Ok(())
+    }
+
+    async fn cancel_strategy(&self, token_id: &str, strategy_id: &str) -> Result<()> {
+        let mut state = self.state.lock().await;
+        let before = state.orders.len();
+        state
+            .orders
+            .retain(|_, p| p.order.token_id != token_id || p.order.strategy_id != strategy_id);
+        debug!(
+            token_id,
+            strategy_id,
+            count = before - state.orders.len(),
+            "cancelled paper orders for strategy"
+        );
+        Ok(())
+    }
+
     async fn open_orders(&self) -> Result<Vec<OpenOrder>> {
         let state = self.state.lock().await;
-        Ok(state.orders.values().cloned().collect())
+        Ok(state.orders.values().map(|p| p.order.clone()).collect())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::trade_log::MemoryTradeLogger;
     use rust_decimal_macros::dec;
 
     fn snapshot(token_id: &str, best_bid: Decimal, best_ask: Decimal) -> MarketSnapshot {
@@ -223,27 +590,167 @@ mod tests {
             best_bid,
             best_ask,
             midpoint: mid,
+            micro_price: mid,
             spread: best_ask - best_bid,
+            bid_size: dec!(100),
+            ask_size: dec!(100),
             timestamp: Utc::now(),
+            book_status: Default::default(),
         }
     }
 
+    fn sized(mut snap: MarketSnapshot, bid_size: Decimal, ask_size: Decimal) -> MarketSnapshot {
+        snap.bid_size = bid_size;
+        snap.ask_size = ask_size;
+        snap
+    }
+
+    fn queue_executor() -> PaperExecutor {
+        PaperExecutor::new().with_fill_model(FillModel::QueuePosition)
+    }
+
+    #[tokio::test]
+    async fn expired_orders_are_cancelled_before_fills() {
+        let clock = eutrader_core::MockClock::new(Utc::now());
+        let exec = PaperExecutor::new()
+            .with_order_ttl_ms(Some(10))
+            .with_clock(Arc::new(clock.clone()))
+            .without_trade_log();
+        let ack = exec
+            .place_order("tok1", Side::Buy, dec!(0.50), dec!(10))
+            .await
+            .unwrap();
+        let order = exec.open_orders().await.unwrap().remove(0);
+        assert_eq!(
+            order.expires_at,
+            Some(ack.placed_at + chrono::Duration::milliseconds(10))
+        );
+
+        // Just short of the expiry the order still rests
+        clock.advance(chrono::Duration::milliseconds(9));
+        exec.check_fills(&snapshot("tok1", dec!(0.40), dec!(0.60)))
+            .await;
+        assert_eq!(exec.open_orders().await.unwrap().len(), 1);
+
+        clock.advance(chrono::Duration::milliseconds(1));
+        exec.place_order("tok1", Side::Buy, dec!(0.49), dec!(5))
+            .await
+            .unwrap();
+
+        // The market now crosses both bids, but only the unexpired one fills
+        let fills = exec
+            .check_fills(&snapshot("tok1", dec!(0.46), dec!(0.48)))
+            .await;
+        assert_eq!(fills.len(), 1);
+        assert_eq!((fills[0].price, fills[0].size), (dec!(0.49), dec!(5)));
+        assert!(exec.open_orders().await.unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn place_and_cancel_order() {
         let exec = PaperExecutor::new();
-        let id = exec
+        let ack = exec
             .place_order("tok1", Side::Buy, dec!(0.50), dec!(10))
             .await
             .unwrap();
 
         let orders = exec.open_orders().await.unwrap();
         assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].id, ack.id);
+        assert_eq!(ack.status, OrderStatus::Live);
+        assert!(ack.is_accepted());
+        assert!(Utc::now() - ack.placed_at < chrono::Duration::seconds(5));
 
-        exec.cancel_order(&id).await.unwrap();
+        exec.cancel_order(&ack.id).await.unwrap();
         let orders = exec.open_orders().await.unwrap();
         assert!(orders.is_empty());
     }
 
+    #[tokio::test]
+    async fn fills_record_edge_versus_mid() {
+        let log = std::env::temp_dir().join(format!("paper_edge_{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&log);
+        let exec = PaperExecutor::new().with_trade_log_path(&log);
+        exec.place_order("tok1", Side::Buy, dec!(0.50), dec!(10))
+            .await
+            .unwrap();
+        exec.place_order("tok1", Side::Sell, dec!(0.56), dec!(10))
+            .await
+            .unwrap();
+
+        // Market drops through our bid: bought 0.02 above the 0.48 mid
+        let fills = exec
+            .check_fills(&snapshot("tok1", dec!(0.47), dec!(0.49)))
+            .await;
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].mid_at_fill, dec!(0.48));
+        assert_eq!(fills[0].edge, dec!(-0.02));
+
+        // Market rallies through our ask: sold 0.02 below the 0.58 mid
+        let fills = exec
+            .check_fills(&snapshot("tok1", dec!(0.57), dec!(0.59)))
+            .await;
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].side, Side::Sell);
+        assert_eq!(fills[0].edge, dec!(-0.02));
+
+        // Passive fills inside the mid capture positive edge on either side
+        assert_eq!(
+            Fill::edge_vs_mid(Side::Buy, dec!(0.49), dec!(0.50)),
+            dec!(0.01)
+        );
+        assert_eq!(
+            Fill::edge_vs_mid(Side::Sell, dec!(0.51), dec!(0.50)),
+            dec!(0.01)
+        );
+
+        let logged: Vec<Fill> = std::fs::read_to_string(&log)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        std::fs::remove_file(&log).unwrap();
+        assert_eq!(logged.len(), 2);
+        assert_eq!(logged[0].mid_at_fill, dec!(0.48));
+        assert_eq!(logged[1].edge, dec!(-0.02));
+    }
+
+    #[tokio::test]
+    async fn fills_are_sent_to_the_trade_logger() {
+        let logger = Arc::new(MemoryTradeLogger::new());
+        let exec = PaperExecutor::new().with_trade_logger(logger.clone());
+        exec.place_order("tok1", Side::Buy, dec!(0.50), dec!(10))
+            .await
+            .unwrap();
+        exec.place_order("tok1", Side::Sell, dec!(0.56), dec!(5))
+            .await
+            .unwrap();
+
+        // Touching neither side logs nothing
+        exec.check_fills(&snapshot("tok1", dec!(0.51), dec!(0.55)))
+            .await;
+        assert!(logger.fills().is_empty());
+
+        exec.check_fills(&snapshot("tok1", dec!(0.47), dec!(0.49)))
+            .await;
+        exec.check_fills(&snapshot("tok1", dec!(0.57), dec!(0.59)))
+            .await;
+        let logged = logger.fills();
+        assert_eq!(logged.len(), 2);
+        assert_eq!(
+            (logged[0].side, logged[0].price, logged[0].size),
+            (Side::Buy, dec!(0.50), dec!(10))
+        );
+        assert_eq!(
+            (logged[1].side, logged[1].price, logged[1].size),
+            (Side::Sell, dec!(0.56), dec!(5))
+        );
+        assert!(logged
+            .iter()
+            .all(|f| f.is_simulated && f.token_id == "tok1"));
+        assert_eq!(logged.len(), exec.fill_count().await);
+    }
+
     #[tokio::test]
     async fn cancel_all_clears_orders() {
         let exec = PaperExecutor::new();
@@ -259,6 +766,26 @@ mod tests {
         assert!(orders.is_empty());
     }
 
+    #[tokio::test]
+    async fn cancel_token_keeps_other_tokens() {
+        let exec = PaperExecutor::new();
+        exec.place_order("tok1", Side::Buy, dec!(0.50), dec!(10))
+            .await
+            .unwrap();
+        exec.place_order("tok1", Side::Sell, dec!(0.55), dec!(10))
+            .await
+            .unwrap();
+        let kept = exec
+            .place_order("tok2", Side::Buy, dec!(0.30), dec!(10))
+            .await
+            .unwrap();
+
+        exec.cancel_token("tok1").await.unwrap();
+        let orders = exec.open_orders().await.unwrap();
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].id, kept.id);
+    }
+
     #[tokio::test]
     async fn buy_order_fills_when_ask_crosses() {
         let exec = PaperExecutor::new();
@@ -321,4 +848,263 @@ mod tests {
         let fills = exec.check_fills(&snap).await;
         assert!(fills.is_empty());
     }
+
+    #[tokio::test]
+    async fn stale_snapshot_is_not_evaluated() {
+        let exec = PaperExecutor::new().with_max_snapshot_age_ms(Some(1000));
+        exec.place_order("tok1", Side::Buy, dec!(0.50), dec!(10))
+            .await
+            .unwrap();
+
+        let mut stale = snapshot("tok1", dec!(0.45), dec!(0.48));
+        stale.timestamp = Utc::now() - chrono::Duration::seconds(10);
+        assert!(exec.check_fills(&stale).await.is_empty());
+        assert_eq!(exec.open_orders().await.unwrap().len(), 1);
+
+        // A fresh snapshot at the same prices fills
+        let fresh = snapshot("tok1", dec!(0.45), dec!(0.48));
+        assert_eq!(exec.check_fills(&fresh).await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn duplicate_snapshot_is_not_evaluated() {
+        let exec = PaperExecutor::new();
+        exec.place_order("tok1", Side::Buy, dec!(0.50), dec!(10))
+            .await
+            .unwrap();
+
+        let first = snapshot("tok1", dec!(0.49), dec!(0.52));
+        assert!(exec.check_fills(&first).await.is_empty());
+
+        // Same timestamp replayed with crossing prices
+        let mut replay = snapshot("tok1", dec!(0.45), dec!(0.48));
+        replay.timestamp = first.timestamp;
+        assert!(exec.check_fills(&replay).await.is_empty());
+        assert_eq!(exec.open_orders().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn touched_order_does_not_fill_under_queue_model() {
+        let exec = queue_executor();
+        // Join the best bid behind 100 shares
+        exec.check_fills(&snapshot("tok1", dec!(0.50), dec!(0.52)))
+            .await;
+        exec.place_order("tok1", Side::Buy, dec!(0.50), dec!(10))
+            .await
+            .unwrap();
+
+        // The ask comes down to our price but never through it
+        let touch = snapshot("tok1", dec!(0.49), dec!(0.50));
+        assert!(exec.check_fills(&touch).await.is_empty());
+        assert_eq!(exec.open_orders().await.unwrap().len(), 1);
+
+        // The same touch fills immediately under the touch model
+        let touch_exec = PaperExecutor::new();
+        touch_exec
+            .check_fills(&snapshot("tok1", dec!(0.50), dec!(0.52)))
+            .await;
+        touch_exec
+            .place_order("tok1", Side::Buy, dec!(0.50), dec!(10))
+            .await
+            .unwrap();
+        assert_eq!(touch_exec.check_fills(&touch).await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn queue_model_fills_when_traded_through() {
+        let exec = queue_executor();
+        exec.check_fills(&snapshot("tok1", dec!(0.50), dec!(0.52)))
+            .await;
+        exec.place_order("tok1", Side::Buy, dec!(0.50), dec!(10))
+            .await
+            .unwrap();
+
+        let through = snapshot("tok1", dec!(0.47), dec!(0.49));
+        assert_eq!(exec.check_fills(&through).await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn queue_model_fills_once_queue_ahead_trades() {
+        let exec = queue_executor();
+        exec.check_fills(&sized(
+            snapshot("tok1", dec!(0.50), dec!(0.52)),
+            dec!(30),
+            dec!(100),
+        ))
+        .await;
+        exec.place_order("tok1", Side::Buy, dec!(0.50), dec!(10))
+            .await
+            .unwrap();
+
+        // Others join behind us, then 20 trades — still 10 ahead
+        let joined = sized(
+            snapshot("tok1", dec!(0.50), dec!(0.52)),
+            dec!(80),
+            dec!(100),
+        );
+        assert!(exec.check_fills(&joined).await.is_empty());
+        let partial = sized(
+            snapshot("tok1", dec!(0.50), dec!(0.52)),
+            dec!(60),
+            dec!(100),
+        );
+        assert!(exec.check_fills(&partial).await.is_empty());
+
+        // Another 15 trades: the queue ahead is gone and volume reaches us
+        let reached = sized(
+            snapshot("tok1", dec!(0.50), dec!(0.52)),
+            dec!(45),
+            dec!(100),
+        );
+        assert_eq!(exec.check_fills(&reached).await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn improving_order_has_no_queue_ahead() {
+        let exec = queue_executor();
+        exec.check_fills(&snapshot("tok1", dec!(0.49), dec!(0.52)))
+            .await;
+        exec.place_order("tok1", Side::Buy, dec!(0.50), dec!(10))
+            .await
+            .unwrap();
+
+        let touch = snapshot("tok1", dec!(0.49), dec!(0.50));
+        assert_eq!(exec.check_fills(&touch).await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn replace_shrinking_keeps_id_and_queue_position() {
+        let exec = queue_executor();
+        exec.check_fills(&sized(
+            snapshot("tok1", dec!(0.50), dec!(0.52)),
+            dec!(30),
+            dec!(100),
+        ))
+        .await;
+        let ack = exec
+            .place_order("tok1", Side::Buy, dec!(0.50), dec!(10))
+            .await
+            .unwrap();
+        let joined = sized(
+            snapshot("tok1", dec!(0.50), dec!(0.52)),
+            dec!(80),
+            dec!(100),
+        );
+        exec.check_fills(&joined).await;
+        let partial = sized(
+            snapshot("tok1", dec!(0.50), dec!(0.52)),
+            dec!(60),
+            dec!(100),
+        );
+        exec.check_fills(&partial).await;
+
+        let old = exec.open_orders().await.unwrap().remove(0);
+        let replaced = exec.replace_order(&old, dec!(0.50), dec!(5)).await.unwrap();
+        assert_eq!(replaced.id, ack.id);
+        let orders = exec.open_orders().await.unwrap();
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].size, dec!(5));
+
+        // Still only 10 ahead, so the next 15 traded reach us
+        let reached = sized(
+            snapshot("tok1", dec!(0.50), dec!(0.52)),
+            dec!(45),
+            dec!(100),
+        );
+        let fills = exec.check_fills(&reached).await;
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].size, dec!(5));
+    }
+
+    #[tokio::test]
+    async fn replace_growing_goes_to_back_of_queue() {
+        let exec = queue_executor();
+        exec.check_fills(&sized(
+            snapshot("tok1", dec!(0.50), dec!(0.52)),
+            dec!(30),
+            dec!(100),
+        ))
+        .await;
+        exec.place_order("tok1", Side::Buy, dec!(0.50), dec!(10))
+            .await
+            .unwrap();
+        let joined = sized(
+            snapshot("tok1", dec!(0.50), dec!(0.52)),
+            dec!(80),
+            dec!(100),
+        );
+        exec.check_fills(&joined).await;
+        let partial = sized(
+            snapshot("tok1", dec!(0.50), dec!(0.52)),
+            dec!(60),
+            dec!(100),
+        );
+        exec.check_fills(&partial).await;
+
+        let old = exec.open_orders().await.unwrap().remove(0);
+        exec.replace_order(&old, dec!(0.50), dec!(20))
+            .await
+            .unwrap();
+
+        // Requeued behind the 60 displayed: 15 traded no longer reaches us
+        let traded = sized(
+            snapshot("tok1", dec!(0.50), dec!(0.52)),
+            dec!(45),
+            dec!(100),
+        );
+        assert!(exec.check_fills(&traded).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn reduce_only_orders_never_grow_position() {
+        let exec = PaperExecutor::new().without_trade_log();
+        exec.place_order("tok1", Side::Buy, dec!(0.50), dec!(10))
+            .await
+            .unwrap();
+        exec.check_fills(&snapshot("tok1", dec!(0.48), dec!(0.50)))
+            .await;
+
+        // Long 10: a reduce-only buy would add to it and is cancelled unfilled
+        exec.place_reduce_only_order("tok1", Side::Buy, dec!(0.50), dec!(5))
+            .await
+            .unwrap();
+        assert!(exec.open_orders().await.unwrap()[0].reduce_only);
+        assert!(exec
+            .check_fills(&snapshot("tok1", dec!(0.47), dec!(0.49)))
+            .await
+            .is_empty());
+        assert!(exec.open_orders().await.unwrap().is_empty());
+
+        // A reduce-only sell larger than the position stops at flat
+        exec.place_reduce_only_order("tok1", Side::Sell, dec!(0.50), dec!(25))
+            .await
+            .unwrap();
+        let fills = exec
+            .check_fills(&snapshot("tok1", dec!(0.50), dec!(0.52)))
+            .await;
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].size, dec!(10));
+    }
+
+    #[tokio::test]
+    async fn seeded_long_can_be_sold_down() {
+        let seeds = [SeedPosition {
+            token_id: "tok1".into(),
+            strategy_id: String::new(),
+            net_position: dec!(10),
+            avg_entry: dec!(0.45),
+        }];
+        let exec = PaperExecutor::new()
+            .with_seed_positions(&seeds)
+            .without_trade_log();
+
+        exec.place_reduce_only_order("tok1", Side::Sell, dec!(0.50), dec!(25))
+            .await
+            .unwrap();
+        let fills = exec
+            .check_fills(&snapshot("tok1", dec!(0.50), dec!(0.52)))
+            .await;
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].size, dec!(10));
+    }
 }
@@ -0,0 +1,300 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use tokio::sync::Mutex;
+use tokio::time::{Duration, Instant};
+use tracing::debug;
+
+use eutrader_core::{ExecutionConfig, OpenOrder, OrderAck, OrderId, Result, Side};
+
+use crate::executor::Executor;
+
+/// Mutable token-bucket state.
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// Token-bucket rate limiter for order operations.
+///
+/// Holds up to `burst` tokens and refills at `per_sec` tokens per second.
+/// Each operation consumes one token.
+pub struct RateLimiter {
+    per_sec: f64,
+    burst: f64,
+    wait: bool,
+    bucket: Mutex<Bucket>,
+}
+
+impl RateLimiter {
+    /// Create a limiter allowing `per_sec` operations per second with a burst
+    /// of `burst`. When `wait` is false, `acquire` fails instead of waiting.
+    pub fn new(per_sec: u32, burst: u32, wait: bool) -> Self {
+        let burst = burst.max(1) as f64;
+        Self {
+            per_sec: per_sec.max(1) as f64,
+            burst,
+            wait,
+            bucket: Mutex::new(Bucket {
+                tokens: burst,
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    /// Build a limiter from config, or `None` if rate limiting is disabled.
+    pub fn from_config(config: &ExecutionConfig) -> Option<Self> {
+        let per_sec = config.rate_limit_per_sec?;
+        let burst = config.rate_limit_burst.unwrap_or(per_sec);
+        Some(Self::new(per_sec, burst, config.rate_limit_wait))
+    }
+
+    /// Take one token, waiting for a refill or failing with
+    /// `Error::Execution("rate limited")` depending on configuration.
+    pub async fn acquire(&self) -> Result<()> {
+        loop {
+            let mut bucket = self.bucket.lock().await;
+            let now = Instant::now();
+            let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+            bucket.tokens = (bucket.tokens + elapsed * self.per_sec).min(self.burst);
+            bucket.last_refill = now;
+
+            if bucket.tokens >= 1.0 {
+                bucket.tokens -= 1.0;
+                return Ok(());
+            }
+            if !self.wait {
+                return Err(eutrader_core::Error::Execution("rate limited".into()));
+            }
+
+            let delay = Duration::from_secs_f64((1.0 - bucket.tokens) / self.per_sec);
+            drop(bucket);
+            debug!(
+                delay_ms = delay.as_millis() as u64,
+                "rate limited — waiting"
+            );
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+/// Wraps any `Executor`, throttling order operations through a `RateLimiter`.
+///
+/// Reads (`open_orders`) and heartbeats are not throttled.
+pub struct RateLimitedExecutor<E: Executor> {
+    inner: E,
+    limiter: Option<RateLimiter>,
+}
+
+impl<E: Executor> RateLimitedExecutor<E> {
+    pub fn new(inner: E, limiter: RateLimiter) -> Self {
+        Self {
+            inner,
+            limiter: Some(limiter),
+        }
+    }
+
+    /// Throttle `inner` as `config` asks, passing every operation straight
+    /// through if rate limiting is disabled.
+    pub fn from_config(inner: E, config: &ExecutionConfig) -> Self {
+        Self {
+            inner,
+            limiter: RateLimiter::from_config(config),
+        }
+    }
+
+    /// Whether operations are throttled at all.
+    pub fn is_limited(&self) -> bool {
+        self.limiter.is_some()
+    }
+
+    /// Take a token for one operation, if limited.
+    async fn acquire(&self) -> Result<()> {
+        match self.limiter {
+            Some(ref limiter) => limiter.acquire().await,
+            None => Ok(()),
+        }
+    }
+
+    /// Access the wrapped executor.
+    pub fn inner(&self) -> &E {
+        &self.inner
+    }
+}
+
+#[async_trait]
+impl<E: Executor> Executor for RateLimitedExecutor<E> {
+    async fn place_order(
+        &self,
+        token_id: &str,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+    ) -> Result<OrderAck> {
+        self.acquire().await?;
+        self.inner.place_order(token_id, side, price, size).await
+    }
+
+    async fn place_reduce_only_order(
+        &self,
+        token_id: &str,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+    ) -> Result<OrderAck> {
+        self.acquire().await?;
+        self.inner
+            .place_reduce_only_order(token_id, side, price, size)
+            .await
+    }
+
+    async fn place_strategy_order(
+        &self,
+        strategy_id: &str,
+        token_id: &str,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+        reduce_only: bool,
+    ) -> Result<OrderAck> {
+        self.acquire().await?;
+        self.inner
+            .place_strategy_order(strategy_id, token_id, side, price, size, reduce_only)
+            .await
+    }
+
+    async fn replace_order(
+        &self,
+        old: &OpenOrder,
+        price: Decimal,
+        size: Decimal,
+    ) -> Result<OrderAck> {
+        self.acquire().await?;
+        self.inner.replace_order(old, price, size).await
+    }
+
+    async fn cancel_order(&self, id: &OrderId) -> Result<()> {
+        self.acquire().await?;
+        self.inner.cancel_order(id).await
+    }
+
+    async fn cancel_all(&self) -> Result<()> {
+        self.acquire().await?;
+        self.inner.cancel_all().await
+    }
+
+    async fn cancel_token(&self, token_id: &str) -> Result<()> {
+        self.acquire().await?;
+        self.inner.cancel_token(token_id).await
+    }
+
+    async fn cancel_strategy(&self, token_id: &str, strategy_id: &str) -> Result<()> {
+        self.acquire().await?;
+        self.inner.cancel_strategy(token_id, strategy_id).await
+    }
+
+    async fn open_orders(&self) -> Result<Vec<OpenOrder>> {
+        self.inner.open_orders().await
+    }
+
+    async fn heartbeat(&self) -> Result<()> {
+        self.inner.heartbeat().await
+    }
+
+    async fn fetch_positions(&self) -> Result<Option<HashMap<String, Decimal>>> {
+        self.inner.fetch_positions().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paper::PaperExecutor;
+    use rust_decimal_macros::dec;
+
+    #[tokio::test(start_paused = true)]
+    async fn placements_are_spread_over_time() {
+        let exec = RateLimitedExecutor::new(PaperExecutor::new(), RateLimiter::new(10, 10, true));
+        let start = Instant::now();
+
+        for _ in 0..100 {
+            exec.place_order("tok1", Side::Buy, dec!(0.50), dec!(10))
+                .await
+                .unwrap();
+        }
+
+        // 10 go out immediately from the burst, the other 90 at 10/sec
+        let elapsed = start.elapsed();
+        assert!(
+            elapsed >= Duration::from_millis(8_900),
+            "elapsed {elapsed:?}"
+        );
+        assert!(
+            elapsed <= Duration::from_millis(9_500),
+            "elapsed {elapsed:?}"
+        );
+        assert_eq!(exec.open_orders().await.unwrap().len(), 100);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn rejects_when_empty_and_not_waiting() {
+        let exec = RateLimitedExecutor::new(PaperExecutor::new(), RateLimiter::new(5, 2, false));
+
+        for _ in 0..2 {
+            exec.place_order("tok1", Side::Buy, dec!(0.50), dec!(10))
+                .await
+                .unwrap();
+        }
+        let err = exec
+            .place_order("tok1", Side::Buy, dec!(0.50), dec!(10))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("rate limited"));
+
+        // Capacity returns after a refill
+        tokio::time::advance(Duration::from_millis(200)).await;
+        assert!(exec
+            .place_order("tok1", Side::Buy, dec!(0.50), dec!(10))
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn bulk_cancels_take_one_token() {
+        let exec = RateLimitedExecutor::new(PaperExecutor::new(), RateLimiter::new(5, 3, false));
+        for _ in 0..2 {
+            exec.inner()
+                .place_order("tok1", Side::Buy, dec!(0.50), dec!(10))
+                .await
+                .unwrap();
+        }
+
+        // One call for both orders, not one per order
+        exec.cancel_token("tok1").await.unwrap();
+        assert!(exec.open_orders().await.unwrap().is_empty());
+        exec.cancel_strategy("tok1", "").await.unwrap();
+        exec.cancel_all().await.unwrap();
+        let err = exec.cancel_token("tok1").await.unwrap_err();
+        assert!(err.to_string().contains("rate limited"));
+    }
+
+    #[test]
+    fn disabled_without_config() {
+        assert!(RateLimiter::from_config(&ExecutionConfig::default()).is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn unconfigured_executor_passes_through() {
+        let exec =
+            RateLimitedExecutor::from_config(PaperExecutor::new(), &ExecutionConfig::default());
+        assert!(!exec.is_limited());
+        let start = Instant::now();
+        for _ in 0..100 {
+            exec.place_order("tok1", Side::Buy, dec!(0.50), dec!(10))
+                .await
+                .unwrap();
+        }
+        assert_eq!(start.elapsed(), Duration::ZERO);
+    }
+}
@@ -0,0 +1,558 @@
+use std::collections::{HashMap, HashSet};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use tokio::sync::Mutex;
+use tracing::warn;
+
+use eutrader_core::{OpenOrder, OrderAck, OrderId, Result, Side};
+
+use crate::executor::Executor;
+
+/// How the primary and shadow executors disagreed about an order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DivergenceKind {
+    /// The shadow rejected an order the primary accepted.
+    ShadowRejected,
+    /// The order left the shadow's book (filled) but still rests on the primary.
+    FilledInShadowOnly,
+    /// The order left the primary's book (filled) but still rests in the shadow.
+    FilledInPrimaryOnly,
+}
+
+/// A single disagreement between the primary and shadow executors.
+#[derive(Debug, Clone)]
+pub struct Divergence {
+    pub primary_id: OrderId,
+    pub token_id: String,
+    pub side: Side,
+    pub price: Decimal,
+    pub size: Decimal,
+    pub kind: DivergenceKind,
+    pub detected_at: DateTime<Utc>,
+}
+
+/// An order mirrored into both executors.
+struct MirroredOrder {
+    shadow_id: OrderId,
+    strategy_id: String,
+    token_id: String,
+    side: Side,
+    price: Decimal,
+    size: Decimal,
+}
+
+struct ShadowState {
+    /// Primary order ID -> its mirror in the shadow executor.
+    mirrored: HashMap<OrderId, MirroredOrder>,
+    divergences: Vec<Divergence>,
+}
+
+/// Runs a primary executor with a second executor shadowing every mutation.
+///
+/// Orders, amends and cancels go to the primary `A` and are mirrored into
+/// the shadow `B` — typically a `PaperExecutor` simulating what the primary
+/// should see. `open_orders` returns the primary's book. Each call compares
+/// which mirrored orders have left each book and records a `Divergence` when
+/// one side filled an order the other did not. Shadow failures are logged and
+/// never affect the primary.
+pub struct ShadowExecutor<A: Executor, B: Executor> {
+    primary: A,
+    shadow: B,
+    state: Mutex<ShadowState>,
+}
+
+impl<A: Executor, B: Executor> ShadowExecutor<A, B> {
+    pub fn new(primary: A, shadow: B) -> Self {
+        Self {
+            primary,
+            shadow,
+            state: Mutex::new(ShadowState {
+                mirrored: HashMap::new(),
+                divergences: Vec::new(),
+            }),
+        }
+    }
+
+    /// Access the primary executor.
+    pub fn primary(&self) -> &A {
+        &self.primary
+    }
+
+    /// Access the shadow executor, e.g. to drive paper fills.
+    pub fn shadow(&self) -> &B {
+        &self.shadow
+    }
+
+    /// Return every divergence recorded so far.
+    pub async fn divergences(&self) -> Vec<Divergence> {
+        self.state.lock().await.divergences.clone()
+    }
+
+    /// Compare the two books and record orders that left only one of them.
+    async fn detect_divergences(&self, primary_open: &[OpenOrder]) {
+        let shadow_open = match self.shadow.open_orders().await {
+            Ok(orders) => orders,
+            Err(e) => {
+                warn!(error = %e, "shadow open_orders failed — skipping divergence check");
+                return;
+            }
+        };
+        let primary_ids: HashSet<&OrderId> = primary_open.iter().map(|o| &o.id).collect();
+        let shadow_ids: HashSet<&OrderId> = shadow_open.iter().map(|o| &o.id).collect();
+
+        let mut state = self.state.lock().await;
+        let mut settled = Vec::new();
+        let mut found = Vec::new();
+        for (primary_id, m) in &state.mirrored {
+            let in_primary = primary_ids.contains(primary_id);
+            let in_shadow = shadow_ids.contains(&m.shadow_id);
+            let kind = match (in_primary, in_shadow) {
+                (true, true) => continue,
+                (false, false) => None,
+                (true, false) => Some(DivergenceKind::FilledInShadowOnly),
+                (false, true) => Some(DivergenceKind::FilledInPrimaryOnly),
+            };
+            settled.push(primary_id.clone());
+            if let Some(kind) = kind {
+                found.push(Divergence {
+                    primary_id: primary_id.clone(),
+                    token_id: m.token_id.clone(),
+                    side: m.side,
+                    price: m.price,
+                    size: m.size,
+                    kind,
+                    detected_at: Utc::now(),
+                });
+            }
+        }
+
+        for id in settled {
+            state.mirrored.remove(&id);
+        }
+        for d in found {
+            warn!(
+                order_id = %d.primary_id,
+                token = %d.token_id,
+                side = %d.side,
+                price = %d.price,
+                size = %d.size,
+                kind = ?d.kind,
+                "shadow executor diverged from primary"
+            );
+            state.divergences.push(d);
+        }
+    }
+
+    /// Place on the primary, then mirror an accepted order into the shadow.
+    async fn place_mirrored(
+        &self,
+        strategy_id: &str,
+        token_id: &str,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+        reduce_only: bool,
+    ) -> Result<OrderAck> {
+        let primary_ack = self
+            .primary
+            .place_strategy_order(strategy_id, token_id, side, price, size, reduce_only)
+            .await?;
+        if !primary_ack.is_accepted() {
+            return Ok(primary_ack);
+        }
+
+        let shadow_ack = self
+            .shadow
+            .place_strategy_order(strategy_id, token_id, side, price, size, reduce_only)
+            .await;
+        self.record_mirror(
+            primary_ack.id.clone(),
+            strategy_id,
+            token_id,
+            side,
+            price,
+            size,
+            shadow_ack,
+        )
+        .await;
+        Ok(primary_ack)
+    }
+
+    /// Track the shadow's copy of primary order `primary_id`, or record a
+    /// divergence if the shadow refused it.
+    #[allow(clippy::too_many_arguments)]
+    async fn record_mirror(
+        &self,
+        primary_id: OrderId,
+        strategy_id: &str,
+        token_id: &str,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+        shadow_ack: Result<OrderAck>,
+    ) {
+        let shadow_result = match shadow_ack {
+            Ok(ack) if !ack.is_accepted() => Err(format!("status {:?}", ack.status)),
+            Ok(ack) => Ok(ack.id),
+            Err(e) => Err(e.to_string()),
+        };
+        let mut state = self.state.lock().await;
+        match shadow_result {
+            Ok(shadow_id) => {
+                state.mirrored.insert(
+                    primary_id,
+                    MirroredOrder {
+                        shadow_id,
+                        strategy_id: strategy_id.to_string(),
+                        token_id: token_id.to_string(),
+                        side,
+                        price,
+                        size,
+                    },
+                );
+            }
+            Err(e) => {
+                warn!(order_id = %primary_id, error = %e, "shadow rejected order");
+                state.divergences.push(Divergence {
+                    primary_id,
+                    token_id: token_id.to_string(),
+                    side,
+                    price,
+                    size,
+                    kind: DivergenceKind::ShadowRejected,
+                    detected_at: Utc::now(),
+                });
+            }
+        }
+    }
+
+    /// The shadow's copy of `m`, or `None` if it has left the shadow's book,
+    /// in which case the shadow filled an order still resting on the primary.
+    async fn shadow_copy(&self, primary_id: &OrderId, m: &MirroredOrder) -> Option<OpenOrder> {
+        let open = match self.shadow.open_orders().await {
+            Ok(open) => open,
+            Err(e) => {
+                warn!(order_id = %primary_id, error = %e, "shadow open_orders failed");
+                return None;
+            }
+        };
+        let copy = open.into_iter().find(|o| o.id == m.shadow_id);
+        if copy.is_none() {
+            warn!(order_id = %primary_id, token = %m.token_id, "shadow executor diverged from primary");
+            self.state.lock().await.divergences.push(Divergence {
+                primary_id: primary_id.clone(),
+                token_id: m.token_id.clone(),
+                side: m.side,
+                price: m.price,
+                size: m.size,
+                kind: DivergenceKind::FilledInShadowOnly,
+                detected_at: Utc::now(),
+            });
+        }
+        copy
+    }
+}
+
+#[async_trait]
+impl<A: Executor, B: Executor> Executor for ShadowExecutor<A, B> {
+    async fn place_order(
+        &self,
+        token_id: &str,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+    ) -> Result<OrderAck> {
+        self.place_mirrored("", token_id, side, price, size, false)
+            .await
+    }
+
+    async fn place_reduce_only_order(
+        &self,
+        token_id: &str,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+    ) -> Result<OrderAck> {
+        self.place_mirrored("", token_id, side, price, size, true)
+            .await
+    }
+
+    async fn place_strategy_order(
+        &self,
+        strategy_id: &str,
+        token_id: &str,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+        reduce_only: bool,
+    ) -> Result<OrderAck> {
+        self.place_mirrored(strategy_id, token_id, side, price, size, reduce_only)
+            .await
+    }
+
+    async fn replace_order(
+        &self,
+        old: &OpenOrder,
+        price: Decimal,
+        size: Decimal,
+    ) -> Result<OrderAck> {
+        let primary_ack = self.primary.replace_order(old, price, size).await?;
+
+        let mirrored = self.state.lock().await.mirrored.remove(&old.id);
+        let shadow_old = match mirrored {
+            Some(ref m) => self.shadow_copy(&old.id, m).await,
+            None => None,
+        };
+        if !primary_ack.is_accepted() {
+            if let Some(order) = shadow_old {
+                if let Err(e) = self.shadow.cancel_order(&order.id).await {
+                    warn!(order_id = %old.id, error = %e, "shadow cancel failed");
+                }
+            }
+            return Ok(primary_ack);
+        }
+
+        let shadow_ack = match shadow_old {
+            Some(order) => self.shadow.replace_order(&order, price, size).await,
+            None => {
+                self.shadow
+                    .place_strategy_order(
+                        &old.strategy_id,
+                        &old.token_id,
+                        old.side,
+                        price,
+                        size,
+                        old.reduce_only,
+                    )
+                    .await
+            }
+        };
+        self.record_mirror(
+            primary_ack.id.clone(),
+            &old.strategy_id,
+            &old.token_id,
+            old.side,
+            price,
+            size,
+            shadow_ack,
+        )
+        .await;
+        Ok(primary_ack)
+    }
+
+    async fn cancel_order(&self, id: &OrderId) -> Result<()> {
+        self.primary.cancel_order(id).await?;
+
+        let mirrored = self.state.lock().await.mirrored.remove(id);
+        if let Some(m) = mirrored {
+            if let Err(e) = self.shadow.cancel_order(&m.shadow_id).await {
+                warn!(order_id = %id, error = %e, "shadow cancel failed");
+            }
+        }
+        Ok(())
+    }
+
+    async fn cancel_all(&self) -> Result<()> {
+        self.primary.cancel_all().await?;
+
+        self.state.lock().await.mirrored.clear();
+        if let Err(e) = self.shadow.cancel_all().await {
+            warn!(error = %e, "shadow cancel_all failed");
+        }
+        Ok(())
+    }
+
+    async fn cancel_token(&self, token_id: &str) -> Result<()> {
+        self.primary.cancel_token(token_id).await?;
+
+        self.state
+            .lock()
+            .await
+            .mirrored
+            .retain(|_, m| m.token_id != token_id);
+        if let Err(e) = self.shadow.cancel_token(token_id).await {
+            warn!(token = %token_id, error = %e, "shadow cancel_token failed");
+        }
+        Ok(())
+    }
+
+    async fn cancel_strategy(&self, token_id: &str, strategy_id: &str) -> Result<()> {
+        self.primary.cancel_strategy(token_id, strategy_id).await?;
+
+        self.state
+            .lock()
+            .await
+            .mirrored
+            .retain(|_, m| m.token_id != token_id || m.strategy_id != strategy_id);
+        if let Err(e) = self.shadow.cancel_strategy(token_id, strategy_id).await {
+            warn!(token = %token_id, error = %e, "shadow cancel_strategy failed");
+        }
+        Ok(())
+    }
+
+    async fn open_orders(&self) -> Result<Vec<OpenOrder>> {
+        let orders = self.primary.open_orders().await?;
+        self.detect_divergences(&orders).await;
+        Ok(orders)
+    }
+
+    async fn heartbeat(&self) -> Result<()> {
+        self.primary.heartbeat().await
+    }
+
+    async fn fetch_positions(&self) -> Result<Option<HashMap<String, Decimal>>> {
+        self.primary.fetch_positions().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paper::PaperExecutor;
+    use eutrader_core::MarketSnapshot;
+    use rust_decimal_macros::dec;
+
+    fn snapshot(best_bid: Decimal, best_ask: Decimal) -> MarketSnapshot {
+        let mid = (best_bid + best_ask) / dec!(2);
+        MarketSnapshot {
+            token_id: "tok1".into(),
+            best_bid,
+            best_ask,
+            midpoint: mid,
+            micro_price: mid,
+            spread: best_ask - best_bid,
+            bid_size: dec!(100),
+            ask_size: dec!(100),
+            timestamp: Utc::now(),
+            book_status: Default::default(),
+        }
+    }
+
+    fn shadowed() -> ShadowExecutor<PaperExecutor, PaperExecutor> {
+        ShadowExecutor::new(PaperExecutor::new(), PaperExecutor::new())
+    }
+
+    #[tokio::test]
+    async fn mirrors_placements_and_cancels() {
+        let exec = shadowed();
+        let ack = exec
+            .place_order("tok1", Side::Buy, dec!(0.50), dec!(10))
+            .await
+            .unwrap();
+        exec.place_order("tok1", Side::Sell, dec!(0.55), dec!(10))
+            .await
+            .unwrap();
+        assert_eq!(exec.shadow().open_orders().await.unwrap().len(), 2);
+
+        exec.cancel_order(&ack.id).await.unwrap();
+        assert_eq!(exec.primary().open_orders().await.unwrap().len(), 1);
+        assert_eq!(exec.shadow().open_orders().await.unwrap().len(), 1);
+
+        exec.cancel_all().await.unwrap();
+        assert!(exec.shadow().open_orders().await.unwrap().is_empty());
+        assert!(exec.divergences().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn records_fill_only_in_shadow() {
+        let exec = shadowed();
+        let ack = exec
+            .place_order("tok1", Side::Buy, dec!(0.50), dec!(10))
+            .await
+            .unwrap();
+
+        // Only the shadow sees the market trade through our bid
+        let fills = exec
+            .shadow()
+            .check_fills(&snapshot(dec!(0.48), dec!(0.49)))
+            .await;
+        assert_eq!(fills.len(), 1);
+
+        let open = exec.open_orders().await.unwrap();
+        assert_eq!(open.len(), 1);
+
+        let divergences = exec.divergences().await;
+        assert_eq!(divergences.len(), 1);
+        assert_eq!(divergences[0].primary_id, ack.id);
+        assert_eq!(divergences[0].kind, DivergenceKind::FilledInShadowOnly);
+
+        // Reported once, not on every poll
+        exec.open_orders().await.unwrap();
+        assert_eq!(exec.divergences().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn mirrors_amends() {
+        let exec = shadowed();
+        exec.place_order("tok1", Side::Buy, dec!(0.50), dec!(10))
+            .await
+            .unwrap();
+
+        let old = exec.primary().open_orders().await.unwrap();
+        exec.replace_order(&old[0], dec!(0.51), dec!(5))
+            .await
+            .unwrap();
+        let shadow = exec.shadow().open_orders().await.unwrap();
+        assert_eq!(shadow.len(), 1);
+        assert_eq!((shadow[0].price, shadow[0].size), (dec!(0.51), dec!(5)));
+
+        // The amended order is still tracked: cancelling it reaches the shadow
+        let new = exec.primary().open_orders().await.unwrap();
+        exec.cancel_order(&new[0].id).await.unwrap();
+        assert!(exec.shadow().open_orders().await.unwrap().is_empty());
+        assert!(exec.divergences().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn mirrors_token_cancels() {
+        let exec = shadowed();
+        exec.place_order("tok1", Side::Buy, dec!(0.50), dec!(10))
+            .await
+            .unwrap();
+        exec.place_order("tok2", Side::Buy, dec!(0.50), dec!(10))
+            .await
+            .unwrap();
+
+        exec.cancel_token("tok1").await.unwrap();
+        let shadow = exec.shadow().open_orders().await.unwrap();
+        assert_eq!(shadow.len(), 1);
+        assert_eq!(shadow[0].token_id, "tok2");
+        assert_eq!(exec.open_orders().await.unwrap().len(), 1);
+        assert!(exec.divergences().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn mirrors_strategy_cancels() {
+        let exec = shadowed();
+        exec.place_strategy_order("a", "tok1", Side::Buy, dec!(0.50), dec!(10), false)
+            .await
+            .unwrap();
+        exec.place_strategy_order("b", "tok1", Side::Buy, dec!(0.49), dec!(10), false)
+            .await
+            .unwrap();
+
+        exec.cancel_strategy("tok1", "a").await.unwrap();
+        let shadow = exec.shadow().open_orders().await.unwrap();
+        assert_eq!(shadow.len(), 1);
+        assert_eq!(shadow[0].strategy_id, "b");
+        assert_eq!(exec.open_orders().await.unwrap().len(), 1);
+        assert!(exec.divergences().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn matching_fills_do_not_diverge() {
+        let exec = shadowed();
+        exec.place_order("tok1", Side::Buy, dec!(0.50), dec!(10))
+            .await
+            .unwrap();
+
+        let snap = snapshot(dec!(0.48), dec!(0.49));
+        exec.primary().check_fills(&snap).await;
+        exec.shadow().check_fills(&snap).await;
+
+        assert!(exec.open_orders().await.unwrap().is_empty());
+        assert!(exec.divergences().await.is_empty());
+    }
+}
@@ -0,0 +1,432 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+
+use eutrader_core::{Config, MarketSnapshot, MockClock};
+use eutrader_feed::PriceSimulator;
+use eutrader_strategy::{Quoter, RiskManager};
+
+use crate::manager::OrderManager;
+use crate::paper::PaperExecutor;
+
+/// Shape of the synthetic price path driving each simulated market.
+#[derive(Debug, Clone, Copy)]
+pub struct SimulationParams {
+    /// Snapshots generated per market.
+    pub ticks: usize,
+    /// Standard deviation of the per-tick log-odds move.
+    pub volatility: f64,
+    /// Mean per-tick log-odds move.
+    pub drift: f64,
+}
+
+impl Default for SimulationParams {
+    fn default() -> Self {
+        Self {
+            ticks: 1000,
+            volatility: 0.05,
+            drift: 0.0,
+        }
+    }
+}
+
+/// Outcome of a single simulated session.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationResult {
+    pub seed: u64,
+    /// Realized PnL net of fees across all markets
+    pub realized_pnl: Decimal,
+    /// Open positions marked at each market's final midpoint
+    pub unrealized_pnl: Decimal,
+    pub fills: u64,
+    pub killed: bool,
+}
+
+impl SimulationResult {
+    pub fn total_pnl(&self) -> Decimal {
+        self.realized_pnl + self.unrealized_pnl
+    }
+}
+
+/// Run the paper quoting loop for every configured market against synthetic
+/// prices derived from `seed`. No network access; the same seed and config
+/// always produce the same result.
+pub async fn simulate(config: &Config, params: &SimulationParams, seed: u64) -> SimulationResult {
+    let executor = PaperExecutor::new()
+        .with_fill_model(config.execution.fill_model)
+        .with_seed_positions(&config.seed_positions)
+        .without_trade_log();
+    let mut manager =
+        OrderManager::new(executor, Quoter::new(), RiskManager::new(), config.clone())
+            .with_size_jitter_seed(seed);
+
+    let start = Utc::now();
+    let mut simulators: Vec<PriceSimulator> = config
+        .markets
+        .iter()
+        .enumerate()
+        .map(|(i, m)| {
+            // Spread seeds apart so market i of seed s never replays market 0 of seed s + i
+            let market_seed = seed.wrapping_mul(1_000_003).wrapping_add(i as u64);
+            PriceSimulator::new(m.token_id.clone(), market_seed)
+                .with_volatility(params.volatility)
+                .with_drift(params.drift)
+                .with_start(start)
+        })
+        .collect();
+
+    let mut last_mids: HashMap<String, Decimal> = HashMap::new();
+    for _ in 0..params.ticks {
+        for sim in &mut simulators {
+            let Some(snapshot) = sim.next() else { continue };
+            manager.process_paper_snapshot(&snapshot).await;
+            last_mids.insert(snapshot.token_id.clone(), snapshot.midpoint);
+        }
+    }
+
+    let positions = manager.positions();
+    SimulationResult {
+        seed,
+        realized_pnl: positions.values().map(|p| p.realized_pnl).sum(),
+        unrealized_pnl: positions
+            .values()
+            .map(|p| p.unrealized_pnl(last_mids.get(&p.token_id).copied().unwrap_or(p.avg_entry)))
+            .sum(),
+        fills: positions.values().map(|p| p.fill_count).sum(),
+        killed: manager.is_killed(),
+    }
+}
+
+/// Outcome of replaying recorded snapshots through one config.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayResult {
+    /// Realized PnL net of fees across all markets
+    pub realized_pnl: Decimal,
+    pub fills: u64,
+    /// Largest absolute position held in any market at any point
+    pub max_inventory: Decimal,
+}
+
+/// Two configs replayed over the same snapshots.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayComparison {
+    pub baseline: ReplayResult,
+    pub candidate: ReplayResult,
+}
+
+impl fmt::Display for ReplayComparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (a, b) = (&self.baseline, &self.candidate);
+        writeln!(
+            f,
+            "{:<20} {:>12} {:>12} {:>12}",
+            "", "baseline", "candidate", "delta"
+        )?;
+        writeln!(f, "{}", "-".repeat(59))?;
+        writeln!(
+            f,
+            "{:<20} {:>12.4} {:>12.4} {:>12.4}",
+            "Realized PnL ($)",
+            a.realized_pnl,
+            b.realized_pnl,
+            b.realized_pnl - a.realized_pnl
+        )?;
+        writeln!(
+            f,
+            "{:<20} {:>12} {:>12} {:>12}",
+            "Fills",
+            a.fills,
+            b.fills,
+            b.fills as i64 - a.fills as i64
+        )?;
+        writeln!(
+            f,
+            "{:<20} {:>12.2} {:>12.2} {:>12.2}",
+            "Max inventory",
+            a.max_inventory,
+            b.max_inventory,
+            b.max_inventory - a.max_inventory
+        )
+    }
+}
+
+/// A paper session driven by recorded snapshots, with time taken from them.
+struct Replayer {
+    manager: OrderManager<PaperExecutor>,
+    clock: MockClock,
+    max_inventory: Decimal,
+}
+
+impl Replayer {
+    fn new(config: &Config, start: DateTime<Utc>) -> Self {
+        let clock = MockClock::new(start);
+        let executor = PaperExecutor::new()
+            .with_fill_model(config.execution.fill_model)
+            .with_seed_positions(&config.seed_positions)
+            .with_clock(Arc::new(clock.clone()))
+            .without_trade_log();
+        let manager =
+            OrderManager::new(executor, Quoter::new(), RiskManager::new(), config.clone())
+                .with_clock(Arc::new(clock.clone()))
+                .with_size_jitter_seed(0);
+        Self {
+            manager,
+            clock,
+            max_inventory: Decimal::ZERO,
+        }
+    }
+
+    async fn step(&mut self, snapshot: &MarketSnapshot) {
+        self.clock.set(snapshot.timestamp);
+        self.manager.process_paper_snapshot(snapshot).await;
+        let held = self
+            .manager
+            .positions()
+            .values()
+            .map(|p| p.net_position.abs());
+        self.max_inventory = held.fold(self.max_inventory, Decimal::max);
+    }
+
+    fn result(&self) -> ReplayResult {
+        let positions = self.manager.positions();
+        ReplayResult {
+            realized_pnl: positions.values().map(|p| p.realized_pnl).sum(),
+            fills: positions.values().map(|p| p.fill_count).sum(),
+            max_inventory: self.max_inventory,
+        }
+    }
+}
+
+/// Replay `snapshots` through `baseline` and `candidate` side by side, each
+/// with its own paper executor, so both see exactly the same market.
+pub async fn compare_replay(
+    baseline: &Config,
+    candidate: &Config,
+    snapshots: &[MarketSnapshot],
+) -> ReplayComparison {
+    let start = snapshots.first().map_or_else(Utc::now, |s| s.timestamp);
+    let mut a = Replayer::new(baseline, start);
+    let mut b = Replayer::new(candidate, start);
+    for snapshot in snapshots {
+        a.step(snapshot).await;
+        b.step(snapshot).await;
+    }
+    ReplayComparison {
+        baseline: a.result(),
+        candidate: b.result(),
+    }
+}
+
+/// Read recorded snapshots, one JSON `MarketSnapshot` per line.
+pub fn read_snapshots(path: &Path) -> eutrader_core::Result<Vec<MarketSnapshot>> {
+    let file = std::fs::File::open(path)?;
+    let mut snapshots = Vec::new();
+    for (i, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let snapshot = serde_json::from_str(&line).map_err(|e| {
+            eutrader_core::Error::FeedParse(format!("{} line {}: {e}", path.display(), i + 1))
+        })?;
+        snapshots.push(snapshot);
+    }
+    Ok(snapshots)
+}
+
+/// Summary statistics of total PnL over many simulated sessions.
+#[derive(Debug, Clone)]
+pub struct PnlDistribution {
+    pub runs: usize,
+    pub mean: f64,
+    pub std_dev: f64,
+    pub min: f64,
+    pub p5: f64,
+    pub median: f64,
+    pub p95: f64,
+    pub max: f64,
+    /// Fraction of runs that ended with positive total PnL
+    pub profitable_fraction: f64,
+    /// Runs in which the kill switch tripped
+    pub killed_runs: usize,
+}
+
+impl PnlDistribution {
+    /// Summarise `results`, or `None` if there are none.
+    pub fn from_results(results: &[SimulationResult]) -> Option<Self> {
+        if results.is_empty() {
+            return None;
+        }
+        let mut pnls: Vec<f64> = results
+            .iter()
+            .map(|r| r.total_pnl().to_f64().unwrap_or(0.0))
+            .collect();
+        pnls.sort_by(|a, b| a.total_cmp(b));
+
+        let n = pnls.len() as f64;
+        let mean = pnls.iter().sum::<f64>() / n;
+        let variance = pnls.iter().map(|p| (p - mean).powi(2)).sum::<f64>() / n;
+        // Nearest-rank percentile on the sorted sample
+        let percentile = |q: f64| pnls[((q * n).ceil() as usize).clamp(1, pnls.len()) - 1];
+
+        Some(Self {
+            runs: pnls.len(),
+            mean,
+            std_dev: variance.sqrt(),
+            min: pnls[0],
+            p5: percentile(0.05),
+            median: percentile(0.5),
+            p95: percentile(0.95),
+            max: pnls[pnls.len() - 1],
+            profitable_fraction: pnls.iter().filter(|p| **p > 0.0).count() as f64 / n,
+            killed_runs: results.iter().filter(|r| r.killed).count(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eutrader_core::{MarketConfig, Mode, RiskConfig};
+    use rust_decimal_macros::dec;
+
+    fn make_config() -> Config {
+        Config {
+            mode: Mode::Paper,
+            risk: RiskConfig {
+                max_position_per_market: dec!(100),
+                max_total_exposure: dec!(500),
+                max_unrealized_loss: dec!(50),
+                quote_refresh_interval_ms: 1000,
+                quote_jitter_ms: 0,
+                max_snapshot_age_ms: None,
+                flatten_on_shutdown: false,
+                flatten_aggression: Default::default(),
+                max_open_orders_per_market: None,
+                warmup_ticks: 0,
+                market_status_interval_ms: None,
+                unwind_cycles: None,
+            },
+            auto_discover: None,
+            markets: vec![MarketConfig {
+                name: "sim".into(),
+                token_id: "sim-0".into(),
+                spread_bps: 400,
+                size: dec!(10),
+                max_inventory: dec!(50),
+                skew_factor: dec!(0.001),
+                ..Default::default()
+            }],
+            fee_bps: 0,
+            midpoint_mode: Default::default(),
+            min_level_size: Decimal::ZERO,
+            feed_startup_timeout_ms: 30_000,
+            last_trade_max_age_ms: 60_000,
+            execution: Default::default(),
+            adverse_selection: None,
+            pnl_rounding: Default::default(),
+            heartbeat_every_cycles: None,
+            metrics: Default::default(),
+            seed_positions: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn fixed_seed_is_reproducible() {
+        let config = make_config();
+        let params = SimulationParams {
+            ticks: 300,
+            volatility: 0.05,
+            drift: 0.0,
+        };
+
+        let first = simulate(&config, &params, 42).await;
+        let second = simulate(&config, &params, 42).await;
+        assert_eq!(first, second);
+        assert!(
+            first.fills > 0,
+            "expected the walk to trade through some quotes"
+        );
+
+        let other = simulate(&config, &params, 43).await;
+        assert_ne!(first, other);
+    }
+
+    #[tokio::test]
+    async fn replay_compares_two_configs_on_the_same_stream() {
+        let snapshots: Vec<MarketSnapshot> = PriceSimulator::new("sim-0", 7)
+            .with_volatility(0.05)
+            .take(300)
+            .collect();
+        let path =
+            std::env::temp_dir().join(format!("eutrader_replay_{}.jsonl", std::process::id()));
+        let lines: Vec<String> = snapshots
+            .iter()
+            .map(|s| serde_json::to_string(s).unwrap())
+            .collect();
+        std::fs::write(&path, lines.join("\n")).unwrap();
+        let snapshots = read_snapshots(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(snapshots.len(), 300);
+
+        let baseline = make_config();
+        let mut candidate = make_config();
+        candidate.markets[0].spread_bps = 200;
+        let comparison = compare_replay(&baseline, &candidate, &snapshots).await;
+
+        // Identical configs see identical sessions; a tighter spread trades more
+        assert_eq!(
+            compare_replay(&baseline, &baseline, &snapshots)
+                .await
+                .candidate,
+            comparison.baseline
+        );
+        let mut jittered = make_config();
+        jittered.markets[0].size_jitter_pct = dec!(0.2);
+        let jitter = compare_replay(&jittered, &jittered, &snapshots).await;
+        assert_eq!(jitter.baseline, jitter.candidate);
+        assert!(comparison.baseline.fills > 0);
+        assert!(comparison.candidate.fills > comparison.baseline.fills);
+        assert!(comparison.candidate.max_inventory > Decimal::ZERO);
+
+        let table = comparison.to_string();
+        for row in ["Realized PnL ($)", "Fills", "Max inventory"] {
+            assert!(table.contains(row), "{table}");
+        }
+        let fill_delta = comparison.candidate.fills - comparison.baseline.fills;
+        assert!(table
+            .lines()
+            .any(|l| l.starts_with("Fills") && l.ends_with(&fill_delta.to_string())));
+    }
+
+    #[test]
+    fn summarises_pnl_distribution() {
+        let results: Vec<SimulationResult> = [-2, 1, 3, 4]
+            .into_iter()
+            .enumerate()
+            .map(|(i, pnl)| SimulationResult {
+                seed: i as u64,
+                realized_pnl: Decimal::from(pnl),
+                unrealized_pnl: Decimal::ZERO,
+                fills: 1,
+                killed: pnl < 0,
+            })
+            .collect();
+
+        let dist = PnlDistribution::from_results(&results).unwrap();
+        assert_eq!(dist.runs, 4);
+        assert_eq!(dist.mean, 1.5);
+        assert_eq!(dist.min, -2.0);
+        assert_eq!(dist.median, 1.0);
+        assert_eq!(dist.max, 4.0);
+        assert_eq!(dist.profitable_fraction, 0.75);
+        assert_eq!(dist.killed_runs, 1);
+        assert!(PnlDistribution::from_results(&[]).is_none());
+    }
+}
@@ -0,0 +1,325 @@
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tracing::{debug, info, warn};
+
+use eutrader_core::dashboard::{MarketRow, SharedDashboard};
+
+use crate::metrics::PrometheusSink;
+
+/// Default staleness threshold for `/health` when none is configured.
+pub const DEFAULT_HEALTH_MAX_AGE_MS: u64 = 30_000;
+
+/// Largest request head we are willing to read.
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+/// How long a client gets to send its request head by default.
+pub const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// JSON body served at `/status`.
+#[derive(Debug, Serialize)]
+pub struct StatusReport {
+    pub mode: String,
+    pub uptime_secs: i64,
+    pub total_realized_pnl: Decimal,
+    pub total_unrealized_pnl: Decimal,
+    pub total_fills: u64,
+    pub markets: Vec<MarketRow>,
+}
+
+/// Minimal HTTP server exposing `/health` and `/status` for supervisors.
+///
+/// `/health` returns 200 while the feed has produced a snapshot within
+/// `max_age_ms`, 503 otherwise. `/status` returns a JSON `StatusReport`
+/// built from the shared dashboard. With a Prometheus sink attached,
+/// `/metrics` serves its series for scraping.
+pub struct StatusServer {
+    dashboard: SharedDashboard,
+    max_age_ms: u64,
+    metrics: Option<Arc<PrometheusSink>>,
+    read_timeout: std::time::Duration,
+}
+
+impl StatusServer {
+    pub fn new(dashboard: SharedDashboard, max_age_ms: u64) -> Self {
+        Self {
+            dashboard,
+            max_age_ms,
+            metrics: None,
+            read_timeout: DEFAULT_READ_TIMEOUT,
+        }
+    }
+
+    /// Drop clients that have not sent their request within `timeout`
+    /// (5 s by default).
+    pub fn with_read_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.read_timeout = timeout;
+        self
+    }
+
+    /// Serve `metrics` at `/metrics`.
+    pub fn with_metrics(mut self, metrics: Arc<PrometheusSink>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Accept connections on `listener` until the task is dropped, serving
+    /// each on a task of its own so a slow client holds up no one else.
+    pub async fn serve(self, listener: TcpListener) {
+        if let Ok(addr) = listener.local_addr() {
+            info!(%addr, "status server listening");
+        }
+        let server = Arc::new(self);
+        loop {
+            match listener.accept().await {
+                Ok((stream, _)) => {
+                    let server = server.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = server.handle(stream).await {
+                            debug!(error = %e, "status request failed");
+                        }
+                    });
+                }
+                Err(e) => warn!(error = %e, "status server accept failed"),
+            }
+        }
+    }
+
+    async fn handle(&self, mut stream: TcpStream) -> std::io::Result<()> {
+        let buf = tokio::time::timeout(self.read_timeout, read_head(&mut stream))
+            .await
+            .map_err(|_| {
+                std::io::Error::new(std::io::ErrorKind::TimedOut, "request head timed out")
+            })??;
+
+        let request = String::from_utf8_lossy(&buf);
+        let mut parts = request
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .split_whitespace();
+        let (method, path) = (
+            parts.next().unwrap_or_default(),
+            parts.next().unwrap_or_default(),
+        );
+
+        let scrape = self
+            .metrics
+            .as_ref()
+            .filter(|_| method == "GET" && path == "/metrics");
+        let (status, body) = match (method, path) {
+            ("GET", "/health") => self.health(),
+            ("GET", "/status") => self.status(),
+            _ => match scrape {
+                Some(metrics) => ("200 OK", metrics.render()),
+                None => ("404 Not Found", r#"{"error":"not found"}"#.to_string()),
+            },
+        };
+        let content_type = if scrape.is_some() {
+            "text/plain; version=0.0.4"
+        } else {
+            "application/json"
+        };
+
+        let response = format!(
+            "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        stream.write_all(response.as_bytes()).await?;
+        stream.shutdown().await
+    }
+
+    fn health(&self) -> (&'static str, String) {
+        let (last, now) = match self.dashboard.read() {
+            Ok(state) => (state.last_snapshot_at(), state.clock.now()),
+            Err(_) => (None, Utc::now()),
+        };
+        let age_ms = last.map(|t| (now - t).num_milliseconds());
+        let healthy =
+            matches!(last, Some(t) if now - t <= Duration::milliseconds(self.max_age_ms as i64));
+
+        let body = serde_json::json!({
+            "healthy": healthy,
+            "last_snapshot_age_ms": age_ms,
+            "max_age_ms": self.max_age_ms,
+        })
+        .to_string();
+        let status = if healthy {
+            "200 OK"
+        } else {
+            "503 Service Unavailable"
+        };
+        (status, body)
+    }
+
+    fn status(&self) -> (&'static str, String) {
+        let report = match self.dashboard.read() {
+            Ok(state) => {
+                let mut markets: Vec<MarketRow> = state.markets.values().cloned().collect();
+                markets.sort_by(|a, b| a.name.cmp(&b.name));
+                StatusReport {
+                    mode: state.mode.clone(),
+                    uptime_secs: state.uptime().num_seconds(),
+                    total_realized_pnl: state.total_realized_pnl,
+                    total_unrealized_pnl: state.total_unrealized_pnl,
+                    total_fills: state.total_fills,
+                    markets,
+                }
+            }
+            Err(_) => {
+                return (
+                    "500 Internal Server Error",
+                    r#"{"error":"dashboard unavailable"}"#.to_string(),
+                )
+            }
+        };
+
+        match serde_json::to_string(&report) {
+            Ok(body) => ("200 OK", body),
+            Err(e) => (
+                "500 Internal Server Error",
+                serde_json::json!({ "error": e.to_string() }).to_string(),
+            ),
+        }
+    }
+}
+
+/// Read up to the end of the request head, or `MAX_REQUEST_BYTES`.
+async fn read_head(stream: &mut TcpStream) -> std::io::Result<Vec<u8>> {
+    let mut buf = Vec::with_capacity(1024);
+    let mut chunk = [0u8; 1024];
+    while !buf.windows(4).any(|w| w == b"\r\n\r\n") && buf.len() < MAX_REQUEST_BYTES {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            break;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+    Ok(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::MetricsSink;
+    use eutrader_core::dashboard::new_shared_dashboard;
+    use rust_decimal_macros::dec;
+
+    async fn get(addr: std::net::SocketAddr, path: &str) -> String {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(format!("GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n").as_bytes())
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    fn market_row(token_id: &str) -> MarketRow {
+        MarketRow {
+            name: "Test".into(),
+            token_id: token_id.into(),
+            midpoint: dec!(0.50),
+            our_bid: dec!(0.48),
+            our_ask: dec!(0.52),
+            spread: dec!(0.04),
+            inventory: dec!(10),
+            realized_pnl: dec!(1.5),
+            unrealized_pnl: Decimal::ZERO,
+            fill_count: 3,
+            last_update: Utc::now(),
+            quote_status: None,
+            open_orders: 2,
+            tick_size: dec!(0.01),
+            spread_pnl: Decimal::ZERO,
+            inventory_pnl: Decimal::ZERO,
+            bid_fill_prob: None,
+            ask_fill_prob: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn health_turns_ok_after_snapshot() {
+        let dashboard = new_shared_dashboard("Paper");
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(StatusServer::new(dashboard.clone(), 5_000).serve(listener));
+
+        let response = get(addr, "/health").await;
+        assert!(response.starts_with("HTTP/1.1 503"), "{response}");
+
+        dashboard.write().unwrap().update_market(market_row("tok1"));
+
+        let response = get(addr, "/health").await;
+        assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+    }
+
+    #[tokio::test]
+    async fn status_reports_markets_as_json() {
+        let dashboard = new_shared_dashboard("Paper");
+        dashboard.write().unwrap().update_market(market_row("tok1"));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(StatusServer::new(dashboard, 5_000).serve(listener));
+
+        let response = get(addr, "/status").await;
+        let body = response.split("\r\n\r\n").nth(1).unwrap();
+        let json: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(json["mode"], "Paper");
+        assert_eq!(json["markets"][0]["token_id"], "tok1");
+        assert_eq!(json["markets"][0]["open_orders"], 2);
+
+        let response = get(addr, "/nope").await;
+        assert!(response.starts_with("HTTP/1.1 404"));
+        // No Prometheus sink attached
+        let response = get(addr, "/metrics").await;
+        assert!(response.starts_with("HTTP/1.1 404"));
+    }
+
+    #[tokio::test]
+    async fn silent_client_neither_blocks_others_nor_lingers() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = StatusServer::new(new_shared_dashboard("Paper"), 5_000)
+            .with_read_timeout(std::time::Duration::from_millis(100));
+        tokio::spawn(server.serve(listener));
+
+        // Connects but never sends a request
+        let mut silent = TcpStream::connect(addr).await.unwrap();
+        let response =
+            tokio::time::timeout(std::time::Duration::from_secs(2), get(addr, "/health"))
+                .await
+                .expect("request stuck behind a silent client");
+        assert!(response.starts_with("HTTP/1.1 503"), "{response}");
+
+        // The silent connection is closed once its read times out
+        let mut rest = Vec::new();
+        let read = tokio::time::timeout(
+            std::time::Duration::from_secs(2),
+            silent.read_to_end(&mut rest),
+        )
+        .await
+        .expect("silent client was never dropped");
+        assert_eq!(read.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn metrics_are_served_for_scraping() {
+        let sink = Arc::new(PrometheusSink::new());
+        sink.count("fills", 2, &[("market", "Rain")]);
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = StatusServer::new(new_shared_dashboard("Paper"), 5_000).with_metrics(sink);
+        tokio::spawn(server.serve(listener));
+
+        let response = get(addr, "/metrics").await;
+        assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+        assert!(response.contains("Content-Type: text/plain"));
+        assert!(response.ends_with("eutrader_fills{market=\"Rain\"} 2\n"));
+    }
+}
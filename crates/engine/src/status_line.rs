@@ -0,0 +1,142 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use tracing::info;
+
+use eutrader_core::dashboard::{DashboardState, SharedDashboard};
+
+/// Tracing target of the status line, so log filters can keep it while
+/// silencing the per-cycle engine logs (e.g. `warn,status_line=info`).
+pub const STATUS_LINE_TARGET: &str = "status_line";
+
+/// Throttled one-line summary of the engine for headless deployments.
+///
+/// Every `interval` it reads the shared dashboard and emits a single line
+/// with total PnL, fills, feed health and per-market inventory, however many
+/// snapshots were processed in between.
+pub struct StatusLine {
+    dashboard: SharedDashboard,
+    interval: Duration,
+    max_age_ms: u64,
+}
+
+impl StatusLine {
+    /// Summarize `dashboard` every `interval`, calling the feed stale once
+    /// its newest snapshot is older than `max_age_ms`.
+    pub fn new(dashboard: SharedDashboard, interval: Duration, max_age_ms: u64) -> Self {
+        Self {
+            dashboard,
+            interval,
+            max_age_ms,
+        }
+    }
+
+    /// Log a status line every interval until the task is dropped.
+    pub async fn run(self) {
+        self.run_with(|line| info!(target: STATUS_LINE_TARGET, "{line}"))
+            .await
+    }
+
+    /// Pass each status line to `emit` instead of logging it.
+    pub async fn run_with(self, mut emit: impl FnMut(String)) {
+        let start = tokio::time::Instant::now() + self.interval;
+        let mut ticker = tokio::time::interval_at(start, self.interval);
+        loop {
+            ticker.tick().await;
+            let Ok(state) = self.dashboard.read() else {
+                continue;
+            };
+            emit(self.render(&state, state.clock.now()));
+        }
+    }
+
+    /// e.g. `pnl=1.5000 upnl=-0.2000 fills=3 feed=ok(120ms) inv=[Rain:10 Snow:-5]`
+    pub fn render(&self, state: &DashboardState, now: DateTime<Utc>) -> String {
+        let feed = match state.last_snapshot_at() {
+            Some(at) => {
+                let age_ms = (now - at).num_milliseconds();
+                let health = if age_ms <= self.max_age_ms as i64 {
+                    "ok"
+                } else {
+                    "stale"
+                };
+                format!("{health}({age_ms}ms)")
+            }
+            None => "waiting".to_string(),
+        };
+        let mut markets: Vec<_> = state.markets.values().collect();
+        markets.sort_by(|a, b| a.name.cmp(&b.name));
+        let inventory: Vec<String> = markets
+            .iter()
+            .map(|m| format!("{}:{}", m.name.replace(' ', "_"), m.inventory.normalize()))
+            .collect();
+
+        format!(
+            "pnl={} upnl={} fills={} feed={} inv=[{}]",
+            state.format_pnl(state.total_realized_pnl),
+            state.format_pnl(state.total_unrealized_pnl),
+            state.total_fills,
+            feed,
+            inventory.join(" "),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eutrader_core::dashboard::{new_shared_dashboard, MarketRow};
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    fn row(name: &str, inventory: Decimal) -> MarketRow {
+        MarketRow {
+            name: name.into(),
+            token_id: name.to_lowercase(),
+            midpoint: dec!(0.50),
+            our_bid: dec!(0.48),
+            our_ask: dec!(0.52),
+            spread: dec!(0.04),
+            inventory,
+            realized_pnl: dec!(0.75),
+            unrealized_pnl: Decimal::ZERO,
+            fill_count: 2,
+            last_update: Utc::now(),
+            quote_status: None,
+            open_orders: 2,
+            tick_size: dec!(0.01),
+            spread_pnl: Decimal::ZERO,
+            inventory_pnl: Decimal::ZERO,
+            bid_fill_prob: None,
+            ask_fill_prob: None,
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn emits_on_cadence_not_per_update() {
+        let dashboard = new_shared_dashboard("Paper");
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let status = StatusLine::new(dashboard.clone(), Duration::from_secs(10), 30_000);
+        tokio::spawn(status.run_with(move |line| {
+            let _ = tx.send(line);
+        }));
+
+        // 35 seconds of market updates every 100ms
+        for i in 0..350 {
+            if let Ok(mut state) = dashboard.write() {
+                state.update_market(row("Rain", Decimal::from(i % 7)));
+                state.update_market(row("Snow", dec!(-5)));
+                state.refresh_totals();
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+
+        let mut lines = Vec::new();
+        while let Ok(line) = rx.try_recv() {
+            lines.push(line);
+        }
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("pnl=1.5000 upnl=0.0000 fills=4 feed=ok("));
+        assert!(lines[0].ends_with("Snow:-5]"));
+    }
+}
@@ -0,0 +1,57 @@
+//! Minimal HTTP stub for exercising the REST clients against a local server.
+//! The integration tests pull this file in by path.
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// Serve `responder(path_and_query)` as a JSON body to every request on a
+/// random local port, or a 404 where it returns `None`. Returns the base URL
+/// (`http://127.0.0.1:<port>`).
+pub async fn serve_json<F>(responder: F) -> String
+where
+    F: Fn(&str) -> Option<String> + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let responder = std::sync::Arc::new(responder);
+
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let responder = responder.clone();
+            tokio::spawn(async move {
+                let mut buf = vec![0u8; 4096];
+                let n = stream.read(&mut buf).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&buf[..n]);
+                let target = request.split_whitespace().nth(1).unwrap_or("/");
+                let response = match responder(target) {
+                    Some(body) => format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len()
+                    ),
+                    None => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                        .to_string(),
+                };
+                let _ = stream.write_all(response.as_bytes()).await;
+            });
+        }
+    });
+
+    format!("http://{addr}")
+}
+
+/// Accept connections on a random local port but never respond. Returns the
+/// base URL.
+#[allow(dead_code)]
+pub async fn serve_silent() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        let mut held = Vec::new();
+        while let Ok((stream, _)) = listener.accept().await {
+            held.push(stream);
+        }
+    });
+
+    format!("http://{addr}")
+}
@@ -0,0 +1,156 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use eutrader_core::{Fill, TradeLogFormat};
+
+/// Column order of CSV trade logs.
+const CSV_HEADER: &str =
+    "timestamp,token_id,strategy_id,side,price,size,is_simulated,mid_at_fill,edge";
+
+/// Destination for the paper executor's fill records. Failures are logged
+/// and dropped, as the log is for post-session analysis only.
+pub trait TradeLogger: Send + Sync {
+    /// Record one simulated fill.
+    fn log_fill(&self, fill: &Fill);
+}
+
+/// A trade logger shared between the executor and whoever reads it.
+pub type SharedTradeLogger = Arc<dyn TradeLogger>;
+
+/// Appends fills to a file in the given format.
+#[derive(Debug, Clone)]
+pub struct FileTradeLogger {
+    path: PathBuf,
+    format: TradeLogFormat,
+}
+
+impl FileTradeLogger {
+    pub fn new(path: impl Into<PathBuf>, format: TradeLogFormat) -> Self {
+        Self {
+            path: path.into(),
+            format,
+        }
+    }
+
+    /// Log to the configured path, or `paper_trades.<format>` if unset.
+    pub fn from_config(path: Option<&Path>, format: TradeLogFormat) -> Self {
+        match path {
+            Some(path) => Self::new(path, format),
+            None => Self::new(format.default_path(), format),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl TradeLogger for FileTradeLogger {
+    fn log_fill(&self, fill: &Fill) {
+        let line = match self.format {
+            TradeLogFormat::Jsonl => match serde_json::to_string(fill) {
+                Ok(json) => json,
+                Err(e) => {
+                    tracing::warn!(error = %e, "failed to serialize fill for log");
+                    return;
+                }
+            },
+            TradeLogFormat::Csv => csv_row(fill),
+        };
+
+        let result = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut f| {
+                // A fresh CSV log starts with its header
+                if self.format == TradeLogFormat::Csv && f.metadata()?.len() == 0 {
+                    writeln!(f, "{CSV_HEADER}")?;
+                }
+                writeln!(f, "{}", line)
+            });
+
+        if let Err(e) = result {
+            tracing::warn!(error = %e, path = %self.path.display(), "failed to write paper trade log");
+        }
+    }
+}
+
+/// Keeps fills in memory, e.g. to inspect them in tests.
+#[derive(Debug, Default)]
+pub struct MemoryTradeLogger {
+    fills: Mutex<Vec<Fill>>,
+}
+
+impl MemoryTradeLogger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every fill logged so far, oldest first.
+    pub fn fills(&self) -> Vec<Fill> {
+        self.fills.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+impl TradeLogger for MemoryTradeLogger {
+    fn log_fill(&self, fill: &Fill) {
+        self.fills
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(fill.clone());
+    }
+}
+
+/// One CSV line for `fill`, in `CSV_HEADER` column order.
+fn csv_row(fill: &Fill) -> String {
+    format!(
+        "{},{},{},{},{},{},{},{},{}",
+        fill.timestamp.to_rfc3339(),
+        csv_field(&fill.token_id),
+        csv_field(&fill.strategy_id),
+        fill.side,
+        fill.price,
+        fill.size,
+        fill.is_simulated,
+        fill.mid_at_fill,
+        fill.edge,
+    )
+}
+
+/// Quote `value` if it would otherwise break the row.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use eutrader_core::Side;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn csv_rows_follow_the_header() {
+        let fill = Fill {
+            token_id: "tok,1".into(),
+            strategy_id: String::new(),
+            side: Side::Sell,
+            price: dec!(0.52),
+            size: dec!(10),
+            timestamp: chrono::Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap(),
+            is_simulated: true,
+            mid_at_fill: dec!(0.51),
+            edge: dec!(0.01),
+        };
+        assert_eq!(
+            csv_row(&fill),
+            "2024-01-02T03:04:05+00:00,\"tok,1\",,SELL,0.52,10,true,0.51,0.01"
+        );
+    }
+}
@@ -0,0 +1,113 @@
+//! End-to-end paper trading against a mock CLOB: `FeedManager` polls a local
+//! HTTP server for order books, and `OrderManager::run_paper` quotes and fills
+//! against them through the `PaperExecutor`.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use eutrader_core::Config;
+use eutrader_engine::{OrderManager, PaperExecutor};
+use eutrader_feed::FeedManager;
+use eutrader_strategy::{Quoter, RiskManager};
+use futures::StreamExt;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+#[path = "../src/test_support.rs"]
+mod test_support;
+
+const TOKEN: &str = "tok_integration";
+const POLL_MS: u64 = 50;
+
+const CONFIG: &str = r#"
+mode = "paper"
+fee_bps = 0
+
+[risk]
+max_position_per_market = 100.0
+max_total_exposure = 500.0
+max_unrealized_loss = 50.0
+quote_refresh_interval_ms = 1000
+
+[[markets]]
+name = "Integration"
+token_id = "tok_integration"
+spread_bps = 400
+size = 10.0
+max_inventory = 50.0
+skew_factor = 0.0
+"#;
+
+/// 0.49 / 0.51 around a 0.50 mid, which we quote at 0.48 / 0.52.
+const BOOK_AT_50: &str = r#"{"market":"0xabc","asset_id":"tok_integration",
+    "bids":[{"price":"0.49","size":"100"}],"asks":[{"price":"0.51","size":"100"}]}"#;
+
+/// The ask drops to 0.47, through our 0.48 bid.
+const BOOK_AT_465: &str = r#"{"market":"0xabc","asset_id":"tok_integration",
+    "bids":[{"price":"0.46","size":"100"}],"asks":[{"price":"0.47","size":"100"}]}"#;
+
+/// Serve `books[i]` for the `i`-th group of `per_book` requests, then keep
+/// serving the last one. Returns the base URL.
+async fn serve_books(books: &'static [&'static str], per_book: usize) -> String {
+    let served = AtomicUsize::new(0);
+    test_support::serve_json(move |target| {
+        (target == format!("/book?token_id={TOKEN}")).then(|| {
+            let i = served.fetch_add(1, Ordering::SeqCst) / per_book;
+            books[i.min(books.len() - 1)].to_string()
+        })
+    })
+    .await
+}
+
+/// Run a paper session against `books` for `polls` snapshots and return the
+/// manager. The session ends with the feed, however long the polls take.
+async fn run_session(
+    books: &'static [&'static str],
+    per_book: usize,
+    polls: usize,
+) -> OrderManager<PaperExecutor> {
+    let base_url = serve_books(books, per_book).await;
+    let config = Config::from_toml_profile(CONFIG, None).unwrap();
+    let feed = FeedManager::with_interval(vec![TOKEN.to_string()], POLL_MS)
+        .with_clob_base_url(base_url)
+        .run()
+        .take(polls);
+
+    let trade_log = std::env::temp_dir().join(format!(
+        "eutrader_integration_{}_{}.jsonl",
+        std::process::id(),
+        books.len()
+    ));
+    let executor = PaperExecutor::new().with_trade_log_path(&trade_log);
+    let mut manager = OrderManager::new(executor, Quoter::new(), RiskManager::new(), config);
+    manager.run_paper(feed).await;
+    let _ = std::fs::remove_file(&trade_log);
+    manager
+}
+
+#[tokio::test]
+async fn steady_book_quotes_without_filling() {
+    let manager = run_session(&[BOOK_AT_50], 1, 3).await;
+
+    let report = manager.session_report();
+    assert_eq!(report.total_fills, 0);
+    assert_eq!(report.total_net_pnl, Decimal::ZERO);
+    let position = &manager.positions()[&TOKEN.into()];
+    assert_eq!(position.net_position, Decimal::ZERO);
+}
+
+#[tokio::test]
+async fn price_moving_through_resting_bid_fills_it() {
+    let manager = run_session(&[BOOK_AT_50, BOOK_AT_465], 3, 5).await;
+
+    // The bid rested at 0.48 until the ask traded through it; the re-centred
+    // quotes around 0.465 stay clear of the new book
+    let position = &manager.positions()[&TOKEN.into()];
+    assert_eq!(position.net_position, dec!(10));
+    assert_eq!(position.avg_entry, dec!(0.48));
+    assert_eq!(position.fill_count, 1);
+    assert_eq!(position.realized_pnl, Decimal::ZERO);
+    assert_eq!(position.unrealized_pnl(dec!(0.465)), dec!(-0.15));
+
+    let report = manager.session_report();
+    assert_eq!(report.total_fills, 1);
+}
@@ -1,12 +1,17 @@
 use chrono::Utc;
-use eutrader_core::{MarketSnapshot, Result};
+use eutrader_core::{BookStatus, MarketConfig, MarketSnapshot, MidpointMode, Result, SizeMode};
 use reqwest::Client;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::str::FromStr;
+use std::time::Duration;
 use tracing::instrument;
 
-const CLOB_BASE_URL: &str = "https://clob.polymarket.com";
+use crate::http::{self, DEFAULT_CONNECT_TIMEOUT, DEFAULT_REQUEST_TIMEOUT};
+
+/// Production CLOB REST endpoint.
+pub const CLOB_BASE_URL: &str = "https://clob.polymarket.com";
 
 /// A single price level (bid or ask) from the CLOB orderbook.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,32 +29,84 @@ pub struct OrderBookResponse {
     pub asks: Vec<PriceLevel>,
 }
 
+/// Order size limits the venue publishes for a market, read from the same
+/// `/book` response as the levels. Either may be absent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct MarketLimits {
+    #[serde(default)]
+    pub min_order_size: Option<Decimal>,
+    #[serde(default)]
+    pub max_order_size: Option<Decimal>,
+}
+
+impl MarketLimits {
+    /// Tighten `market`'s `min_order_size`/`max_order_size` to these limits,
+    /// warning when its configured share `size` is above the venue max.
+    pub fn apply(&self, market: &mut MarketConfig) {
+        if let Some(min) = self.min_order_size {
+            market.min_order_size = market.min_order_size.max(min);
+        }
+        if let Some(max) = self.max_order_size {
+            market.max_order_size = Some(market.max_order_size.map_or(max, |m| m.min(max)));
+            if market.size_mode == SizeMode::Shares && market.size > max {
+                tracing::warn!(
+                    market = %market.name,
+                    size = %market.size,
+                    venue_max = %max,
+                    "configured size exceeds the venue's max order size — orders will be clamped"
+                );
+            }
+        }
+    }
+}
+
 /// Client for the Polymarket CLOB REST API.
+#[derive(Clone)]
 pub struct BookClient {
     client: Client,
+    base_url: String,
 }
 
 impl BookClient {
     /// Create a new `BookClient` with a default reqwest client.
     pub fn new() -> Self {
+        Self::with_base_url(CLOB_BASE_URL)
+    }
+
+    /// Create a `BookClient` that talks to `base_url` instead of the
+    /// production CLOB (e.g. a staging host, proxy or mock server).
+    pub fn with_base_url(base_url: impl Into<String>) -> Self {
         Self {
-            client: Client::new(),
+            client: http::build_client(DEFAULT_REQUEST_TIMEOUT, DEFAULT_CONNECT_TIMEOUT),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
         }
     }
 
+    /// Override the request and connect timeouts.
+    pub fn with_timeouts(mut self, timeout: Duration, connect_timeout: Duration) -> Self {
+        self.client = http::build_client(timeout, connect_timeout);
+        self
+    }
+
+    /// The CLOB base URL requests are sent to.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
     /// Fetch the full orderbook for a given token.
     #[instrument(skip(self), name = "book_get_orderbook")]
     pub async fn get_orderbook(&self, token_id: &str) -> Result<OrderBookResponse> {
-        let url = format!("{CLOB_BASE_URL}/book?token_id={token_id}");
+        let url = format!("{}/book?token_id={token_id}", self.base_url);
         let book: OrderBookResponse = self
             .client
             .get(&url)
             .send()
-            .await?
-            .error_for_status()
-            .map_err(|e| eutrader_core::Error::Feed(format!("CLOB book HTTP error: {e}")))?
+            .await
+            .map_err(|e| http::map_error("CLOB book", e))
+            .and_then(|resp| http::check_status("CLOB book", resp))?
             .json()
-            .await?;
+            .await
+            .map_err(|e| http::map_error("CLOB book", e))?;
 
         tracing::debug!(
             token_id,
@@ -60,26 +117,66 @@ impl BookClient {
         Ok(book)
     }
 
+    /// Fetch the order size limits the venue publishes for a given token.
+    #[instrument(skip(self), name = "book_get_market_limits")]
+    pub async fn get_market_limits(&self, token_id: &str) -> Result<MarketLimits> {
+        let url = format!("{}/book?token_id={token_id}", self.base_url);
+        self.client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| http::map_error("CLOB limits", e))
+            .and_then(|resp| http::check_status("CLOB limits", resp))?
+            .json()
+            .await
+            .map_err(|e| http::map_error("CLOB limits", e))
+    }
+
     /// Fetch the midpoint price for a given token.
     #[instrument(skip(self), name = "book_get_midpoint")]
     pub async fn get_midpoint(&self, token_id: &str) -> Result<Decimal> {
-        let url = format!("{CLOB_BASE_URL}/midpoint?token_id={token_id}");
+        let url = format!("{}/midpoint?token_id={token_id}", self.base_url);
         let resp: serde_json::Value = self
             .client
             .get(&url)
             .send()
-            .await?
-            .error_for_status()
-            .map_err(|e| eutrader_core::Error::Feed(format!("CLOB midpoint HTTP error: {e}")))?
+            .await
+            .map_err(|e| http::map_error("CLOB midpoint", e))
+            .and_then(|resp| http::check_status("CLOB midpoint", resp))?
             .json()
-            .await?;
+            .await
+            .map_err(|e| http::map_error("CLOB midpoint", e))?;
 
-        let mid_str = resp["mid"]
-            .as_str()
-            .ok_or_else(|| eutrader_core::Error::Feed("missing 'mid' field in response".into()))?;
+        let mid_str = resp["mid"].as_str().ok_or_else(|| {
+            eutrader_core::Error::FeedParse("missing 'mid' field in response".into())
+        })?;
 
         Decimal::from_str(mid_str)
-            .map_err(|e| eutrader_core::Error::Feed(format!("invalid midpoint decimal: {e}")))
+            .map_err(|e| eutrader_core::Error::FeedParse(format!("invalid midpoint decimal: {e}")))
+    }
+
+    /// Fetch the price of the most recent trade for a given token.
+    #[instrument(skip(self), name = "book_get_last_trade_price")]
+    pub async fn get_last_trade_price(&self, token_id: &str) -> Result<Decimal> {
+        let url = format!("{}/last-trade-price?token_id={token_id}", self.base_url);
+        let resp: serde_json::Value = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| http::map_error("CLOB last trade", e))
+            .and_then(|resp| http::check_status("CLOB last trade", resp))?
+            .json()
+            .await
+            .map_err(|e| http::map_error("CLOB last trade", e))?;
+
+        let price_str = resp["price"].as_str().ok_or_else(|| {
+            eutrader_core::Error::FeedParse("missing 'price' field in response".into())
+        })?;
+
+        Decimal::from_str(price_str).map_err(|e| {
+            eutrader_core::Error::FeedParse(format!("invalid last trade decimal: {e}"))
+        })
     }
 }
 
@@ -91,38 +188,225 @@ impl Default for BookClient {
 
 /// Convert a raw `OrderBookResponse` into a core `MarketSnapshot`.
 ///
-/// Returns `None` if bids or asks are empty (cannot compute meaningful snapshot).
-pub fn to_snapshot(token_id: &str, book: &OrderBookResponse) -> Option<MarketSnapshot> {
-    let best_bid = book
-        .bids
-        .iter()
-        .filter_map(|l| Decimal::from_str(&l.price).ok())
-        .max()?;
+/// Empty, crossed and locked books still produce a snapshot, flagged by its
+/// `book_status`, so the engine can pull quotes rather than leave them resting.
+/// A side whose levels all have unparseable prices is a `FeedParse` error
+/// instead, so a format change is not mistaken for an empty book.
+pub fn to_snapshot(token_id: &str, book: &OrderBookResponse) -> Result<MarketSnapshot> {
+    to_snapshot_with_mode(token_id, book, MidpointMode::Simple, Decimal::ZERO)
+}
 
-    let best_ask = book
-        .asks
-        .iter()
-        .filter_map(|l| Decimal::from_str(&l.price).ok())
-        .min()?;
+/// Convert a raw `OrderBookResponse` into a `MarketSnapshot`, centering the
+/// midpoint according to `mode`.
+///
+/// Levels resting less than `min_level_size` are dust and skipped, so the
+/// best bid and ask are the best levels with real size behind them. A side
+/// with only dust counts as empty.
+///
+/// The micro-price is always computed and stored alongside the midpoint. A
+/// book missing either side has zero midpoint, micro-price and spread.
+pub fn to_snapshot_with_mode(
+    token_id: &str,
+    book: &OrderBookResponse,
+    mode: MidpointMode,
+    min_level_size: Decimal,
+) -> Result<MarketSnapshot> {
+    let best_bid = parse_levels(token_id, "bid", &book.bids)?
+        .into_iter()
+        .filter(|(_, size)| *size >= min_level_size)
+        .max_by(|a, b| a.0.cmp(&b.0));
+    let best_ask = parse_levels(token_id, "ask", &book.asks)?
+        .into_iter()
+        .filter(|(_, size)| *size >= min_level_size)
+        .min_by(|a, b| a.0.cmp(&b.0));
+    let (Some((best_bid, bid_size)), Some((best_ask, ask_size))) = (best_bid, best_ask) else {
+        tracing::warn!(token_id, "empty book side — flagging snapshot");
+        let (best_bid, bid_size) = best_bid.unwrap_or_default();
+        let (best_ask, ask_size) = best_ask.unwrap_or_default();
+        return Ok(MarketSnapshot {
+            token_id: token_id.to_string(),
+            best_bid,
+            best_ask,
+            midpoint: Decimal::ZERO,
+            micro_price: Decimal::ZERO,
+            spread: Decimal::ZERO,
+            bid_size,
+            ask_size,
+            timestamp: Utc::now(),
+            book_status: BookStatus::Empty,
+        });
+    };
 
-    if best_bid >= best_ask {
-        tracing::warn!(token_id, %best_bid, %best_ask, "crossed book — skipping snapshot");
-        return None;
+    let book_status = match best_bid.cmp(&best_ask) {
+        Ordering::Less => BookStatus::Normal,
+        Ordering::Equal => BookStatus::Locked,
+        Ordering::Greater => BookStatus::Crossed,
+    };
+    if book_status != BookStatus::Normal {
+        tracing::warn!(token_id, %best_bid, %best_ask, status = %book_status, "unquotable book");
     }
 
-    let midpoint = (best_bid + best_ask) / Decimal::from(2);
+    let simple_mid = (best_bid + best_ask) / Decimal::from(2);
+    let micro_price = micro_price(best_bid, bid_size, best_ask, ask_size).unwrap_or(simple_mid);
+    let midpoint = match mode {
+        MidpointMode::Simple => simple_mid,
+        MidpointMode::MicroPrice => micro_price,
+    };
     let spread = best_ask - best_bid;
 
-    Some(MarketSnapshot {
+    Ok(MarketSnapshot {
         token_id: token_id.to_string(),
         best_bid,
         best_ask,
         midpoint,
+        micro_price,
         spread,
+        bid_size,
+        ask_size,
         timestamp: Utc::now(),
+        book_status,
     })
 }
 
+/// A snapshot flagging `token_id`'s book as unreadable, published in place
+/// of one `to_snapshot` could not build so the engine pulls its quotes.
+pub fn invalid_snapshot(token_id: &str) -> MarketSnapshot {
+    MarketSnapshot {
+        token_id: token_id.to_string(),
+        best_bid: Decimal::ZERO,
+        best_ask: Decimal::ZERO,
+        midpoint: Decimal::ZERO,
+        micro_price: Decimal::ZERO,
+        spread: Decimal::ZERO,
+        bid_size: Decimal::ZERO,
+        ask_size: Decimal::ZERO,
+        timestamp: Utc::now(),
+        book_status: BookStatus::Invalid,
+    }
+}
+
+/// Number of levels in `book` whose price does not parse, which
+/// `to_snapshot` drops.
+pub fn unparseable_levels(book: &OrderBookResponse) -> usize {
+    book.bids
+        .iter()
+        .chain(&book.asks)
+        .filter(|l| Decimal::from_str(&l.price).is_err())
+        .count()
+}
+
+/// Derive the NO-outcome snapshot from a YES-outcome snapshot.
+///
+/// Assumes a strictly binary market where one YES share plus one NO share
+/// always redeem for 1.0, so buying NO at `p` is equivalent to selling YES at
+/// `1 - p`. Under that assumption `no_bid = 1 - yes_ask` and
+/// `no_ask = 1 - yes_bid`, sizes swap sides and the spread is unchanged.
+/// Do not use this for
+/// multi-outcome markets.
+pub fn synthetic_no_snapshot(yes: &MarketSnapshot, no_token_id: &str) -> MarketSnapshot {
+    let one = Decimal::ONE;
+    MarketSnapshot {
+        token_id: no_token_id.to_string(),
+        best_bid: one - yes.best_ask,
+        best_ask: one - yes.best_bid,
+        midpoint: one - yes.midpoint,
+        micro_price: one - yes.micro_price,
+        spread: yes.spread,
+        bid_size: yes.ask_size,
+        ask_size: yes.bid_size,
+        timestamp: yes.timestamp,
+        book_status: yes.book_status,
+    }
+}
+
+/// Derive the NO-outcome orderbook from a YES-outcome orderbook.
+///
+/// YES asks become NO bids and YES bids become NO asks, each mirrored to
+/// `1 - price` with sizes preserved. Same strictly-binary assumption as
+/// [`synthetic_no_snapshot`].
+pub fn synthetic_no_book(yes: &OrderBookResponse, no_token_id: &str) -> OrderBookResponse {
+    let mirror = |levels: &[PriceLevel]| -> Vec<PriceLevel> {
+        levels
+            .iter()
+            .filter_map(|l| {
+                let price = Decimal::from_str(&l.price).ok()?;
+                Some(PriceLevel {
+                    price: (Decimal::ONE - price).to_string(),
+                    size: l.size.clone(),
+                })
+            })
+            .collect()
+    };
+
+    OrderBookResponse {
+        market: yes.market.clone(),
+        asset_id: no_token_id.to_string(),
+        bids: mirror(&yes.asks),
+        asks: mirror(&yes.bids),
+    }
+}
+
+/// Parse one side's price levels into `(price, size)` pairs.
+///
+/// Levels with a malformed price are dropped and logged; a malformed size is
+/// treated as zero. A non-empty side where no price parses is a `FeedParse`
+/// error.
+fn parse_levels(
+    token_id: &str,
+    side: &str,
+    levels: &[PriceLevel],
+) -> Result<Vec<(Decimal, Decimal)>> {
+    let parsed: Vec<(Decimal, Decimal)> = levels
+        .iter()
+        .filter_map(|l| {
+            let price = Decimal::from_str(&l.price).ok()?;
+            let size = Decimal::from_str(&l.size).unwrap_or(Decimal::ZERO);
+            Some((price, size))
+        })
+        .collect();
+
+    let dropped = levels.len() - parsed.len();
+    if dropped == 0 {
+        return Ok(parsed);
+    }
+    let example = levels
+        .iter()
+        .find(|l| Decimal::from_str(&l.price).is_err())
+        .map(|l| l.price.as_str())
+        .unwrap_or_default();
+    if parsed.is_empty() {
+        return Err(eutrader_core::Error::FeedParse(format!(
+            "{token_id}: none of the {dropped} {side} levels has a parseable price (e.g. {example:?})"
+        )));
+    }
+    tracing::warn!(
+        token_id,
+        side,
+        dropped,
+        example,
+        "dropped book levels with unparseable prices"
+    );
+    Ok(parsed)
+}
+
+/// Depth-weighted micro-price of the top of book.
+///
+/// `(best_bid * ask_size + best_ask * bid_size) / (bid_size + ask_size)` —
+/// a heavy bid pulls the price toward the ask and vice versa. Returns `None`
+/// when there is no size on either side.
+pub fn micro_price(
+    best_bid: Decimal,
+    bid_size: Decimal,
+    best_ask: Decimal,
+    ask_size: Decimal,
+) -> Option<Decimal> {
+    let total = bid_size + ask_size;
+    if total <= Decimal::ZERO {
+        return None;
+    }
+    Some((best_bid * ask_size + best_ask * bid_size) / total)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -150,7 +434,10 @@ mod tests {
 
     #[test]
     fn snapshot_from_valid_book() {
-        let book = make_book(&[("0.48", "100"), ("0.47", "50")], &[("0.52", "80"), ("0.53", "60")]);
+        let book = make_book(
+            &[("0.48", "100"), ("0.47", "50")],
+            &[("0.52", "80"), ("0.53", "60")],
+        );
         let snap = to_snapshot("tok1", &book).unwrap();
 
         assert_eq!(snap.best_bid, Decimal::from_str("0.48").unwrap());
@@ -161,20 +448,240 @@ mod tests {
     }
 
     #[test]
-    fn snapshot_none_for_empty_bids() {
+    fn micro_price_leans_toward_thin_side() {
+        // Heavy bid (300) vs thin ask (100): price pressure is upward
+        let book = make_book(&[("0.48", "300")], &[("0.52", "100")]);
+
+        let simple =
+            to_snapshot_with_mode("tok1", &book, MidpointMode::Simple, Decimal::ZERO).unwrap();
+        let weighted =
+            to_snapshot_with_mode("tok1", &book, MidpointMode::MicroPrice, Decimal::ZERO).unwrap();
+
+        // (0.48 * 100 + 0.52 * 300) / 400 = 0.51
+        assert_eq!(simple.midpoint, Decimal::from_str("0.50").unwrap());
+        assert_eq!(weighted.midpoint, Decimal::from_str("0.51").unwrap());
+        assert_eq!(simple.micro_price, weighted.micro_price);
+        assert!(weighted.midpoint > simple.midpoint);
+    }
+
+    #[test]
+    fn dust_levels_are_skipped_below_min_level_size() {
+        let book = make_book(
+            &[("0.47", "400"), ("0.46", "900")],
+            &[("0.50", "1"), ("0.53", "500")],
+        );
+
+        let raw = to_snapshot("tok1", &book).unwrap();
+        assert_eq!(raw.best_ask, Decimal::from_str("0.50").unwrap());
+
+        let snap =
+            to_snapshot_with_mode("tok1", &book, MidpointMode::Simple, Decimal::from(10)).unwrap();
+        assert_eq!(snap.best_ask, Decimal::from_str("0.53").unwrap());
+        assert_eq!(snap.ask_size, Decimal::from(500));
+        assert_eq!(snap.best_bid, Decimal::from_str("0.47").unwrap());
+        assert_eq!(snap.midpoint, Decimal::from_str("0.50").unwrap());
+
+        // Only dust on a side leaves it empty
+        let snap =
+            to_snapshot_with_mode("tok1", &book, MidpointMode::Simple, Decimal::from(600)).unwrap();
+        assert_eq!(snap.book_status, BookStatus::Empty);
+    }
+
+    #[test]
+    fn micro_price_equals_simple_mid_on_symmetric_book() {
+        let book = make_book(&[("0.48", "100")], &[("0.52", "100")]);
+        let snap =
+            to_snapshot_with_mode("tok1", &book, MidpointMode::MicroPrice, Decimal::ZERO).unwrap();
+        assert_eq!(snap.midpoint, Decimal::from_str("0.50").unwrap());
+    }
+
+    #[test]
+    fn micro_price_falls_back_without_size() {
+        let book = make_book(&[("0.48", "0")], &[("0.52", "0")]);
+        let snap =
+            to_snapshot_with_mode("tok1", &book, MidpointMode::MicroPrice, Decimal::ZERO).unwrap();
+        assert_eq!(snap.midpoint, Decimal::from_str("0.50").unwrap());
+    }
+
+    #[tokio::test]
+    async fn fetches_orderbook_from_custom_base_url() {
+        let base = crate::test_support::serve_json(|target| {
+            assert_eq!(target, "/book?token_id=tok_test");
+            r#"{"market":"0xabc","asset_id":"tok_test","bids":[{"price":"0.48","size":"10"}],"asks":[{"price":"0.52","size":"10"}]}"#
+                .to_string()
+        })
+        .await;
+
+        let client = BookClient::with_base_url(base);
+        let book = client.get_orderbook("tok_test").await.unwrap();
+        assert_eq!(book.asset_id, "tok_test");
+        assert_eq!(book.bids[0].price, "0.48");
+    }
+
+    #[tokio::test]
+    async fn market_limits_are_read_from_the_book_and_tighten_config() {
+        let base = crate::test_support::serve_json(|target| {
+            assert_eq!(target, "/book?token_id=tok_test");
+            r#"{"market":"0xabc","asset_id":"tok_test","bids":[],"asks":[],"min_order_size":"5","max_order_size":"250","tick_size":"0.01"}"#
+                .to_string()
+        })
+        .await;
+        let limits = BookClient::with_base_url(base)
+            .get_market_limits("tok_test")
+            .await
+            .unwrap();
+        assert_eq!(limits.min_order_size, Some(Decimal::from(5)));
+        assert_eq!(limits.max_order_size, Some(Decimal::from(250)));
+        let absent: MarketLimits = serde_json::from_str(r#"{"bids":[],"asks":[]}"#).unwrap();
+        assert_eq!(absent, MarketLimits::default());
+
+        let mut market: MarketConfig = serde_json::from_value(serde_json::json!({
+            "name": "Test", "token_id": "tok_test", "spread_bps": 400, "size": 500,
+            "max_inventory": 1000, "skew_factor": 0.001, "max_order_size": 300
+        }))
+        .unwrap();
+        limits.apply(&mut market);
+        assert_eq!(market.min_order_size, Decimal::from(5));
+        assert_eq!(market.max_order_size, Some(Decimal::from(250)));
+    }
+
+    #[tokio::test]
+    async fn hung_endpoint_times_out_as_transient_feed_error() {
+        let base = crate::test_support::serve_silent().await;
+        let client = BookClient::with_base_url(base)
+            .with_timeouts(Duration::from_millis(200), Duration::from_millis(200));
+
+        let started = std::time::Instant::now();
+        let err = client.get_orderbook("tok_test").await.unwrap_err();
+        assert!(started.elapsed() < Duration::from_secs(2));
+        assert!(
+            matches!(err, eutrader_core::Error::FeedTransient(_)),
+            "got {err:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn http_statuses_map_to_feed_error_kinds() {
+        use crate::test_support::serve_status;
+        use eutrader_core::Error;
+
+        let not_found = BookClient::with_base_url(serve_status("404 Not Found").await);
+        let err = not_found.get_orderbook("tok_test").await.unwrap_err();
+        assert!(matches!(err, Error::FeedNotFound(_)), "got {err:?}");
+        assert!(!err.is_retryable());
+
+        let unavailable = BookClient::with_base_url(serve_status("503 Service Unavailable").await);
+        let err = unavailable.get_midpoint("tok_test").await.unwrap_err();
+        assert!(matches!(err, Error::FeedTransient(_)), "got {err:?}");
+        assert!(err.is_retryable());
+
+        let throttled = BookClient::with_base_url(serve_status("429 Too Many Requests").await);
+        let err = throttled
+            .get_last_trade_price("tok_test")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::FeedRateLimited(_)), "got {err:?}");
+        assert!(err.is_retryable());
+    }
+
+    #[test]
+    fn synthetic_no_snapshot_mirrors_yes() {
+        let book = make_book(&[("0.48", "300")], &[("0.52", "100")]);
+        let yes =
+            to_snapshot_with_mode("yes", &book, MidpointMode::MicroPrice, Decimal::ZERO).unwrap();
+        let no = synthetic_no_snapshot(&yes, "no");
+
+        assert_eq!(no.token_id, "no");
+        assert_eq!(no.best_bid, Decimal::from_str("0.48").unwrap());
+        assert_eq!(no.best_ask, Decimal::from_str("0.52").unwrap());
+        assert_eq!(yes.midpoint + no.midpoint, Decimal::ONE);
+        assert_eq!(yes.micro_price + no.micro_price, Decimal::ONE);
+        assert_eq!(no.spread, yes.spread);
+    }
+
+    #[test]
+    fn synthetic_no_book_preserves_sizes() {
+        let book = make_book(&[("0.30", "300"), ("0.29", "50")], &[("0.35", "100")]);
+        let no_book = synthetic_no_book(&book, "no");
+
+        assert_eq!(no_book.asset_id, "no");
+        assert_eq!(no_book.bids.len(), 1);
+        assert_eq!(no_book.bids[0].price, "0.65");
+        assert_eq!(no_book.bids[0].size, "100");
+        assert_eq!(no_book.asks.len(), 2);
+        assert_eq!(no_book.asks[0].price, "0.70");
+        assert_eq!(no_book.asks[0].size, "300");
+
+        // Snapshots derived either way agree
+        let yes =
+            to_snapshot_with_mode("yes", &book, MidpointMode::MicroPrice, Decimal::ZERO).unwrap();
+        let via_book =
+            to_snapshot_with_mode("no", &no_book, MidpointMode::MicroPrice, Decimal::ZERO).unwrap();
+        let via_snapshot = synthetic_no_snapshot(&yes, "no");
+        assert_eq!(via_book.best_bid, via_snapshot.best_bid);
+        assert_eq!(via_book.best_ask, via_snapshot.best_ask);
+        assert_eq!(via_book.micro_price, via_snapshot.micro_price);
+        assert_eq!(via_book.spread, via_snapshot.spread);
+    }
+
+    #[test]
+    fn unparseable_prices_are_counted_and_an_all_bad_side_is_an_error() {
+        // One bad level among good ones is dropped with a warning
+        let book = make_book(&[("0.48", "100"), ("n/a", "50")], &[("0.52", "80")]);
+        let snap = to_snapshot("tok1", &book).unwrap();
+        assert_eq!(snap.best_bid, Decimal::from_str("0.48").unwrap());
+        assert!(snap.is_quotable());
+        assert_eq!(unparseable_levels(&book), 1);
+
+        // A side where nothing parses is not an empty book
+        let book = make_book(&[("0.48", "100")], &[("fifty-two", "80"), ("0,53", "10")]);
+        let err = to_snapshot("tok1", &book).unwrap_err();
+        assert!(matches!(err, eutrader_core::Error::FeedParse(_)));
+        assert!(err.to_string().contains("none of the 2 ask levels"));
+        assert_eq!(unparseable_levels(&book), 2);
+
+        // A side with no levels at all still is
+        let book = make_book(&[("0.48", "100")], &[]);
+        assert_eq!(
+            to_snapshot("tok1", &book).unwrap().book_status,
+            BookStatus::Empty
+        );
+    }
+
+    #[test]
+    fn empty_bids_flag_snapshot_empty() {
         let book = make_book(&[], &[("0.52", "80")]);
-        assert!(to_snapshot("tok1", &book).is_none());
+        let snap = to_snapshot("tok1", &book).unwrap();
+        assert_eq!(snap.book_status, BookStatus::Empty);
+        assert_eq!(snap.best_ask, Decimal::from_str("0.52").unwrap());
+        assert_eq!(snap.midpoint, Decimal::ZERO);
+        assert!(!snap.is_quotable());
     }
 
     #[test]
-    fn snapshot_none_for_empty_asks() {
+    fn empty_asks_flag_snapshot_empty() {
         let book = make_book(&[("0.48", "100")], &[]);
-        assert!(to_snapshot("tok1", &book).is_none());
+        assert_eq!(
+            to_snapshot("tok1", &book).unwrap().book_status,
+            BookStatus::Empty
+        );
     }
 
     #[test]
-    fn snapshot_none_for_crossed_book() {
-        let book = make_book(&[("0.55", "100")], &[("0.50", "80")]);
-        assert!(to_snapshot("tok1", &book).is_none());
+    fn crossed_and_locked_books_are_flagged() {
+        let crossed = make_book(&[("0.55", "100")], &[("0.50", "80")]);
+        assert_eq!(
+            to_snapshot("tok1", &crossed).unwrap().book_status,
+            BookStatus::Crossed
+        );
+
+        let locked = make_book(&[("0.50", "100")], &[("0.50", "80")]);
+        assert_eq!(
+            to_snapshot("tok1", &locked).unwrap().book_status,
+            BookStatus::Locked
+        );
+
+        let normal = make_book(&[("0.49", "100")], &[("0.50", "80")]);
+        assert!(to_snapshot("tok1", &normal).unwrap().is_quotable());
     }
 }
@@ -0,0 +1,135 @@
+use eutrader_core::MarketSnapshot;
+use futures::stream::{Stream, StreamExt};
+use std::collections::{HashMap, VecDeque};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tracing::debug;
+
+/// Most upstream items drained in one poll, so an always-ready source cannot
+/// starve the consumer.
+const MAX_DRAIN_PER_POLL: usize = 1024;
+
+/// Stream adapter that hands a slow consumer only the newest snapshot per token.
+///
+/// Each poll drains whatever the upstream already has buffered, keeping the
+/// latest snapshot for every token and dropping the ones it supersedes.
+/// Tokens are yielded in the order they first became pending, so one busy
+/// market cannot starve the others. A consumer that keeps up sees every
+/// snapshot unchanged.
+pub struct Coalesce<S> {
+    inner: S,
+    pending: HashMap<String, MarketSnapshot>,
+    order: VecDeque<String>,
+    done: bool,
+}
+
+impl<S> Coalesce<S>
+where
+    S: Stream<Item = MarketSnapshot> + Unpin,
+{
+    /// Wrap `inner`, coalescing whatever it has buffered on each poll.
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            pending: HashMap::new(),
+            order: VecDeque::new(),
+            done: false,
+        }
+    }
+}
+
+impl<S> Stream for Coalesce<S>
+where
+    S: Stream<Item = MarketSnapshot> + Unpin,
+{
+    type Item = MarketSnapshot;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<MarketSnapshot>> {
+        let this = self.get_mut();
+        let mut drained = 0;
+        while !this.done && drained < MAX_DRAIN_PER_POLL {
+            match this.inner.poll_next_unpin(cx) {
+                Poll::Ready(Some(snapshot)) => {
+                    drained += 1;
+                    let token_id = snapshot.token_id.clone();
+                    if this.pending.insert(token_id.clone(), snapshot).is_some() {
+                        debug!(token = %token_id, "consumer behind — dropped superseded snapshot");
+                    } else {
+                        this.order.push_back(token_id);
+                    }
+                }
+                Poll::Ready(None) => this.done = true,
+                Poll::Pending => break,
+            }
+        }
+
+        if let Some(token_id) = this.order.pop_front() {
+            return Poll::Ready(this.pending.remove(&token_id));
+        }
+        if this.done {
+            return Poll::Ready(None);
+        }
+        if drained == MAX_DRAIN_PER_POLL {
+            // Stopped at the cap rather than on Pending: nothing else will wake us
+            cx.waker().wake_by_ref();
+        }
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use eutrader_core::BookStatus;
+    use rust_decimal::Decimal;
+    use tokio::sync::mpsc;
+
+    fn snapshot(token_id: &str, cents: i64) -> MarketSnapshot {
+        let mid = Decimal::new(cents, 2);
+        MarketSnapshot {
+            token_id: token_id.into(),
+            best_bid: mid,
+            best_ask: mid,
+            midpoint: mid,
+            micro_price: mid,
+            spread: Decimal::ZERO,
+            bid_size: Decimal::ONE,
+            ask_size: Decimal::ONE,
+            timestamp: Utc::now(),
+            book_status: BookStatus::Normal,
+        }
+    }
+
+    #[tokio::test]
+    async fn slow_consumer_sees_only_latest_per_token() {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let upstream =
+            futures::stream::unfold(rx, |mut rx| async move { rx.recv().await.map(|s| (s, rx)) });
+        let mut coalesced = Coalesce::new(Box::pin(upstream));
+
+        // Ten rapid updates for one token arrive while the consumer is busy
+        for cents in 40..50 {
+            tx.send(snapshot("tok1", cents)).unwrap();
+        }
+        tx.send(snapshot("tok2", 70)).unwrap();
+
+        let first = coalesced.next().await.unwrap();
+        assert_eq!(
+            (first.token_id.as_str(), first.midpoint),
+            ("tok1", Decimal::new(49, 2))
+        );
+        let second = coalesced.next().await.unwrap();
+        assert_eq!(second.token_id, "tok2");
+
+        // Keeping up passes snapshots straight through
+        tx.send(snapshot("tok1", 51)).unwrap();
+        assert_eq!(
+            coalesced.next().await.unwrap().midpoint,
+            Decimal::new(51, 2)
+        );
+
+        drop(tx);
+        assert!(coalesced.next().await.is_none());
+    }
+}
@@ -1,12 +1,27 @@
 use eutrader_core::config::{AutoDiscoverConfig, MarketConfig};
-use eutrader_core::Result;
+use eutrader_core::{truncate, Result, DEFAULT_TICK_SIZE};
 use reqwest::Client;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
-use tracing::{info, instrument};
+use std::borrow::Cow;
+use std::future::Future;
+use std::time::Duration;
+use tracing::{info, instrument, warn};
 
-const GAMMA_API_URL: &str =
-    "https://gamma-api.polymarket.com/markets?closed=false&enableOrderBook=true&limit=100";
+use crate::http::{self, DEFAULT_CONNECT_TIMEOUT, DEFAULT_REQUEST_TIMEOUT};
+
+/// Production Gamma API endpoint.
+pub const GAMMA_BASE_URL: &str = "https://gamma-api.polymarket.com";
+
+/// Markets requested per Gamma API page.
+pub const DEFAULT_PAGE_LIMIT: usize = 100;
+/// Upper bound on pages fetched by `fetch_markets`, so discovery never walks
+/// the whole exchange.
+pub const DEFAULT_MAX_PAGES: usize = 10;
+/// Attempts per Gamma request when the failure is transient or rate limited.
+pub const DEFAULT_ATTEMPTS: usize = 3;
+/// Pause before the first retry; doubles on each further attempt.
+const RETRY_DELAY: Duration = Duration::from_millis(500);
 
 /// A token within a Gamma market (Yes / No outcome).
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,12 +42,37 @@ pub struct GammaMarket {
     pub tokens: Vec<Token>,
     /// CLOB token IDs: [Yes token ID, No token ID].
     /// The Gamma API returns this as a JSON string (stringified array), not a native array.
-    #[serde(default, deserialize_with = "deserialize_clob_token_ids")]
+    #[serde(default, deserialize_with = "deserialize_string_list")]
     pub clob_token_ids: Vec<String>,
     pub active: bool,
     pub closed: bool,
     #[serde(default)]
     pub volume_num: f64,
+    /// Maker fee in basis points, when the Gamma API exposes it.
+    #[serde(default)]
+    pub maker_base_fee: Option<u32>,
+    /// Minimum price increment of the market's order book.
+    #[serde(default)]
+    pub order_price_min_tick_size: Option<Decimal>,
+    /// Outcome names, parallel to `clob_token_ids` (also a stringified array).
+    #[serde(
+        default,
+        rename = "outcomes",
+        deserialize_with = "deserialize_string_list"
+    )]
+    pub outcome_names: Vec<String>,
+    /// Last price per outcome, parallel to `clob_token_ids` (stringified array).
+    /// Settles at 0 or 1 once the market resolves.
+    #[serde(default, deserialize_with = "deserialize_string_list")]
+    pub outcome_prices: Vec<String>,
+}
+
+/// One tradeable outcome of a market, borrowed from it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome<'a> {
+    pub index: usize,
+    pub name: Cow<'a, str>,
+    pub token_id: &'a str,
 }
 
 impl GammaMarket {
@@ -51,38 +91,203 @@ impl GammaMarket {
             .map(|s| s.as_str())
             .or_else(|| self.tokens.get(1).map(|t| t.token_id.as_str()))
     }
+
+    /// Every outcome token ID, in outcome order.
+    pub fn all_token_ids(&self) -> impl Iterator<Item = &str> + '_ {
+        self.outcomes_iter().map(|(_, token_id)| token_id)
+    }
+
+    /// Every outcome with its name and token ID, in outcome order.
+    ///
+    /// Binary markets yield two outcomes (YES, NO); multi-outcome markets one
+    /// per candidate. Names default to `Outcome N` when the API omits them.
+    pub fn outcomes(&self) -> impl Iterator<Item = Outcome<'_>> + '_ {
+        self.outcomes_iter()
+            .enumerate()
+            .map(|(index, (name, token_id))| Outcome {
+                index,
+                name: name.map_or_else(|| Cow::Owned(format!("Outcome {index}")), Cow::Borrowed),
+                token_id,
+            })
+    }
+
+    /// Whether the market no longer accepts orders.
+    pub fn is_closed(&self) -> bool {
+        self.closed || !self.active
+    }
+
+    /// Price `token_id` resolved at, once the closed market has settled to
+    /// exactly 0 or 1.
+    pub fn resolution_price(&self, token_id: &str) -> Option<Decimal> {
+        if !self.closed {
+            return None;
+        }
+        let index = self.all_token_ids().position(|t| t == token_id)?;
+        let price: Decimal = self.outcome_prices.get(index)?.parse().ok()?;
+        (price == Decimal::ZERO || price == Decimal::ONE).then_some(price)
+    }
+
+    /// `(name, token_id)` pairs, preferring clobTokenIds over tokens[].
+    fn outcomes_iter(&self) -> Box<dyn Iterator<Item = (Option<&str>, &str)> + '_> {
+        if self.clob_token_ids.is_empty() {
+            Box::new(
+                self.tokens
+                    .iter()
+                    .map(|t| (Some(t.outcome.as_str()), t.token_id.as_str())),
+            )
+        } else {
+            Box::new(
+                self.clob_token_ids
+                    .iter()
+                    .enumerate()
+                    .map(|(i, id)| (self.outcome_names.get(i).map(|s| s.as_str()), id.as_str())),
+            )
+        }
+    }
 }
 
 /// Client for the Polymarket Gamma API.
 pub struct GammaClient {
     client: Client,
+    base_url: String,
+    page_limit: usize,
+    max_pages: usize,
+    attempts: usize,
 }
 
 impl GammaClient {
     /// Create a new `GammaClient` with a default reqwest client.
     pub fn new() -> Self {
+        Self::with_base_url(GAMMA_BASE_URL)
+    }
+
+    /// Create a `GammaClient` that talks to `base_url` instead of the
+    /// production Gamma API (e.g. a staging host, proxy or mock server).
+    pub fn with_base_url(base_url: impl Into<String>) -> Self {
         Self {
-            client: Client::new(),
+            client: http::build_client(DEFAULT_REQUEST_TIMEOUT, DEFAULT_CONNECT_TIMEOUT),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            page_limit: DEFAULT_PAGE_LIMIT,
+            max_pages: DEFAULT_MAX_PAGES,
+            attempts: DEFAULT_ATTEMPTS,
         }
     }
 
+    /// Override the request and connect timeouts.
+    pub fn with_timeouts(mut self, timeout: Duration, connect_timeout: Duration) -> Self {
+        self.client = http::build_client(timeout, connect_timeout);
+        self
+    }
+
+    /// The Gamma API base URL requests are sent to.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn markets_url(&self) -> String {
+        format!("{}/markets", self.base_url)
+    }
+
+    /// Cap the number of pages `fetch_markets` will request (minimum 1).
+    pub fn with_max_pages(mut self, max_pages: usize) -> Self {
+        self.max_pages = max_pages.max(1);
+        self
+    }
+
+    /// Try each request up to `attempts` times (minimum 1). Only transient
+    /// and rate-limited failures are retried.
+    pub fn with_attempts(mut self, attempts: usize) -> Self {
+        self.attempts = attempts.max(1);
+        self
+    }
+
     /// Fetch active, order-book-enabled markets from the Gamma API.
+    ///
+    /// Pages through results with `offset` until a short page is returned or
+    /// `max_pages` pages have been fetched.
     #[instrument(skip(self), name = "gamma_fetch_markets")]
     pub async fn fetch_markets(&self) -> Result<Vec<GammaMarket>> {
-        let markets: Vec<GammaMarket> = self
-            .client
-            .get(GAMMA_API_URL)
-            .send()
-            .await?
-            .error_for_status()
-            .map_err(|e| eutrader_core::Error::Feed(format!("Gamma API HTTP error: {e}")))?
-            .json()
-            .await?;
+        let markets = collect_pages(self.page_limit, self.max_pages, |offset| {
+            self.fetch_markets_page(offset)
+        })
+        .await?;
 
         tracing::info!(count = markets.len(), "fetched markets from Gamma API");
         Ok(markets)
     }
 
+    /// Fetch a single page of active markets starting at `offset`.
+    async fn fetch_markets_page(&self, offset: usize) -> Result<Vec<GammaMarket>> {
+        let markets = self
+            .get_markets(&[
+                ("closed", "false".to_string()),
+                ("enableOrderBook", "true".to_string()),
+                ("limit", self.page_limit.to_string()),
+                ("offset", offset.to_string()),
+            ])
+            .await?;
+
+        tracing::debug!(offset, count = markets.len(), "fetched Gamma markets page");
+        Ok(markets)
+    }
+
+    /// Fetch the markets that contain any of the given CLOB token IDs.
+    #[instrument(skip(self), name = "gamma_fetch_markets_by_token_ids")]
+    pub async fn fetch_markets_by_token_ids(
+        &self,
+        token_ids: &[String],
+    ) -> Result<Vec<GammaMarket>> {
+        let query: Vec<(&str, &str)> = token_ids
+            .iter()
+            .map(|t| ("clob_token_ids", t.as_str()))
+            .collect();
+        let markets = self.get_markets(&query).await?;
+
+        tracing::debug!(count = markets.len(), "fetched markets by token id");
+        Ok(markets)
+    }
+
+    /// GET `/markets` with `query`, retrying transient failures.
+    async fn get_markets<Q: Serialize + ?Sized>(&self, query: &Q) -> Result<Vec<GammaMarket>> {
+        http::retry("Gamma API", self.attempts, RETRY_DELAY, || async {
+            self.client
+                .get(self.markets_url())
+                .query(query)
+                .send()
+                .await
+                .map_err(|e| http::map_error("Gamma API", e))
+                .and_then(|resp| http::check_status("Gamma API", resp))?
+                .json()
+                .await
+                .map_err(|e| http::map_error("Gamma API", e))
+        })
+        .await
+    }
+
+    /// Check that every token ID belongs to an active, open market.
+    ///
+    /// Returns `Error::Config` listing the offending tokens if any are unknown
+    /// or no longer tradeable.
+    #[instrument(skip(self), name = "gamma_validate_tokens")]
+    pub async fn validate_tokens(&self, token_ids: &[String]) -> Result<()> {
+        if token_ids.is_empty() {
+            return Ok(());
+        }
+        let markets = self.fetch_markets_by_token_ids(token_ids).await?;
+        let unknown = find_unknown_tokens(&markets, token_ids);
+        if !unknown.is_empty() {
+            return Err(eutrader_core::Error::Config(format!(
+                "unknown or inactive token IDs: {}",
+                unknown.join(", ")
+            )));
+        }
+        info!(
+            count = token_ids.len(),
+            "all configured token IDs validated"
+        );
+        Ok(())
+    }
+
     /// Auto-discover markets based on volume and config criteria.
     ///
     /// Fetches active markets from the Gamma API, filters by minimum volume,
@@ -98,28 +303,16 @@ impl GammaClient {
             .collect();
 
         // Sort by volume descending — highest volume = tightest spreads = best for MM
-        candidates.sort_by(|a, b| b.volume_num.partial_cmp(&a.volume_num).unwrap_or(std::cmp::Ordering::Equal));
+        candidates.sort_by(|a, b| {
+            b.volume_num
+                .partial_cmp(&a.volume_num)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
         candidates.truncate(config.max_markets);
 
         let market_configs: Vec<MarketConfig> = candidates
             .iter()
-            .filter_map(|m| {
-                let token_id = m.yes_token_id()?;
-                info!(
-                    question = %m.question,
-                    token_id = %token_id,
-                    volume = m.volume_num,
-                    "auto-discovered market"
-                );
-                Some(MarketConfig {
-                    name: truncate_question(&m.question, 50),
-                    token_id: token_id.to_string(),
-                    spread_bps: config.spread_bps,
-                    size: config.size,
-                    max_inventory: config.max_inventory,
-                    skew_factor: config.skew_factor,
-                })
-            })
+            .flat_map(|m| market_configs_for(m, config))
             .collect();
 
         info!(count = market_configs.len(), "auto-discovery complete");
@@ -127,8 +320,42 @@ impl GammaClient {
     }
 }
 
-/// Deserialize clobTokenIds which can be either a JSON array or a stringified JSON array.
-fn deserialize_clob_token_ids<'de, D>(deserializer: D) -> std::result::Result<Vec<String>, D::Error>
+/// Build the `MarketConfig`s to trade for one discovered market.
+///
+/// Only the first (YES) outcome is traded unless `expand_outcomes` is set, in
+/// which case every outcome token becomes its own independently quoted market.
+pub fn market_configs_for(m: &GammaMarket, config: &AutoDiscoverConfig) -> Vec<MarketConfig> {
+    let expand = config.expand_outcomes && m.all_token_ids().nth(1).is_some();
+    let traded = if expand { usize::MAX } else { 1 };
+
+    m.outcomes()
+        .take(traded)
+        .map(|outcome| {
+            info!(
+                question = %m.question,
+                outcome = %outcome.name,
+                token_id = %outcome.token_id,
+                volume = m.volume_num,
+                "auto-discovered market"
+            );
+            let name = if expand {
+                format!("{} [{}]", truncate(&m.question, 40), outcome.name)
+            } else {
+                truncate(&m.question, 50)
+            };
+            MarketConfig {
+                // Unknown fees fall back to the global `fee_bps`
+                fee_bps: m.maker_base_fee,
+                outcome: Some(outcome.name.into_owned()),
+                tick_size: m.order_price_min_tick_size.unwrap_or(DEFAULT_TICK_SIZE),
+                ..config.market_config(name, outcome.token_id.to_string())
+            }
+        })
+        .collect()
+}
+
+/// Deserialize clobTokenIds / outcomes, which can be either a JSON array or a stringified JSON array.
+fn deserialize_string_list<'de, D>(deserializer: D) -> std::result::Result<Vec<String>, D::Error>
 where
     D: serde::Deserializer<'de>,
 {
@@ -150,12 +377,48 @@ where
     }
 }
 
-fn truncate_question(q: &str, max: usize) -> String {
-    if q.len() <= max {
-        q.to_string()
-    } else {
-        format!("{}...", &q[..max - 3])
+/// Drive an offset-paginated fetch, merging pages until one comes back short
+/// or `max_pages` is reached.
+async fn collect_pages<F, Fut>(
+    limit: usize,
+    max_pages: usize,
+    mut fetch_page: F,
+) -> Result<Vec<GammaMarket>>
+where
+    F: FnMut(usize) -> Fut,
+    Fut: Future<Output = Result<Vec<GammaMarket>>>,
+{
+    let mut markets = Vec::new();
+    for page in 0..max_pages {
+        let batch = fetch_page(page * limit).await?;
+        let full = batch.len() >= limit;
+        markets.extend(batch);
+        if !full {
+            return Ok(markets);
+        }
     }
+    warn!(
+        max_pages,
+        count = markets.len(),
+        "Gamma pagination stopped at max_pages cap"
+    );
+    Ok(markets)
+}
+
+/// Return the token IDs that do not belong to any active, open market.
+pub fn find_unknown_tokens(markets: &[GammaMarket], token_ids: &[String]) -> Vec<String> {
+    token_ids
+        .iter()
+        .filter(|t| {
+            !markets.iter().any(|m| {
+                m.active
+                    && !m.closed
+                    && (m.clob_token_ids.contains(t)
+                        || m.tokens.iter().any(|tok| &tok.token_id == *t))
+            })
+        })
+        .cloned()
+        .collect()
 }
 
 impl Default for GammaClient {
@@ -186,6 +449,200 @@ mod tests {
         assert_eq!(market.no_token_id(), Some("tok_no_456"));
         assert!(market.active);
         assert!(!market.closed);
+        assert_eq!(market.maker_base_fee, None);
+    }
+
+    fn make_market(clob_token_ids: &[&str], closed: bool) -> GammaMarket {
+        GammaMarket {
+            condition_id: "0xabc".into(),
+            question: "Will it rain?".into(),
+            tokens: vec![],
+            clob_token_ids: clob_token_ids.iter().map(|s| s.to_string()).collect(),
+            active: true,
+            closed,
+            volume_num: 0.0,
+            maker_base_fee: None,
+            order_price_min_tick_size: None,
+            outcome_names: vec![],
+            outcome_prices: vec![],
+        }
+    }
+
+    #[test]
+    fn resolution_price_only_for_settled_closed_markets() {
+        let json = r#"{
+            "conditionId": "0xabc",
+            "question": "Will it rain?",
+            "clobTokenIds": "[\"tok_yes\", \"tok_no\"]",
+            "outcomePrices": "[\"1\", \"0\"]",
+            "active": true,
+            "closed": true
+        }"#;
+        let mut market: GammaMarket = serde_json::from_str(json).unwrap();
+        assert!(market.is_closed());
+        assert_eq!(market.resolution_price("tok_yes"), Some(Decimal::ONE));
+        assert_eq!(market.resolution_price("tok_no"), Some(Decimal::ZERO));
+        assert_eq!(market.resolution_price("tok_other"), None);
+
+        // Closed but not yet settled
+        market.outcome_prices = vec!["0.97".into(), "0.03".into()];
+        assert_eq!(market.resolution_price("tok_yes"), None);
+
+        market.closed = false;
+        market.outcome_prices = vec!["1".into(), "0".into()];
+        assert!(!market.is_closed());
+        assert_eq!(market.resolution_price("tok_yes"), None);
+    }
+
+    #[test]
+    fn finds_unknown_and_closed_tokens() {
+        let markets = vec![
+            make_market(&["tok_yes", "tok_no"], false),
+            make_market(&["tok_closed_yes", "tok_closed_no"], true),
+        ];
+        let configured = vec![
+            "tok_no".to_string(),
+            "tok_typo".to_string(),
+            "tok_closed_yes".to_string(),
+        ];
+
+        let unknown = find_unknown_tokens(&markets, &configured);
+        assert_eq!(
+            unknown,
+            vec!["tok_typo".to_string(), "tok_closed_yes".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn pagination_merges_pages_until_short_page() {
+        let pages = [
+            vec![make_market(&["a"], false), make_market(&["b"], false)],
+            vec![make_market(&["c"], false)],
+        ];
+        let mut offsets = Vec::new();
+        let markets = collect_pages(2, 10, |offset| {
+            offsets.push(offset);
+            let page = pages[offset / 2].clone();
+            async move { Ok(page) }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(offsets, vec![0, 2]);
+        let ids: Vec<_> = markets.iter().filter_map(|m| m.yes_token_id()).collect();
+        assert_eq!(ids, vec!["a", "b", "c"]);
+    }
+
+    #[tokio::test]
+    async fn pagination_stops_at_max_pages() {
+        let mut calls = 0;
+        let markets = collect_pages(1, 3, |_| {
+            calls += 1;
+            async { Ok(vec![make_market(&["x"], false)]) }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(calls, 3);
+        assert_eq!(markets.len(), 3);
+    }
+
+    fn market_json(id: &str) -> String {
+        format!(
+            r#"{{"conditionId":"0x{id}","question":"Q {id}","clobTokenIds":["{id}"],"active":true,"closed":false}}"#
+        )
+    }
+
+    #[tokio::test]
+    async fn fetch_markets_pages_through_mock_server() {
+        let base = crate::test_support::serve_json(|target| {
+            assert!(target.starts_with("/markets?"), "unexpected path {target}");
+            if target.contains("offset=0") {
+                format!("[{},{}]", market_json("a"), market_json("b"))
+            } else if target.contains("offset=2") {
+                format!("[{}]", market_json("c"))
+            } else {
+                "[]".to_string()
+            }
+        })
+        .await;
+
+        let mut client = GammaClient::with_base_url(format!("{base}/"));
+        client.page_limit = 2;
+        let markets = client.fetch_markets().await.unwrap();
+
+        let ids: Vec<_> = markets.iter().filter_map(|m| m.yes_token_id()).collect();
+        assert_eq!(ids, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn enumerates_multi_outcome_market() {
+        let json = r#"{
+            "conditionId": "0xabc",
+            "question": "Who wins the election?",
+            "clobTokenIds": "[\"tok_a\", \"tok_b\", \"tok_c\"]",
+            "outcomes": "[\"Alice\", \"Bob\", \"Carol\"]",
+            "active": true,
+            "closed": false,
+            "volumeNum": 500000.0
+        }"#;
+
+        let market: GammaMarket = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            market.all_token_ids().collect::<Vec<_>>(),
+            ["tok_a", "tok_b", "tok_c"]
+        );
+        let outcomes: Vec<Outcome> = market.outcomes().collect();
+        assert_eq!(outcomes.len(), 3);
+        assert_eq!(outcomes[2].index, 2);
+        assert_eq!(outcomes[2].name, "Carol");
+        assert_eq!(outcomes[2].token_id, "tok_c");
+
+        let mut config: AutoDiscoverConfig =
+            serde_json::from_str(r#"{ "size": "10", "max_inventory": "50" }"#).unwrap();
+        assert_eq!(market_configs_for(&market, &config).len(), 1);
+
+        config.expand_outcomes = true;
+        let configs = market_configs_for(&market, &config);
+        let tokens: Vec<_> = configs.iter().map(|c| c.token_id.as_str()).collect();
+        assert_eq!(tokens, vec!["tok_a", "tok_b", "tok_c"]);
+        assert_eq!(configs[1].outcome.as_deref(), Some("Bob"));
+        assert!(configs[1].name.ends_with("[Bob]"));
+    }
+
+    #[test]
+    fn legacy_tokens_provide_outcome_names() {
+        let json = r#"{
+            "conditionId": "0xdef",
+            "question": "Will BTC hit 100k?",
+            "tokens": [
+                { "token_id": "tok_yes", "outcome": "Yes", "price": 0.55 },
+                { "token_id": "tok_no", "outcome": "No", "price": 0.45 }
+            ],
+            "active": true,
+            "closed": false
+        }"#;
+
+        let market: GammaMarket = serde_json::from_str(json).unwrap();
+        let names: Vec<_> = market.outcomes().map(|o| o.name).collect();
+        assert_eq!(names, vec!["Yes", "No"]);
+    }
+
+    #[test]
+    fn deserializes_gamma_market_fee() {
+        let json = r#"{
+            "conditionId": "0xabc",
+            "question": "Will it rain?",
+            "clobTokenIds": ["tok_yes_123", "tok_no_456"],
+            "active": true,
+            "closed": false,
+            "makerBaseFee": 20,
+            "orderPriceMinTickSize": 0.001
+        }"#;
+
+        let market: GammaMarket = serde_json::from_str(json).unwrap();
+        assert_eq!(market.maker_base_fee, Some(20));
+        assert_eq!(market.order_price_min_tick_size, Some(Decimal::new(1, 3)));
     }
 
     #[test]
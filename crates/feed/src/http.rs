@@ -0,0 +1,112 @@
+//! Shared reqwest client setup for the REST clients.
+
+use std::future::Future;
+use std::time::Duration;
+
+use reqwest::{Client, Response, StatusCode};
+use tracing::warn;
+
+/// Default total request timeout.
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// Default TCP connect timeout.
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Build a reqwest client with the given timeouts.
+pub fn build_client(timeout: Duration, connect_timeout: Duration) -> Client {
+    Client::builder()
+        .timeout(timeout)
+        .connect_timeout(connect_timeout)
+        .build()
+        .expect("failed to build HTTP client")
+}
+
+/// Convert a reqwest error, reporting timeouts and connection failures as
+/// `Error::FeedTransient` and undecodable bodies as `Error::FeedParse`.
+pub(crate) fn map_error(what: &str, e: reqwest::Error) -> eutrader_core::Error {
+    if e.is_timeout() {
+        eutrader_core::Error::FeedTransient(format!("{what} request timed out: {e}"))
+    } else if e.is_connect() {
+        eutrader_core::Error::FeedTransient(format!("{what} connection failed: {e}"))
+    } else if e.is_decode() {
+        eutrader_core::Error::FeedParse(format!("{what} response malformed: {e}"))
+    } else {
+        eutrader_core::Error::Http(e)
+    }
+}
+
+/// Turn an error status into the matching feed error: 404 is
+/// `FeedNotFound`, 429 `FeedRateLimited`, 5xx `FeedTransient`. Other client
+/// errors stay `Error::Http`.
+pub(crate) fn check_status(what: &str, resp: Response) -> eutrader_core::Result<Response> {
+    let status = resp.status();
+    match status {
+        StatusCode::NOT_FOUND => Err(eutrader_core::Error::FeedNotFound(format!(
+            "{what}: {status}"
+        ))),
+        StatusCode::TOO_MANY_REQUESTS => Err(eutrader_core::Error::FeedRateLimited(format!(
+            "{what}: {status}"
+        ))),
+        s if s.is_server_error() => Err(eutrader_core::Error::FeedTransient(format!(
+            "{what}: {status}"
+        ))),
+        _ => resp.error_for_status().map_err(eutrader_core::Error::Http),
+    }
+}
+
+/// Run `op` up to `attempts` times, retrying only errors that are
+/// `is_retryable`. The pause starts at `delay` and doubles after each failure.
+pub(crate) async fn retry<T, F, Fut>(
+    what: &str,
+    attempts: usize,
+    mut delay: Duration,
+    mut op: F,
+) -> eutrader_core::Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = eutrader_core::Result<T>>,
+{
+    let mut attempt = 1;
+    loop {
+        match op().await {
+            Err(e) if e.is_retryable() && attempt < attempts => {
+                warn!(what, attempt, error = %e, "request failed — retrying");
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eutrader_core::Error;
+
+    #[tokio::test]
+    async fn retries_only_transient_errors() {
+        let mut calls = 0;
+        let result: eutrader_core::Result<u32> = retry("test", 3, Duration::from_millis(1), || {
+            calls += 1;
+            let outcome = if calls < 3 {
+                Err(Error::FeedTransient("503".into()))
+            } else {
+                Ok(7)
+            };
+            async move { outcome }
+        })
+        .await;
+        assert_eq!(result.unwrap(), 7);
+        assert_eq!(calls, 3);
+
+        let mut calls = 0;
+        let result: eutrader_core::Result<u32> = retry("test", 3, Duration::from_millis(1), || {
+            calls += 1;
+            async { Err(Error::FeedNotFound("404".into())) }
+        })
+        .await;
+        assert!(matches!(result, Err(Error::FeedNotFound(_))));
+        assert_eq!(calls, 1);
+    }
+}
@@ -1,7 +1,19 @@
 pub mod book;
+pub mod coalesce;
 pub mod gamma;
+pub mod http;
 pub mod manager;
+pub mod simulator;
+pub mod trades;
+pub mod volatility;
 
-pub use book::BookClient;
+#[cfg(test)]
+mod test_support;
+
+pub use book::{BookClient, MarketLimits};
+pub use coalesce::Coalesce;
 pub use gamma::GammaClient;
-pub use manager::FeedManager;
+pub use manager::{FeedManager, SkippedSnapshots, UnparseableLevels};
+pub use simulator::PriceSimulator;
+pub use trades::{LastTradePrices, TradeEvent, TradesFeed};
+pub use volatility::VolatilityTracker;
@@ -1,19 +1,79 @@
-use eutrader_core::MarketSnapshot;
-use futures::stream::{self, Stream};
+use eutrader_core::{Config, MarketSnapshot, MidSource, MidpointMode};
+use futures::stream::{self, Stream, StreamExt};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
 use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
 use tracing::{info, warn};
 
 use crate::book::{self, BookClient};
+use crate::trades::LastTradePrices;
 
 /// Default polling interval in milliseconds.
 const DEFAULT_INTERVAL_MS: u64 = 1000;
 
+/// Default number of snapshots buffered for a consumer that falls behind.
+pub const DEFAULT_CAPACITY: usize = 256;
+
+/// How long `stream` waits for the first snapshot by default.
+pub const DEFAULT_STARTUP_TIMEOUT_MS: u64 = 30_000;
+
+/// Running count of snapshots a lagging consumer never saw. Clone the handle
+/// from `FeedManager::skipped` before calling `run`.
+#[derive(Debug, Clone, Default)]
+pub struct SkippedSnapshots(Arc<AtomicU64>);
+
+impl SkippedSnapshots {
+    /// Total snapshots dropped so far.
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    fn add(&self, n: u64) -> u64 {
+        self.0.fetch_add(n, Ordering::Relaxed) + n
+    }
+}
+
+/// Running count of book levels dropped because their price did not parse.
+/// A rising count points at a change in the API's number format. Clone the
+/// handle from `FeedManager::unparseable_levels` before calling `run`.
+#[derive(Debug, Clone, Default)]
+pub struct UnparseableLevels(Arc<AtomicU64>);
+
+impl UnparseableLevels {
+    /// Total levels dropped so far.
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    fn add(&self, n: u64) {
+        self.0.fetch_add(n, Ordering::Relaxed);
+    }
+}
+
 /// Manages periodic polling of orderbooks and produces a stream of `MarketSnapshot`s.
 pub struct FeedManager {
     token_ids: Vec<String>,
     interval: Duration,
+    midpoint_mode: MidpointMode,
+    /// Book levels smaller than this are skipped as dust.
+    min_level_size: Decimal,
+    clob_base_url: String,
+    jitter: Duration,
+    /// Seed for the jitter offsets; random per run unless fixed.
+    jitter_seed: Option<u64>,
+    mid_sources: HashMap<String, MidSource>,
+    last_trades: Option<LastTradePrices>,
+    capacity: usize,
+    skipped: SkippedSnapshots,
+    unparseable: UnparseableLevels,
+    startup_timeout: Duration,
 }
 
 impl FeedManager {
@@ -24,6 +84,17 @@ impl FeedManager {
         Self {
             token_ids,
             interval: Duration::from_millis(DEFAULT_INTERVAL_MS),
+            midpoint_mode: MidpointMode::default(),
+            min_level_size: Decimal::ZERO,
+            clob_base_url: book::CLOB_BASE_URL.to_string(),
+            jitter: Duration::ZERO,
+            jitter_seed: None,
+            mid_sources: HashMap::new(),
+            last_trades: None,
+            capacity: DEFAULT_CAPACITY,
+            skipped: SkippedSnapshots::default(),
+            unparseable: UnparseableLevels::default(),
+            startup_timeout: Duration::from_millis(DEFAULT_STARTUP_TIMEOUT_MS),
         }
     }
 
@@ -35,123 +106,658 @@ impl FeedManager {
         Self {
             token_ids,
             interval: Duration::from_millis(interval_ms),
+            midpoint_mode: MidpointMode::default(),
+            min_level_size: Decimal::ZERO,
+            clob_base_url: book::CLOB_BASE_URL.to_string(),
+            jitter: Duration::ZERO,
+            jitter_seed: None,
+            mid_sources: HashMap::new(),
+            last_trades: None,
+            capacity: DEFAULT_CAPACITY,
+            skipped: SkippedSnapshots::default(),
+            unparseable: UnparseableLevels::default(),
+            startup_timeout: Duration::from_millis(DEFAULT_STARTUP_TIMEOUT_MS),
         }
     }
 
-    /// Start polling and return a `Stream` of `MarketSnapshot`s.
+    /// Create a `FeedManager` that polls `token_ids` as `config` asks: every
+    /// `risk.quote_refresh_interval_ms`, with its jitter, midpoint mode, dust
+    /// filter, startup timeout and per-market mid sources.
+    pub fn from_config(token_ids: Vec<String>, config: &Config) -> Self {
+        Self::with_interval(token_ids, config.risk.quote_refresh_interval_ms)
+            .with_midpoint_mode(config.midpoint_mode)
+            .with_min_level_size(config.min_level_size)
+            .with_startup_timeout(Duration::from_millis(config.feed_startup_timeout_ms))
+            .with_jitter_ms(config.risk.quote_jitter_ms)
+            .with_mid_sources(
+                config
+                    .markets
+                    .iter()
+                    .map(|m| (m.token_id.clone(), m.mid_source)),
+            )
+    }
+
+    /// How often each token is polled.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Choose how snapshot midpoints are computed (simple mid by default).
+    pub fn with_midpoint_mode(mut self, mode: MidpointMode) -> Self {
+        self.midpoint_mode = mode;
+        self
+    }
+
+    /// Skip book levels resting less than `size` when picking the best bid
+    /// and ask (none skipped by default).
+    pub fn with_min_level_size(mut self, size: Decimal) -> Self {
+        self.min_level_size = size;
+        self
+    }
+
+    /// Take the midpoint of the given tokens from somewhere other than the
+    /// book. Tokens not listed use `MidSource::Book`.
+    pub fn with_mid_sources(
+        mut self,
+        sources: impl IntoIterator<Item = (String, MidSource)>,
+    ) -> Self {
+        self.mid_sources = sources.into_iter().collect();
+        self
+    }
+
+    /// Serve `MidSource::LastTrade` from prices kept by a `TradesFeed`,
+    /// falling back to the REST endpoint for tokens that have not traded yet.
+    pub fn with_last_trades(mut self, prices: LastTradePrices) -> Self {
+        self.last_trades = Some(prices);
+        self
+    }
+
+    /// Poll orderbooks from `url` instead of the production CLOB.
+    pub fn with_clob_base_url(mut self, url: impl Into<String>) -> Self {
+        self.clob_base_url = url.into();
+        self
+    }
+
+    /// Buffer up to `capacity` snapshots for a slow consumer before the
+    /// oldest are dropped (256 by default).
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    /// Give up in `stream` if no snapshot arrives within `timeout`
+    /// (30 s by default).
+    pub fn with_startup_timeout(mut self, timeout: Duration) -> Self {
+        self.startup_timeout = timeout;
+        self
+    }
+
+    /// Handle counting snapshots dropped because the consumer lagged.
+    pub fn skipped(&self) -> SkippedSnapshots {
+        self.skipped.clone()
+    }
+
+    /// Handle counting book levels dropped because their price did not parse.
+    pub fn unparseable_levels(&self) -> UnparseableLevels {
+        self.unparseable.clone()
+    }
+
+    /// Offset each token's polling cycle by a random delay of up to
+    /// `jitter_ms`, so markets sharing an interval don't all reconcile in the
+    /// same tick. Zero (the default) polls every token back-to-back.
+    pub fn with_jitter_ms(mut self, jitter_ms: u64) -> Self {
+        self.jitter = Duration::from_millis(jitter_ms);
+        self
+    }
+
+    /// Fix the seed behind the jitter offsets, so they are reproducible.
+    pub fn with_jitter_seed(mut self, seed: u64) -> Self {
+        self.jitter_seed = Some(seed);
+        self
+    }
+
+    /// Each token's start offset within the first interval.
+    fn start_offsets(&self) -> Vec<Duration> {
+        let n = self.token_ids.len();
+        match self.jitter_seed {
+            Some(seed) => jitter_offsets(n, self.jitter, &mut StdRng::seed_from_u64(seed)),
+            None => jitter_offsets(n, self.jitter, &mut rand::thread_rng()),
+        }
+    }
+
+    /// Start polling and return a `Stream` of `MarketSnapshot`s once the
+    /// feed has produced its first one.
     ///
-    /// Internally spawns a tokio task that polls each token's orderbook on a
-    /// fixed interval and pushes snapshots through a broadcast channel. The
-    /// returned stream will receive all snapshots.
+    /// Internally spawns tokio tasks that poll each token's orderbook on a
+    /// fixed interval and push snapshots through a broadcast channel. Failed
+    /// polls are retried every interval; if none succeeds within the startup
+    /// timeout the feed is abandoned with `Error::FeedTransient`. The returned
+    /// stream starts with that first snapshot.
     pub async fn stream(
         self,
     ) -> eutrader_core::Result<Pin<Box<dyn Stream<Item = MarketSnapshot> + Send>>> {
-        let (tx, rx) = broadcast::channel::<MarketSnapshot>(256);
-        let token_ids = self.token_ids.clone();
-        let interval = self.interval;
-
-        tokio::spawn(async move {
-            let client = BookClient::new();
-            let mut ticker = tokio::time::interval(interval);
-
-            info!(
-                tokens = token_ids.len(),
-                interval_ms = interval.as_millis() as u64,
-                "feed manager started"
-            );
-
-            loop {
-                ticker.tick().await;
-
-                for token_id in &token_ids {
-                    match client.get_orderbook(token_id).await {
-                        Ok(book_resp) => {
-                            if let Some(snapshot) = book::to_snapshot(token_id, &book_resp) {
-                                if tx.send(snapshot).is_err() {
-                                    // All receivers dropped -- stop the loop
-                                    info!("all feed receivers dropped, stopping feed manager");
-                                    return;
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            warn!(token_id, error = %e, "failed to fetch orderbook");
-                        }
-                    }
-                }
-            }
-        });
+        let timeout = self.startup_timeout;
+        let (tx, mut rx) = broadcast::channel::<MarketSnapshot>(self.capacity);
+        let skipped = self.skipped.clone();
+        let tasks = self.spawn_polling(tx);
 
-        // Convert the broadcast receiver into a Stream
-        let stream = stream::unfold(rx, |mut rx| async move {
+        let first = tokio::time::timeout(timeout, async {
             loop {
                 match rx.recv().await {
-                    Ok(snapshot) => return Some((snapshot, rx)),
-                    Err(broadcast::error::RecvError::Lagged(n)) => {
-                        warn!(skipped = n, "feed consumer lagged, skipping messages");
-                        continue;
-                    }
+                    Ok(snapshot) => return Some(snapshot),
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
                     Err(broadcast::error::RecvError::Closed) => return None,
                 }
             }
-        });
-
-        Ok(Box::pin(stream))
+        })
+        .await
+        .ok()
+        .flatten();
+        let Some(first) = first else {
+            for task in &tasks {
+                task.abort();
+            }
+            return Err(eutrader_core::Error::FeedTransient(format!(
+                "no market data within {}ms of startup",
+                timeout.as_millis()
+            )));
+        };
+        info!(token_id = %first.token_id, "feed healthy — first snapshot received");
+        Ok(Box::pin(
+            stream::once(async { first }).chain(receiver_stream(rx, skipped)),
+        ))
     }
 
     /// Start polling and return a `Stream` of `MarketSnapshot`s (infallible variant).
     ///
-    /// Same as `stream()` but does not return a `Result` — use when you don't need
-    /// startup validation.
+    /// Same as `stream()` but returns immediately, without waiting for the
+    /// first snapshot.
     pub fn run(self) -> Pin<Box<dyn Stream<Item = MarketSnapshot> + Send>> {
-        let (tx, rx) = broadcast::channel::<MarketSnapshot>(256);
-        let token_ids = self.token_ids.clone();
+        let (tx, rx) = broadcast::channel::<MarketSnapshot>(self.capacity);
+        let skipped = self.skipped.clone();
+        self.spawn_polling(tx);
+        receiver_stream(rx, skipped)
+    }
+
+    /// Spawn the polling task(s) feeding `tx`, returning their handles.
+    ///
+    /// Without jitter one task polls every token in turn each tick. With
+    /// jitter each token gets its own task whose interval starts at a random
+    /// offset, spreading the cycles across the interval.
+    fn spawn_polling(self, tx: broadcast::Sender<MarketSnapshot>) -> Vec<JoinHandle<()>> {
+        let offsets = self.start_offsets();
         let interval = self.interval;
+        let midpoint_mode = self.midpoint_mode;
+        let min_level_size = self.min_level_size;
+        let client = BookClient::with_base_url(self.clob_base_url);
+        let last_trades = self.last_trades;
+        let unparseable = self.unparseable;
 
-        tokio::spawn(async move {
-            let client = BookClient::new();
-            let mut ticker = tokio::time::interval(interval);
+        info!(
+            tokens = self.token_ids.len(),
+            interval_ms = interval.as_millis() as u64,
+            jitter_ms = self.jitter.as_millis() as u64,
+            "feed manager started"
+        );
 
-            info!(
-                tokens = token_ids.len(),
-                interval_ms = interval.as_millis() as u64,
-                "feed manager started"
-            );
+        let tokens: Vec<(String, MidSource)> = self
+            .token_ids
+            .into_iter()
+            .map(|t| {
+                let source = self.mid_sources.get(&t).copied().unwrap_or_default();
+                (t, source)
+            })
+            .collect();
 
-            loop {
-                ticker.tick().await;
-
-                for token_id in &token_ids {
-                    match client.get_orderbook(token_id).await {
-                        Ok(book_resp) => {
-                            if let Some(snapshot) = book::to_snapshot(token_id, &book_resp) {
-                                if tx.send(snapshot).is_err() {
-                                    info!("all feed receivers dropped, stopping feed manager");
-                                    return;
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            warn!(token_id, error = %e, "failed to fetch orderbook");
+        if self.jitter.is_zero() {
+            let task = tokio::spawn(async move {
+                let mut ticker = tokio::time::interval(interval);
+                loop {
+                    ticker.tick().await;
+                    for (token_id, mid_source) in &tokens {
+                        let last_trades = last_trades.as_ref();
+                        if !poll_token(
+                            &client,
+                            token_id,
+                            midpoint_mode,
+                            min_level_size,
+                            *mid_source,
+                            last_trades,
+                            &unparseable,
+                            &tx,
+                        )
+                        .await
+                        {
+                            return;
                         }
                     }
                 }
-            }
-        });
+            });
+            return vec![task];
+        }
+
+        let mut tasks = Vec::with_capacity(offsets.len());
+        for ((token_id, mid_source), offset) in tokens.into_iter().zip(offsets) {
+            let client = client.clone();
+            let tx = tx.clone();
+            let last_trades = last_trades.clone();
+            let unparseable = unparseable.clone();
+            tasks.push(tokio::spawn(async move {
+                let start = tokio::time::Instant::now() + offset;
+                let mut ticker = tokio::time::interval_at(start, interval);
+                loop {
+                    ticker.tick().await;
+                    let last_trades = last_trades.as_ref();
+                    if !poll_token(
+                        &client,
+                        &token_id,
+                        midpoint_mode,
+                        min_level_size,
+                        mid_source,
+                        last_trades,
+                        &unparseable,
+                        &tx,
+                    )
+                    .await
+                    {
+                        return;
+                    }
+                }
+            }));
+        }
+        tasks
+    }
+}
 
-        let stream = stream::unfold(rx, |mut rx| async move {
+/// Turn a broadcast receiver into a `Stream`, counting into `skipped` the
+/// snapshots lost whenever the consumer lags behind the channel.
+fn receiver_stream(
+    rx: broadcast::Receiver<MarketSnapshot>,
+    skipped: SkippedSnapshots,
+) -> Pin<Box<dyn Stream<Item = MarketSnapshot> + Send>> {
+    Box::pin(stream::unfold(
+        (rx, skipped),
+        |(mut rx, skipped)| async move {
             loop {
                 match rx.recv().await {
-                    Ok(snapshot) => return Some((snapshot, rx)),
+                    Ok(snapshot) => return Some((snapshot, (rx, skipped))),
                     Err(broadcast::error::RecvError::Lagged(n)) => {
-                        warn!(skipped = n, "feed consumer lagged, skipping messages");
-                        continue;
+                        let total = skipped.add(n);
+                        warn!(
+                        skipped = n,
+                        total_skipped = total,
+                        "feed consumer lagged — snapshots dropped (raise the feed capacity or coalesce)"
+                    );
                     }
                     Err(broadcast::error::RecvError::Closed) => return None,
                 }
             }
+        },
+    ))
+}
+
+/// Fetch one token's book and publish its snapshot.
+///
+/// Returns `false` once every receiver has been dropped and polling should stop.
+#[allow(clippy::too_many_arguments)]
+async fn poll_token(
+    client: &BookClient,
+    token_id: &str,
+    midpoint_mode: MidpointMode,
+    min_level_size: Decimal,
+    mid_source: MidSource,
+    last_trades: Option<&LastTradePrices>,
+    unparseable: &UnparseableLevels,
+    tx: &broadcast::Sender<MarketSnapshot>,
+) -> bool {
+    match fetch_snapshot(
+        client,
+        token_id,
+        midpoint_mode,
+        min_level_size,
+        mid_source,
+        last_trades,
+        unparseable,
+    )
+    .await
+    {
+        Ok(snapshot) => {
+            if tx.send(snapshot).is_err() {
+                info!("all feed receivers dropped, stopping feed manager");
+                return false;
+            }
+        }
+        Err(e) => {
+            warn!(token_id, error = %e, "failed to fetch market data");
+        }
+    }
+    true
+}
+
+/// Build a snapshot from the token's book, taking the midpoint from `mid_source`.
+/// `LastTrade` prefers the streamed price in `last_trades` over a REST call.
+///
+/// Unquotable books keep their book-derived midpoint; the snapshot is only
+/// published so the engine sees the book status. A book whose prices do not
+/// parse is published as `BookStatus::Invalid`, its dropped levels counted
+/// in `unparseable`.
+async fn fetch_snapshot(
+    client: &BookClient,
+    token_id: &str,
+    midpoint_mode: MidpointMode,
+    min_level_size: Decimal,
+    mid_source: MidSource,
+    last_trades: Option<&LastTradePrices>,
+    unparseable: &UnparseableLevels,
+) -> eutrader_core::Result<MarketSnapshot> {
+    let book_resp = client.get_orderbook(token_id).await?;
+    unparseable.add(book::unparseable_levels(&book_resp) as u64);
+    let mut snapshot =
+        match book::to_snapshot_with_mode(token_id, &book_resp, midpoint_mode, min_level_size) {
+            Ok(snapshot) => snapshot,
+            Err(e @ eutrader_core::Error::FeedParse(_)) => {
+                warn!(token_id, error = %e, "unreadable book — flagging snapshot");
+                return Ok(book::invalid_snapshot(token_id));
+            }
+            Err(e) => return Err(e),
+        };
+    if !snapshot.is_quotable() {
+        return Ok(snapshot);
+    }
+    match mid_source {
+        MidSource::Book => {}
+        MidSource::Midpoint => snapshot.midpoint = client.get_midpoint(token_id).await?,
+        MidSource::LastTrade => {
+            snapshot.midpoint = match last_trades.and_then(|prices| prices.get(token_id)) {
+                Some(price) => price,
+                None => client.get_last_trade_price(token_id).await?,
+            }
+        }
+    }
+    Ok(snapshot)
+}
+
+/// Draw a uniformly random start offset in `[0, jitter)` for each of `n` tokens.
+fn jitter_offsets(n: usize, jitter: Duration, rng: &mut impl Rng) -> Vec<Duration> {
+    let max_ms = jitter.as_millis() as u64;
+    (0..n)
+        .map(|_| {
+            if max_ms == 0 {
+                Duration::ZERO
+            } else {
+                Duration::from_millis(rng.gen_range(0..max_ms))
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{serve_json, serve_json_after_failures, serve_status_counted};
+
+    #[test]
+    fn jitter_offsets_stay_within_bound() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let offsets = jitter_offsets(20, Duration::from_millis(500), &mut rng);
+        assert_eq!(offsets.len(), 20);
+        assert!(offsets.iter().all(|o| *o < Duration::from_millis(500)));
+
+        let none = jitter_offsets(3, Duration::ZERO, &mut rng);
+        assert!(none.iter().all(|o| o.is_zero()));
+    }
+
+    #[tokio::test]
+    async fn lagging_consumer_counts_skipped_snapshots() {
+        let (tx, rx) = broadcast::channel(2);
+        let skipped = SkippedSnapshots::default();
+        let mut stream = receiver_stream(rx, skipped.clone());
+
+        // Five snapshots into a two-slot channel before anyone reads
+        for cents in 40..45 {
+            let book = book::OrderBookResponse {
+                market: "0xabc".into(),
+                asset_id: "tok1".into(),
+                bids: vec![book::PriceLevel {
+                    price: format!("0.{cents}"),
+                    size: "10".into(),
+                }],
+                asks: vec![book::PriceLevel {
+                    price: "0.60".into(),
+                    size: "10".into(),
+                }],
+            };
+            tx.send(book::to_snapshot("tok1", &book).unwrap()).unwrap();
+        }
+
+        let first = futures::StreamExt::next(&mut stream).await.unwrap();
+        assert_eq!(first.best_bid, rust_decimal::Decimal::new(43, 2));
+        assert_eq!(skipped.get(), 3);
+
+        drop(tx);
+        assert!(futures::StreamExt::next(&mut stream).await.is_some());
+        assert!(futures::StreamExt::next(&mut stream).await.is_none());
+        assert_eq!(skipped.get(), 3);
+    }
+
+    fn book_or_prices(target: &str) -> String {
+        if target.starts_with("/midpoint") {
+            r#"{"mid":"0.455"}"#.to_string()
+        } else if target.starts_with("/last-trade-price") {
+            r#"{"price":"0.47","side":"BUY"}"#.to_string()
+        } else {
+            r#"{"market":"0xabc","asset_id":"tok","bids":[{"price":"0.40","size":"100"}],"asks":[{"price":"0.60","size":"100"}]}"#
+                .to_string()
+        }
+    }
+
+    #[tokio::test]
+    async fn midpoint_source_overrides_book_mid() {
+        let client = BookClient::with_base_url(serve_json(book_or_prices).await);
+
+        let from_book = fetch_snapshot(
+            &client,
+            "tok",
+            MidpointMode::Simple,
+            Decimal::ZERO,
+            MidSource::Book,
+            None,
+            &UnparseableLevels::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(from_book.midpoint, "0.50".parse().unwrap());
+
+        let from_endpoint = fetch_snapshot(
+            &client,
+            "tok",
+            MidpointMode::Simple,
+            Decimal::ZERO,
+            MidSource::Midpoint,
+            None,
+            &UnparseableLevels::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(from_endpoint.midpoint, "0.455".parse().unwrap());
+        // The rest of the snapshot still comes from the book
+        assert_eq!(from_endpoint.best_bid, "0.40".parse().unwrap());
+        assert_eq!(from_endpoint.spread, "0.20".parse().unwrap());
+
+        let from_trade = fetch_snapshot(
+            &client,
+            "tok",
+            MidpointMode::Simple,
+            Decimal::ZERO,
+            MidSource::LastTrade,
+            None,
+            &UnparseableLevels::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(from_trade.midpoint, "0.47".parse().unwrap());
+
+        // A streamed trade price wins over the REST endpoint
+        let prices = LastTradePrices::new();
+        prices.record(&crate::TradeEvent {
+            token_id: "tok".into(),
+            price: "0.52".parse().unwrap(),
+            size: rust_decimal::Decimal::ONE,
+            side: eutrader_core::Side::Buy,
+            ts: chrono::Utc::now(),
         });
+        let streamed = fetch_snapshot(
+            &client,
+            "tok",
+            MidpointMode::Simple,
+            Decimal::ZERO,
+            MidSource::LastTrade,
+            Some(&prices),
+            &UnparseableLevels::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(streamed.midpoint, "0.52".parse().unwrap());
+    }
+
+    #[tokio::test]
+    async fn unreadable_book_is_published_as_invalid() {
+        let client = BookClient::with_base_url(
+            serve_json(|_| {
+                r#"{"market":"0xabc","asset_id":"tok","bids":[{"price":"0.40","size":"100"}],"asks":[{"price":"sixty","size":"100"},{"price":"0,61","size":"5"}]}"#
+                    .to_string()
+            })
+            .await,
+        );
+        let unparseable = UnparseableLevels::default();
+
+        let snapshot = fetch_snapshot(
+            &client,
+            "tok",
+            MidpointMode::Simple,
+            Decimal::ZERO,
+            MidSource::Book,
+            None,
+            &unparseable,
+        )
+        .await
+        .unwrap();
+        assert_eq!(snapshot.book_status, eutrader_core::BookStatus::Invalid);
+        assert!(!snapshot.is_quotable());
+        assert_eq!(unparseable.get(), 2);
+    }
+
+    #[tokio::test]
+    async fn config_refresh_interval_drives_polling() {
+        let config: Config = serde_json::from_value(serde_json::json!({
+            "mode": "paper",
+            "risk": {
+                "max_position_per_market": 100, "max_total_exposure": 500,
+                "max_unrealized_loss": 50, "quote_refresh_interval_ms": 40
+            },
+            "markets": [{
+                "name": "Test", "token_id": "tok", "spread_bps": 400, "size": 10,
+                "max_inventory": 50, "skew_factor": 0.001
+            }]
+        }))
+        .unwrap();
+        let feed = FeedManager::from_config(vec!["tok".into()], &config)
+            .with_clob_base_url(serve_json(book_or_prices).await);
+        assert_eq!(feed.interval(), Duration::from_millis(40));
 
-        Box::pin(stream)
+        // The configured interval is the one the stream polls on
+        let mut snapshots = feed.stream().await.unwrap();
+        for _ in 0..3 {
+            futures::StreamExt::next(&mut snapshots).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn stream_waits_out_failed_startup_polls() {
+        let base_url = serve_json_after_failures(
+            3,
+            r#"{"market":"0xabc","asset_id":"tok","bids":[{"price":"0.48","size":"100"}],"asks":[{"price":"0.52","size":"100"}]}"#,
+        )
+        .await;
+        let mut snapshots = FeedManager::with_interval(vec!["tok".into()], 20)
+            .with_clob_base_url(base_url)
+            .with_startup_timeout(Duration::from_secs(5))
+            .stream()
+            .await
+            .unwrap();
+
+        // The first snapshot is the one the startup gate waited for
+        let first = futures::StreamExt::next(&mut snapshots).await.unwrap();
+        assert_eq!(first.midpoint, "0.50".parse().unwrap());
+        assert!(futures::StreamExt::next(&mut snapshots).await.is_some());
+    }
+
+    #[tokio::test]
+    async fn stream_fails_when_feed_never_answers() {
+        let (base_url, requests) = serve_status_counted("503 Service Unavailable").await;
+        let result = FeedManager::with_interval(vec!["tok".into()], 20)
+            .with_clob_base_url(base_url)
+            .with_startup_timeout(Duration::from_millis(200))
+            .stream()
+            .await;
+        assert!(matches!(
+            result,
+            Err(eutrader_core::Error::FeedTransient(_))
+        ));
+
+        // Polling stops with the error; a request already in flight may land
+        let polled = requests.load(std::sync::atomic::Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(requests.load(std::sync::atomic::Ordering::SeqCst) <= polled + 1);
+    }
+
+    #[tokio::test]
+    async fn stream_uses_configured_mid_source_per_token() {
+        let base_url = serve_json(book_or_prices).await;
+        let mut snapshots = FeedManager::with_interval(vec!["a".into(), "b".into()], 5_000)
+            .with_clob_base_url(base_url)
+            .with_mid_sources([("b".to_string(), MidSource::Midpoint)])
+            .run();
+
+        let mut mids = HashMap::new();
+        while mids.len() < 2 {
+            let snapshot = futures::StreamExt::next(&mut snapshots).await.unwrap();
+            mids.insert(snapshot.token_id, snapshot.midpoint);
+        }
+        assert_eq!(mids["a"], "0.50".parse().unwrap());
+        assert_eq!(mids["b"], "0.455".parse().unwrap());
+    }
+
+    #[tokio::test]
+    async fn jitter_spreads_first_snapshots_across_interval() {
+        let base_url = serve_json(|_| {
+            r#"{"market":"0xabc","asset_id":"tok","bids":[{"price":"0.48","size":"100"}],"asks":[{"price":"0.52","size":"100"}]}"#
+                .to_string()
+        })
+        .await;
+        let tokens: Vec<String> = (0..8).map(|i| format!("tok{i}")).collect();
+        let feed = FeedManager::with_interval(tokens.clone(), 5_000)
+            .with_clob_base_url(base_url)
+            .with_jitter_ms(400)
+            .with_jitter_seed(7);
+
+        // A fixed seed reproduces the same offsets, spread over the window
+        let offsets = feed.start_offsets();
+        assert_eq!(offsets, feed.start_offsets());
+        let first = *offsets.iter().min().unwrap();
+        let last = *offsets.iter().max().unwrap();
+        assert!(
+            last - first > Duration::from_millis(50),
+            "offsets clustered: {offsets:?}"
+        );
+        assert!(last < Duration::from_millis(400));
+
+        // Every token still gets polled
+        let mut snapshots = feed.run();
+        let mut seen = std::collections::HashSet::new();
+        while seen.len() < tokens.len() {
+            let snapshot = futures::StreamExt::next(&mut snapshots).await.unwrap();
+            seen.insert(snapshot.token_id);
+        }
     }
 }
@@ -0,0 +1,262 @@
+use chrono::{DateTime, Utc};
+use eutrader_core::Side;
+use futures::stream::{self, Stream};
+use futures::{SinkExt, StreamExt};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tokio_tungstenite::tungstenite::Message;
+use tracing::{debug, info, warn};
+
+/// Polymarket's public market-channel WebSocket.
+pub const MARKET_WS_URL: &str = "wss://ws-subscriptions-clob.polymarket.com/ws/market";
+
+/// Default pause before reconnecting after the socket drops.
+const DEFAULT_RECONNECT_MS: u64 = 2_000;
+
+/// A single trade printed on the CLOB.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TradeEvent {
+    pub token_id: String,
+    pub price: Decimal,
+    pub size: Decimal,
+    /// Aggressor side
+    pub side: Side,
+    pub ts: DateTime<Utc>,
+}
+
+/// Latest trade price per token, shared between the trades feed that writes
+/// it and the consumers (e.g. `FeedManager` for `MidSource::LastTrade`) that
+/// read it.
+#[derive(Debug, Clone, Default)]
+pub struct LastTradePrices {
+    prices: Arc<RwLock<HashMap<String, (Decimal, Instant)>>>,
+    max_age: Option<Duration>,
+}
+
+impl LastTradePrices {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Treat a price received more than `max_age` ago as unknown, so a dead
+    /// socket can't keep serving it (no limit by default).
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Remember `trade` as its token's latest price.
+    pub fn record(&self, trade: &TradeEvent) {
+        if let Ok(mut prices) = self.prices.write() {
+            prices.insert(trade.token_id.clone(), (trade.price, Instant::now()));
+        }
+    }
+
+    /// Latest trade price seen for `token_id`, unless older than the age
+    /// limit.
+    pub fn get(&self, token_id: &str) -> Option<Decimal> {
+        let (price, at) = *self.prices.read().ok()?.get(token_id)?;
+        match self.max_age {
+            Some(max_age) if at.elapsed() > max_age => None,
+            _ => Some(price),
+        }
+    }
+}
+
+/// Subscribes to the market channel and streams `TradeEvent`s.
+///
+/// Runs independently of the book feed: it only reports trades, reconnecting
+/// whenever the socket drops. Attach a `LastTradePrices` to keep a shared
+/// last-price map up to date as trades arrive.
+pub struct TradesFeed {
+    token_ids: Vec<String>,
+    url: String,
+    last_trades: Option<LastTradePrices>,
+    reconnect_delay: Duration,
+}
+
+impl TradesFeed {
+    pub fn new(token_ids: Vec<String>) -> Self {
+        Self {
+            token_ids,
+            url: MARKET_WS_URL.to_string(),
+            last_trades: None,
+            reconnect_delay: Duration::from_millis(DEFAULT_RECONNECT_MS),
+        }
+    }
+
+    /// Connect to `url` instead of the production market channel.
+    pub fn with_url(mut self, url: impl Into<String>) -> Self {
+        self.url = url.into();
+        self
+    }
+
+    /// Record every trade into `prices` as well as streaming it.
+    pub fn with_last_trades(mut self, prices: LastTradePrices) -> Self {
+        self.last_trades = Some(prices);
+        self
+    }
+
+    /// Connect and return a stream of trades. The connection is retried
+    /// until the stream is dropped.
+    pub fn run(self) -> Pin<Box<dyn Stream<Item = TradeEvent> + Send>> {
+        let (tx, rx) = mpsc::channel::<TradeEvent>(256);
+        tokio::spawn(self.connect_loop(tx));
+        Box::pin(stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|t| (t, rx))
+        }))
+    }
+
+    async fn connect_loop(self, tx: mpsc::Sender<TradeEvent>) {
+        info!(tokens = self.token_ids.len(), url = %self.url, "trades feed started");
+        while !tx.is_closed() {
+            if let Err(e) = self.stream_once(&tx).await {
+                warn!(error = %e, "trades socket failed — reconnecting");
+            }
+            tokio::time::sleep(self.reconnect_delay).await;
+        }
+        info!("all trade receivers dropped, stopping trades feed");
+    }
+
+    /// One connection's lifetime: subscribe, then forward trades until the
+    /// socket closes or the receiver goes away.
+    async fn stream_once(&self, tx: &mpsc::Sender<TradeEvent>) -> eutrader_core::Result<()> {
+        let (mut ws, _) = tokio_tungstenite::connect_async(self.url.as_str())
+            .await
+            .map_err(|e| {
+                eutrader_core::Error::FeedTransient(format!("trades connect failed: {e}"))
+            })?;
+        let subscribe = serde_json::json!({ "assets_ids": self.token_ids, "type": "market" });
+        ws.send(Message::Text(subscribe.to_string()))
+            .await
+            .map_err(|e| {
+                eutrader_core::Error::FeedTransient(format!("trades subscribe failed: {e}"))
+            })?;
+
+        while let Some(msg) = ws.next().await {
+            let msg = msg.map_err(|e| {
+                eutrader_core::Error::FeedTransient(format!("trades socket error: {e}"))
+            })?;
+            match msg {
+                Message::Text(text) => {
+                    for trade in parse_trade_message(&text) {
+                        if let Some(ref prices) = self.last_trades {
+                            prices.record(&trade);
+                        }
+                        if tx.send(trade).await.is_err() {
+                            return Ok(());
+                        }
+                    }
+                }
+                Message::Ping(payload) => {
+                    let _ = ws.send(Message::Pong(payload)).await;
+                }
+                Message::Close(_) => break,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Extract the `last_trade_price` events from a market-channel message.
+///
+/// The channel sends single events or arrays of them, mixed with book and
+/// price-change updates; anything that is not a well-formed trade is skipped.
+pub fn parse_trade_message(text: &str) -> Vec<TradeEvent> {
+    let value: serde_json::Value = match serde_json::from_str(text) {
+        Ok(v) => v,
+        Err(e) => {
+            debug!(error = %e, "ignoring non-JSON trades message");
+            return Vec::new();
+        }
+    };
+    let events = match value {
+        serde_json::Value::Array(items) => items,
+        other => vec![other],
+    };
+    events.iter().filter_map(parse_trade_event).collect()
+}
+
+fn parse_trade_event(event: &serde_json::Value) -> Option<TradeEvent> {
+    if event["event_type"].as_str()? != "last_trade_price" {
+        return None;
+    }
+    let side = match event["side"].as_str()? {
+        s if s.eq_ignore_ascii_case("buy") => Side::Buy,
+        s if s.eq_ignore_ascii_case("sell") => Side::Sell,
+        _ => return None,
+    };
+    let ts = event["timestamp"]
+        .as_str()
+        .and_then(|ms| ms.parse::<i64>().ok())
+        .and_then(DateTime::from_timestamp_millis)
+        .unwrap_or_else(Utc::now);
+
+    Some(TradeEvent {
+        token_id: event["asset_id"].as_str()?.to_string(),
+        price: Decimal::from_str(event["price"].as_str()?).ok()?,
+        size: Decimal::from_str(event["size"].as_str()?).ok()?,
+        side,
+        ts,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_trades_and_tracks_last_price() {
+        let msg = r#"[
+            {"asset_id":"tok1","event_type":"last_trade_price","fee_rate_bps":"0","market":"0xabc",
+             "price":"0.456","side":"BUY","size":"219.22","timestamp":"1750428146322"},
+            {"asset_id":"tok1","event_type":"price_change","price":"0.46"},
+            {"asset_id":"tok1","event_type":"last_trade_price","market":"0xabc",
+             "price":"0.45","side":"SELL","size":"10","timestamp":"1750428147000"}
+        ]"#;
+
+        let trades = parse_trade_message(msg);
+        assert_eq!(trades.len(), 2);
+        assert_eq!(trades[0].price, Decimal::new(456, 3));
+        assert_eq!(trades[0].size, Decimal::new(21922, 2));
+        assert_eq!(trades[0].side, Side::Buy);
+        assert_eq!(trades[0].ts.timestamp_millis(), 1_750_428_146_322);
+        assert_eq!(trades[1].side, Side::Sell);
+
+        let prices = LastTradePrices::new();
+        for trade in &trades {
+            prices.record(trade);
+        }
+        assert_eq!(prices.get("tok1"), Some(Decimal::new(45, 2)));
+        assert_eq!(prices.get("tok2"), None);
+
+        // A lone object is accepted too; junk is ignored
+        let single = r#"{"asset_id":"tok2","event_type":"last_trade_price","price":"0.1","side":"BUY","size":"5"}"#;
+        assert_eq!(parse_trade_message(single).len(), 1);
+        assert!(parse_trade_message("PONG").is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn stale_last_trades_are_not_served() {
+        let prices = LastTradePrices::new().with_max_age(Duration::from_secs(60));
+        prices.record(&TradeEvent {
+            token_id: "tok1".into(),
+            price: Decimal::new(45, 2),
+            size: Decimal::TEN,
+            side: Side::Buy,
+            ts: Utc::now(),
+        });
+        assert_eq!(prices.get("tok1"), Some(Decimal::new(45, 2)));
+
+        // The socket went quiet: the last print is too old to quote off
+        tokio::time::advance(Duration::from_secs(61)).await;
+        assert_eq!(prices.get("tok1"), None);
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+
+use eutrader_core::MarketSnapshot;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+
+use crate::trades::TradeEvent;
+
+/// Default EWMA half-life, in snapshots.
+pub const DEFAULT_HALF_LIFE: u32 = 60;
+
+/// Per-token EWMA state.
+#[derive(Debug, Clone, Copy)]
+struct EwmaState {
+    last_mid: f64,
+    variance: f64,
+    samples: u64,
+}
+
+/// Tracks an exponentially weighted moving average of squared midpoint
+/// log-returns per token.
+///
+/// Feed it every `MarketSnapshot` as it arrives; `sigma` returns the current
+/// per-snapshot volatility estimate. The half-life is measured in snapshots,
+/// so the estimate's time scale follows the feed polling interval.
+#[derive(Debug, Clone)]
+pub struct VolatilityTracker {
+    alpha: f64,
+    half_life: u32,
+    states: HashMap<String, EwmaState>,
+}
+
+impl Default for VolatilityTracker {
+    fn default() -> Self {
+        Self::new(DEFAULT_HALF_LIFE)
+    }
+}
+
+impl VolatilityTracker {
+    /// Create a tracker whose weights halve every `half_life` snapshots.
+    ///
+    /// A `half_life` of zero is treated as one (no smoothing).
+    pub fn new(half_life: u32) -> Self {
+        let half_life = half_life.max(1);
+        Self {
+            alpha: 1.0 - 0.5_f64.powf(1.0 / half_life as f64),
+            half_life,
+            states: HashMap::new(),
+        }
+    }
+
+    /// The configured half-life, in snapshots.
+    pub fn half_life(&self) -> u32 {
+        self.half_life
+    }
+
+    /// Fold a new snapshot into its token's estimate and return the updated
+    /// sigma.
+    ///
+    /// Snapshots with a non-positive midpoint are ignored since their
+    /// log-return is undefined.
+    pub fn update(&mut self, snapshot: &MarketSnapshot) -> Decimal {
+        if let Some(mid) = snapshot
+            .midpoint
+            .to_f64()
+            .filter(|_| snapshot.is_quotable())
+        {
+            self.fold(&snapshot.token_id, mid);
+        }
+        self.sigma(&snapshot.token_id)
+    }
+
+    /// Fold a trade print into its token's estimate and return the updated
+    /// sigma. Returns are then measured trade to trade, so feed a token either
+    /// trades or snapshots, not both.
+    pub fn update_trade(&mut self, trade: &TradeEvent) -> Decimal {
+        if let Some(price) = trade.price.to_f64() {
+            self.fold(&trade.token_id, price);
+        }
+        self.sigma(&trade.token_id)
+    }
+
+    /// Fold a new price into `token_id`'s EWMA. Non-positive prices are
+    /// ignored since their log-return is undefined.
+    fn fold(&mut self, token_id: &str, price: f64) {
+        if price <= 0.0 {
+            return;
+        }
+        match self.states.get_mut(token_id) {
+            Some(state) => {
+                let r = (price / state.last_mid).ln();
+                state.variance = (1.0 - self.alpha) * state.variance + self.alpha * r * r;
+                state.last_mid = price;
+                state.samples += 1;
+            }
+            None => {
+                self.states.insert(
+                    token_id.to_string(),
+                    EwmaState {
+                        last_mid: price,
+                        variance: 0.0,
+                        samples: 0,
+                    },
+                );
+            }
+        }
+    }
+
+    /// Current volatility estimate (standard deviation of per-snapshot
+    /// log-returns) for `token_id`.
+    ///
+    /// Returns zero until at least two snapshots have been seen.
+    pub fn sigma(&self, token_id: &str) -> Decimal {
+        self.states
+            .get(token_id)
+            .and_then(|s| Decimal::from_f64(s.variance.sqrt()))
+            .unwrap_or(Decimal::ZERO)
+    }
+
+    /// Number of returns folded into the estimate for `token_id`.
+    pub fn sample_count(&self, token_id: &str) -> u64 {
+        self.states.get(token_id).map(|s| s.samples).unwrap_or(0)
+    }
+
+    /// Drop all state for `token_id`.
+    pub fn reset(&mut self, token_id: &str) {
+        self.states.remove(token_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::str::FromStr;
+
+    fn snap(token: &str, mid: &str) -> MarketSnapshot {
+        let mid = Decimal::from_str(mid).unwrap();
+        MarketSnapshot {
+            token_id: token.into(),
+            best_bid: mid,
+            best_ask: mid,
+            midpoint: mid,
+            micro_price: mid,
+            spread: Decimal::ZERO,
+            bid_size: Decimal::ZERO,
+            ask_size: Decimal::ZERO,
+            timestamp: Utc::now(),
+            book_status: Default::default(),
+        }
+    }
+
+    #[test]
+    fn constant_price_has_zero_volatility() {
+        let mut vt = VolatilityTracker::new(10);
+        for _ in 0..50 {
+            vt.update(&snap("tok", "0.50"));
+        }
+        assert_eq!(vt.sigma("tok"), Decimal::ZERO);
+        assert_eq!(vt.sample_count("tok"), 49);
+    }
+
+    #[test]
+    fn alternating_path_converges_to_step_size() {
+        // Every return is ±ln(1.1), so the EWMA of r² converges to ln(1.1)².
+        let mut vt = VolatilityTracker::new(5);
+        for i in 0..200 {
+            let mid = if i % 2 == 0 { "0.50" } else { "0.55" };
+            vt.update(&snap("tok", mid));
+        }
+        let expected = 1.1_f64.ln();
+        let sigma = vt.sigma("tok").to_f64().unwrap();
+        assert!(
+            (sigma - expected).abs() < 1e-6,
+            "sigma {sigma} vs {expected}"
+        );
+    }
+
+    #[test]
+    fn half_life_weights_new_returns() {
+        // From zero variance, one return r gives variance alpha * r², and a
+        // half-life of 1 means alpha = 0.5.
+        let mut vt = VolatilityTracker::new(1);
+        vt.update(&snap("tok", "0.50"));
+        vt.update(&snap("tok", "0.55"));
+        let expected = (0.5 * 1.1_f64.ln().powi(2)).sqrt();
+        let sigma = vt.sigma("tok").to_f64().unwrap();
+        assert!((sigma - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn tokens_are_tracked_independently() {
+        let mut vt = VolatilityTracker::default();
+        vt.update(&snap("a", "0.50"));
+        vt.update(&snap("a", "0.60"));
+        vt.update(&snap("b", "0.50"));
+        vt.update(&snap("b", "0.50"));
+        assert!(vt.sigma("a") > Decimal::ZERO);
+        assert_eq!(vt.sigma("b"), Decimal::ZERO);
+        assert_eq!(vt.sigma("unknown"), Decimal::ZERO);
+    }
+}
@@ -0,0 +1,203 @@
+use std::collections::{HashMap, VecDeque};
+
+use chrono::{DateTime, Duration, Utc};
+use eutrader_core::{AdverseSelectionConfig, Fill, MarketSnapshot, Side};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use tracing::debug;
+
+/// A fill waiting for its markout horizon to pass.
+#[derive(Debug, Clone)]
+struct PendingFill {
+    side: Side,
+    mid: Decimal,
+    due: DateTime<Utc>,
+}
+
+/// Tracks how the mid moves after our fills and widens spreads on markets
+/// where we are consistently picked off.
+///
+/// Fills are recorded as they happen and marked out against the first
+/// snapshot at least `markout_ms` later. Markouts are signed in bps of the
+/// mid at fill: positive when the mid moved our way, negative when it moved
+/// against us.
+#[derive(Debug, Clone)]
+pub struct AdverseSelectionTracker {
+    config: AdverseSelectionConfig,
+    pending: HashMap<String, VecDeque<PendingFill>>,
+    markouts: HashMap<String, VecDeque<Decimal>>,
+}
+
+impl AdverseSelectionTracker {
+    pub fn new(config: AdverseSelectionConfig) -> Self {
+        Self {
+            config,
+            pending: HashMap::new(),
+            markouts: HashMap::new(),
+        }
+    }
+
+    /// Swap in new settings, keeping the recorded history.
+    pub fn set_config(&mut self, config: AdverseSelectionConfig) {
+        self.config = config;
+        for history in self.markouts.values_mut() {
+            while history.len() > self.config.lookback_fills {
+                history.pop_front();
+            }
+        }
+    }
+
+    /// Start tracking `fill`, measured from `mid` — the midpoint when it traded.
+    pub fn record_fill(&mut self, fill: &Fill, mid: Decimal) {
+        if mid <= Decimal::ZERO {
+            return;
+        }
+        self.pending
+            .entry(fill.token_id.clone())
+            .or_default()
+            .push_back(PendingFill {
+                side: fill.side,
+                mid,
+                due: fill.timestamp + Duration::milliseconds(self.config.markout_ms as i64),
+            });
+    }
+
+    /// Mark out every pending fill of the snapshot's token whose horizon has passed.
+    pub fn observe(&mut self, snapshot: &MarketSnapshot) {
+        let Some(pending) = self.pending.get_mut(&snapshot.token_id) else {
+            return;
+        };
+        let history = self.markouts.entry(snapshot.token_id.clone()).or_default();
+        while pending.front().is_some_and(|p| p.due <= snapshot.timestamp) {
+            let Some(fill) = pending.pop_front() else {
+                break;
+            };
+            let markout =
+                Fill::edge_vs_mid(fill.side, fill.mid, snapshot.midpoint) / fill.mid * dec!(10000);
+            debug!(token = %snapshot.token_id, side = %fill.side, %markout, "fill markout (bps)");
+            history.push_back(markout);
+            if history.len() > self.config.lookback_fills {
+                history.pop_front();
+            }
+        }
+    }
+
+    /// Average markout in bps over the recent history, if any fill has been marked out.
+    pub fn average_markout_bps(&self, token_id: &str) -> Option<Decimal> {
+        let history = self.markouts.get(token_id).filter(|h| !h.is_empty())?;
+        Some(history.iter().sum::<Decimal>() / Decimal::from(history.len()))
+    }
+
+    /// Factor to apply to `spread_bps` for this market (1 when not adverse).
+    pub fn spread_multiplier(&self, token_id: &str, spread_bps: u32) -> Decimal {
+        let half_spread_bps = Decimal::from(spread_bps) / dec!(2);
+        match self.average_markout_bps(token_id) {
+            Some(avg) if avg < Decimal::ZERO && half_spread_bps > Decimal::ZERO => {
+                let widened = Decimal::ONE + self.config.sensitivity * -avg / half_spread_bps;
+                widened.min(self.config.max_multiplier).max(Decimal::ONE)
+            }
+            _ => Decimal::ONE,
+        }
+    }
+
+    /// `spread_bps` widened by the market's adverse-selection multiplier.
+    pub fn effective_spread_bps(&self, token_id: &str, spread_bps: u32) -> u32 {
+        let widened = Decimal::from(spread_bps) * self.spread_multiplier(token_id, spread_bps);
+        widened.ceil().to_u32().unwrap_or(spread_bps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fill(side: Side, at: DateTime<Utc>) -> Fill {
+        Fill {
+            token_id: "tok".into(),
+            strategy_id: String::new(),
+            side,
+            price: dec!(0.50),
+            size: dec!(10),
+            timestamp: at,
+            is_simulated: true,
+            mid_at_fill: dec!(0.50),
+            edge: Decimal::ZERO,
+        }
+    }
+
+    fn snapshot(mid: Decimal, at: DateTime<Utc>) -> MarketSnapshot {
+        MarketSnapshot {
+            token_id: "tok".into(),
+            best_bid: mid - dec!(0.01),
+            best_ask: mid + dec!(0.01),
+            midpoint: mid,
+            micro_price: mid,
+            spread: dec!(0.02),
+            bid_size: dec!(100),
+            ask_size: dec!(100),
+            timestamp: at,
+            book_status: Default::default(),
+        }
+    }
+
+    fn tracker() -> AdverseSelectionTracker {
+        AdverseSelectionTracker::new(AdverseSelectionConfig {
+            lookback_fills: 4,
+            markout_ms: 1000,
+            sensitivity: Decimal::ONE,
+            max_multiplier: dec!(3),
+        })
+    }
+
+    #[test]
+    fn repeated_adverse_fills_widen_spread() {
+        let mut tracker = tracker();
+        let start = Utc::now();
+        assert_eq!(tracker.effective_spread_bps("tok", 400), 400);
+
+        // Each buy at 0.50 is followed by the mid dropping to 0.49: -200bps
+        for i in 0..3 {
+            let at = start + Duration::seconds(10 * i);
+            tracker.record_fill(&fill(Side::Buy, at), dec!(0.50));
+            // Not yet due — no markout
+            tracker.observe(&snapshot(dec!(0.49), at + Duration::milliseconds(500)));
+            tracker.observe(&snapshot(dec!(0.49), at + Duration::seconds(1)));
+        }
+
+        assert_eq!(tracker.average_markout_bps("tok"), Some(dec!(-200)));
+        // 1 + 200 / 200 = 2x
+        assert_eq!(tracker.spread_multiplier("tok", 400), dec!(2));
+        assert_eq!(tracker.effective_spread_bps("tok", 400), 800);
+        // Capped for tight spreads
+        assert_eq!(tracker.spread_multiplier("tok", 100), dec!(3));
+    }
+
+    #[test]
+    fn favourable_fills_leave_spread_unchanged() {
+        let mut tracker = tracker();
+        let start = Utc::now();
+        tracker.record_fill(&fill(Side::Sell, start), dec!(0.50));
+        tracker.observe(&snapshot(dec!(0.48), start + Duration::seconds(2)));
+
+        assert_eq!(tracker.average_markout_bps("tok"), Some(dec!(400)));
+        assert_eq!(tracker.effective_spread_bps("tok", 400), 400);
+    }
+
+    #[test]
+    fn history_is_limited_to_lookback() {
+        let mut tracker = tracker();
+        let start = Utc::now();
+        // One old adverse fill, then four neutral ones push it out of the window
+        tracker.record_fill(&fill(Side::Buy, start), dec!(0.50));
+        tracker.observe(&snapshot(dec!(0.40), start + Duration::seconds(1)));
+        for i in 1..=4 {
+            let at = start + Duration::seconds(10 * i);
+            tracker.record_fill(&fill(Side::Buy, at), dec!(0.50));
+            tracker.observe(&snapshot(dec!(0.50), at + Duration::seconds(1)));
+        }
+
+        assert_eq!(tracker.average_markout_bps("tok"), Some(Decimal::ZERO));
+        assert_eq!(tracker.spread_multiplier("tok", 400), Decimal::ONE);
+    }
+}
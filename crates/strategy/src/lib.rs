@@ -1,5 +1,7 @@
+pub mod adverse;
 pub mod quoter;
 pub mod risk;
 
-pub use quoter::Quoter;
+pub use adverse::AdverseSelectionTracker;
+pub use quoter::{QuoteSkipReason, Quoter};
 pub use risk::RiskManager;
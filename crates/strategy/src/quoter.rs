@@ -1,9 +1,60 @@
-use eutrader_core::{InventoryPosition, MarketSnapshot, Quote};
+use chrono::{DateTime, Utc};
 use eutrader_core::config::MarketConfig;
+use eutrader_core::price::{
+    ceil_to_tick, floor_to_lot, floor_to_tick, min_size_for_notional, shares_for_notional,
+};
+use eutrader_core::{
+    Fill, InventoryPosition, MarketSnapshot, Quote, QuoteAnchor, Side, SizeMode, SkewCurve,
+};
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
+use std::fmt;
 use tracing::debug;
 
+/// Why a market is not being quoted this cycle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuoteSkipReason {
+    /// The market's own spread is wider than `max_market_spread_bps`.
+    WideSpread { spread_bps: Decimal, max_bps: u32 },
+    /// The snapshot is older than `max_snapshot_age_ms`.
+    StaleData { age_ms: i64, max_ms: u64 },
+    /// The mid is outside the market's `quote_price_band`.
+    OutOfBand {
+        mid: Decimal,
+        low: Decimal,
+        high: Decimal,
+    },
+    /// The current time is outside every window of the market's `schedule`.
+    OffSchedule,
+}
+
+impl fmt::Display for QuoteSkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QuoteSkipReason::WideSpread {
+                spread_bps,
+                max_bps,
+            } => {
+                write!(
+                    f,
+                    "wide spread {spread_bps}bps > {max_bps}bps — not quoting"
+                )
+            }
+            QuoteSkipReason::StaleData { age_ms, max_ms } => {
+                write!(f, "stale data {age_ms}ms > {max_ms}ms — not quoting")
+            }
+            QuoteSkipReason::OutOfBand { mid, low, high } => {
+                write!(
+                    f,
+                    "out of band: mid {mid} outside [{low}, {high}] — not quoting"
+                )
+            }
+            QuoteSkipReason::OffSchedule => write!(f, "outside quoting schedule — not quoting"),
+        }
+    }
+}
+
 /// The core market-making quoting engine.
 ///
 /// Given a market snapshot, current inventory, and config, produces a two-sided
@@ -22,40 +73,172 @@ impl Quoter {
         Self
     }
 
+    /// Check whether market conditions are sane enough to quote into.
+    ///
+    /// Returns a reason to skip quoting if the book is wider than the market's
+    /// `max_market_spread_bps`, its mid is outside `quote_price_band`, the
+    /// snapshot is older than `max_snapshot_age_ms`, or `now` is outside the
+    /// market's `schedule`.
+    pub fn skip_reason(
+        snapshot: &MarketSnapshot,
+        config: &MarketConfig,
+        max_snapshot_age_ms: Option<u64>,
+        now: DateTime<Utc>,
+    ) -> Option<QuoteSkipReason> {
+        if !config.is_scheduled(now) {
+            return Some(QuoteSkipReason::OffSchedule);
+        }
+
+        if let Some(max_ms) = max_snapshot_age_ms {
+            let age_ms = (now - snapshot.timestamp).num_milliseconds();
+            if age_ms > max_ms as i64 {
+                return Some(QuoteSkipReason::StaleData { age_ms, max_ms });
+            }
+        }
+
+        if let Some(max_bps) = config.max_market_spread_bps {
+            let spread_bps = snapshot.spread * dec!(10000);
+            if spread_bps > Decimal::from(max_bps) {
+                return Some(QuoteSkipReason::WideSpread {
+                    spread_bps,
+                    max_bps,
+                });
+            }
+        }
+
+        if let Some((low, high)) = config.quote_price_band {
+            let mid = snapshot.midpoint;
+            if mid < low || mid > high {
+                return Some(QuoteSkipReason::OutOfBand { mid, low, high });
+            }
+        }
+
+        None
+    }
+
     /// Compute a two-sided quote for a market.
     ///
     /// Returns `None` if the resulting bid >= ask (spread too tight after
-    /// clamping/skew) or if the snapshot is invalid.
+    /// clamping/skew), if the half-spread does not cover the position's fee
+    /// plus `min_edge_bps`, or if the snapshot is invalid.
     pub fn quote(
         snapshot: &MarketSnapshot,
         inventory: &InventoryPosition,
         config: &MarketConfig,
     ) -> Option<Quote> {
-        let mid = snapshot.midpoint;
+        Self::quote_around(snapshot, None, inventory, config)
+    }
+
+    /// Center for a `LastFill`-anchored market: `mid` pulled toward the price
+    /// of `last_fill`, all the way at the moment of the fill and not at all
+    /// once it is `anchor_decay_ms` old.
+    ///
+    /// `None` when the market is centered on the mid or the fill has faded.
+    pub fn anchor(
+        mid: Decimal,
+        last_fill: Option<&Fill>,
+        config: &MarketConfig,
+        now: DateTime<Utc>,
+    ) -> Option<Decimal> {
+        if config.quote_anchor != QuoteAnchor::LastFill || config.anchor_decay_ms == 0 {
+            return None;
+        }
+        let fill = last_fill?;
+        let age_ms = (now - fill.timestamp).num_milliseconds().max(0) as u64;
+        if age_ms >= config.anchor_decay_ms {
+            return None;
+        }
+        let weight = Decimal::ONE - Decimal::from(age_ms) / Decimal::from(config.anchor_decay_ms);
+        Some(mid + (fill.price - mid) * weight)
+    }
+
+    /// Like `quote`, but centered on `fair_value` instead of the snapshot
+    /// midpoint when one is given.
+    ///
+    /// Quotes around an external fair value are kept passive: the bid stays
+    /// below the book's best ask and the ask above its best bid, so a fair
+    /// value far from the book never crosses it.
+    pub fn quote_around(
+        snapshot: &MarketSnapshot,
+        fair_value: Option<Decimal>,
+        inventory: &InventoryPosition,
+        config: &MarketConfig,
+    ) -> Option<Quote> {
+        let mid = fair_value.unwrap_or(snapshot.midpoint);
 
         // --- Half spread ---
-        let half_spread =
-            Decimal::from(config.spread_bps) / dec!(10000) / dec!(2);
+        // Widened on a wide book so we never quote much tighter than the market.
+        let mut half_spread = Decimal::from(config.spread_bps) / dec!(10000) / dec!(2);
+        if config.min_spread_over_market > Decimal::ZERO {
+            let floor = snapshot.spread * config.min_spread_over_market / dec!(2);
+            if floor > half_spread {
+                debug!(
+                    token_id = %snapshot.token_id,
+                    market_spread = %snapshot.spread,
+                    %half_spread, %floor,
+                    "widening half-spread to the market spread floor"
+                );
+                half_spread = floor;
+            }
+        }
 
         // --- Base quotes ---
+        let target_half_spread = half_spread;
         let mut bid = mid - half_spread;
         let mut ask = mid + half_spread;
 
         // --- Inventory skew ---
-        // Positive net_position (long) => skew pushes both quotes down so we
-        // become more eager to sell and less eager to buy.
-        let skew = inventory.net_position * config.skew_factor;
+        // Long of target => skew pushes both quotes down so we become more
+        // eager to sell and less eager to buy. Short of target does the reverse.
+        let skew = inventory_skew(inventory.net_position - config.target_inventory, config);
         bid -= skew;
         ask -= skew;
 
-        // --- Round to tick size 0.01 ---
+        // --- Book imbalance ---
+        // More size on the bid than the ask predicts an up-move, so shift the
+        // reservation price up by imbalance * factor (imbalance in [-1, 1]).
+        if config.imbalance_factor != Decimal::ZERO {
+            let depth = snapshot.bid_size + snapshot.ask_size;
+            if depth > Decimal::ZERO {
+                let imbalance = (snapshot.bid_size - snapshot.ask_size) / depth;
+                let shift = imbalance * config.imbalance_factor;
+                bid += shift;
+                ask += shift;
+            }
+        }
+
+        // --- Round to the market's tick size ---
         // Floor for bid (conservative buy), ceil for ask (conservative sell).
-        bid = floor_to_tick(bid, dec!(0.01));
-        ask = ceil_to_tick(ask, dec!(0.01));
+        let tick = config.tick_size;
+        bid = floor_to_tick(bid, tick);
+        ask = ceil_to_tick(ask, tick);
+
+        // --- Reducing-side aggression ---
+        // Off target, pull the side that works the position back toward the
+        // market so it fills first, stopping a tick short of the opposite
+        // touch. Applied after rounding, in whole ticks, so rounding cannot
+        // undo it. The other side keeps its skewed price.
+        if config.reduce_side_aggression > Decimal::ZERO {
+            let shift = config.reduce_side_aggression.ceil() * tick;
+            let offset = inventory.net_position - config.target_inventory;
+            if offset > Decimal::ZERO {
+                let room = (ask - (snapshot.best_bid + tick)).max(Decimal::ZERO);
+                ask -= shift.min(room);
+            } else if offset < Decimal::ZERO {
+                let room = ((snapshot.best_ask - tick) - bid).max(Decimal::ZERO);
+                bid += shift.min(room);
+            }
+        }
+
+        // --- Stay passive against the book when off-book fair value ---
+        if fair_value.is_some() {
+            bid = bid.min(snapshot.best_ask - tick);
+            ask = ask.max(snapshot.best_bid + tick);
+        }
 
-        // --- Clamp to [0.01, 0.99] ---
-        bid = bid.max(dec!(0.01)).min(dec!(0.99));
-        ask = ask.max(dec!(0.01)).min(dec!(0.99));
+        // --- Clamp to [tick, 1 - tick] ---
+        bid = bid.max(tick).min(Decimal::ONE - tick);
+        ask = ask.max(tick).min(Decimal::ONE - tick);
 
         // --- Check spread validity ---
         if bid >= ask {
@@ -67,40 +250,263 @@ impl Quoter {
             return None;
         }
 
-        // --- Size reduction near max inventory ---
-        let mut size = config.size;
+        // --- Edge over fees ---
+        // Each fill captures half the spread and pays the fee on its notional.
+        // Take the lesser of the half-spread aimed for (after any widening)
+        // and the rounded one, so tick rounding never masks a spread that is
+        // priced below fees.
+        let required_bps = Decimal::from(inventory.fee_bps + config.min_edge_bps);
+        if required_bps > Decimal::ZERO && mid > Decimal::ZERO {
+            let quoted_bps = (ask - bid) / dec!(2) / mid * dec!(10000);
+            let captured_bps = quoted_bps.min(target_half_spread / mid * dec!(10000));
+            if captured_bps < required_bps {
+                debug!(
+                    token_id = %snapshot.token_id,
+                    %captured_bps, %required_bps,
+                    "half-spread does not cover fees plus min edge — no quote"
+                );
+                return None;
+            }
+        }
+
+        // --- Base size per side ---
+        // Notional sizing buys the same dollar exposure at either price.
+        let (mut bid_size, mut ask_size) = match config.size_mode {
+            SizeMode::Shares => (config.size, config.size),
+            SizeMode::Notional => (
+                shares_for_notional(config.size, bid),
+                shares_for_notional(config.size, ask),
+            ),
+        };
+
+        // --- Asymmetric size scaling with inventory ---
+        // The side that would grow the position shrinks linearly from full size
+        // when flat to 20% at max inventory; the reducing side stays at full size.
+        if config.max_inventory > Decimal::ZERO {
+            let utilization = inventory.net_position / config.max_inventory;
+            let reduction = (dec!(1) - utilization.abs() * dec!(0.8)).max(dec!(0.2));
+            if utilization > Decimal::ZERO {
+                bid_size *= reduction;
+            } else if utilization < Decimal::ZERO {
+                ask_size *= reduction;
+            }
+        }
+        let bid_raised = raised_for_notional(bid_size, bid, config);
+        let ask_raised = raised_for_notional(ask_size, ask, config);
+        let bid_size = clamp_order_size(bid_size, bid, config);
+        let ask_size = clamp_order_size(ask_size, ask, config);
+
+        // --- One-sided quoting beyond the inventory band ---
+        // Long past the band: only sell (distribute). Short past it: only buy (accumulate).
+        let mut one_sided = None;
+        if let Some(threshold) = config.one_sided_threshold {
+            let band = config.max_inventory * threshold;
+            if inventory.net_position > band {
+                one_sided = Some(Side::Sell);
+            } else if inventory.net_position < -band {
+                one_sided = Some(Side::Buy);
+            }
+        }
+
+        // --- Minimum notional ---
+        // A side raised to `min_notional` is pulled if filling it would carry
+        // the position past `max_inventory`.
         if config.max_inventory > Decimal::ZERO {
-            let utilization = inventory.net_position.abs() / config.max_inventory;
-            if utilization > dec!(0.8) {
-                // Linear reduction: at 80% usage keep full size, at 100% reduce to 20%
-                let reduction = dec!(1) - (utilization - dec!(0.8)) / dec!(0.2) * dec!(0.8);
-                size = (size * reduction.max(dec!(0.2))).max(dec!(1));
+            let pull_bid = bid_raised && inventory.net_position + bid_size > config.max_inventory;
+            let pull_ask = ask_raised && inventory.net_position - ask_size < -config.max_inventory;
+            if pull_bid || pull_ask {
+                debug!(token = %snapshot.token_id, pull_bid, pull_ask, "min notional would breach max inventory — pulling side");
+            }
+            // Whatever is left must still be quotable on its own
+            one_sided = match (pull_bid, pull_ask, one_sided) {
+                (false, false, kept) => kept,
+                (true, false, None | Some(Side::Sell)) => Some(Side::Sell),
+                (false, true, None | Some(Side::Buy)) => Some(Side::Buy),
+                _ => return None,
+            };
+        }
+
+        // --- Reduce-only near the inventory limit ---
+        // The side that unwinds the position is tagged so a stale view of
+        // inventory can never make it overshoot through flat.
+        let mut reduce_only = None;
+        if let Some(threshold) = config.reduce_only_threshold {
+            let band = config.max_inventory * threshold;
+            if inventory.net_position > band {
+                reduce_only = Some(Side::Sell);
+            } else if inventory.net_position < -band {
+                reduce_only = Some(Side::Buy);
             }
         }
 
         Some(Quote {
             token_id: snapshot.token_id.clone(),
+            strategy_id: config.strategy_id.clone(),
             bid_price: bid,
             ask_price: ask,
-            size,
+            bid_size,
+            ask_size,
+            one_sided,
+            reduce_only,
         })
     }
+
+    /// A passive, reduce-only quote that only works `inventory` back toward
+    /// flat, for a session winding down; `None` once flat.
+    ///
+    /// `progress` runs from 0 at the start of the unwind to 1 at its
+    /// deadline. The reducing side, at its normal size, starts at its normal
+    /// price and walks linearly to one tick off the opposite touch, so it
+    /// grows more aggressive each cycle without ever crossing the book.
+    pub fn unwind(
+        snapshot: &MarketSnapshot,
+        inventory: &InventoryPosition,
+        config: &MarketConfig,
+        progress: Decimal,
+    ) -> Option<Quote> {
+        let net = inventory.net_position;
+        if net == Decimal::ZERO {
+            return None;
+        }
+        let tick = config.tick_size;
+        let progress = progress.clamp(Decimal::ZERO, Decimal::ONE);
+        let normal = Self::quote(snapshot, inventory, config);
+        let (side, bid, ask, size) = if net > Decimal::ZERO {
+            let floor = snapshot.best_bid + tick;
+            let start = normal
+                .as_ref()
+                .map_or(snapshot.midpoint, |q| q.ask_price)
+                .max(floor);
+            let ask = ceil_to_tick(start - (start - floor) * progress, tick).max(floor);
+            let size = normal.map_or(net, |q| q.ask_size.min(net));
+            (
+                Side::Sell,
+                snapshot.best_bid,
+                ask.min(Decimal::ONE - tick),
+                size,
+            )
+        } else {
+            let ceiling = snapshot.best_ask - tick;
+            let start = normal
+                .as_ref()
+                .map_or(snapshot.midpoint, |q| q.bid_price)
+                .min(ceiling);
+            let bid = floor_to_tick(start + (ceiling - start) * progress, tick).min(ceiling);
+            let size = normal.map_or(-net, |q| q.bid_size.min(-net));
+            (Side::Buy, bid.max(tick), snapshot.best_ask, size)
+        };
+        Some(Quote {
+            token_id: snapshot.token_id.clone(),
+            strategy_id: config.strategy_id.clone(),
+            bid_price: bid,
+            ask_price: ask,
+            bid_size: size,
+            ask_size: size,
+            one_sided: Some(side),
+            reduce_only: Some(side),
+        })
+    }
+
+    /// Randomize the quote's sizes within `±size_jitter_pct` of target,
+    /// rounded down to the 0.01-share lot and re-clamped to the size limits.
+    ///
+    /// The draw is a hash of `seed`, the strategy key, side and price, so a
+    /// resting order keeps its size until its price moves instead of being
+    /// replaced every cycle, and a fixed seed reproduces the same sizes.
+    pub fn jitter_sizes(quote: &mut Quote, config: &MarketConfig, seed: u64) {
+        if config.size_jitter_pct <= Decimal::ZERO {
+            return;
+        }
+        let key = config.key().to_string();
+        quote.bid_size = jitter_size(
+            quote.bid_size,
+            quote.bid_price,
+            Side::Buy,
+            &key,
+            config,
+            seed,
+        );
+        quote.ask_size = jitter_size(
+            quote.ask_size,
+            quote.ask_price,
+            Side::Sell,
+            &key,
+            config,
+            seed,
+        );
+    }
+}
+
+fn jitter_size(
+    size: Decimal,
+    price: Decimal,
+    side: Side,
+    key: &str,
+    config: &MarketConfig,
+    seed: u64,
+) -> Decimal {
+    // FNV-1a over the inputs, finished with the splitmix64 mixer.
+    let mut h = 0xcbf2_9ce4_8422_2325u64 ^ seed;
+    let price_text = price.normalize().to_string();
+    let side = [side as u8];
+    for bytes in [key.as_bytes(), &side, price_text.as_bytes()] {
+        for &b in bytes {
+            h = (h ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    h = (h ^ (h >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    h = (h ^ (h >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    h ^= h >> 31;
+    // Uniform in [-1, 1).
+    let unit = Decimal::from(h >> 11) / Decimal::from(1u64 << 52) - Decimal::ONE;
+    let jittered = size * (Decimal::ONE + unit * config.size_jitter_pct);
+    clamp_order_size(jittered, price, config)
+}
+
+/// Round `size` down to the 0.01-share lot and clamp it into
+/// `[min_order_size, max_order_size]`, raising the floor to `min_notional` at
+/// `price`. The ceiling wins if they conflict.
+fn clamp_order_size(size: Decimal, price: Decimal, config: &MarketConfig) -> Decimal {
+    let floor = config
+        .min_order_size
+        .max(min_size_for_notional(config.min_notional, price));
+    let size = floor_to_lot(size).max(floor);
+    match config.max_order_size {
+        Some(max) => size.min(max),
+        None => size,
+    }
 }
 
-/// Floor a value to the nearest tick (round down).
-fn floor_to_tick(value: Decimal, tick: Decimal) -> Decimal {
-    (value / tick).floor() * tick
+/// Whether `clamp_order_size` has to raise `size` to meet `min_notional`.
+fn raised_for_notional(size: Decimal, price: Decimal, config: &MarketConfig) -> bool {
+    size.max(config.min_order_size) < min_size_for_notional(config.min_notional, price)
 }
 
-/// Ceil a value to the nearest tick (round up).
-fn ceil_to_tick(value: Decimal, tick: Decimal) -> Decimal {
-    (value / tick).ceil() * tick
+/// Price shift for a position `offset` away from target, per `skew_curve`.
+///
+/// The non-linear curves are shaped by utilization of `max_inventory`; with no
+/// inventory limit they fall back to linear.
+fn inventory_skew(offset: Decimal, config: &MarketConfig) -> Decimal {
+    if config.max_inventory <= Decimal::ZERO {
+        return offset * config.skew_factor;
+    }
+    match config.skew_curve {
+        SkewCurve::Linear => offset * config.skew_factor,
+        SkewCurve::Quadratic => config.skew_factor * offset * offset.abs() / config.max_inventory,
+        SkewCurve::Tanh => {
+            let x = (offset / config.max_inventory * config.skew_steepness)
+                .to_f64()
+                .unwrap_or(0.0);
+            config.max_skew * Decimal::from_f64(x.tanh()).unwrap_or(Decimal::ZERO)
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use chrono::Utc;
+    use eutrader_core::price::min_order_size_at;
     use rust_decimal_macros::dec;
 
     fn make_snapshot(mid: Decimal) -> MarketSnapshot {
@@ -109,8 +515,12 @@ mod tests {
             best_bid: mid - dec!(0.01),
             best_ask: mid + dec!(0.01),
             midpoint: mid,
+            micro_price: mid,
             spread: dec!(0.02),
+            bid_size: dec!(100),
+            ask_size: dec!(100),
             timestamp: Utc::now(),
+            book_status: Default::default(),
         }
     }
 
@@ -122,6 +532,7 @@ mod tests {
             size: dec!(10),
             max_inventory: dec!(50),
             skew_factor: dec!(0.001),
+            ..Default::default()
         }
     }
 
@@ -132,6 +543,7 @@ mod tests {
             avg_entry: dec!(0.50),
             realized_pnl: Decimal::ZERO,
             fill_count: 0,
+            ..Default::default()
         }
     }
 
@@ -148,7 +560,46 @@ mod tests {
         // ask = 0.50 + 0.015 = 0.515 -> ceil(0.01) = 0.52
         assert_eq!(quote.bid_price, dec!(0.48));
         assert_eq!(quote.ask_price, dec!(0.52));
-        assert_eq!(quote.size, dec!(10));
+        assert_eq!(quote.bid_size, dec!(10));
+        assert_eq!(quote.ask_size, dec!(10));
+    }
+
+    #[test]
+    fn wide_market_spread_widens_quotes_past_config() {
+        let inv = make_inventory(Decimal::ZERO);
+        let mut config = make_config(300);
+        config.min_spread_over_market = dec!(0.6);
+        let wide = MarketSnapshot {
+            best_bid: dec!(0.45),
+            best_ask: dec!(0.55),
+            spread: dec!(0.10),
+            ..make_snapshot(dec!(0.50))
+        };
+
+        // half-spread = max(0.015, 0.10 * 0.6 / 2 = 0.03)
+        let quote = Quoter::quote(&wide, &inv, &config).unwrap();
+        assert_eq!((quote.bid_price, quote.ask_price), (dec!(0.47), dec!(0.53)));
+
+        // A tight book leaves the configured 300bps in charge
+        let quote = Quoter::quote(&make_snapshot(dec!(0.50)), &inv, &config).unwrap();
+        assert_eq!((quote.bid_price, quote.ask_price), (dec!(0.48), dec!(0.52)));
+    }
+
+    #[test]
+    fn quotes_round_to_market_tick_size() {
+        let snap = make_snapshot(dec!(0.4857));
+        let inv = make_inventory(Decimal::ZERO);
+
+        let coarse = Quoter::quote(&snap, &inv, &make_config(100)).unwrap();
+        assert_eq!(
+            (coarse.bid_price, coarse.ask_price),
+            (dec!(0.48), dec!(0.50))
+        );
+
+        let mut config = make_config(100);
+        config.tick_size = dec!(0.001);
+        let fine = Quoter::quote(&snap, &inv, &config).unwrap();
+        assert_eq!((fine.bid_price, fine.ask_price), (dec!(0.480), dec!(0.491)));
     }
 
     #[test]
@@ -166,6 +617,70 @@ mod tests {
         assert_eq!(quote.ask_price, dec!(0.50));
     }
 
+    #[test]
+    fn reduce_side_aggression_moves_only_the_reducing_side_toward_the_touch() {
+        let mut snap = make_snapshot(dec!(0.50));
+        snap.best_bid = dec!(0.47);
+        snap.best_ask = dec!(0.53);
+        snap.spread = dec!(0.06);
+        let long = make_inventory(dec!(20));
+        let short = make_inventory(dec!(-20));
+        let mut config = make_config(300);
+
+        // Symmetric: ask = 0.495 -> 0.50, bid = 0.465 -> 0.46
+        let base_long = Quoter::quote(&snap, &long, &config).unwrap();
+        let base_short = Quoter::quote(&snap, &short, &config).unwrap();
+        assert_eq!(
+            (base_long.bid_price, base_long.ask_price),
+            (dec!(0.46), dec!(0.50))
+        );
+
+        // Half a tick rounds up to one: the long ask to 0.49, the short bid up a tick
+        config.reduce_side_aggression = dec!(0.5);
+        let long_q = Quoter::quote(&snap, &long, &config).unwrap();
+        assert_eq!(long_q.ask_price, dec!(0.49));
+        assert!(long_q.ask_price - snap.best_bid < base_long.ask_price - snap.best_bid);
+        assert_eq!(long_q.bid_price, base_long.bid_price);
+        let short_q = Quoter::quote(&snap, &short, &config).unwrap();
+        assert_eq!(short_q.bid_price, base_short.bid_price + dec!(0.01));
+        assert_eq!(short_q.ask_price, base_short.ask_price);
+
+        // Never closer than a tick off the opposite touch
+        config.reduce_side_aggression = dec!(5);
+        let capped = Quoter::quote(&snap, &long, &config).unwrap();
+        assert_eq!(capped.ask_price, snap.best_bid + config.tick_size);
+
+        // Flat inventory has no reducing side
+        let flat = make_inventory(Decimal::ZERO);
+        let flat_q = Quoter::quote(&snap, &flat, &config).unwrap();
+        config.reduce_side_aggression = Decimal::ZERO;
+        let flat_base = Quoter::quote(&snap, &flat, &config).unwrap();
+        assert_eq!(
+            (flat_q.bid_price, flat_q.ask_price),
+            (flat_base.bid_price, flat_base.ask_price)
+        );
+    }
+
+    #[test]
+    fn reduce_side_aggression_survives_an_aligned_quote() {
+        let mut snap = make_snapshot(dec!(0.50));
+        snap.best_bid = dec!(0.47);
+        snap.best_ask = dec!(0.53);
+        snap.spread = dec!(0.06);
+        let long = make_inventory(dec!(20));
+        let mut config = make_config(400);
+        config.skew_factor = Decimal::ZERO;
+
+        // 0.52 sits on a tick, so rounding leaves nothing to absorb the shift
+        let base = Quoter::quote(&snap, &long, &config).unwrap();
+        assert_eq!(base.ask_price, dec!(0.52));
+
+        config.reduce_side_aggression = dec!(0.5);
+        let quote = Quoter::quote(&snap, &long, &config).unwrap();
+        assert_eq!(quote.ask_price, dec!(0.51));
+        assert_eq!(quote.bid_price, base.bid_price);
+    }
+
     #[test]
     fn short_inventory_skews_quotes_up() {
         let snap = make_snapshot(dec!(0.50));
@@ -181,6 +696,67 @@ mod tests {
         assert_eq!(quote.ask_price, dec!(0.54));
     }
 
+    #[test]
+    fn skew_centers_on_target_inventory() {
+        let snap = make_snapshot(dec!(0.50));
+        let flat = make_inventory(Decimal::ZERO);
+        let mut config = make_config(300);
+
+        // Target 0: flat is on target, quotes stay symmetric around mid
+        let untargeted = Quoter::quote(&snap, &flat, &config).unwrap();
+        assert_eq!(
+            (untargeted.bid_price, untargeted.ask_price),
+            (dec!(0.48), dec!(0.52))
+        );
+
+        // Target +20: flat is 20 short of target, so quotes lean up to buy
+        // skew = (0 - 20) * 0.001 = -0.02
+        // bid = 0.50 - 0.015 + 0.02 = 0.505 -> floor = 0.50
+        // ask = 0.50 + 0.015 + 0.02 = 0.535 -> ceil  = 0.54
+        config.target_inventory = dec!(20);
+        let targeted = Quoter::quote(&snap, &flat, &config).unwrap();
+        assert_eq!(
+            (targeted.bid_price, targeted.ask_price),
+            (dec!(0.50), dec!(0.54))
+        );
+
+        // Holding the target is the new neutral point
+        let on_target = Quoter::quote(&snap, &make_inventory(dec!(20)), &config).unwrap();
+        assert_eq!(
+            (on_target.bid_price, on_target.ask_price),
+            (dec!(0.48), dec!(0.52))
+        );
+    }
+
+    #[test]
+    fn bid_heavy_book_nudges_quotes_up() {
+        let mut snap = make_snapshot(dec!(0.50));
+        snap.bid_size = dec!(900);
+        snap.ask_size = dec!(100);
+        let inv = make_inventory(Decimal::ZERO);
+        let mut config = make_config(300);
+
+        // Off by default: same quotes as a balanced book
+        let plain = Quoter::quote(&snap, &inv, &config).unwrap();
+        assert_eq!((plain.bid_price, plain.ask_price), (dec!(0.48), dec!(0.52)));
+
+        // imbalance = (900 - 100) / 1000 = 0.8, shift = 0.8 * 0.025 = 0.02
+        // bid = 0.485 + 0.02 = 0.505 -> floor = 0.50
+        // ask = 0.515 + 0.02 = 0.535 -> ceil  = 0.54
+        config.imbalance_factor = dec!(0.025);
+        let leaned = Quoter::quote(&snap, &inv, &config).unwrap();
+        assert_eq!(
+            (leaned.bid_price, leaned.ask_price),
+            (dec!(0.50), dec!(0.54))
+        );
+
+        // An ask-heavy book leans the other way
+        snap.bid_size = dec!(100);
+        snap.ask_size = dec!(900);
+        let down = Quoter::quote(&snap, &inv, &config).unwrap();
+        assert_eq!((down.bid_price, down.ask_price), (dec!(0.46), dec!(0.50)));
+    }
+
     #[test]
     fn prices_clamped_to_valid_range() {
         // Very high midpoint — ask should be clamped to 0.99
@@ -211,6 +787,7 @@ mod tests {
             size: dec!(10),
             max_inventory: dec!(50),
             skew_factor: dec!(0.01), // aggressive skew
+            ..Default::default()
         };
 
         // skew = -500 * 0.01 = -5.0 (massive upward push)
@@ -222,30 +799,440 @@ mod tests {
     }
 
     #[test]
-    fn size_reduced_near_max_inventory() {
+    fn wide_market_spread_skips_quoting() {
+        let mut snap = make_snapshot(dec!(0.50));
+        snap.best_bid = dec!(0.10);
+        snap.best_ask = dec!(0.90);
+        snap.spread = dec!(0.80);
+        let mut config = make_config(300);
+        config.max_market_spread_bps = Some(1000);
+
+        let reason = Quoter::skip_reason(&snap, &config, None, Utc::now());
+        assert_eq!(
+            reason,
+            Some(QuoteSkipReason::WideSpread {
+                spread_bps: dec!(8000),
+                max_bps: 1000
+            })
+        );
+    }
+
+    #[test]
+    fn mid_outside_price_band_skips_quoting() {
+        let mut config = make_config(300);
+        config.quote_price_band = Some((dec!(0.10), dec!(0.90)));
+
+        let reason = Quoter::skip_reason(&make_snapshot(dec!(0.97)), &config, None, Utc::now());
+        assert_eq!(
+            reason,
+            Some(QuoteSkipReason::OutOfBand {
+                mid: dec!(0.97),
+                low: dec!(0.10),
+                high: dec!(0.90)
+            })
+        );
+        assert!(reason.unwrap().to_string().starts_with("out of band"));
+        // The band is inclusive
+        assert!(
+            Quoter::skip_reason(&make_snapshot(dec!(0.90)), &config, None, Utc::now()).is_none()
+        );
+    }
+
+    #[test]
+    fn spread_within_threshold_is_quoted() {
+        let snap = make_snapshot(dec!(0.50)); // spread 0.02 = 200 bps
+        let mut config = make_config(300);
+        config.max_market_spread_bps = Some(1000);
+
+        assert!(Quoter::skip_reason(&snap, &config, Some(5_000), Utc::now()).is_none());
+        // No threshold configured => never skip on spread
+        config.max_market_spread_bps = None;
+        assert!(Quoter::skip_reason(&snap, &config, None, Utc::now()).is_none());
+    }
+
+    #[test]
+    fn stale_snapshot_skips_quoting() {
+        let mut snap = make_snapshot(dec!(0.50));
+        let now = Utc::now();
+        snap.timestamp = now - chrono::Duration::milliseconds(3_000);
+        let config = make_config(300);
+
+        let reason = Quoter::skip_reason(&snap, &config, Some(2_000), now);
+        assert_eq!(
+            reason,
+            Some(QuoteSkipReason::StaleData {
+                age_ms: 3_000,
+                max_ms: 2_000
+            })
+        );
+    }
+
+    #[test]
+    fn long_beyond_band_quotes_ask_only() {
+        let snap = make_snapshot(dec!(0.50));
+        let mut config = make_config(300);
+        config.one_sided_threshold = Some(dec!(0.6)); // band = 30
+
+        let quote = Quoter::quote(&snap, &make_inventory(dec!(35)), &config).unwrap();
+        assert_eq!(quote.one_sided, Some(Side::Sell));
+        assert!(!quote.bid_active());
+        assert!(quote.ask_active());
+
+        // Inside the band both sides are quoted
+        let quote = Quoter::quote(&snap, &make_inventory(dec!(25)), &config).unwrap();
+        assert_eq!(quote.one_sided, None);
+    }
+
+    #[test]
+    fn short_beyond_band_quotes_bid_only() {
         let snap = make_snapshot(dec!(0.50));
-        let inv = make_inventory(dec!(45)); // 90% of max_inventory=50
+        let mut config = make_config(300);
+        config.one_sided_threshold = Some(dec!(0.6));
+
+        let quote = Quoter::quote(&snap, &make_inventory(dec!(-35)), &config).unwrap();
+        assert_eq!(quote.one_sided, Some(Side::Buy));
+        assert!(quote.bid_active());
+        assert!(!quote.ask_active());
+    }
+
+    #[test]
+    fn skew_curves_scale_differently_with_utilization() {
+        // Utilization of the 50-share limit: 20% vs 90%
+        let (low, high) = (dec!(10), dec!(45));
+        let mut config = make_config(300);
+        let ratio =
+            |config: &MarketConfig| inventory_skew(high, config) / inventory_skew(low, config);
+
+        assert_eq!(ratio(&config), dec!(4.5));
+
+        config.skew_curve = SkewCurve::Quadratic;
+        assert_eq!(ratio(&config), dec!(20.25));
+        // Matches linear at the limit, gentler below it
+        assert_eq!(
+            inventory_skew(dec!(50), &config),
+            dec!(50) * config.skew_factor
+        );
+        assert!(inventory_skew(low, &config) < low * config.skew_factor);
+
+        config.skew_curve = SkewCurve::Tanh;
+        let r = ratio(&config);
+        assert!(r > Decimal::ONE && r < dec!(4.5), "tanh saturates: {r}");
+        assert!(inventory_skew(high, &config) < config.max_skew);
+        assert_eq!(
+            inventory_skew(-high, &config),
+            -inventory_skew(high, &config)
+        );
+    }
+
+    #[test]
+    fn high_utilization_tags_reducing_side_reduce_only() {
+        let snap = make_snapshot(dec!(0.50));
+        let mut config = make_config(300);
+        config.reduce_only_threshold = Some(dec!(0.8)); // band = 40
+
+        let quote = Quoter::quote(&snap, &make_inventory(dec!(45)), &config).unwrap();
+        assert_eq!(quote.reduce_only, Some(Side::Sell));
+        let quote = Quoter::quote(&snap, &make_inventory(dec!(-45)), &config).unwrap();
+        assert_eq!(quote.reduce_only, Some(Side::Buy));
+        let quote = Quoter::quote(&snap, &make_inventory(dec!(35)), &config).unwrap();
+        assert_eq!(quote.reduce_only, None);
+    }
+
+    #[test]
+    fn no_threshold_never_one_sided() {
+        let snap = make_snapshot(dec!(0.50));
+        let quote = Quoter::quote(&snap, &make_inventory(dec!(45)), &make_config(300)).unwrap();
+        assert_eq!(quote.one_sided, None);
+    }
+
+    #[test]
+    fn spread_below_fees_yields_none() {
+        let snap = make_snapshot(dec!(0.50));
+        let mut inv = make_inventory(Decimal::ZERO);
+        inv.fee_bps = 60;
+
+        // A 0.0025 half-spread is 50bps of the 0.50 mid, short of a 60bps fee
+        assert!(Quoter::quote(&snap, &inv, &make_config(50)).is_none());
+        // 0.015 is 300bps and covers it
+        assert!(Quoter::quote(&snap, &inv, &make_config(300)).is_some());
+    }
+
+    #[test]
+    fn widened_spread_counts_toward_fees() {
+        let mut inv = make_inventory(Decimal::ZERO);
+        inv.fee_bps = 60;
+        let mut config = make_config(50);
+        config.min_spread_over_market = dec!(1);
+        let wide = MarketSnapshot {
+            best_bid: dec!(0.47),
+            best_ask: dec!(0.53),
+            spread: dec!(0.06),
+            ..make_snapshot(dec!(0.50))
+        };
+
+        // Widened to the 6-cent book, the half-spread is 600bps of mid
+        let quote = Quoter::quote(&wide, &inv, &config).unwrap();
+        assert_eq!((quote.bid_price, quote.ask_price), (dec!(0.47), dec!(0.53)));
+
+        // Without widening the configured 50bps cannot cover the fee
+        config.min_spread_over_market = Decimal::ZERO;
+        assert!(Quoter::quote(&wide, &inv, &config).is_none());
+    }
+
+    #[test]
+    fn min_edge_bps_adds_to_fee_requirement() {
+        let snap = make_snapshot(dec!(0.50));
+        let mut inv = make_inventory(Decimal::ZERO);
+        inv.fee_bps = 30;
+        let mut config = make_config(300); // 0.015 half-spread, 300bps of mid
+
+        config.min_edge_bps = 270;
+        assert!(Quoter::quote(&snap, &inv, &config).is_some());
+
+        config.min_edge_bps = 271;
+        assert!(Quoter::quote(&snap, &inv, &config).is_none());
+    }
+
+    #[test]
+    fn long_inventory_shrinks_bid_size_only() {
+        let snap = make_snapshot(dec!(0.50));
+        let inv = make_inventory(dec!(40)); // +80% of max_inventory=50
         let config = make_config(300);
 
         let quote = Quoter::quote(&snap, &inv, &config).unwrap();
 
-        // utilization = 45/50 = 0.9 > 0.8
-        // reduction = 1 - (0.9 - 0.8)/0.2 * 0.8 = 1 - 0.5*0.8 = 1 - 0.4 = 0.6
-        // size = 10 * 0.6 = 6
-        assert_eq!(quote.size, dec!(6));
+        // reduction = 1 - 0.8 * 0.8 = 0.36
+        // bid size = 10 * 0.36 = 3.6; ask stays full
+        assert_eq!(quote.bid_size, dec!(3.6));
+        assert_eq!(quote.ask_size, dec!(10));
     }
 
     #[test]
-    fn size_at_max_inventory_is_minimum() {
+    fn short_inventory_shrinks_ask_size_only() {
         let snap = make_snapshot(dec!(0.50));
-        let inv = make_inventory(dec!(50)); // 100% of max_inventory
+        let inv = make_inventory(dec!(-40)); // -80% of max_inventory
         let config = make_config(300);
 
         let quote = Quoter::quote(&snap, &inv, &config).unwrap();
 
-        // utilization = 50/50 = 1.0
-        // reduction = 1 - (1.0 - 0.8)/0.2 * 0.8 = 1 - 1.0*0.8 = 0.2
-        // size = 10 * 0.2 = 2, but min is 1
-        assert_eq!(quote.size, dec!(2));
+        assert_eq!(quote.bid_size, dec!(10));
+        assert_eq!(quote.ask_size, dec!(3.6));
+    }
+
+    #[test]
+    fn scaled_sizes_round_down_to_the_lot() {
+        let snap = make_snapshot(dec!(0.50));
+        let inv = make_inventory(dec!(13.37));
+        let config = make_config(300);
+
+        let quote = Quoter::quote(&snap, &inv, &config).unwrap();
+
+        // reduction = 1 - 0.2674 * 0.8 = 0.78608; 7.8608 shares floors to 7.86
+        assert_eq!(quote.bid_size, dec!(7.86));
+        assert_eq!(quote.ask_size, dec!(10));
+    }
+
+    #[test]
+    fn size_near_max_inventory_is_minimum() {
+        let snap = make_snapshot(dec!(0.30));
+        let inv = make_inventory(dec!(45)); // 90% of max_inventory
+        let config = make_config(300);
+
+        let quote = Quoter::quote(&snap, &inv, &config).unwrap();
+
+        // utilization = 45/50 = 0.9
+        // reduction = 1 - 0.9 * 0.8 = 0.28
+        // bid size = 10 * 0.28 = 2.8, but at the skewed bid below 0.30 that is
+        // under $1 notional, so it is raised to the venue minimum
+        assert!(quote.bid_price < dec!(0.30));
+        assert_eq!(quote.bid_size, min_order_size_at(quote.bid_price));
+        assert_eq!(quote.ask_size, dec!(10));
+        assert_eq!(quote.one_sided, None);
+    }
+
+    #[test]
+    fn min_notional_raises_low_price_sizes_or_pulls_the_side() {
+        let snap = make_snapshot(dec!(0.05));
+        let mut config = make_config(300);
+
+        // A penny bid needs far more than 10 shares to be worth $1
+        let quote = Quoter::quote(&snap, &make_inventory(Decimal::ZERO), &config).unwrap();
+        assert!(quote.bid_size > dec!(10));
+        assert_eq!(
+            quote.bid_size,
+            min_size_for_notional(dec!(1), quote.bid_price)
+        );
+        assert!(quote.bid_size * quote.bid_price >= dec!(1));
+        assert_eq!(quote.one_sided, None);
+
+        // Long 30 of 50: the raised bid would overshoot max_inventory
+        let quote = Quoter::quote(&snap, &make_inventory(dec!(30)), &config).unwrap();
+        assert!(dec!(30) + min_size_for_notional(dec!(1), quote.bid_price) > dec!(50));
+        assert_eq!(quote.one_sided, Some(Side::Sell));
+
+        config.min_notional = Decimal::ZERO;
+        let quote = Quoter::quote(&snap, &make_inventory(Decimal::ZERO), &config).unwrap();
+        assert_eq!(quote.bid_size, dec!(10));
+    }
+
+    #[test]
+    fn sizes_clamp_to_configured_floor_and_ceiling() {
+        let snap = make_snapshot(dec!(0.50));
+        let inv = make_inventory(dec!(40)); // bid would shrink to 3.6
+        let mut config = make_config(300);
+        config.min_order_size = dec!(5);
+        config.max_order_size = Some(dec!(8));
+
+        let quote = Quoter::quote(&snap, &inv, &config).unwrap();
+        assert_eq!(quote.bid_size, dec!(5));
+        assert_eq!(quote.ask_size, dec!(8));
+    }
+
+    #[test]
+    fn external_fair_value_recenters_quotes() {
+        let inv = make_inventory(Decimal::ZERO);
+        let config = make_config(300);
+
+        // Wide book around 0.50: quotes sit around the 0.60 fair value
+        let mut wide = make_snapshot(dec!(0.50));
+        wide.best_bid = dec!(0.40);
+        wide.best_ask = dec!(0.70);
+        let quote = Quoter::quote_around(&wide, Some(dec!(0.60)), &inv, &config).unwrap();
+        assert_eq!((quote.bid_price, quote.ask_price), (dec!(0.58), dec!(0.62)));
+
+        // Tight book at 0.49/0.51: the bid is held below the best ask
+        let tight = make_snapshot(dec!(0.50));
+        let quote = Quoter::quote_around(&tight, Some(dec!(0.60)), &inv, &config).unwrap();
+        assert_eq!((quote.bid_price, quote.ask_price), (dec!(0.50), dec!(0.62)));
+
+        // No fair value is the plain book-mid quote
+        let plain = Quoter::quote_around(&tight, None, &inv, &config).unwrap();
+        assert_eq!((plain.bid_price, plain.ask_price), (dec!(0.48), dec!(0.52)));
+    }
+
+    #[test]
+    fn last_fill_anchor_fades_back_to_the_mid() {
+        let inv = make_inventory(Decimal::ZERO);
+        let mut config = make_config(400);
+        let mut snap = make_snapshot(dec!(0.50));
+        snap.best_bid = dec!(0.40);
+        snap.best_ask = dec!(0.60);
+        let now = Utc::now();
+        let fill = Fill {
+            token_id: "tok_test".into(),
+            strategy_id: String::new(),
+            side: Side::Buy,
+            price: dec!(0.46),
+            size: dec!(10),
+            timestamp: now,
+            is_simulated: true,
+            mid_at_fill: dec!(0.50),
+            edge: dec!(0.04),
+        };
+        let quote_at = |config: &MarketConfig, age_secs: i64| {
+            let at = now + chrono::Duration::seconds(age_secs);
+            let center = Quoter::anchor(snap.midpoint, Some(&fill), config, at);
+            let q = Quoter::quote_around(&snap, center, &inv, config).unwrap();
+            (q.bid_price, q.ask_price)
+        };
+
+        // Mid-centered quotes ignore the fill
+        assert_eq!(quote_at(&config, 0), (dec!(0.48), dec!(0.52)));
+
+        config.quote_anchor = QuoteAnchor::LastFill;
+        assert_eq!(quote_at(&config, 0), (dec!(0.44), dec!(0.48)));
+        // Half way through anchor_decay_ms the center is half way back
+        assert_eq!(quote_at(&config, 30), (dec!(0.46), dec!(0.50)));
+        assert_eq!(quote_at(&config, 60), (dec!(0.48), dec!(0.52)));
+        assert_eq!(Quoter::anchor(snap.midpoint, None, &config, now), None);
+    }
+
+    #[test]
+    fn unwind_walks_the_reducing_side_to_the_touch_without_crossing() {
+        let mut snap = make_snapshot(dec!(0.50));
+        snap.best_bid = dec!(0.45);
+        snap.best_ask = dec!(0.55);
+        let config = make_config(400);
+
+        let long = make_inventory(dec!(30));
+        let asks: Vec<Decimal> = [0, 1, 2, 4]
+            .into_iter()
+            .map(|i| {
+                let q =
+                    Quoter::unwind(&snap, &long, &config, Decimal::new(i, 0) / dec!(4)).unwrap();
+                assert_eq!(
+                    (q.one_sided, q.reduce_only),
+                    (Some(Side::Sell), Some(Side::Sell))
+                );
+                assert_eq!(q.ask_size, dec!(10));
+                q.ask_price
+            })
+            .collect();
+        assert!(
+            asks.windows(2).all(|w| w[1] <= w[0]) && asks[0] > asks[3],
+            "{asks:?}"
+        );
+        assert_eq!(asks[3], dec!(0.46));
+
+        let short = make_inventory(dec!(-5));
+        let q = Quoter::unwind(&snap, &short, &config, Decimal::ONE).unwrap();
+        assert_eq!(
+            (q.one_sided, q.bid_price, q.bid_size),
+            (Some(Side::Buy), dec!(0.54), dec!(5))
+        );
+        assert!(
+            Quoter::unwind(&snap, &make_inventory(Decimal::ZERO), &config, dec!(0.5)).is_none()
+        );
+    }
+
+    #[test]
+    fn jittered_sizes_stay_in_band_and_average_near_target() {
+        let inv = make_inventory(Decimal::ZERO);
+        let mut config = make_config(200);
+        config.size = dec!(100);
+        config.max_inventory = Decimal::ZERO;
+        config.size_jitter_pct = dec!(0.2);
+        let quote_at = |config: &MarketConfig, mid: Decimal, seed: u64| {
+            let mut q = Quoter::quote(&make_snapshot(mid), &inv, config).unwrap();
+            Quoter::jitter_sizes(&mut q, config, seed);
+            q
+        };
+
+        let mut sizes = Vec::new();
+        for cents in 20..80 {
+            let q = quote_at(&config, Decimal::new(cents, 2), 7);
+            sizes.extend([q.bid_size, q.ask_size]);
+        }
+        assert!(sizes
+            .iter()
+            .all(|s| *s >= dec!(80) && *s <= dec!(120) && s.scale() <= 2));
+        assert!(sizes.iter().any(|s| *s != sizes[0]));
+        let mean = sizes.iter().sum::<Decimal>() / Decimal::from(sizes.len());
+        assert!((mean - dec!(100)).abs() < dec!(4), "mean {mean}");
+
+        // Same seed and price, same size; another seed draws differently
+        let q = quote_at(&config, dec!(0.50), 7);
+        assert_eq!(q.bid_size, quote_at(&config, dec!(0.50), 7).bid_size);
+        assert_ne!((q.bid_size, q.ask_size), {
+            let other = quote_at(&config, dec!(0.50), 8);
+            (other.bid_size, other.ask_size)
+        });
+
+        config.size_jitter_pct = Decimal::ZERO;
+        assert_eq!(quote_at(&config, dec!(0.50), 7).bid_size, dec!(100));
+    }
+
+    #[test]
+    fn notional_size_mode_converts_dollars_to_shares_per_side() {
+        let snap = make_snapshot(dec!(0.26));
+        let inv = make_inventory(Decimal::ZERO);
+        let mut config = make_config(200);
+        config.size_mode = SizeMode::Notional;
+
+        let quote = Quoter::quote(&snap, &inv, &config).unwrap();
+        assert_eq!((quote.bid_price, quote.ask_price), (dec!(0.25), dec!(0.27)));
+        // $10 at 0.25 is 40 shares; at 0.27 it rounds down to 37.03
+        assert_eq!(quote.bid_size, dec!(40));
+        assert_eq!(quote.ask_size, dec!(37.03));
     }
 }
@@ -32,14 +32,15 @@ impl RiskManager {
     ///
     /// Checks that both the bid side and ask side of the quote, when filled,
     /// would not push the position beyond `max_position_per_market`.
+    /// Suppressed sides of a one-sided quote are not checked.
     pub fn check_order(
         inventory: &InventoryPosition,
         quote: &Quote,
         config: &RiskConfig,
     ) -> Result<()> {
         // After a buy fill at bid, position would increase
-        let position_after_buy = inventory.net_position + quote.size;
-        if position_after_buy.abs() > config.max_position_per_market {
+        let position_after_buy = inventory.net_position + quote.bid_size;
+        if quote.bid_active() && position_after_buy.abs() > config.max_position_per_market {
             return Err(eutrader_core::Error::RiskBreach(format!(
                 "bid fill would breach per-market limit: position would be {} (max {})",
                 position_after_buy, config.max_position_per_market
@@ -47,8 +48,8 @@ impl RiskManager {
         }
 
         // After a sell fill at ask, position would decrease
-        let position_after_sell = inventory.net_position - quote.size;
-        if position_after_sell.abs() > config.max_position_per_market {
+        let position_after_sell = inventory.net_position - quote.ask_size;
+        if quote.ask_active() && position_after_sell.abs() > config.max_position_per_market {
             return Err(eutrader_core::Error::RiskBreach(format!(
                 "ask fill would breach per-market limit: position would be {} (max {})",
                 position_after_sell, config.max_position_per_market
@@ -58,7 +59,8 @@ impl RiskManager {
         debug!(
             token_id = %quote.token_id,
             net_position = %inventory.net_position,
-            quote_size = %quote.size,
+            bid_size = %quote.bid_size,
+            ask_size = %quote.ask_size,
             "order passed risk check"
         );
         Ok(())
@@ -68,14 +70,8 @@ impl RiskManager {
     /// `max_total_exposure`.
     ///
     /// Total exposure is the sum of absolute position values.
-    pub fn check_portfolio(
-        positions: &[InventoryPosition],
-        config: &RiskConfig,
-    ) -> Result<()> {
-        let total_exposure: Decimal = positions
-            .iter()
-            .map(|p| p.net_position.abs())
-            .sum();
+    pub fn check_portfolio(positions: &[InventoryPosition], config: &RiskConfig) -> Result<()> {
+        let total_exposure: Decimal = positions.iter().map(|p| p.net_position.abs()).sum();
 
         if total_exposure > config.max_total_exposure {
             return Err(eutrader_core::Error::RiskBreach(format!(
@@ -97,10 +93,7 @@ impl RiskManager {
     /// Returns `true` if total unrealized loss across all positions exceeds
     /// `max_unrealized_loss`. Uses each position's `avg_entry` as a rough
     /// mid-price proxy (in production you'd pass real mid-prices).
-    pub fn should_kill_switch(
-        positions: &[InventoryPosition],
-        config: &RiskConfig,
-    ) -> bool {
+    pub fn should_kill_switch(positions: &[InventoryPosition], config: &RiskConfig) -> bool {
         // Sum unrealized P&L using avg_entry as a conservative mid-price estimate.
         // In production, you would pass actual mid-prices for each position.
         let total_unrealized: Decimal = positions
@@ -115,9 +108,7 @@ impl RiskManager {
         // unrealized_pnl returns 0 when mid == avg_entry, so in the absence of
         // real mid-prices this is a no-op sentinel. Callers should use the
         // overload below for production checks.
-        if total_unrealized < Decimal::ZERO
-            && total_unrealized.abs() > config.max_unrealized_loss
-        {
+        if total_unrealized < Decimal::ZERO && total_unrealized.abs() > config.max_unrealized_loss {
             warn!(
                 total_unrealized = %total_unrealized,
                 max_loss = %config.max_unrealized_loss,
@@ -149,9 +140,7 @@ impl RiskManager {
             .map(|(p, &mid)| p.unrealized_pnl(mid))
             .sum();
 
-        if total_unrealized < Decimal::ZERO
-            && total_unrealized.abs() > config.max_unrealized_loss
-        {
+        if total_unrealized < Decimal::ZERO && total_unrealized.abs() > config.max_unrealized_loss {
             warn!(
                 total_unrealized = %total_unrealized,
                 max_loss = %config.max_unrealized_loss,
@@ -167,6 +156,7 @@ impl RiskManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use eutrader_core::Side;
     use rust_decimal_macros::dec;
 
     fn make_risk_config() -> RiskConfig {
@@ -175,6 +165,14 @@ mod tests {
             max_total_exposure: dec!(500),
             max_unrealized_loss: dec!(50),
             quote_refresh_interval_ms: 1000,
+            quote_jitter_ms: 0,
+            max_snapshot_age_ms: None,
+            flatten_on_shutdown: false,
+            flatten_aggression: Default::default(),
+            max_open_orders_per_market: None,
+            warmup_ticks: 0,
+            market_status_interval_ms: None,
+            unwind_cycles: None,
         }
     }
 
@@ -185,15 +183,20 @@ mod tests {
             avg_entry: dec!(0.50),
             realized_pnl: Decimal::ZERO,
             fill_count: 0,
+            ..Default::default()
         }
     }
 
     fn make_quote(size: Decimal) -> Quote {
         Quote {
             token_id: "tok_test".into(),
+            strategy_id: String::new(),
             bid_price: dec!(0.48),
             ask_price: dec!(0.52),
-            size,
+            bid_size: size,
+            ask_size: size,
+            one_sided: None,
+            reduce_only: None,
         }
     }
 
@@ -228,6 +231,17 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn suppressed_side_not_checked() {
+        let config = make_risk_config();
+        let inv = make_inventory("tok_test", dec!(95));
+        let mut quote = make_quote(dec!(10));
+        quote.one_sided = Some(Side::Sell);
+
+        // Buying would breach, but only the ask is live
+        assert!(RiskManager::check_order(&inv, &quote, &config).is_ok());
+    }
+
     #[test]
     fn portfolio_within_limits_passes() {
         let config = make_risk_config();
@@ -282,6 +296,7 @@ mod tests {
                 avg_entry: dec!(0.50),
                 realized_pnl: Decimal::ZERO,
                 fill_count: 0,
+                ..Default::default()
             },
             InventoryPosition {
                 token_id: "tok2".into(),
@@ -289,6 +304,7 @@ mod tests {
                 avg_entry: dec!(0.50),
                 realized_pnl: Decimal::ZERO,
                 fill_count: 0,
+                ..Default::default()
             },
         ];
         let mid_prices = vec![dec!(0.10), dec!(0.90)];
@@ -308,6 +324,7 @@ mod tests {
             avg_entry: dec!(0.40),
             realized_pnl: Decimal::ZERO,
             fill_count: 0,
+            ..Default::default()
         }];
         // Long 100 at 0.40, current mid 0.60 => profit = 100 * 0.20 = +20
         let mid_prices = vec![dec!(0.60)];